@@ -0,0 +1,195 @@
+//! Step-phase benchmarks at several population sizes.
+//!
+//! Run with `cargo bench -p digital-life-core --features bench-internals`. Every
+//! world is built from a fixed seed and warmed up for a few steps, so numbers are
+//! comparable across commits on the same machine.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use digital_life_core::config::{BoundaryExposure, MetricsSelection, SimConfig};
+use digital_life_core::genome::{Genome, MutationRates};
+use digital_life_core::world::World;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::hint::black_box;
+use std::time::Duration;
+
+const SEED: u64 = 42;
+const AGENTS_PER_ORGANISM: usize = 10;
+const WARMUP_STEPS: usize = 5;
+const AGENT_COUNTS: [usize; 3] = [1_000, 10_000, 50_000];
+
+/// World with `agents` agents at the default config's density (500 agents on a
+/// 100×100 torus).
+fn world_with_agents(agents: usize) -> World {
+    warmed_world(bench_config(agents))
+}
+
+fn bench_config(agents: usize) -> SimConfig {
+    SimConfig {
+        seed: SEED,
+        num_organisms: agents / AGENTS_PER_ORGANISM,
+        agents_per_organism: AGENTS_PER_ORGANISM,
+        world_size: 100.0 * (agents as f64 / 500.0).sqrt(),
+        ..SimConfig::default()
+    }
+}
+
+fn warmed_world(config: SimConfig) -> World {
+    let mut world = World::from_config(config).expect("benchmark config is valid");
+    for _ in 0..WARMUP_STEPS {
+        world.step();
+    }
+    world
+}
+
+/// Reduced sample counts keep the whole suite to a few minutes.
+fn configure() -> Criterion {
+    Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(3))
+}
+
+fn bench_world_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("world_step");
+    for agents in AGENT_COUNTS {
+        let world = world_with_agents(agents);
+        group.bench_with_input(BenchmarkId::from_parameter(agents), &world, |b, world| {
+            b.iter_batched_ref(|| world.clone(), |w| w.step(), BatchSize::LargeInput);
+        });
+    }
+    group.finish();
+}
+
+/// Cost of the extra agent pass that `BoundaryExposure::Fresh` adds to a step.
+fn bench_boundary_exposure(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boundary_exposure");
+    let agents = AGENT_COUNTS[1];
+    for (label, exposure) in [
+        ("lagged", BoundaryExposure::Lagged),
+        ("fresh", BoundaryExposure::Fresh),
+    ] {
+        let world = warmed_world(SimConfig {
+            boundary_exposure: exposure,
+            ..bench_config(agents)
+        });
+        group.bench_with_input(BenchmarkId::new(label, agents), &world, |b, world| {
+            b.iter_batched_ref(|| world.clone(), |w| w.step(), BatchSize::LargeInput);
+        });
+    }
+    group.finish();
+}
+
+fn bench_nn_query_phase(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nn_query_phase");
+    for agents in AGENT_COUNTS {
+        let mut world = world_with_agents(agents);
+        let tree = world.bench_spatial_index();
+        group.bench_function(BenchmarkId::from_parameter(agents), |b| {
+            b.iter(|| world.bench_nn_query_phase(&tree));
+        });
+    }
+    group.finish();
+}
+
+/// Boundary and metabolism phases at 10k organisms, serial against parallel.
+fn bench_organism_phases(c: &mut Criterion) {
+    let mut group = c.benchmark_group("organism_phases");
+    let agents = 10_000 * AGENTS_PER_ORGANISM;
+    for (label, parallel) in [("serial", false), ("parallel", true)] {
+        let world = warmed_world(SimConfig {
+            parallel_organism_phases: parallel,
+            ..bench_config(agents)
+        });
+        group.bench_with_input(BenchmarkId::new(label, agents), &world, |b, world| {
+            b.iter_batched_ref(
+                || world.clone(),
+                |w| w.bench_organism_phases(),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn bench_step_metrics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collect_step_metrics");
+    for agents in AGENT_COUNTS {
+        let world = world_with_agents(agents);
+        group.bench_with_input(BenchmarkId::from_parameter(agents), &world, |b, world| {
+            b.iter(|| black_box(world.collect_step_metrics(WARMUP_STEPS)));
+        });
+    }
+    group.finish();
+}
+
+/// Per-sample cost of `CORE`-only metrics against every metric group.
+fn bench_metrics_selection(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collect_step_metrics_selection");
+    for agents in AGENT_COUNTS {
+        for (name, selection) in [
+            ("core", MetricsSelection::CORE),
+            ("all", MetricsSelection::all()),
+        ] {
+            let world = warmed_world(SimConfig {
+                metrics_selection: selection,
+                ..bench_config(agents)
+            });
+            group.bench_with_input(BenchmarkId::new(name, agents), &world, |b, world| {
+                b.iter(|| black_box(world.collect_step_metrics(WARMUP_STEPS)));
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_spatial_cohesion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spatial_cohesion");
+    for agents in AGENT_COUNTS {
+        let world = world_with_agents(agents);
+        group.bench_with_input(BenchmarkId::from_parameter(agents), &world, |b, world| {
+            b.iter(|| black_box(world.bench_spatial_cohesion()));
+        });
+    }
+    group.finish();
+}
+
+/// One-flag config update on a live world, which revalidates and rebuilds the
+/// derived mutation rates and shared metabolism engine.
+fn bench_set_config(c: &mut Criterion) {
+    let mut group = c.benchmark_group("set_config");
+    for agents in AGENT_COUNTS {
+        let mut world = world_with_agents(agents);
+        group.bench_function(BenchmarkId::from_parameter(agents), |b| {
+            b.iter(|| {
+                world
+                    .config_mut_with(|config| config.enable_evolution = !config.enable_evolution)
+                    .expect("toggling evolution keeps the config valid");
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_genome_mutate(c: &mut Criterion) {
+    let mut rng = ChaCha12Rng::seed_from_u64(SEED);
+    let data = (0..256).map(|_| rng.random_range(-1.0f32..1.0)).collect();
+    let genome = Genome::from_data(data).expect("256 floats cover the regulatory segments");
+    let rates = MutationRates::default();
+    c.bench_function("genome_mutate_256", |b| {
+        b.iter_batched_ref(
+            || genome.clone(),
+            |g| g.mutate(&mut rng, &rates),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = configure();
+    targets = bench_world_step, bench_boundary_exposure, bench_nn_query_phase,
+        bench_organism_phases, bench_step_metrics, bench_metrics_selection, bench_spatial_cohesion,
+        bench_set_config, bench_genome_mutate
+}
+criterion_main!(benches);
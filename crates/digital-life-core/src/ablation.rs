@@ -0,0 +1,412 @@
+//! Criterion ablation matrix.
+//!
+//! Runs a baseline config and one single-criterion knockout per [`Criterion`]
+//! across a list of seeds, producing one [`AblationCell`] per (condition, seed).
+//! Every cell builds its own world from the seed, so results depend only on the
+//! spec and are identical regardless of how cells are scheduled.
+
+use crate::config::{CriteriaMask, SimConfig};
+use crate::metrics::RunSummary;
+use crate::world::{ExperimentError, World, WorldInitError};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt};
+
+/// One of the seven life criteria, mapped onto its `enable_*` config flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Criterion {
+    Metabolism,
+    Homeostasis,
+    BoundaryMaintenance,
+    Growth,
+    Reproduction,
+    Response,
+    Evolution,
+}
+
+impl Criterion {
+    pub const ALL: [Criterion; 7] = [
+        Criterion::Metabolism,
+        Criterion::Homeostasis,
+        Criterion::BoundaryMaintenance,
+        Criterion::Growth,
+        Criterion::Reproduction,
+        Criterion::Response,
+        Criterion::Evolution,
+    ];
+
+    /// Name of the `SimConfig` flag this criterion toggles.
+    pub fn flag_name(self) -> &'static str {
+        match self {
+            Criterion::Metabolism => "enable_metabolism",
+            Criterion::Homeostasis => "enable_homeostasis",
+            Criterion::BoundaryMaintenance => "enable_boundary_maintenance",
+            Criterion::Growth => "enable_growth",
+            Criterion::Reproduction => "enable_reproduction",
+            Criterion::Response => "enable_response",
+            Criterion::Evolution => "enable_evolution",
+        }
+    }
+
+    /// Copy of `config` with this criterion disabled.
+    pub fn ablate(self, config: &SimConfig) -> SimConfig {
+        let mut ablated = config.clone();
+        ablated.set_criteria_mask(config.criteria_mask() - self.into());
+        ablated
+    }
+}
+
+impl From<Criterion> for CriteriaMask {
+    fn from(criterion: Criterion) -> Self {
+        match criterion {
+            Criterion::Metabolism => Self::METABOLISM,
+            Criterion::Homeostasis => Self::HOMEOSTASIS,
+            Criterion::BoundaryMaintenance => Self::BOUNDARY,
+            Criterion::Growth => Self::GROWTH,
+            Criterion::Reproduction => Self::REPRODUCTION,
+            Criterion::Response => Self::RESPONSE,
+            Criterion::Evolution => Self::EVOLUTION,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AblationSpec {
+    pub baseline: SimConfig,
+    pub criteria: Vec<Criterion>,
+    /// Each seed overrides `baseline.seed` for one cell per condition.
+    pub seeds: Vec<u64>,
+    pub steps: usize,
+    pub sample_every: usize,
+}
+
+impl AblationSpec {
+    /// Full seven-criterion matrix against `baseline`.
+    pub fn all_criteria(
+        baseline: SimConfig,
+        seeds: Vec<u64>,
+        steps: usize,
+        sample_every: usize,
+    ) -> Self {
+        Self {
+            baseline,
+            criteria: Criterion::ALL.to_vec(),
+            seeds,
+            steps,
+            sample_every,
+        }
+    }
+}
+
+/// Headline numbers derived from a cell's [`RunSummary`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CellSummary {
+    pub final_alive_count: usize,
+    /// First sampled step with no alive organisms, if the population collapsed.
+    pub collapse_step: Option<usize>,
+    /// Mean of sampled `energy_mean` over the last 10% of steps (at least the final sample).
+    pub late_energy_mean: f32,
+}
+
+impl CellSummary {
+    pub fn from_run(run: &RunSummary) -> Self {
+        let collapse_step = run
+            .samples
+            .iter()
+            .find(|s| s.alive_count == 0)
+            .map(|s| s.step);
+        let window_start = run.steps - run.steps.div_ceil(10);
+        let late: Vec<f32> = run
+            .samples
+            .iter()
+            .filter(|s| s.step > window_start)
+            .map(|s| s.energy_mean)
+            .collect();
+        let late_energy_mean = if late.is_empty() {
+            0.0
+        } else {
+            late.iter().sum::<f32>() / late.len() as f32
+        };
+        Self {
+            final_alive_count: run.final_alive_count,
+            collapse_step,
+            late_energy_mean,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AblationCell {
+    /// Disabled criterion, or `None` for the baseline condition.
+    pub criterion: Option<Criterion>,
+    pub seed: u64,
+    pub summary: CellSummary,
+    pub run: RunSummary,
+}
+
+/// Cells ordered baseline first, then by `spec.criteria`, each in `spec.seeds` order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AblationResults {
+    pub spec: AblationSpec,
+    pub cells: Vec<AblationCell>,
+}
+
+impl AblationResults {
+    /// Cells for one condition (`None` for baseline), in seed order.
+    pub fn condition(&self, criterion: Option<Criterion>) -> impl Iterator<Item = &AblationCell> {
+        self.cells.iter().filter(move |c| c.criterion == criterion)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AblationError {
+    NoSeeds,
+    World {
+        criterion: Option<Criterion>,
+        seed: u64,
+        source: WorldInitError,
+    },
+    Experiment {
+        criterion: Option<Criterion>,
+        seed: u64,
+        source: ExperimentError,
+    },
+}
+
+fn condition_label(criterion: Option<Criterion>) -> &'static str {
+    criterion.map_or("baseline", Criterion::flag_name)
+}
+
+impl fmt::Display for AblationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AblationError::NoSeeds => write!(f, "ablation spec must list at least one seed"),
+            AblationError::World {
+                criterion,
+                seed,
+                source,
+            } => write!(
+                f,
+                "{} (seed {seed}): invalid world configuration: {source}",
+                condition_label(*criterion)
+            ),
+            AblationError::Experiment {
+                criterion,
+                seed,
+                source,
+            } => write!(f, "{} (seed {seed}): {source}", condition_label(*criterion)),
+        }
+    }
+}
+
+impl Error for AblationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AblationError::NoSeeds => None,
+            AblationError::World { source, .. } => Some(source),
+            AblationError::Experiment { source, .. } => Some(source),
+        }
+    }
+}
+
+fn run_cell(
+    spec: &AblationSpec,
+    criterion: Option<Criterion>,
+    seed: u64,
+) -> Result<AblationCell, AblationError> {
+    let mut config = match criterion {
+        Some(c) => c.ablate(&spec.baseline),
+        None => spec.baseline.clone(),
+    };
+    config.seed = seed;
+    let mut world = World::from_config(config).map_err(|source| AblationError::World {
+        criterion,
+        seed,
+        source,
+    })?;
+    let run = world
+        .try_run_experiment(spec.steps, spec.sample_every)
+        .map_err(|source| AblationError::Experiment {
+            criterion,
+            seed,
+            source,
+        })?;
+    Ok(AblationCell {
+        criterion,
+        seed,
+        summary: CellSummary::from_run(&run),
+        run,
+    })
+}
+
+/// Run every (condition, seed) cell of `spec` in parallel.
+pub fn run_ablation(spec: AblationSpec) -> Result<AblationResults, AblationError> {
+    if spec.seeds.is_empty() {
+        return Err(AblationError::NoSeeds);
+    }
+    let conditions: Vec<(Option<Criterion>, u64)> = std::iter::once(None)
+        .chain(spec.criteria.iter().copied().map(Some))
+        .flat_map(|c| spec.seeds.iter().map(move |&seed| (c, seed)))
+        .collect();
+    let cells = conditions
+        .into_par_iter()
+        .map(|(criterion, seed)| run_cell(&spec, criterion, seed))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(AblationResults { spec, cells })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MetricsSelection;
+    use crate::metrics::{LifespanSummary, StepMetrics};
+
+    fn small_spec(criteria: Vec<Criterion>) -> AblationSpec {
+        AblationSpec {
+            baseline: SimConfig {
+                num_organisms: 4,
+                agents_per_organism: 5,
+                world_size: 40.0,
+                ..SimConfig::default()
+            },
+            criteria,
+            seeds: vec![3, 11],
+            steps: 20,
+            sample_every: 5,
+        }
+    }
+
+    #[test]
+    fn ablate_disables_only_the_mapped_flag() {
+        let base = SimConfig::default();
+        for criterion in Criterion::ALL {
+            let ablated = toml::Table::try_from(criterion.ablate(&base)).unwrap();
+            let base_table = toml::Table::try_from(&base).unwrap();
+            let changed: Vec<&String> = ablated
+                .iter()
+                .filter(|(k, v)| base_table.get(*k) != Some(*v))
+                .map(|(k, _)| k)
+                .collect();
+            assert_eq!(changed, vec![criterion.flag_name()]);
+        }
+    }
+
+    #[test]
+    fn matrix_covers_baseline_and_each_criterion_per_seed() {
+        let spec = small_spec(vec![Criterion::Metabolism, Criterion::Reproduction]);
+        let results = run_ablation(spec).expect("ablation should run");
+        assert_eq!(results.cells.len(), 6);
+        let order: Vec<(Option<Criterion>, u64)> = results
+            .cells
+            .iter()
+            .map(|c| (c.criterion, c.seed))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (None, 3),
+                (None, 11),
+                (Some(Criterion::Metabolism), 3),
+                (Some(Criterion::Metabolism), 11),
+                (Some(Criterion::Reproduction), 3),
+                (Some(Criterion::Reproduction), 11),
+            ]
+        );
+        assert_eq!(results.condition(None).count(), 2);
+        for cell in &results.cells {
+            assert_eq!(cell.run.steps, 20);
+            assert_eq!(cell.summary.final_alive_count, cell.run.final_alive_count);
+        }
+        assert!(results
+            .condition(Some(Criterion::Reproduction))
+            .all(|c| c.run.total_reproduction_events == 0));
+    }
+
+    #[test]
+    fn ablation_is_reproducible_and_exports_json() {
+        let spec = small_spec(vec![Criterion::Response]);
+        let a = run_ablation(spec.clone()).unwrap();
+        let b = run_ablation(spec).unwrap();
+        let json = a.to_json().unwrap();
+        assert_eq!(json, b.to_json().unwrap());
+        let parsed: AblationResults = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.cells.len(), a.cells.len());
+        assert_eq!(parsed.spec, a.spec);
+        let hashes: Vec<u64> = a.cells.iter().map(|c| c.run.final_state_hash).collect();
+        assert_ne!(hashes[0], hashes[1], "distinct seeds should diverge");
+    }
+
+    #[test]
+    fn empty_seed_list_is_rejected() {
+        let mut spec = small_spec(vec![]);
+        spec.seeds.clear();
+        assert_eq!(run_ablation(spec).unwrap_err(), AblationError::NoSeeds);
+    }
+
+    #[test]
+    fn invalid_baseline_reports_condition_and_seed() {
+        let mut spec = small_spec(vec![]);
+        spec.baseline.num_organisms = 0;
+        let err = run_ablation(spec).unwrap_err();
+        assert!(matches!(
+            err,
+            AblationError::World {
+                criterion: None,
+                seed: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn cell_summary_finds_collapse_and_late_energy() {
+        let sample = |step, alive_count, energy_mean| StepMetrics {
+            step,
+            alive_count,
+            energy_mean,
+            ..StepMetrics::default()
+        };
+        let run = RunSummary {
+            schema_version: RunSummary::SCHEMA_VERSION,
+            steps: 100,
+            sample_every: 10,
+            final_alive_count: 0,
+            samples: vec![
+                sample(10, 4, 1.0),
+                sample(80, 2, 0.5),
+                sample(90, 0, 0.3),
+                sample(100, 0, 0.1),
+            ],
+            lifespans: Vec::new(),
+            lifespan_summary: LifespanSummary::default(),
+            shock_recoveries: Vec::new(),
+            total_reproduction_events: 0,
+            lineage_events: Vec::new(),
+            lineage_streamed: false,
+            fusion_events: Vec::new(),
+            births_per_generation: Vec::new(),
+            organism_snapshots: Vec::new(),
+            final_state_hash: 0,
+            events: Vec::new(),
+            events_truncated: false,
+            steps_executed: 100,
+            cancelled: false,
+            metabolism_mode: crate::config::MetabolismMode::Toy,
+            criteria_mask: Some(CriteriaMask::all()),
+            metrics_selection: MetricsSelection::all(),
+            timings: None,
+            migrated_from: None,
+            paired_seed: None,
+            snapshot_schedule: None,
+        };
+        let summary = CellSummary::from_run(&run);
+        assert_eq!(summary.final_alive_count, 0);
+        assert_eq!(summary.collapse_step, Some(90));
+        assert!((summary.late_energy_mean - 0.1).abs() < f32::EPSILON);
+    }
+}
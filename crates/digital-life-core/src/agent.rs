@@ -7,16 +7,28 @@ pub struct Agent {
     pub position: [f64; 2],
     pub velocity: [f64; 2],
     pub internal_state: [f32; 4],
+    /// Facing angle in radians, counter-clockwise from +x, in `[0, 2π)`. Only
+    /// moves the agent under `Locomotion::Heading`.
+    pub heading: f64,
+    /// Index of the agent's role archetype in its organism's developmental
+    /// program; always 0 when `agent_role_count` is 0.
+    pub role: u8,
 }
 
 impl Agent {
     pub fn new(id: u32, organism_id: u16, position: [f64; 2]) -> Self {
+        Self::with_heading(id, organism_id, position, 0.0)
+    }
+
+    pub fn with_heading(id: u32, organism_id: u16, position: [f64; 2], heading: f64) -> Self {
         Self {
             id,
             organism_id,
             position,
             velocity: [0.0; 2],
             internal_state: [0.5; 4],
+            heading: heading.rem_euclid(std::f64::consts::TAU),
+            role: 0,
         }
     }
 }
@@ -0,0 +1,183 @@
+//! Post-run population-genetic statistics.
+//!
+//! Pure functions over a finished [`RunSummary`]: founder-lineage survival from
+//! `lineage_events`, and bottleneck statistics from the sampled population sizes.
+//! None of them need the world that produced the summary. [`compare`] and
+//! [`compare_replicates`] set runs against each other for A/B analysis.
+
+use crate::lineage::LineageTree;
+use crate::metrics::RunSummary;
+
+mod compare;
+
+pub use compare::{
+    cohens_d, compare, compare_replicates, mann_whitney_u, ComparisonReport, MannWhitneyU,
+    MetricComparison, MANN_WHITNEY_EXACT_MAX,
+};
+
+/// Distinct founder stable ids, ascending, that `final_alive_ids` descend from.
+///
+/// Each alive organism is traced back through `summary.lineage_events` to an
+/// organism with no logged parent: an initial founder, an immigrant, or a parent
+/// born before the log started. Alive ids absent from the log count as their own
+/// founder, as does every alive id when lineage was streamed instead of embedded.
+pub fn surviving_founder_lineages(summary: &RunSummary, final_alive_ids: &[u64]) -> Vec<u64> {
+    let tree = LineageTree::from_events(&summary.lineage_events, &[]);
+    let mut founders: Vec<u64> = final_alive_ids
+        .iter()
+        .map(|&id| {
+            let mut founder = id;
+            while let Some(parent) = tree.parent(founder) {
+                founder = parent;
+            }
+            founder
+        })
+        .collect();
+    founders.sort_unstable();
+    founders.dedup();
+    founders
+}
+
+/// Running effective population size at each sample of `summary.samples`.
+///
+/// Entry `i` is the harmonic mean of `alive_count` over samples `0..=i`, each
+/// weighted by the generation turnover it represents (`birth_count /
+/// alive_count`), so bottlenecks during heavy reproduction dominate. Before the
+/// first birth every sample weighs equally. Once the population hits zero the
+/// estimate stays zero.
+pub fn effective_population_size_series(summary: &RunSummary) -> Vec<f64> {
+    let mut series = Vec::with_capacity(summary.samples.len());
+    let (mut turnover, mut turnover_over_n) = (0.0f64, 0.0f64);
+    let mut inverse_sum = 0.0f64;
+    let mut extinct = false;
+    for (i, sample) in summary.samples.iter().enumerate() {
+        let n = sample.alive_count as f64;
+        extinct |= sample.alive_count == 0;
+        if extinct {
+            series.push(0.0);
+            continue;
+        }
+        let generations = sample.birth_count as f64 / n;
+        turnover += generations;
+        turnover_over_n += generations / n;
+        inverse_sum += 1.0 / n;
+        series.push(if turnover > 0.0 {
+            turnover / turnover_over_n
+        } else {
+            (i + 1) as f64 / inverse_sum
+        });
+    }
+    series
+}
+
+/// Smallest sampled `alive_count` and the step of its first occurrence, or `None`
+/// without samples.
+pub fn min_population_and_step(summary: &RunSummary) -> Option<(usize, usize)> {
+    summary
+        .samples
+        .iter()
+        .min_by_key(|s| s.alive_count)
+        .map(|s| (s.alive_count, s.step))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{LineageEvent, StepMetrics};
+
+    fn birth(step: usize, parent: u64, child: u64, generation: u32) -> LineageEvent {
+        LineageEvent {
+            step,
+            parent_stable_id: parent,
+            child_stable_id: child,
+            generation,
+        }
+    }
+
+    fn sample(step: usize, alive_count: usize, birth_count: usize) -> StepMetrics {
+        StepMetrics {
+            step,
+            alive_count,
+            birth_count,
+            ..StepMetrics::default()
+        }
+    }
+
+    /// Founders 0 and 1 both reproduce; the population then crashes to two
+    /// organisms of founder 0's lineage, which regrows while founder 1's dies out.
+    fn two_founder_collapse() -> RunSummary {
+        let mut summary: RunSummary = serde_json::from_value(serde_json::json!({
+            "steps": 50,
+            "sample_every": 10,
+            "final_alive_count": 4,
+            "samples": [],
+        }))
+        .unwrap();
+        summary.lineage_events = vec![
+            birth(5, 0, 2, 1),
+            birth(6, 1, 3, 1),
+            birth(7, 1, 4, 1),
+            birth(30, 2, 5, 2),
+            birth(31, 5, 6, 3),
+        ];
+        summary.samples = vec![
+            sample(10, 8, 4),
+            sample(20, 2, 1),
+            sample(30, 3, 1),
+            sample(40, 4, 1),
+            sample(50, 4, 0),
+        ];
+        summary
+    }
+
+    #[test]
+    fn collapse_leaves_a_single_founder_lineage() {
+        let summary = two_founder_collapse();
+        assert_eq!(surviving_founder_lineages(&summary, &[2, 5, 6]), vec![0]);
+        assert_eq!(surviving_founder_lineages(&summary, &[4, 6]), vec![0, 1]);
+        // Unlogged survivors found their own lineage.
+        assert_eq!(surviving_founder_lineages(&summary, &[6, 9]), vec![0, 9]);
+        assert!(surviving_founder_lineages(&summary, &[]).is_empty());
+    }
+
+    #[test]
+    fn bottleneck_drags_effective_size_below_census() {
+        let summary = two_founder_collapse();
+        assert_eq!(min_population_and_step(&summary), Some((2, 20)));
+        let ne = effective_population_size_series(&summary);
+        assert_eq!(ne.len(), 5);
+        assert!((ne[0] - 8.0).abs() < 1e-12);
+        // Turnover 1/2 at N=8, 1/2 at N=2 and 1/3 at N=3.
+        let expected = (0.5 + 0.5 + 1.0 / 3.0) / (0.5 / 8.0 + 0.5 / 2.0 + 1.0 / 9.0);
+        assert!((ne[2] - expected).abs() < 1e-12);
+        let census_mean = summary
+            .samples
+            .iter()
+            .map(|s| s.alive_count as f64)
+            .sum::<f64>()
+            / 5.0;
+        assert!(ne[4] < census_mean, "{} vs {census_mean}", ne[4]);
+    }
+
+    #[test]
+    fn extinction_and_empty_samples() {
+        let mut summary = two_founder_collapse();
+        summary.samples.push(sample(60, 0, 0));
+        summary.samples.push(sample(70, 0, 0));
+        let ne = effective_population_size_series(&summary);
+        assert_eq!(&ne[5..], &[0.0, 0.0]);
+        assert_eq!(min_population_and_step(&summary), Some((0, 60)));
+
+        summary.samples.clear();
+        assert!(effective_population_size_series(&summary).is_empty());
+        assert_eq!(min_population_and_step(&summary), None);
+    }
+
+    #[test]
+    fn no_births_gives_harmonic_mean_of_census() {
+        let mut summary = two_founder_collapse();
+        summary.samples = vec![sample(0, 4, 0), sample(1, 2, 0)];
+        let ne = effective_population_size_series(&summary);
+        assert!((ne[1] - 2.0 / (0.25 + 0.5)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,466 @@
+//! Treatment-versus-control comparison of finished runs.
+//!
+//! Every numeric field of [`StepMetrics`] is a metric series, named by its
+//! serialized path (`internal_state_mean[1]`, `early_warning.variance`). Differences
+//! are always `b - a`, so pass the control as `a` and the treatment as `b`.
+
+use crate::metrics::{RunSummary, StepMetrics};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Samples at or below this size per group get an exact Mann-Whitney p-value.
+pub const MANN_WHITNEY_EXACT_MAX: usize = 30;
+
+/// How one metric series differs between two runs or two groups of runs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MetricComparison {
+    pub metric: String,
+    /// Final value of `b` minus final value of `a`; replicate groups compare the
+    /// means of their final values.
+    pub final_difference: f64,
+    /// Mean of `b - a` over the last `tail_fraction` of samples; replicate groups
+    /// compare the means of each run's own tail mean.
+    pub tail_mean_difference: f64,
+    /// Cohen's d of the replicates' tail means, pooled standard deviation; `None`
+    /// for single runs or when the pooled deviation is zero.
+    pub cohens_d: Option<f64>,
+}
+
+/// Two-sided Mann-Whitney U (rank-sum) test, ties given midranks.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MannWhitneyU {
+    /// U statistic of the first group: its rank sum minus `n(n+1)/2`.
+    pub u: f64,
+    pub p_value: f64,
+    /// Whether `p_value` is exact (both groups within [`MANN_WHITNEY_EXACT_MAX`]) or
+    /// from the tie-corrected normal approximation.
+    pub exact: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    pub tail_fraction: f64,
+    pub a_runs: usize,
+    pub b_runs: usize,
+    /// Metrics present in every run, by name.
+    pub metrics: Vec<MetricComparison>,
+    /// Mann-Whitney U over `final_alive_count`; `None` when either group is empty.
+    pub final_alive_count: Option<MannWhitneyU>,
+}
+
+impl ComparisonReport {
+    pub fn metric(&self, name: &str) -> Option<&MetricComparison> {
+        self.metrics.iter().find(|m| m.metric == name)
+    }
+}
+
+/// Compare two runs over the metric series they share, aligned on sampled step.
+/// A metric with no step in common is left out.
+pub fn compare(a: &RunSummary, b: &RunSummary, tail_fraction: f64) -> ComparisonReport {
+    let (series_a, series_b) = (metric_series(a), metric_series(b));
+    let metrics = series_a
+        .iter()
+        .filter_map(|(name, values_a)| {
+            let values_b = series_b.get(name)?;
+            let by_step: BTreeMap<usize, f64> = values_a.iter().copied().collect();
+            let differences: Vec<f64> = values_b
+                .iter()
+                .filter_map(|(step, vb)| Some(vb - by_step.get(step)?))
+                .collect();
+            let final_difference = *differences.last()?;
+            Some(MetricComparison {
+                metric: name.clone(),
+                final_difference,
+                tail_mean_difference: mean(tail(&differences, tail_fraction)),
+                cohens_d: None,
+            })
+        })
+        .collect();
+    ComparisonReport {
+        tail_fraction,
+        a_runs: 1,
+        b_runs: 1,
+        metrics,
+        final_alive_count: mann_whitney_u(
+            &[a.final_alive_count as f64],
+            &[b.final_alive_count as f64],
+        ),
+    }
+}
+
+/// Compare two groups of replicate runs. Each run is reduced to its final value and
+/// its own tail mean per metric, so runs need not share sampled steps.
+pub fn compare_replicates(
+    a: &[RunSummary],
+    b: &[RunSummary],
+    tail_fraction: f64,
+) -> ComparisonReport {
+    let reduce = |runs: &[RunSummary]| -> Vec<BTreeMap<String, (f64, f64)>> {
+        runs.iter()
+            .map(|run| {
+                metric_series(run)
+                    .into_iter()
+                    .filter_map(|(name, series)| {
+                        let values: Vec<f64> = series.iter().map(|&(_, v)| v).collect();
+                        let last = *values.last()?;
+                        Some((name, (last, mean(tail(&values, tail_fraction)))))
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    let (reduced_a, reduced_b) = (reduce(a), reduce(b));
+    let metrics = match (reduced_a.first(), reduced_b.is_empty()) {
+        (Some(first), false) => first
+            .keys()
+            .filter_map(|name| {
+                let values = |runs: &[BTreeMap<String, (f64, f64)>]| {
+                    runs.iter()
+                        .map(|run| run.get(name).copied())
+                        .collect::<Option<Vec<_>>>()
+                        .map(|values| values.into_iter().unzip::<_, _, Vec<_>, Vec<_>>())
+                };
+                let (finals_a, tails_a) = values(&reduced_a)?;
+                let (finals_b, tails_b) = values(&reduced_b)?;
+                Some(MetricComparison {
+                    metric: name.clone(),
+                    final_difference: mean(&finals_b) - mean(&finals_a),
+                    tail_mean_difference: mean(&tails_b) - mean(&tails_a),
+                    cohens_d: cohens_d(&tails_a, &tails_b),
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let final_alive = |runs: &[RunSummary]| -> Vec<f64> {
+        runs.iter().map(|r| r.final_alive_count as f64).collect()
+    };
+    ComparisonReport {
+        tail_fraction,
+        a_runs: a.len(),
+        b_runs: b.len(),
+        metrics,
+        final_alive_count: mann_whitney_u(&final_alive(a), &final_alive(b)),
+    }
+}
+
+/// Cohen's d of `b` over `a` with the pooled sample standard deviation. `None`
+/// with fewer than three values in total or no spread.
+pub fn cohens_d(a: &[f64], b: &[f64]) -> Option<f64> {
+    let (n_a, n_b) = (a.len(), b.len());
+    if n_a == 0 || n_b == 0 || n_a + n_b < 3 {
+        return None;
+    }
+    let sum_sq = |values: &[f64]| {
+        let m = mean(values);
+        values.iter().map(|v| (v - m).powi(2)).sum::<f64>()
+    };
+    let pooled = ((sum_sq(a) + sum_sq(b)) / (n_a + n_b - 2) as f64).sqrt();
+    let d = (mean(b) - mean(a)) / pooled;
+    (pooled > 0.0 && d.is_finite()).then_some(d)
+}
+
+/// Two-sided Mann-Whitney U test of `a` against `b`; `None` if either is empty.
+///
+/// Exact when both groups have at most [`MANN_WHITNEY_EXACT_MAX`] values, by
+/// enumerating the rank-sum distribution of every split of the pooled midranks, so
+/// ties are handled exactly too. Larger groups use the normal approximation with tie
+/// and continuity corrections.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> Option<MannWhitneyU> {
+    let (n_a, n_b) = (a.len(), b.len());
+    if n_a == 0 || n_b == 0 {
+        return None;
+    }
+    let n = n_a + n_b;
+    let mut pooled: Vec<(f64, bool)> = a
+        .iter()
+        .map(|&v| (v, true))
+        .chain(b.iter().map(|&v| (v, false)))
+        .collect();
+    pooled.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // Midranks doubled so they stay integral: a tie over 1-based ranks i+1..=j
+    // has midrank (i+1+j)/2.
+    let mut doubled_ranks = vec![0u64; n];
+    let mut tie_sizes = Vec::new();
+    let mut i = 0;
+    while i < n {
+        let j = (i..n).find(|&j| pooled[j].0 != pooled[i].0).unwrap_or(n);
+        doubled_ranks[i..j].fill((i + 1 + j) as u64);
+        tie_sizes.push((j - i) as f64);
+        i = j;
+    }
+    let observed: u64 = doubled_ranks
+        .iter()
+        .zip(&pooled)
+        .filter(|(_, (_, in_a))| *in_a)
+        .map(|(rank, _)| rank)
+        .sum();
+    let u = observed as f64 / 2.0 - (n_a * (n_a + 1)) as f64 / 2.0;
+
+    let exact = n_a <= MANN_WHITNEY_EXACT_MAX && n_b <= MANN_WHITNEY_EXACT_MAX;
+    let p_value = if exact {
+        exact_rank_sum_p(&doubled_ranks, n_a, observed)
+    } else {
+        let mean_u = (n_a * n_b) as f64 / 2.0;
+        let ties: f64 = tie_sizes.iter().map(|t| t * t * t - t).sum();
+        let variance =
+            (n_a * n_b) as f64 / 12.0 * ((n + 1) as f64 - ties / (n as f64 * (n - 1) as f64));
+        if variance > 0.0 {
+            let z = ((u - mean_u).abs() - 0.5).max(0.0) / variance.sqrt();
+            erfc(z / std::f64::consts::SQRT_2)
+        } else {
+            1.0
+        }
+    };
+    Some(MannWhitneyU {
+        u,
+        p_value: p_value.min(1.0),
+        exact,
+    })
+}
+
+/// Probability that `n_a` of the pooled `doubled_ranks`, drawn uniformly, sum at
+/// least as far from their expectation as `observed`.
+fn exact_rank_sum_p(doubled_ranks: &[u64], n_a: usize, observed: u64) -> f64 {
+    let max_sum: u64 = doubled_ranks.iter().sum();
+    // ways[k][s]: subsets of k ranks summing to s. At most C(60, 30) < 2^64.
+    let mut ways = vec![vec![0u64; max_sum as usize + 1]; n_a + 1];
+    ways[0][0] = 1;
+    for &rank in doubled_ranks {
+        let rank = rank as usize;
+        for k in (1..=n_a).rev() {
+            let (lower, upper) = ways.split_at_mut(k);
+            for (s, count) in lower[k - 1]
+                .iter()
+                .enumerate()
+                .take(max_sum as usize + 1 - rank)
+            {
+                upper[0][s + rank] += count;
+            }
+        }
+    }
+    let expected = (n_a * (doubled_ranks.len() + 1)) as i64;
+    let distance = (observed as i64 - expected).abs();
+    let (mut extreme, mut total) = (0u64, 0u64);
+    for (s, &count) in ways[n_a].iter().enumerate() {
+        total += count;
+        if (s as i64 - expected).abs() >= distance {
+            extreme += count;
+        }
+    }
+    extreme as f64 / total as f64
+}
+
+/// Complementary error function, fractional error below 1.2e-7 (Numerical Recipes'
+/// Chebyshev fit).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let r = t * poly.exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+/// Every numeric metric of every sample as `(step, value)` series, by metric name.
+fn metric_series(summary: &RunSummary) -> BTreeMap<String, Vec<(usize, f64)>> {
+    let mut series: BTreeMap<String, Vec<(usize, f64)>> = BTreeMap::new();
+    for sample in &summary.samples {
+        let mut values = Vec::new();
+        flatten_numbers(&sample_value(sample), String::new(), &mut values);
+        for (name, value) in values {
+            if name != "step" {
+                series.entry(name).or_default().push((sample.step, value));
+            }
+        }
+    }
+    series
+}
+
+fn sample_value(sample: &StepMetrics) -> Value {
+    serde_json::to_value(sample).expect("StepMetrics serializes to JSON")
+}
+
+fn flatten_numbers(value: &Value, path: String, out: &mut Vec<(String, f64)>) {
+    match value {
+        Value::Number(number) => out.extend(number.as_f64().map(|v| (path, v))),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten_numbers(item, format!("{path}[{i}]"), out);
+            }
+        }
+        Value::Object(fields) => {
+            for (key, item) in fields {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                flatten_numbers(item, path, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The last `fraction` of `values`, at least one value when any exist.
+fn tail(values: &[f64], fraction: f64) -> &[f64] {
+    let n = values.len();
+    let count = ((n as f64 * fraction).ceil() as usize).clamp(1.min(n), n);
+    &values[n - count..]
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(final_alive_count: usize, samples: &[(usize, f32, usize)]) -> RunSummary {
+        let mut summary: RunSummary = serde_json::from_value(serde_json::json!({
+            "steps": 100,
+            "sample_every": 10,
+            "final_alive_count": final_alive_count,
+            "samples": [],
+        }))
+        .unwrap();
+        summary.samples = samples
+            .iter()
+            .map(|&(step, energy_mean, alive_count)| StepMetrics {
+                step,
+                energy_mean,
+                alive_count,
+                ..StepMetrics::default()
+            })
+            .collect();
+        summary
+    }
+
+    #[test]
+    fn compare_aligns_on_shared_steps() {
+        let a = run(4, &[(10, 1.0, 4), (20, 2.0, 4), (30, 3.0, 4), (40, 4.0, 4)]);
+        // Step 25 has no partner in `a` and is skipped.
+        let b = run(
+            6,
+            &[
+                (10, 2.0, 5),
+                (20, 4.0, 5),
+                (25, 9.0, 9),
+                (30, 5.0, 6),
+                (40, 8.0, 6),
+            ],
+        );
+        let report = compare(&a, &b, 0.5);
+        let energy = report.metric("energy_mean").unwrap();
+        assert_eq!(energy.final_difference, 4.0);
+        // Differences 1, 2, 2, 4; the last half averages 3.
+        assert_eq!(energy.tail_mean_difference, 3.0);
+        assert_eq!(energy.cohens_d, None);
+        let alive = report.metric("alive_count").unwrap();
+        assert_eq!(alive.final_difference, 2.0);
+        assert_eq!(alive.tail_mean_difference, 2.0);
+        assert!(report.metric("step").is_none());
+        assert!(report.metric("internal_state_mean[3]").is_some());
+        assert_eq!((report.a_runs, report.b_runs), (1, 1));
+        let test = report.final_alive_count.unwrap();
+        assert_eq!((test.u, test.p_value), (0.0, 1.0));
+
+        // A tiny fraction still keeps the final sample.
+        let report = compare(&a, &b, 0.0);
+        assert_eq!(
+            report.metric("energy_mean").unwrap().tail_mean_difference,
+            4.0
+        );
+    }
+
+    #[test]
+    fn compare_replicates_reports_cohens_d_of_tail_means() {
+        let control: Vec<_> = [1.0, 2.0, 3.0]
+            .iter()
+            .map(|&e| run(2, &[(10, 0.0, 2), (20, e, 2)]))
+            .collect();
+        let treatment: Vec<_> = [3.0, 4.0, 5.0]
+            .iter()
+            .map(|&e| run(5, &[(10, 0.0, 5), (20, e, 5)]))
+            .collect();
+        let report = compare_replicates(&control, &treatment, 0.5);
+        let energy = report.metric("energy_mean").unwrap();
+        assert_eq!(energy.final_difference, 2.0);
+        assert_eq!(energy.tail_mean_difference, 2.0);
+        // Means 2 and 4, both sample variances 1.
+        assert_eq!(energy.cohens_d, Some(2.0));
+        // No spread in either group.
+        assert_eq!(report.metric("alive_count").unwrap().cohens_d, None);
+        assert_eq!((report.a_runs, report.b_runs), (3, 3));
+        let test = report.final_alive_count.unwrap();
+        assert_eq!(test.u, 0.0);
+        // Three tied values per group: every split but the observed two is closer.
+        assert!((test.p_value - 0.1).abs() < 1e-12);
+
+        let empty = compare_replicates(&control, &[], 0.5);
+        assert!(empty.metrics.is_empty());
+        assert_eq!(empty.final_alive_count, None);
+    }
+
+    #[test]
+    fn mann_whitney_exact_matches_hand_enumeration() {
+        let separated = mann_whitney_u(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]).unwrap();
+        assert!(separated.exact);
+        assert_eq!(separated.u, 0.0);
+        // Only the two most extreme of the C(6,3) = 20 splits.
+        assert!((separated.p_value - 0.1).abs() < 1e-12);
+        let reversed = mann_whitney_u(&[4.0, 5.0, 6.0], &[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(reversed.u, 9.0);
+        assert_eq!(reversed.p_value, separated.p_value);
+
+        // Ranks 1, 2.5, 2.5, 4: rank sums 3.5, 3.5, 5, 5, 6.5, 6.5 over the splits.
+        let tied = mann_whitney_u(&[1.0, 2.0], &[2.0, 3.0]).unwrap();
+        assert_eq!(tied.u, 0.5);
+        assert!((tied.p_value - 4.0 / 6.0).abs() < 1e-12);
+
+        let identical = mann_whitney_u(&[1.0, 1.0], &[1.0, 1.0]).unwrap();
+        assert_eq!((identical.u, identical.p_value), (2.0, 1.0));
+        assert_eq!(mann_whitney_u(&[], &[1.0]), None);
+    }
+
+    #[test]
+    fn mann_whitney_switches_to_normal_approximation_above_thirty() {
+        let low: Vec<f64> = (0..30).map(f64::from).collect();
+        let high: Vec<f64> = (30..60).map(f64::from).collect();
+        let exact = mann_whitney_u(&low, &high).unwrap();
+        assert!(exact.exact);
+        assert!(exact.p_value > 0.0 && exact.p_value < 1e-15);
+
+        let low: Vec<f64> = (0..31).map(f64::from).collect();
+        let high: Vec<f64> = (31..62).map(f64::from).collect();
+        let approx = mann_whitney_u(&low, &high).unwrap();
+        assert!(!approx.exact);
+        assert!(approx.p_value < 1e-9, "{}", approx.p_value);
+
+        let mixed: Vec<f64> = (0..40).map(|i| f64::from(i % 2)).collect();
+        let shuffled: Vec<f64> = (0..40).map(|i| f64::from((i + 1) % 2)).collect();
+        let null = mann_whitney_u(&mixed, &shuffled).unwrap();
+        assert!((null.p_value - 1.0).abs() < 1e-6, "{}", null.p_value);
+    }
+
+    #[test]
+    fn erfc_matches_reference_values() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-7);
+        assert!((erfc(1.0) - 0.157_299_207).abs() < 1e-7);
+        assert!((erfc(-1.0) - 1.842_700_793).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,313 @@
+//! Stimulus–response assays.
+//!
+//! [`run_response_assay`] tests whether organisms move toward a stimulus. Each
+//! tested organism gets its own [`World::fork`] with the stimulus placed at a fixed
+//! offset from its center, and all of them are compared with one unstimulated
+//! control fork. Forks copy the exact RNG position, so until the arms' random draws
+//! diverge, any difference in where the organism ends up is caused by the stimulus.
+
+use crate::world::{InterventionError, World};
+use rayon::prelude::*;
+use std::{error::Error, fmt};
+
+/// What a response assay applies to the stimulus fork before stepping it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stimulus {
+    /// A resource cone from [`World::deposit_resource_pulse`] peaking at `amount`,
+    /// centered `offset` away from the organism's center.
+    ResourcePulse {
+        offset: [f64; 2],
+        radius: f64,
+        amount: f32,
+    },
+}
+
+impl Stimulus {
+    fn offset(&self) -> [f64; 2] {
+        match *self {
+            Stimulus::ResourcePulse { offset, .. } => offset,
+        }
+    }
+
+    fn apply(&self, world: &mut World, center: [f64; 2]) -> Result<(), InterventionError> {
+        match *self {
+            Stimulus::ResourcePulse {
+                offset,
+                radius,
+                amount,
+            } => world.deposit_resource_pulse(
+                [center[0] + offset[0], center[1] + offset[1]],
+                radius,
+                amount,
+            ),
+        }
+    }
+}
+
+/// A stimulus and how many steps to run after applying it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResponseAssay {
+    pub stimulus: Stimulus,
+    pub steps: usize,
+}
+
+/// Center displacements of one organism, projected onto the direction from its
+/// starting center to the stimulus; positive values are movement toward it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResponseAssayResult {
+    pub stable_id: u64,
+    /// Displacement in the stimulus fork.
+    pub displacement_toward: f64,
+    /// Displacement in the control fork, along the same direction.
+    pub control_displacement: f64,
+    /// `displacement_toward - control_displacement`.
+    pub effect: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssayError {
+    ZeroSteps,
+    /// The stimulus offset is zero or non-finite, so it has no direction.
+    InvalidOffset([f64; 2]),
+    /// No alive organism has this stable id.
+    UnknownOrganism(u64),
+    Stimulus {
+        stable_id: u64,
+        source: InterventionError,
+    },
+}
+
+impl fmt::Display for AssayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssayError::ZeroSteps => write!(f, "assay steps must be positive"),
+            AssayError::InvalidOffset(offset) => write!(
+                f,
+                "stimulus offset must be finite and non-zero, got [{}, {}]",
+                offset[0], offset[1]
+            ),
+            AssayError::UnknownOrganism(stable_id) => {
+                write!(f, "no alive organism with stable id {stable_id}")
+            }
+            AssayError::Stimulus { stable_id, source } => {
+                write!(f, "stimulus for organism {stable_id} rejected: {source}")
+            }
+        }
+    }
+}
+
+impl Error for AssayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AssayError::Stimulus { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Shortest signed offset from `from` to `to` on a ring of length `extent`.
+fn toroidal_offset(from: f64, to: f64, extent: f64) -> f64 {
+    let d = (to - from).rem_euclid(extent);
+    if d > extent / 2.0 {
+        d - extent
+    } else {
+        d
+    }
+}
+
+/// Run `assay` on each organism in `stable_ids`, forking `world` so it is left
+/// unchanged. Stimulus forks run in parallel; results follow `stable_ids` order.
+/// Organisms that die in either arm before the last step are omitted.
+pub fn run_response_assay(
+    world: &World,
+    stable_ids: &[u64],
+    assay: &ResponseAssay,
+) -> Result<Vec<ResponseAssayResult>, AssayError> {
+    if assay.steps == 0 {
+        return Err(AssayError::ZeroSteps);
+    }
+    let offset = assay.stimulus.offset();
+    let norm = offset[0].hypot(offset[1]);
+    if !norm.is_finite() || norm == 0.0 {
+        return Err(AssayError::InvalidOffset(offset));
+    }
+    let direction = [offset[0] / norm, offset[1] / norm];
+    let starts = stable_ids
+        .iter()
+        .map(|&stable_id| {
+            world
+                .alive_organism_center(stable_id)
+                .ok_or(AssayError::UnknownOrganism(stable_id))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let world_size = world.config().world_size;
+    let displacement = |world: &World, stable_id: u64, start: [f64; 2]| -> Option<f64> {
+        let end = world.alive_organism_center(stable_id)?;
+        Some(
+            toroidal_offset(start[0], end[0], world_size) * direction[0]
+                + toroidal_offset(start[1], end[1], world_size) * direction[1],
+        )
+    };
+    let run = |mut fork: World| -> World {
+        for _ in 0..assay.steps {
+            fork.step();
+        }
+        fork
+    };
+
+    let (control, stimulated) = rayon::join(
+        || run(world.fork()),
+        || {
+            stable_ids
+                .par_iter()
+                .zip(&starts)
+                .map(|(&stable_id, &start)| {
+                    let mut fork = world.fork();
+                    assay
+                        .stimulus
+                        .apply(&mut fork, start)
+                        .map_err(|source| AssayError::Stimulus { stable_id, source })?;
+                    Ok(run(fork))
+                })
+                .collect::<Result<Vec<_>, AssayError>>()
+        },
+    );
+    Ok(stable_ids
+        .iter()
+        .zip(&starts)
+        .zip(&stimulated?)
+        .filter_map(|((&stable_id, &start), fork)| {
+            let displacement_toward = displacement(fork, stable_id, start)?;
+            let control_displacement = displacement(&control, stable_id, start)?;
+            Some(ResponseAssayResult {
+                stable_id,
+                displacement_toward,
+                control_displacement,
+                effect: displacement_toward - control_displacement,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::Agent;
+    use crate::config::SimConfig;
+    use crate::nn::NeuralNet;
+
+    /// One organism on a flat field with gradient sensing, so only a stimulus gives
+    /// it a gradient to follow.
+    fn flat_world(nn: NeuralNet) -> World {
+        let agents: Vec<Agent> = (0..8)
+            .map(|i| {
+                Agent::new(
+                    i,
+                    0,
+                    [20.0 + (i % 2) as f64 * 0.5, 20.0 + (i / 2) as f64 * 0.5],
+                )
+            })
+            .collect();
+        let config = SimConfig {
+            seed: 1,
+            num_organisms: 1,
+            agents_per_organism: 8,
+            world_size: 40.0,
+            enable_reproduction: false,
+            enable_resource_gradient_sensing: true,
+            resource_gradient_norm: 0.001,
+            resource_regeneration_rate: 0.0,
+            ..SimConfig::default()
+        };
+        World::new(agents, vec![nn], config).unwrap()
+    }
+
+    /// Hidden unit 0 reads ∂R/∂x and drives the x-velocity output.
+    fn climber_nn() -> NeuralNet {
+        let mut weights = vec![0.0f32; NeuralNet::EXTENDED_WEIGHT_COUNT];
+        weights[NeuralNet::WEIGHT_COUNT] = 2.0;
+        let mut nn = NeuralNet::from_weights_with_sensors(weights.into_iter());
+        nn.w_ho[0][0] = 3.0;
+        nn
+    }
+
+    fn pulse_assay(offset_x: f64) -> ResponseAssay {
+        ResponseAssay {
+            stimulus: Stimulus::ResourcePulse {
+                offset: [offset_x, 0.0],
+                radius: 12.0,
+                amount: 2.0,
+            },
+            steps: 40,
+        }
+    }
+
+    #[test]
+    fn gradient_climber_moves_toward_pulses_on_either_side() {
+        let world = flat_world(climber_nn());
+        let ids = world.alive_stable_ids();
+        let hash = world.state_hash();
+        for offset_x in [6.0, -6.0] {
+            let results = run_response_assay(&world, &ids, &pulse_assay(offset_x)).unwrap();
+            assert_eq!(results.len(), 1);
+            let result = results[0];
+            assert_eq!(result.stable_id, ids[0]);
+            assert!(
+                result.effect > 3.0,
+                "offset {offset_x}: effect {}",
+                result.effect
+            );
+            assert_eq!(
+                result.effect,
+                result.displacement_toward - result.control_displacement
+            );
+        }
+        assert_eq!(world.state_hash(), hash);
+    }
+
+    #[test]
+    fn zero_weight_network_shows_no_effect() {
+        let world = flat_world(NeuralNet::from_weights_with_sensors(std::iter::repeat(0.0)));
+        let ids = world.alive_stable_ids();
+        let result = run_response_assay(&world, &ids, &pulse_assay(6.0)).unwrap()[0];
+        assert!(result.effect.abs() < 1e-9, "effect {}", result.effect);
+    }
+
+    #[test]
+    fn invalid_assays_are_rejected() {
+        let world = flat_world(climber_nn());
+        let ids = world.alive_stable_ids();
+        let assay = ResponseAssay {
+            steps: 0,
+            ..pulse_assay(6.0)
+        };
+        assert_eq!(
+            run_response_assay(&world, &ids, &assay),
+            Err(AssayError::ZeroSteps)
+        );
+        assert_eq!(
+            run_response_assay(&world, &ids, &pulse_assay(0.0)),
+            Err(AssayError::InvalidOffset([0.0, 0.0]))
+        );
+        assert_eq!(
+            run_response_assay(&world, &[99], &pulse_assay(6.0)),
+            Err(AssayError::UnknownOrganism(99))
+        );
+        let assay = ResponseAssay {
+            stimulus: Stimulus::ResourcePulse {
+                offset: [6.0, 0.0],
+                radius: -1.0,
+                amount: 2.0,
+            },
+            steps: 5,
+        };
+        assert_eq!(
+            run_response_assay(&world, &ids, &assay),
+            Err(AssayError::Stimulus {
+                stable_id: ids[0],
+                source: InterventionError::InvalidPulseRadius(-1.0),
+            })
+        );
+    }
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
 
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -10,6 +12,17 @@ pub enum MetabolismMode {
     Counter,
 }
 
+/// Hidden-layer activation of agent NNs. The output layer always uses tanh.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Activation {
+    #[default]
+    Tanh,
+    Relu,
+    Sigmoid,
+    Linear,
+}
+
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum BoundaryMode {
@@ -18,6 +31,35 @@ pub enum BoundaryMode {
     SpatialHullFeedback,
 }
 
+/// When agents see their organism's boundary integrity in `internal_state[2]`.
+///
+/// - `Lagged`: the agent-state phase copies the integrity left by the previous
+///   step, so the NN reacts to boundary damage a step late.
+/// - `Fresh`: an extra pass at the end of each step copies the integrity computed
+///   in that step, so the next NN query sees it. The pass is O(agents); the
+///   `boundary_exposure` benchmark puts it within noise of `Lagged` at 10k agents.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryExposure {
+    #[default]
+    Lagged,
+    Fresh,
+}
+
+/// How the NN's two motor outputs move an agent.
+///
+/// - `Holonomic`: the outputs are added to the x/y velocity.
+/// - `Heading`: each agent faces `Agent::heading`; the outputs are forward thrust
+///   and turn rate (scaled by `heading_max_turn_rate`), and the velocity points
+///   along the heading with a speed that decays by `heading_drag`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Locomotion {
+    #[default]
+    Holonomic,
+    Heading,
+}
+
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum HomeostasisMode {
@@ -26,6 +68,288 @@ pub enum HomeostasisMode {
     SetpointPid,
 }
 
+/// Which eligible parents reproduce first when the agent cap
+/// (`SimConfig::max_total_agents`) cannot fit every child in a step.
+///
+/// - `FirstComeFirstServed`: organism index order, which favours older lineages.
+/// - `RandomLottery`: a shuffle drawn from the world RNG each step.
+/// - `EnergyRanked`: highest energy first; ties keep index order.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacityPolicy {
+    #[default]
+    FirstComeFirstServed,
+    RandomLottery,
+    EnergyRanked,
+}
+
+/// Source of the randomness an organism's reproduction consumes (mutation and
+/// offspring placement).
+///
+/// - `Global`: the world RNG, so each draw depends on every draw made before it.
+/// - `PerOrganism`: a counter-based stream seeded from `(seed, stable_id)`, so a
+///   lineage's outcomes do not depend on other organisms or on compaction timing.
+///   World-level events (lottery shuffles, immigration, resource noise) still use
+///   the world RNG.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RngMode {
+    #[default]
+    Global,
+    PerOrganism,
+}
+
+/// Age-dependent decline of boundary repair and metabolic efficiency after
+/// `senescence_onset_steps`.
+///
+/// - `Off`: no decline; only `max_organism_age_steps` ends life.
+/// - `Linear`: factor `1 - senescence_rate * (age - onset)`, floored at 0.
+/// - `Exponential`: factor `exp(-senescence_rate * (age - onset))`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SenescenceMode {
+    #[default]
+    Off,
+    Linear,
+    Exponential,
+}
+
+/// Where organisms draw external resource from during metabolism.
+///
+/// - `Center`: read and deplete the single cell at the organism's toroidal mean center.
+/// - `PerAgent`: every distinct cell occupied by an alive agent contributes its
+///   concentration; consumption is split across those cells in proportion to it.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceUptakeMode {
+    #[default]
+    Center,
+    PerAgent,
+}
+
+/// How the organism-center resource concentration is sampled in `ResourceUptakeMode::Center`.
+///
+/// - `Nearest`: value of the cell containing the center.
+/// - `Bilinear`: toroidally wrapped bilinear interpolation between the four surrounding
+///   cell centers; consumption is drawn from those cells by their weighted contribution.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceSamplingMode {
+    #[default]
+    Nearest,
+    Bilinear,
+}
+
+/// Axis along which a `ResourceInit::Gradient` field varies.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GradientAxis {
+    #[default]
+    X,
+    Y,
+}
+
+/// What [`World::set_config`](crate::world::World::set_config) does to the resource
+/// field when only `world_size` changes.
+///
+/// - `Preserve`: resample the old field onto the new grid toroidally and bilinearly,
+///   then scale each channel so its total mass is unchanged.
+/// - `Reset`: rebuild the field from `resource_init`, discarding depletion and any
+///   spatial structure.
+///
+/// Changing `resource_init` or `resource_channels` always resets the field.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceResetPolicy {
+    #[default]
+    Preserve,
+    Reset,
+}
+
+/// Random perturbation added to the resource field each step when
+/// `resource_noise_amplitude` is positive. Changes are drawn from the world RNG and
+/// cells are clamped at zero.
+///
+/// - `White`: every cell moves independently, uniformly within the amplitude.
+/// - `SpatiallyCorrelated`: a few random toroidal cosine modes with wavelengths of at
+///   least `length_scale` world units, redrawn every `resource_noise_refresh_steps`
+///   steps, shift whole regions together.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResourceNoiseKind {
+    #[default]
+    White,
+    SpatiallyCorrelated {
+        length_scale: f64,
+    },
+}
+
+/// Initial agent layout used by [`crate::world::World::from_config`]. Organism
+/// centers are drawn from `seed`, so a config always produces the same layout.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlacementStrategy {
+    /// Every agent at an independent uniformly random position.
+    UniformGlobal,
+    /// Each organism's agents in a disc of `radius` around a uniformly random
+    /// center. Without a radius, agents are offset uniformly per axis by up to
+    /// `min(sensing_radius, world_size / 4)`, the original layout.
+    ClusteredPerOrganism {
+        #[serde(default)]
+        radius: Option<f64>,
+    },
+    /// Agents at the cell centers of the smallest square lattice that holds them,
+    /// row by row in agent id order, so each organism fills a contiguous run.
+    Grid,
+    /// Organism centers on the smallest square lattice that holds them, `spacing`
+    /// apart and wrapped toroidally, with each organism's agents in a disc of
+    /// `radius` around its center.
+    GridOfClusters { spacing: f64, radius: f64 },
+}
+
+impl Default for PlacementStrategy {
+    fn default() -> Self {
+        Self::ClusteredPerOrganism { radius: None }
+    }
+}
+
+/// Initial spatial layout of the resource field. Non-uniform layouts also set each
+/// cell's regeneration ceiling, so the structure persists as cells refill.
+///
+/// - `Uniform`: every cell starts at 1.0.
+/// - `Patches`: `count` toroidal Gaussian bumps of standard deviation `radius`
+///   peaking at `peak`, over `background`, at seed-determined centers.
+/// - `Gradient`: linear from `low` to `high` along `axis`.
+/// - `Noise`: independent per-cell uniform values in `mean ± amplitude`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResourceInit {
+    #[default]
+    Uniform,
+    Patches {
+        count: usize,
+        radius: f64,
+        peak: f32,
+        background: f32,
+    },
+    Gradient {
+        axis: GradientAxis,
+        low: f32,
+        high: f32,
+    },
+    Noise {
+        mean: f32,
+        amplitude: f32,
+    },
+}
+
+/// Where the resource field regenerates each step.
+///
+/// - `Static`: every cell refills toward its initial value.
+/// - `MovingHotspots`: the same per-step mass (regeneration rate × cell count) is
+///   spread evenly over the cells within `radius` of `count` hotspot centers, each
+///   capped at `peak`. Centers start at seed-determined positions and drift in
+///   straight toroidal lines at `speed` world units per unit time, each along its
+///   own seed-determined direction.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResourceDynamics {
+    #[default]
+    Static,
+    MovingHotspots {
+        count: usize,
+        radius: f64,
+        speed: f64,
+        peak: f32,
+    },
+}
+
+/// How uniform regeneration refills each resource cell toward its capacity `K`, the
+/// smaller of `resource_cell_capacity` and the cell's initial value.
+///
+/// - `Linear`: `rate` per step, capped at `K`.
+/// - `Logistic`: `rate * max(value, K / 100) * (1 - value / K)` per step, so refill
+///   slows as a cell fills; the floor lets fully depleted cells recover.
+///
+/// Moving hotspots always regenerate linearly.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RegenerationModel {
+    #[default]
+    Linear,
+    Logistic,
+}
+
+/// What feeds the neighbor input of agent NNs.
+///
+/// - `Radius`: count of agents within `sensing_radius`, divided by `neighbor_norm`.
+/// - `KNearest`: closeness of the `k` nearest agents, `1 - mean_distance / r` clamped
+///   to `[0, 1]` where `r` is the agent's effective sensing radius, so it rises with
+///   local density like the radius count but does not saturate in dense clusters. Agents with no neighbors
+///   read 0; with fewer than `k`, the mean is over those present.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SensingMode {
+    #[default]
+    Radius,
+    KNearest {
+        k: usize,
+    },
+}
+
+/// Estimator used for the `spatial_cohesion_mean` step metric.
+///
+/// - `Exact`: mean toroidal distance over all agent pairs, O(k²) per organism.
+/// - `Sampled`: mean over `max_cohesion_pairs` uniformly drawn pairs (deterministically
+///   seeded from the step index). Unbiased for the exact value; organisms with at most
+///   `max_cohesion_pairs` pairs fall back to the exact enumeration.
+/// - `Center`: mean toroidal distance from each agent to the organism's circular-mean
+///   center, O(k). Measures radius rather than diameter, so it is systematically lower
+///   than the pairwise mean (about 0.74× for a uniformly filled disc) and is not directly
+///   comparable with `Exact`/`Sampled` values.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CohesionMetricMode {
+    #[default]
+    Exact,
+    Sampled,
+    Center,
+}
+
+/// Estimator for the `genome_diversity` step metric, the mean pairwise L2 distance
+/// between alive genomes.
+///
+/// Every pair is enumerated when there are fewer than `exact_below` alive organisms
+/// or at most `max_pairs` pairs; otherwise `max_pairs` pairs are drawn with a
+/// stream seeded from the run seed and step index. `per_segment` additionally
+/// reports `StepMetrics::genome_diversity_by_segment` over the same pairs.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GenomeDiversityConfig {
+    pub max_pairs: usize,
+    pub exact_below: usize,
+    pub per_segment: bool,
+}
+
+impl Default for GenomeDiversityConfig {
+    fn default() -> Self {
+        Self {
+            max_pairs: crate::constants::GENOME_DIVERSITY_MAX_PAIRS,
+            exact_below: 0,
+            per_segment: false,
+        }
+    }
+}
+
+/// Genome span the speciation clustering measures distances over.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeciationGenomeScope {
+    #[default]
+    NnWeights,
+    FullGenome,
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum AblationTarget {
@@ -38,7 +362,97 @@ pub enum AblationTarget {
     Growth,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+bitflags::bitflags! {
+    /// The seven criterion-ablation toggles (`SimConfig::enable_*`) as one value, so an
+    /// ablation condition is named once instead of spelled out field by field.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct CriteriaMask: u8 {
+        const METABOLISM = 1 << 0;
+        const HOMEOSTASIS = 1 << 1;
+        const BOUNDARY = 1 << 2;
+        const GROWTH = 1 << 3;
+        const REPRODUCTION = 1 << 4;
+        const RESPONSE = 1 << 5;
+        const EVOLUTION = 1 << 6;
+    }
+}
+
+impl Default for CriteriaMask {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+bitflags::bitflags! {
+    /// Groups of `StepMetrics` fields computed per sample. Fields of unselected groups
+    /// keep their `Default` values and their computation is skipped. `CORE` (means,
+    /// counts and totals) is always required.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct MetricsSelection: u8 {
+        const CORE = 1 << 0;
+        /// `energy_std`, `waste_std`, `boundary_std`.
+        const STD_DEVS = 1 << 1;
+        /// `genome_diversity` and `genome_diversity_by_segment`.
+        const GENOME_DIVERSITY = 1 << 2;
+        /// `spatial_cohesion_mean`, `spatial_entropy`, `occupied_cell_fraction`.
+        const SPATIAL_COHESION = 1 << 3;
+        /// `internal_state_mean`, `internal_state_std` and `role_fractions`.
+        const INTERNAL_STATE = 1 << 4;
+        /// `early_warning`, which also needs `collect_early_warning`.
+        const EARLY_WARNING = 1 << 5;
+    }
+}
+
+impl Default for MetricsSelection {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl From<AblationTarget> for CriteriaMask {
+    fn from(target: AblationTarget) -> Self {
+        match target {
+            AblationTarget::Metabolism => Self::METABOLISM,
+            AblationTarget::Boundary => Self::BOUNDARY,
+            AblationTarget::Homeostasis => Self::HOMEOSTASIS,
+            AblationTarget::Response => Self::RESPONSE,
+            AblationTarget::Reproduction => Self::REPRODUCTION,
+            AblationTarget::Evolution => Self::EVOLUTION,
+            AblationTarget::Growth => Self::GROWTH,
+        }
+    }
+}
+
+/// Genome segment, in `Genome::segments` order.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GenomeSegment {
+    NnWeights,
+    Metabolic,
+    Homeostasis,
+    Developmental,
+    Reproduction,
+    Sensory,
+    Evolution,
+}
+
+impl GenomeSegment {
+    /// Index into `Genome::segments`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Mutation probabilities that replace the global `mutation_*_rate` values for one segment.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SegmentMutationOverride {
+    pub segment: GenomeSegment,
+    pub point_rate: f32,
+    pub reset_rate: f32,
+    pub scale_rate: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct SimConfig {
     /// Deterministic seed for reproducible simulation runs.
@@ -49,19 +463,71 @@ pub struct SimConfig {
     pub num_organisms: usize,
     /// Expected number of agents per organism.
     pub agents_per_organism: usize,
+    /// Most agents a world may hold, at construction and through reproduction.
+    pub max_total_agents: usize,
+    /// Longest run, in steps, that `World::try_run_experiment` and its variants accept.
+    pub max_experiment_steps: usize,
+    /// Most metric samples a single experiment may record.
+    pub max_experiment_samples: usize,
+    /// Refuse to build a world whose [`SimConfig::estimated_memory_bytes`] exceeds
+    /// this many bytes. `None` sets no limit.
+    pub memory_limit_bytes: Option<u64>,
+    /// Initial agent layout of worlds built from this config.
+    pub placement: PlacementStrategy,
     /// Radius for local neighbor sensing.
     pub sensing_radius: f64,
+    /// Decode genome segment 5 into per-organism sensory modifiers
+    /// (`organism::SensoryProgram`): a sensing-radius multiplier, a `neighbor_norm`
+    /// multiplier and extended-sensor gains, with the radius charged per step at
+    /// `sensing_cost_per_radius`.
+    pub enable_genetic_sensing: bool,
+    /// Energy lost per unit time per unit of genetically scaled sensing radius,
+    /// under `enable_genetic_sensing`.
+    pub sensing_cost_per_radius: f32,
     /// Maximum speed clamp for agent velocity.
     pub max_speed: f64,
+    /// How NN motor outputs drive agent movement.
+    pub locomotion: Locomotion,
+    /// Heading locomotion: fraction of forward speed lost per unit time.
+    pub heading_drag: f64,
+    /// Heading locomotion: turn rate in radians per unit time at full turn output.
+    pub heading_max_turn_rate: f64,
+    /// Energy debited from an organism per unit of agent speed per unit time.
+    pub movement_energy_cost: f32,
     /// Simulation timestep (seconds in model time).
     pub dt: f64,
     /// Normalization factor for neighbor-count NN input channel.
     pub neighbor_norm: f64,
+    /// Neighbor input source for agent NNs. Crowding always uses the radius count.
+    pub sensing_mode: SensingMode,
+    /// Extended sensor suite: feed the local resource gradient (∂R/∂x, ∂R/∂y) and the
+    /// other-organism neighbor count to agent NNs as three extra inputs. The base
+    /// neighbor input then counts only same-organism agents (under
+    /// `SensingMode::Radius`), so own-body density and foreign contact are separate.
+    pub enable_resource_gradient_sensing: bool,
+    /// Normalization factor for the resource gradient NN input channels.
+    pub resource_gradient_norm: f32,
+    /// Within-lifetime Hebbian plasticity: each organism carries weight offsets that
+    /// are updated after every forward pass and are not inherited by offspring.
+    pub enable_hebbian_plasticity: bool,
+    /// Hebbian learning rate η.
+    pub hebbian_learning_rate: f32,
+    /// Fraction of each Hebbian offset lost per update.
+    pub hebbian_decay: f32,
+    /// Hebbian offsets are clamped to `[-hebbian_max_delta, hebbian_max_delta]`.
+    pub hebbian_max_delta: f32,
+    /// Activation applied to agent NN hidden units.
+    pub nn_hidden_activation: Activation,
+    /// Scale applied to agent NN output sums before their tanh, so gains above 1
+    /// saturate motor outputs sooner.
+    pub nn_output_gain: f32,
     /// Criterion-ablation toggle for metabolism updates.
     pub enable_metabolism: bool,
     /// Criterion-ablation toggle for boundary maintenance updates.
     pub enable_boundary_maintenance: bool,
-    /// Criterion-ablation toggle for homeostasis (internal state regulation).
+    /// Criterion-ablation toggle for homeostasis (internal state regulation). With
+    /// `enable_genetic_homeostasis`, disabling it removes only the genetic corrective
+    /// term and the NN's state deltas still apply.
     pub enable_homeostasis: bool,
     /// Criterion-ablation toggle for response to stimuli (velocity from NN).
     pub enable_response: bool,
@@ -71,6 +537,13 @@ pub struct SimConfig {
     pub enable_evolution: bool,
     /// Criterion-ablation toggle for growth/development (placeholder until developmental program).
     pub enable_growth: bool,
+    /// Under `enable_growth`, also cap immature agents' speed at `max_speed` times
+    /// their developmental movement factor and scale their neighbor input by the
+    /// neighbor-sensing factor (`organism::StageFactors`).
+    pub enable_maturity_gating: bool,
+    /// Decode the gating factors and their stage threshold from dedicated genes in
+    /// the developmental segment instead of fixed defaults.
+    pub maturity_gating_evolvable: bool,
     /// Simulation step at which scheduled criterion ablation should be applied (0 = disabled).
     pub ablation_step: usize,
     /// Criteria to ablate at `ablation_step` (subset of: metabolism, boundary, homeostasis,
@@ -78,6 +551,8 @@ pub struct SimConfig {
     pub ablation_targets: Vec<AblationTarget>,
     /// Boundary maintenance implementation mode.
     pub boundary_mode: BoundaryMode,
+    /// Whether agents see this step's or the previous step's boundary integrity.
+    pub boundary_exposure: BoundaryExposure,
     /// Homeostasis implementation mode.
     pub homeostasis_mode: HomeostasisMode,
     /// Base internal-state setpoint used by `HomeostasisMode::SetpointPid`.
@@ -110,12 +585,78 @@ pub struct SimConfig {
     pub boundary_repair_rate: f32,
     /// Boundary threshold below which the organism is considered collapsed.
     pub boundary_collapse_threshold: f32,
+    /// Extra boundary decay per unit of dispersion above `boundary_cohesion_threshold`.
+    /// Dispersion is one minus the toroidal cohesion of the organism's agents (0 when
+    /// they coincide, near 1 when spread across the world). Zero disables the term.
+    pub cohesion_boundary_coupling: f32,
+    /// Dispersion in `[0, 1]` an organism tolerates before `cohesion_boundary_coupling`
+    /// accelerates its boundary decay.
+    pub boundary_cohesion_threshold: f32,
     /// Energy threshold used in terminal viability checks.
     pub death_energy_threshold: f32,
     /// Boundary threshold used in terminal viability checks.
     pub death_boundary_threshold: f32,
+    /// Starvation bet-hedging: organisms below `dormancy_entry_threshold` energy stop
+    /// moving and feeding, skip their NN, pause growth and reproduction, and pay reduced
+    /// basal costs until the resource at their center exceeds `dormancy_exit_resource`.
+    pub enable_dormancy: bool,
+    /// Energy below which an organism becomes dormant.
+    pub dormancy_entry_threshold: f32,
+    /// Multiplier on basal metabolic costs and boundary decay while dormant.
+    pub dormancy_metabolic_scale: f32,
+    /// Resource at the organism center above which a dormant organism wakes.
+    pub dormancy_exit_resource: f32,
     /// Selects metabolism engine behavior.
     pub metabolism_mode: MetabolismMode,
+    /// Length of the metabolic genome segment; larger segments encode graphs with
+    /// more nodes (16 → 4, 32 → 6, 64 → 10).
+    pub metabolic_genome_size: usize,
+    /// Agent role archetypes decoded from the developmental genome segment: 0
+    /// disables differentiation, otherwise 2–4 roles that each scale sensing
+    /// radius, resource uptake and movement.
+    pub agent_role_count: usize,
+    /// Counter engine: maximum external resource consumed per unit time.
+    pub counter_uptake_rate: f32,
+    /// Counter engine: energy gained per unit of consumed resource.
+    pub counter_conversion_efficiency: f32,
+    /// Counter engine: fraction of consumed resource emitted as waste.
+    pub counter_waste_fraction: f32,
+    /// Counter engine: energy debited per unit time regardless of intake.
+    pub counter_basal_cost: f32,
+    /// Selects where organisms draw external resource from.
+    pub resource_uptake_mode: ResourceUptakeMode,
+    /// Interpolation used when sampling the resource field at organism centers.
+    pub resource_sampling: ResourceSamplingMode,
+    /// Initial spatial layout of the resource field.
+    pub resource_init: ResourceInit,
+    /// Where regeneration is applied each step.
+    pub resource_dynamics: ResourceDynamics,
+    /// Growth law of uniform regeneration.
+    pub regeneration_model: RegenerationModel,
+    /// Most resource a cell regenerates to, below its initial value. `None` leaves
+    /// the initial value as the only ceiling.
+    pub resource_cell_capacity: Option<f32>,
+    /// Number of independent substrate channels in the resource field. Graph-mode
+    /// organisms weight each channel by uptake affinities decoded from genome segment 1;
+    /// other modes take from every channel equally.
+    pub resource_channels: usize,
+    /// How `set_config` carries the resource field across a `world_size` change.
+    pub resource_reset_policy: ResourceResetPolicy,
+    /// Largest change per unit time resource noise makes to a cell; 0 disables it.
+    pub resource_noise_amplitude: f32,
+    /// Spatial structure of resource noise.
+    pub resource_noise_kind: ResourceNoiseKind,
+    /// Steps between redraws of the `SpatiallyCorrelated` noise modes.
+    pub resource_noise_refresh_steps: usize,
+    /// Give each agent an energy reserve in `internal_state[3]`. Metabolic gains go to
+    /// the agents on the richest cells, reserves diffuse between same-organism agents
+    /// within `energy_sharing_radius`, and the organism's energy is their sum.
+    pub enable_agent_energy_reserves: bool,
+    /// Fraction of the reserve difference between two neighboring agents exchanged per
+    /// unit time (0 disables sharing).
+    pub energy_sharing_rate: f32,
+    /// Distance within which same-organism agents share reserves.
+    pub energy_sharing_radius: f64,
     /// Minimum energy required before an organism can reproduce.
     pub reproduction_min_energy: f32,
     /// Minimum boundary integrity required before an organism can reproduce.
@@ -126,14 +667,71 @@ pub struct SimConfig {
     pub reproduction_child_min_agents: usize,
     /// Maximum radius used when spawning child agents around the parent center.
     pub reproduction_spawn_radius: f64,
+    /// Order in which eligible parents reproduce when the agent cap is reached.
+    pub capacity_policy: CapacityPolicy,
+    /// Whether reproduction draws from the world RNG or per-organism streams.
+    pub rng_mode: RngMode,
     /// Neighbor-density threshold where crowding damage starts.
     pub crowding_neighbor_threshold: f32,
     /// Per-step boundary decay scale induced by crowding.
     pub crowding_boundary_decay: f32,
+    /// Energy lost per unit time per neighbor above `crowding_neighbor_threshold`.
+    pub crowding_energy_cost: f32,
+    /// Toggle for agent-level predation between organisms.
+    pub enable_predation: bool,
+    /// Contact radius between agents of different organisms for predation.
+    pub predation_radius: f64,
+    /// Minimum energy lead a predator organism needs over its prey.
+    pub predation_energy_advantage: f32,
+    /// Energy moved from prey to predator per step of contact.
+    pub predation_transfer: f32,
+    /// Boundary integrity damage dealt to prey per step of contact.
+    pub predation_boundary_damage: f32,
+    /// Let an organism absorb a genetically compatible neighbour whose center lies
+    /// within `fusion_radius` of its own. The smaller organism ends as
+    /// [`DeathCause::Fused`](crate::metrics::DeathCause::Fused).
+    pub enable_fusion: bool,
+    /// Maximum toroidal distance between organism centers for fusion.
+    pub fusion_radius: f64,
+    /// Maximum L2 distance between NN weight vectors for fusion.
+    pub fusion_compatibility_threshold: f32,
+    /// Fraction of a dying organism's biomass deposited back into the resource field
+    /// at its last center. 0 disables recycling.
+    pub corpse_recycling_fraction: f32,
+    /// Weight of remaining waste relative to energy in recycled biomass.
+    pub corpse_waste_recycle_scale: f32,
+    /// Per-cell resource ceiling for corpse deposits; biomass beyond it is lost.
+    pub corpse_deposit_cell_cap: f32,
+    /// Excrete each organism's metabolic waste into a world-level waste field at its
+    /// center. Graph-mode organisms with a waste affinity gene (a float past the graph
+    /// encoding, e.g. `metabolic_genome_size = 17`) feed on it.
+    pub enable_waste_field: bool,
+    /// Fraction of the waste field lost per unit time.
+    pub waste_field_decay_rate: f32,
+    /// Energy gained per unit of field waste taken up.
+    pub waste_uptake_efficiency: f32,
+    /// Boundary integrity lost per unit time per unit of field waste at an
+    /// organism's center, reduced by its toxin resistance (homeostasis gene g[4]).
+    /// 0 leaves the waste field harmless.
+    pub waste_field_toxicity: f32,
+    /// Energy per unit time an organism spends per unit of toxin resistance, scaled
+    /// by its cost multiplier gene. Charged while the waste field is enabled.
+    pub toxin_resistance_cost: f32,
     /// Maximum age in simulation steps before forced organism death.
     pub max_organism_age_steps: usize,
+    /// Shape of age-dependent decline; `max_organism_age_steps` remains a backstop.
+    pub senescence_mode: SenescenceMode,
+    /// Age in steps at which senescence begins.
+    pub senescence_onset_steps: usize,
+    /// Per-step decline rate of the senescence factor after onset.
+    pub senescence_rate: f32,
+    /// Scale onset and rate per organism by the genome-encoded aging pace.
+    pub senescence_evolvable: bool,
     /// Step interval used for pruning dead entities.
     pub compaction_interval_steps: usize,
+    /// Reuse the ids of pruned agents before allocating new ones. Off by default so
+    /// an agent id never refers to two different agents over a run.
+    pub recycle_agent_ids: bool,
     /// Per-gene point mutation probability.
     pub mutation_point_rate: f32,
     /// Magnitude bound for additive point mutation deltas.
@@ -148,8 +746,33 @@ pub struct SimConfig {
     pub mutation_scale_max: f32,
     /// Absolute clamp used for mutated genome values.
     pub mutation_value_limit: f32,
+    /// Per-segment mutation probabilities; segments not listed use the global rates.
+    pub mutation_segment_overrides: Vec<SegmentMutationOverride>,
+    /// Scale mutation rates per organism by multipliers decoded from genome segment 6.
+    pub enable_self_adaptive_mutation: bool,
+    /// Let offspring genomes duplicate and delete runs of genes in the metabolic and
+    /// developmental segments, varying their in-use lengths. Off keeps every
+    /// segment at its fixed length.
+    pub enable_structural_mutation: bool,
+    /// Per-segment probability of a gene-run duplication per birth, under
+    /// `enable_structural_mutation`.
+    pub mutation_duplication_rate: f32,
+    /// Per-segment probability of a gene-run deletion per birth, under
+    /// `enable_structural_mutation`.
+    pub mutation_deletion_rate: f32,
     /// Per-step decay rate for internal state (homeostatic entropy).
     pub homeostasis_decay_rate: f32,
+    /// Add a proportional correction toward per-organism set-points decoded from
+    /// genome segment 2 to the NN's internal-state deltas. Requires
+    /// `HomeostasisMode::NnRegulator`.
+    pub enable_genetic_homeostasis: bool,
+    /// Scheduled homeostasis challenges as `(step, amount)` pairs with strictly
+    /// increasing steps: at each step every alive agent's internal-state channels 0
+    /// and 1 are displaced by `amount`, clamped to [0,1].
+    pub homeostasis_shock_schedule: Vec<(usize, f32)>,
+    /// A shock counts as recovered once both displaced channels' means are back
+    /// within this distance of their pre-shock values.
+    pub homeostasis_recovery_epsilon: f32,
     /// Number of simulation steps for a child organism to reach full maturity.
     pub growth_maturation_steps: usize,
     /// Metabolic efficiency multiplier for fully immature organisms (maturity=0).
@@ -167,8 +790,61 @@ pub struct SimConfig {
     pub environment_cycle_period: usize,
     /// Resource regeneration rate during the low phase of cyclic modulation.
     pub environment_cycle_low_rate: f32,
+    /// Piecewise-constant regeneration schedule as `(step, rate)` breakpoints with strictly
+    /// increasing steps; each rate applies from its step until the next breakpoint, and
+    /// `resource_regeneration_rate` applies before the first. Mutually exclusive with the
+    /// legacy `environment_shift_step` / `environment_cycle_period` fields.
+    pub environment_schedule: Vec<(usize, f32)>,
     /// Toggle for sham (no-op) computational process control.
     pub enable_sham_process: bool,
+    /// Estimator used for the spatial cohesion metric.
+    pub cohesion_metric_mode: CohesionMetricMode,
+    /// Pair budget per organism for `CohesionMetricMode::Sampled`.
+    pub max_cohesion_pairs: usize,
+    /// Estimator for the `genome_diversity` metric.
+    pub genome_diversity: GenomeDiversityConfig,
+    /// Side length G of the G×G grid binning agents for `spatial_entropy`; 0 uses
+    /// the resource field's grid.
+    pub spatial_entropy_grid: usize,
+    /// Record per-organism birth/death events into `RunSummary::events`.
+    pub enable_event_log: bool,
+    /// Maximum events kept per run; later events are dropped and flagged as truncated.
+    pub event_log_max_entries: usize,
+    /// Steps between flushes of a `World::set_lineage_writer` stream; 0 flushes
+    /// only when a run ends.
+    pub lineage_flush_interval_steps: usize,
+    /// Aggregate per-phase step timings into `RunSummary::timings`.
+    pub collect_timings: bool,
+    /// Run the boundary and metabolism phases across organisms on the rayon pool.
+    /// Simulation state matches the serial phases; it pays off from thousands of
+    /// organisms.
+    pub parallel_organism_phases: bool,
+    /// Balance the world's resource, waste and organism stocks against every input
+    /// and sink each step, reporting the accumulated imbalance as
+    /// `StepMetrics::conservation_residual`. Debug builds panic on a step whose
+    /// imbalance exceeds float rounding.
+    pub audit_conservation: bool,
+    /// Tally same- vs other-organism neighbors during sensing queries and report
+    /// `StepMetrics::same_organism_neighbor_fraction`.
+    pub collect_contact_metrics: bool,
+    /// Attach each organism's genome-decoded parameters to snapshot frames as
+    /// `OrganismSnapshot::decoded_params`.
+    pub snapshot_decoded_params: bool,
+    /// Metric groups computed per sample; the rest are left at their defaults.
+    pub metrics_selection: MetricsSelection,
+    /// Attach rolling collapse indicators (`StepMetrics::early_warning`) to samples.
+    pub collect_early_warning: bool,
+    /// Number of most recent samples the early-warning indicators are computed over.
+    pub early_warning_window: usize,
+    /// Attach early-warning indicators to every k-th sample; the window still advances
+    /// on every sample.
+    pub early_warning_every: usize,
+    /// Cluster alive genomes into species (`StepMetrics::speciation`) on every k-th
+    /// sample; 0 disables clustering.
+    pub speciation_metric_every: usize,
+    /// Single-linkage L2 distance at or below which two genomes share a species.
+    pub speciation_distance_threshold: f32,
+    pub speciation_genome_scope: SpeciationGenomeScope,
 }
 
 impl Default for SimConfig {
@@ -178,10 +854,30 @@ impl Default for SimConfig {
             world_size: 100.0,
             num_organisms: 50,
             agents_per_organism: 50,
+            max_total_agents: Self::MAX_TOTAL_AGENTS,
+            max_experiment_steps: Self::MAX_EXPERIMENT_STEPS,
+            max_experiment_samples: Self::MAX_EXPERIMENT_SAMPLES,
+            memory_limit_bytes: None,
+            placement: PlacementStrategy::default(),
             sensing_radius: 5.0,
+            enable_genetic_sensing: false,
+            sensing_cost_per_radius: 0.001,
             max_speed: 2.0,
+            locomotion: Locomotion::Holonomic,
+            heading_drag: 0.5,
+            heading_max_turn_rate: std::f64::consts::PI,
+            movement_energy_cost: 0.0,
             dt: 0.1,
             neighbor_norm: 50.0,
+            sensing_mode: SensingMode::Radius,
+            enable_resource_gradient_sensing: false,
+            resource_gradient_norm: 0.1,
+            enable_hebbian_plasticity: false,
+            hebbian_learning_rate: 0.01,
+            hebbian_decay: 0.01,
+            hebbian_max_delta: 0.5,
+            nn_hidden_activation: Activation::Tanh,
+            nn_output_gain: 1.0,
             enable_metabolism: true,
             enable_boundary_maintenance: true,
             enable_homeostasis: true,
@@ -189,9 +885,12 @@ impl Default for SimConfig {
             enable_reproduction: true,
             enable_evolution: true,
             enable_growth: true,
+            enable_maturity_gating: false,
+            maturity_gating_evolvable: false,
             ablation_step: 0,
             ablation_targets: Vec::new(),
             boundary_mode: BoundaryMode::ScalarRepair,
+            boundary_exposure: BoundaryExposure::Lagged,
             homeostasis_mode: HomeostasisMode::NnRegulator,
             setpoint_pid_base: 0.45,
             setpoint_pid_energy_scale: 0.1,
@@ -208,18 +907,68 @@ impl Default for SimConfig {
             boundary_repair_waste_penalty_scale: 0.4,
             boundary_repair_rate: 0.05,
             boundary_collapse_threshold: 0.05,
+            cohesion_boundary_coupling: 0.0,
+            boundary_cohesion_threshold: 0.5,
             death_energy_threshold: 0.0,
             death_boundary_threshold: 0.1,
+            enable_dormancy: false,
+            dormancy_entry_threshold: 0.1,
+            dormancy_metabolic_scale: 0.1,
+            dormancy_exit_resource: 0.5,
             metabolism_mode: MetabolismMode::Toy,
+            metabolic_genome_size: crate::genome::Genome::METABOLIC_SIZE,
+            agent_role_count: 0,
+            counter_uptake_rate: 0.4,
+            counter_conversion_efficiency: 0.5,
+            counter_waste_fraction: 0.0,
+            counter_basal_cost: 0.0,
+            resource_uptake_mode: ResourceUptakeMode::Center,
+            resource_sampling: ResourceSamplingMode::Nearest,
+            resource_init: ResourceInit::Uniform,
+            resource_dynamics: ResourceDynamics::Static,
+            regeneration_model: RegenerationModel::Linear,
+            resource_cell_capacity: None,
+            resource_channels: 1,
+            resource_reset_policy: ResourceResetPolicy::Preserve,
+            resource_noise_amplitude: 0.0,
+            resource_noise_kind: ResourceNoiseKind::White,
+            resource_noise_refresh_steps: 50,
+            enable_agent_energy_reserves: false,
+            energy_sharing_rate: 0.5,
+            energy_sharing_radius: 2.0,
             reproduction_min_energy: 0.85,
             reproduction_min_boundary: 0.70,
             reproduction_energy_cost: 0.30,
             reproduction_child_min_agents: 4,
+            capacity_policy: CapacityPolicy::FirstComeFirstServed,
+            rng_mode: RngMode::Global,
             reproduction_spawn_radius: 1.0,
             crowding_neighbor_threshold: 8.0,
             crowding_boundary_decay: 0.0015,
+            crowding_energy_cost: 0.0,
+            enable_predation: false,
+            predation_radius: 1.0,
+            predation_energy_advantage: 0.2,
+            predation_transfer: 0.01,
+            predation_boundary_damage: 0.02,
+            enable_fusion: false,
+            fusion_radius: 2.0,
+            fusion_compatibility_threshold: 1.0,
+            corpse_recycling_fraction: 0.0,
+            corpse_waste_recycle_scale: 0.0,
+            corpse_deposit_cell_cap: 2.0,
+            enable_waste_field: false,
+            waste_field_decay_rate: 0.05,
+            waste_uptake_efficiency: 0.3,
+            waste_field_toxicity: 0.0,
+            toxin_resistance_cost: 0.0,
             max_organism_age_steps: 20_000,
+            senescence_mode: SenescenceMode::Off,
+            senescence_onset_steps: 5_000,
+            senescence_rate: 0.0002,
+            senescence_evolvable: false,
             compaction_interval_steps: 64,
+            recycle_agent_ids: false,
             mutation_point_rate: 0.02,
             mutation_point_scale: 0.15,
             mutation_reset_rate: 0.002,
@@ -227,7 +976,15 @@ impl Default for SimConfig {
             mutation_scale_min: 0.8,
             mutation_scale_max: 1.2,
             mutation_value_limit: 2.0,
+            mutation_segment_overrides: Vec::new(),
+            enable_self_adaptive_mutation: false,
+            enable_structural_mutation: false,
+            mutation_duplication_rate: 0.01,
+            mutation_deletion_rate: 0.01,
             homeostasis_decay_rate: 0.01,
+            enable_genetic_homeostasis: false,
+            homeostasis_shock_schedule: Vec::new(),
+            homeostasis_recovery_epsilon: 0.05,
             growth_maturation_steps: 200,
             growth_immature_metabolic_efficiency: 0.3,
             resource_regeneration_rate: 0.01,
@@ -236,7 +993,27 @@ impl Default for SimConfig {
             metabolism_efficiency_multiplier: 1.0,
             environment_cycle_period: 0,
             environment_cycle_low_rate: 0.005,
+            environment_schedule: Vec::new(),
             enable_sham_process: false,
+            cohesion_metric_mode: CohesionMetricMode::Exact,
+            max_cohesion_pairs: 256,
+            genome_diversity: GenomeDiversityConfig::default(),
+            spatial_entropy_grid: 0,
+            enable_event_log: false,
+            event_log_max_entries: 100_000,
+            lineage_flush_interval_steps: 100,
+            collect_timings: false,
+            parallel_organism_phases: false,
+            audit_conservation: false,
+            collect_contact_metrics: false,
+            snapshot_decoded_params: false,
+            metrics_selection: MetricsSelection::all(),
+            collect_early_warning: false,
+            early_warning_window: 20,
+            early_warning_every: 1,
+            speciation_metric_every: 0,
+            speciation_distance_threshold: 1.0,
+            speciation_genome_scope: SpeciationGenomeScope::NnWeights,
         }
     }
 }
@@ -271,11 +1048,23 @@ define_sim_config_error! {
     InvalidAgentsPerOrganism => "agents_per_organism must be greater than 0";
     AgentCountOverflow => "Total agent count overflow";
     TooManyAgents { max: usize, actual: usize } => "Too many agents: {} > max {}", actual, max;
+    InvalidMaxExperimentSteps => "max_experiment_steps must be greater than 0";
+    InvalidMaxExperimentSamples => "max_experiment_samples must be greater than 0";
     InvalidWorldSize => "world_size must be positive and finite";
     InvalidDt => "dt must be positive and finite";
     InvalidMaxSpeed => "max_speed must be positive and finite";
+    InvalidHeadingDrag => "heading_drag must be finite and non-negative";
+    InvalidHeadingMaxTurnRate => "heading_max_turn_rate must be finite and non-negative";
+    InvalidMovementEnergyCost => "movement_energy_cost must be finite and non-negative";
     InvalidSensingRadius => "sensing_radius must be non-negative and finite";
     InvalidNeighborNorm => "neighbor_norm must be positive and finite";
+    InvalidSensingCostPerRadius => "sensing_cost_per_radius must be finite and non-negative";
+    InvalidSensingMode => "sensing_mode k must be greater than 0";
+    InvalidHebbianLearningRate => "hebbian_learning_rate must be finite and non-negative";
+    InvalidHebbianDecay => "hebbian_decay must be finite and within [0,1]";
+    InvalidHebbianMaxDelta => "hebbian_max_delta must be finite and non-negative";
+    InvalidNnOutputGain => "nn_output_gain must be finite and positive";
+    InvalidResourceGradientNorm => "resource_gradient_norm must be positive and finite";
     InvalidMetabolicViabilityFloor => "metabolic_viability_floor must be finite and non-negative";
     InvalidSetpointPidBase => "setpoint_pid_base must be finite and within [0,1]";
     InvalidSetpointPidEnergyScale => "setpoint_pid_energy_scale must be finite and non-negative";
@@ -291,8 +1080,15 @@ define_sim_config_error! {
     InvalidBoundaryRepairWastePenaltyScale => "boundary_repair_waste_penalty_scale must be finite and non-negative";
     InvalidBoundaryRepairRate => "boundary_repair_rate must be finite and non-negative";
     InvalidBoundaryCollapseThreshold => "boundary_collapse_threshold must be finite and within [0,1]";
+    InvalidCohesionBoundaryCoupling => "cohesion_boundary_coupling must be finite and non-negative";
+    InvalidBoundaryCohesionThreshold => "boundary_cohesion_threshold must be finite and within [0,1]";
     InvalidDeathEnergyThreshold => "death_energy_threshold must be finite and non-negative";
     InvalidDeathBoundaryThreshold => "death_boundary_threshold must be finite and within [0,1]";
+    InvalidDormancyEntryThreshold => "dormancy_entry_threshold must be finite and non-negative";
+    InvalidDormancyMetabolicScale => "dormancy_metabolic_scale must be finite and within [0,1]";
+    InvalidDormancyExitResource => "dormancy_exit_resource must be finite and non-negative";
+    InvalidEnergySharingRate => "energy_sharing_rate must be finite and non-negative";
+    InvalidEnergySharingRadius => "energy_sharing_radius must be finite and non-negative";
     InvalidReproductionMinEnergy => "reproduction_min_energy must be finite and non-negative";
     InvalidReproductionMinBoundary => "reproduction_min_boundary must be finite and within [0,1]";
     InvalidReproductionEnergyCost => "reproduction_energy_cost must be finite and positive";
@@ -301,7 +1097,22 @@ define_sim_config_error! {
     InvalidReproductionSpawnRadius => "reproduction_spawn_radius must be finite and non-negative";
     InvalidCrowdingNeighborThreshold => "crowding_neighbor_threshold must be finite and non-negative";
     InvalidCrowdingBoundaryDecay => "crowding_boundary_decay must be finite and non-negative";
+    InvalidCrowdingEnergyCost => "crowding_energy_cost must be finite and non-negative";
+    InvalidPredationRadius => "predation_radius must be finite and non-negative";
+    InvalidPredationEnergyAdvantage => "predation_energy_advantage must be finite and non-negative";
+    InvalidPredationTransfer => "predation_transfer must be finite and non-negative";
+    InvalidPredationBoundaryDamage => "predation_boundary_damage must be finite and within [0,1]";
+    InvalidFusionRadius => "fusion_radius must be finite and non-negative";
+    InvalidFusionCompatibilityThreshold => "fusion_compatibility_threshold must be finite and non-negative";
+    InvalidCorpseRecyclingFraction => "corpse_recycling_fraction must be finite and within [0,1]";
+    InvalidCorpseWasteRecycleScale => "corpse_waste_recycle_scale must be finite and non-negative";
+    InvalidCorpseDepositCellCap => "corpse_deposit_cell_cap must be finite and non-negative";
+    InvalidWasteFieldDecayRate => "waste_field_decay_rate must be finite and non-negative";
+    InvalidWasteUptakeEfficiency => "waste_uptake_efficiency must be finite and within [0,1]";
+    InvalidWasteFieldToxicity => "waste_field_toxicity must be finite and non-negative";
+    InvalidToxinResistanceCost => "toxin_resistance_cost must be finite and non-negative";
     InvalidMaxOrganismAgeSteps => "max_organism_age_steps must be positive";
+    InvalidSenescenceRate => "senescence_rate must be finite and non-negative";
     InvalidCompactionIntervalSteps => "compaction_interval_steps must be positive";
     InvalidMutationPointRate => "mutation_point_rate must be finite and within [0,1]";
     InvalidMutationPointScale => "mutation_point_scale must be finite and non-negative";
@@ -310,288 +1121,991 @@ define_sim_config_error! {
     InvalidMutationScaleBounds => "mutation_scale_min/mutation_scale_max must be finite, positive, and ordered";
     InvalidMutationValueLimit => "mutation_value_limit must be finite and positive";
     InvalidMutationProbabilityBudget => "mutation_point_rate + mutation_reset_rate + mutation_scale_rate must be <= 1.0";
+    InvalidMutationDuplicationRate => "mutation_duplication_rate must be finite and within [0,1]";
+    InvalidMutationDeletionRate => "mutation_deletion_rate must be finite and within [0,1]";
+    InvalidMutationSegmentRate => "mutation_segment_overrides rates must be finite and within [0,1]";
+    InvalidMutationSegmentBudget => "mutation_segment_overrides point_rate + reset_rate + scale_rate must be <= 1.0 for every segment";
+    DuplicateMutationSegmentOverride => "mutation_segment_overrides must list each segment at most once";
     InvalidHomeostasisDecayRate => "homeostasis_decay_rate must be finite and non-negative";
+    GeneticHomeostasisRequiresNnRegulator => "enable_genetic_homeostasis requires homeostasis_mode = nn_regulator";
+    InvalidHomeostasisShockSchedule => "homeostasis_shock_schedule steps must be strictly increasing with finite amounts";
+    InvalidHomeostasisRecoveryEpsilon => "homeostasis_recovery_epsilon must be finite and positive";
     InvalidGrowthMaturationSteps => "growth_maturation_steps must be positive";
     InvalidGrowthImmatureMetabolicEfficiency => "growth_immature_metabolic_efficiency must be finite and within [0,1]";
     InvalidResourceRegenerationRate => "resource_regeneration_rate must be finite and non-negative";
+    InvalidResourceInit => "resource_init parameters must be finite and non-negative, with positive patch count and radius";
+    InvalidResourceDynamics { max_count: usize } => "resource_dynamics moving_hotspots needs a count in 1..={max_count}, positive finite radius and peak, and finite non-negative speed";
+    InvalidResourceCellCapacity => "resource_cell_capacity must be finite and positive";
+    InvalidResourceChannels => "resource_channels must be between 1 and 3";
+    InvalidResourceNoiseAmplitude => "resource_noise_amplitude must be finite and non-negative";
+    InvalidResourceNoiseKind => "resource_noise_kind length_scale must be finite and positive";
+    InvalidResourceNoiseRefreshSteps => "resource_noise_refresh_steps must be positive";
+    InvalidPlacement => "placement radius must be finite and non-negative, and grid spacing finite and positive";
+    MultiChannelUptakeUnsupported => "resource_channels > 1 requires resource_uptake_mode = center and resource_sampling = nearest";
     InvalidEnvironmentShiftResourceRate => "environment_shift_resource_rate must be finite and non-negative";
     InvalidMetabolismEfficiencyMultiplier => "metabolism_efficiency_multiplier must be finite and within [0,1]";
+    MetabolicGenomeSizeOutOfRange { min: usize, max: usize, actual: usize } => "metabolic_genome_size ({actual}) must be between {min} and {max}";
+    InvalidAgentRoleCount { max: usize } => "agent_role_count must be 0 or between 2 and {max}";
+    InvalidCounterUptakeRate => "counter_uptake_rate must be finite and non-negative";
+    InvalidCounterConversionEfficiency => "counter_conversion_efficiency must be finite and within [0,1]";
+    InvalidCounterWasteFraction => "counter_waste_fraction must be finite and within [0,1]";
+    InvalidCounterBasalCost => "counter_basal_cost must be finite and non-negative";
     InvalidEnvironmentCycleLowRate => "environment_cycle_low_rate must be finite and non-negative";
+    InvalidMaxCohesionPairs => "max_cohesion_pairs must be positive";
+    InvalidGenomeDiversityMaxPairs => "genome_diversity.max_pairs must be positive";
+    SpatialEntropyGridTooLarge { max: usize, actual: usize } => "spatial_entropy_grid ({actual}) exceeds supported maximum ({max})";
+    EarlyWarningWindowOutOfRange { min: usize, max: usize, actual: usize } => "early_warning_window ({actual}) must be between {min} and {max}";
+    InvalidEarlyWarningEvery => "early_warning_every must be positive";
+    MetricsSelectionWithoutCore => "metrics_selection must include CORE";
+    InvalidSpeciationDistanceThreshold => "speciation_distance_threshold must be finite and positive";
     ConflictingEnvironmentFeatures => "environment_shift_step and environment_cycle_period are mutually exclusive";
+    UnsortedEnvironmentSchedule => "environment_schedule steps must be strictly increasing";
+    InvalidEnvironmentScheduleRate => "environment_schedule rates must be finite and non-negative";
+    ConflictingEnvironmentSchedule => "environment_schedule cannot be combined with environment_shift_step or environment_cycle_period";
     WorldSizeTooLarge { max: f64, actual: f64 } => "world_size ({actual}) exceeds supported maximum ({max})";
 }
 
 impl std::error::Error for SimConfigError {}
 
+/// One failed check from [`SimConfig::validate`], located in the config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimConfigViolation {
+    pub error: SimConfigError,
+    /// Config key the error refers to. Error messages lead with the field name, so
+    /// this is the longest key prefixing the message.
+    pub field: Option<String>,
+    /// Provided value of `field`, rendered as JSON. Only set when the message is
+    /// about that field alone (`"<field> must ..."`), not a relation between fields.
+    pub value: Option<String>,
+}
+
+impl fmt::Display for SimConfigViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{} (got {value})", self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+/// Every violation found by [`SimConfig::validate`], in check order. Never empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimConfigErrors {
+    violations: Vec<SimConfigViolation>,
+}
+
+impl SimConfigErrors {
+    fn new(config: &SimConfig, errors: Vec<SimConfigError>) -> Self {
+        // Round-trip through text so f32 fields print as written rather than widened.
+        let fields: serde_json::Map<String, serde_json::Value> = serde_json::to_string(config)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        let violations = errors
+            .into_iter()
+            .map(|error| {
+                let message = error.to_string();
+                let field = fields
+                    .keys()
+                    .filter(|key| message.starts_with(key.as_str()))
+                    .max_by_key(|key| key.len())
+                    .cloned();
+                let value = field
+                    .as_deref()
+                    .filter(|key| message[key.len()..].starts_with(" must"))
+                    .and_then(|key| fields.get(key))
+                    .map(|value| match value {
+                        serde_json::Value::Null => "non-finite".to_string(),
+                        other => other.to_string(),
+                    });
+                SimConfigViolation {
+                    error,
+                    field,
+                    value,
+                }
+            })
+            .collect();
+        Self { violations }
+    }
+
+    pub fn violations(&self) -> &[SimConfigViolation] {
+        &self.violations
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &SimConfigError> {
+        self.violations.iter().map(|v| &v.error)
+    }
+
+    pub fn first(&self) -> &SimConfigError {
+        &self.violations[0].error
+    }
+}
+
+impl fmt::Display for SimConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let [only] = self.violations.as_slice() {
+            return write!(f, "{only}");
+        }
+        write!(f, "{} invalid config values:", self.violations.len())?;
+        for violation in &self.violations {
+            write!(f, "\n  - {violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SimConfigErrors {}
+
+/// A non-fatal finding from [`SimConfig::warnings`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimConfigWarning {
+    ReproductionWithoutMetabolism,
+    EvolutionWithoutReproduction,
+    AblationTargetAlreadyDisabled(AblationTarget),
+}
+
+impl fmt::Display for SimConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReproductionWithoutMetabolism => write!(
+                f,
+                "enable_reproduction is on while enable_metabolism is off, so no energy is gathered to pay for offspring"
+            ),
+            Self::EvolutionWithoutReproduction => write!(
+                f,
+                "enable_evolution has no effect while enable_reproduction is off, since mutation only happens at birth"
+            ),
+            Self::AblationTargetAlreadyDisabled(target) => write!(
+                f,
+                "ablation_targets includes {target:?}, which is already disabled before ablation_step"
+            ),
+        }
+    }
+}
+
 impl SimConfig {
     pub const MAX_WORLD_SIZE: f64 = crate::constants::MAX_WORLD_SIZE;
 
+    /// Default `max_total_agents`.
     pub const MAX_TOTAL_AGENTS: usize = 250_000;
+    /// Default `max_experiment_steps`.
+    pub const MAX_EXPERIMENT_STEPS: usize = 1_000_000;
+    /// Default `max_experiment_samples`.
+    pub const MAX_EXPERIMENT_SAMPLES: usize = 50_000;
+    /// Heap bytes per agent beyond `size_of::<Agent>()` in
+    /// [`SimConfig::estimated_memory_bytes`]: the world's per-agent scratch buffers
+    /// and organism agent lists.
+    const AGENT_BOOKKEEPING_BYTES: u64 = 64;
 
-    pub fn validate(&self) -> Result<(), SimConfigError> {
-        self.validate_agents()?;
-        self.validate_world_and_physics()?;
-        self.validate_metabolism()?;
-        self.validate_boundary()?;
-        self.validate_death()?;
-        self.validate_reproduction()?;
-        self.validate_crowding()?;
-        self.validate_simulation_steps()?;
-        self.validate_mutation()?;
-        self.validate_homeostasis()?;
-        self.validate_growth()?;
-        self.validate_environment()?;
-        Ok(())
+    /// Smallest `metabolic_genome_size`: the encoding of a two-node graph.
+    pub const MIN_METABOLIC_GENOME_SIZE: usize = crate::metabolism::MIN_METABOLIC_SEGMENT_SIZE;
+
+    /// Largest `metabolic_genome_size`, keeping decoded graphs to at most 21 nodes.
+    pub const MAX_METABOLIC_GENOME_SIZE: usize = 256;
+
+    /// Most agent role archetypes a genome may encode.
+    pub const MAX_AGENT_ROLES: usize = 4;
+
+    /// Most hotspots `ResourceDynamics::MovingHotspots` may place.
+    pub const MAX_RESOURCE_HOTSPOTS: usize = 64;
+
+    /// Largest `spatial_entropy_grid`, bounding the occupancy histogram to 4M cells.
+    pub const MAX_SPATIAL_ENTROPY_GRID: usize = 2048;
+
+    /// Smallest `early_warning_window` giving a lag-1 autocorrelation over two pairs.
+    pub const MIN_EARLY_WARNING_WINDOW: usize = 3;
+
+    /// Largest `early_warning_window`.
+    pub const MAX_EARLY_WARNING_WINDOW: usize = 10_000;
+
+    /// Genome segment layout implied by `metabolic_genome_size`,
+    /// `agent_role_count` and `maturity_gating_evolvable`.
+    pub fn genome_layout(&self) -> crate::genome::GenomeLayout {
+        crate::genome::GenomeLayout {
+            metabolic_size: self.metabolic_genome_size,
+            agent_roles: self.agent_role_count,
+            maturity_gating_genes: self.maturity_gating_evolvable,
+        }
+    }
+
+    /// Check every field, reporting all violations rather than stopping at the first.
+    pub fn validate(&self) -> Result<(), SimConfigErrors> {
+        let mut errors = Vec::new();
+        self.validate_agents(&mut errors);
+        self.validate_world_and_physics(&mut errors);
+        self.validate_metabolism(&mut errors);
+        self.validate_boundary(&mut errors);
+        self.validate_death(&mut errors);
+        self.validate_reproduction(&mut errors);
+        self.validate_crowding(&mut errors);
+        self.validate_predation(&mut errors);
+        self.validate_simulation_steps(&mut errors);
+        self.validate_mutation(&mut errors);
+        self.validate_homeostasis(&mut errors);
+        self.validate_growth(&mut errors);
+        self.validate_environment(&mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SimConfigErrors::new(self, errors))
+        }
+    }
+
+    /// Rough heap footprint, in bytes, of a freshly built world: its agents and
+    /// their bookkeeping, each organism's runtime state with its genome and
+    /// ancestor genome, and the resource (and waste) grids. Growth through
+    /// reproduction and transient per-step allocations are not included.
+    pub fn estimated_memory_bytes(&self) -> u64 {
+        let organisms = self.num_organisms as u64;
+        let agents = organisms.saturating_mul(self.agents_per_organism as u64);
+        let nn_weights = if self.enable_resource_gradient_sensing {
+            crate::nn::NeuralNet::EXTENDED_WEIGHT_COUNT
+        } else {
+            crate::nn::NeuralNet::WEIGHT_COUNT
+        };
+        let genome_bytes = (self.genome_layout().total_len(nn_weights) * size_of::<f32>()) as u64;
+        let organism_bytes =
+            size_of::<crate::organism::OrganismRuntime>() as u64 + 2 * genome_bytes;
+        // Each grid stores a value and a regeneration ceiling per cell and channel.
+        let side = (self.world_size.max(0.0).ceil() as u64).min(u32::MAX as u64);
+        let grids = self.resource_channels as u64 + u64::from(self.enable_waste_field);
+        let grid_bytes = side * side * grids * 2 * size_of::<f32>() as u64;
+        agents
+            .saturating_mul(size_of::<crate::agent::Agent>() as u64 + Self::AGENT_BOOKKEEPING_BYTES)
+            .saturating_add(organisms.saturating_mul(organism_bytes))
+            .saturating_add(grid_bytes)
+    }
+
+    /// Like [`SimConfig::validate`], but only reports the first violation.
+    pub fn validate_first(&self) -> Result<(), SimConfigError> {
+        self.validate().map_err(|errors| errors.first().clone())
+    }
+
+    /// Like [`SimConfig::validate`], returning the [`SimConfig::warnings`] of a valid
+    /// config.
+    pub fn validate_with_warnings(&self) -> Result<Vec<SimConfigWarning>, SimConfigErrors> {
+        self.validate().map(|()| self.warnings())
+    }
+
+    /// Settings that are individually valid but contradict each other, so the run
+    /// would not test the condition the config suggests.
+    pub fn warnings(&self) -> Vec<SimConfigWarning> {
+        let mut warnings = Vec::new();
+        let criteria = self.criteria_mask();
+        if criteria.contains(CriteriaMask::REPRODUCTION)
+            && !criteria.contains(CriteriaMask::METABOLISM)
+        {
+            warnings.push(SimConfigWarning::ReproductionWithoutMetabolism);
+        }
+        if criteria.contains(CriteriaMask::EVOLUTION)
+            && !criteria.contains(CriteriaMask::REPRODUCTION)
+        {
+            warnings.push(SimConfigWarning::EvolutionWithoutReproduction);
+        }
+        if self.ablation_step > 0 {
+            for &target in &self.ablation_targets {
+                if !criteria.contains(target.into()) {
+                    warnings.push(SimConfigWarning::AblationTargetAlreadyDisabled(target));
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Default config with exactly the criteria in `mask` enabled.
+    pub fn with_criteria(mask: CriteriaMask) -> Self {
+        let mut config = Self::default();
+        config.set_criteria_mask(mask);
+        config
+    }
+
+    /// Default config with every criterion except `criteria` enabled.
+    pub fn ablate(criteria: impl Into<CriteriaMask>) -> Self {
+        Self::with_criteria(CriteriaMask::all() - criteria.into())
+    }
+
+    /// The criterion-ablation toggles that are currently on.
+    pub fn criteria_mask(&self) -> CriteriaMask {
+        let mut mask = CriteriaMask::empty();
+        mask.set(CriteriaMask::METABOLISM, self.enable_metabolism);
+        mask.set(CriteriaMask::HOMEOSTASIS, self.enable_homeostasis);
+        mask.set(CriteriaMask::BOUNDARY, self.enable_boundary_maintenance);
+        mask.set(CriteriaMask::GROWTH, self.enable_growth);
+        mask.set(CriteriaMask::REPRODUCTION, self.enable_reproduction);
+        mask.set(CriteriaMask::RESPONSE, self.enable_response);
+        mask.set(CriteriaMask::EVOLUTION, self.enable_evolution);
+        mask
     }
 
-    fn validate_agents(&self) -> Result<(), SimConfigError> {
+    /// Set every criterion-ablation toggle from `mask`.
+    pub fn set_criteria_mask(&mut self, mask: CriteriaMask) {
+        self.enable_metabolism = mask.contains(CriteriaMask::METABOLISM);
+        self.enable_homeostasis = mask.contains(CriteriaMask::HOMEOSTASIS);
+        self.enable_boundary_maintenance = mask.contains(CriteriaMask::BOUNDARY);
+        self.enable_growth = mask.contains(CriteriaMask::GROWTH);
+        self.enable_reproduction = mask.contains(CriteriaMask::REPRODUCTION);
+        self.enable_response = mask.contains(CriteriaMask::RESPONSE);
+        self.enable_evolution = mask.contains(CriteriaMask::EVOLUTION);
+    }
+
+    fn validate_agents(&self, errors: &mut Vec<SimConfigError>) {
         if self.num_organisms == 0 {
-            return Err(SimConfigError::InvalidNumOrganisms);
+            errors.push(SimConfigError::InvalidNumOrganisms);
         }
         if self.agents_per_organism == 0 {
-            return Err(SimConfigError::InvalidAgentsPerOrganism);
-        }
-        let total_agents = self
-            .num_organisms
-            .checked_mul(self.agents_per_organism)
-            .ok_or(SimConfigError::AgentCountOverflow)?;
-        if total_agents > Self::MAX_TOTAL_AGENTS {
-            return Err(SimConfigError::TooManyAgents {
-                max: Self::MAX_TOTAL_AGENTS,
-                actual: total_agents,
-            });
+            errors.push(SimConfigError::InvalidAgentsPerOrganism);
+        }
+        match self.num_organisms.checked_mul(self.agents_per_organism) {
+            None => errors.push(SimConfigError::AgentCountOverflow),
+            Some(total_agents) if total_agents > self.max_total_agents => {
+                errors.push(SimConfigError::TooManyAgents {
+                    max: self.max_total_agents,
+                    actual: total_agents,
+                });
+            }
+            Some(_) => {}
+        }
+        if self.max_experiment_steps == 0 {
+            errors.push(SimConfigError::InvalidMaxExperimentSteps);
+        }
+        if self.max_experiment_samples == 0 {
+            errors.push(SimConfigError::InvalidMaxExperimentSamples);
         }
-        Ok(())
     }
 
-    fn validate_world_and_physics(&self) -> Result<(), SimConfigError> {
+    fn validate_world_and_physics(&self, errors: &mut Vec<SimConfigError>) {
         if !(self.world_size.is_finite() && self.world_size > 0.0) {
-            return Err(SimConfigError::InvalidWorldSize);
+            errors.push(SimConfigError::InvalidWorldSize);
         }
         if self.world_size > Self::MAX_WORLD_SIZE {
-            return Err(SimConfigError::WorldSizeTooLarge {
+            errors.push(SimConfigError::WorldSizeTooLarge {
                 max: Self::MAX_WORLD_SIZE,
                 actual: self.world_size,
             });
         }
         if !(self.dt.is_finite() && self.dt > 0.0) {
-            return Err(SimConfigError::InvalidDt);
+            errors.push(SimConfigError::InvalidDt);
         }
         if !(self.max_speed.is_finite() && self.max_speed > 0.0) {
-            return Err(SimConfigError::InvalidMaxSpeed);
+            errors.push(SimConfigError::InvalidMaxSpeed);
+        }
+        if !(self.heading_drag.is_finite() && self.heading_drag >= 0.0) {
+            errors.push(SimConfigError::InvalidHeadingDrag);
+        }
+        if !(self.heading_max_turn_rate.is_finite() && self.heading_max_turn_rate >= 0.0) {
+            errors.push(SimConfigError::InvalidHeadingMaxTurnRate);
+        }
+        if !(self.movement_energy_cost.is_finite() && self.movement_energy_cost >= 0.0) {
+            errors.push(SimConfigError::InvalidMovementEnergyCost);
         }
         if !(self.sensing_radius.is_finite() && self.sensing_radius >= 0.0) {
-            return Err(SimConfigError::InvalidSensingRadius);
+            errors.push(SimConfigError::InvalidSensingRadius);
         }
         if !(self.neighbor_norm.is_finite() && self.neighbor_norm > 0.0) {
-            return Err(SimConfigError::InvalidNeighborNorm);
+            errors.push(SimConfigError::InvalidNeighborNorm);
+        }
+        if !(self.sensing_cost_per_radius.is_finite() && self.sensing_cost_per_radius >= 0.0) {
+            errors.push(SimConfigError::InvalidSensingCostPerRadius);
+        }
+        if self.sensing_mode == (SensingMode::KNearest { k: 0 }) {
+            errors.push(SimConfigError::InvalidSensingMode);
+        }
+        if !(self.resource_gradient_norm.is_finite() && self.resource_gradient_norm > 0.0) {
+            errors.push(SimConfigError::InvalidResourceGradientNorm);
+        }
+        if !(self.hebbian_learning_rate.is_finite() && self.hebbian_learning_rate >= 0.0) {
+            errors.push(SimConfigError::InvalidHebbianLearningRate);
+        }
+        if !(self.hebbian_decay.is_finite() && (0.0..=1.0).contains(&self.hebbian_decay)) {
+            errors.push(SimConfigError::InvalidHebbianDecay);
+        }
+        if !(self.hebbian_max_delta.is_finite() && self.hebbian_max_delta >= 0.0) {
+            errors.push(SimConfigError::InvalidHebbianMaxDelta);
+        }
+        if !(self.nn_output_gain.is_finite() && self.nn_output_gain > 0.0) {
+            errors.push(SimConfigError::InvalidNnOutputGain);
         }
-        Ok(())
     }
 
-    fn validate_metabolism(&self) -> Result<(), SimConfigError> {
+    fn validate_metabolism(&self, errors: &mut Vec<SimConfigError>) {
         if !(self.metabolic_viability_floor.is_finite() && self.metabolic_viability_floor >= 0.0) {
-            return Err(SimConfigError::InvalidMetabolicViabilityFloor);
+            errors.push(SimConfigError::InvalidMetabolicViabilityFloor);
         }
         if !(self.metabolism_efficiency_multiplier.is_finite()
             && (0.0..=1.0).contains(&self.metabolism_efficiency_multiplier))
         {
-            return Err(SimConfigError::InvalidMetabolismEfficiencyMultiplier);
+            errors.push(SimConfigError::InvalidMetabolismEfficiencyMultiplier);
+        }
+        if !(Self::MIN_METABOLIC_GENOME_SIZE..=Self::MAX_METABOLIC_GENOME_SIZE)
+            .contains(&self.metabolic_genome_size)
+        {
+            errors.push(SimConfigError::MetabolicGenomeSizeOutOfRange {
+                min: Self::MIN_METABOLIC_GENOME_SIZE,
+                max: Self::MAX_METABOLIC_GENOME_SIZE,
+                actual: self.metabolic_genome_size,
+            });
+        }
+        if self.agent_role_count == 1 || self.agent_role_count > Self::MAX_AGENT_ROLES {
+            errors.push(SimConfigError::InvalidAgentRoleCount {
+                max: Self::MAX_AGENT_ROLES,
+            });
+        }
+        if !(self.counter_uptake_rate.is_finite() && self.counter_uptake_rate >= 0.0) {
+            errors.push(SimConfigError::InvalidCounterUptakeRate);
+        }
+        if !(self.counter_conversion_efficiency.is_finite()
+            && (0.0..=1.0).contains(&self.counter_conversion_efficiency))
+        {
+            errors.push(SimConfigError::InvalidCounterConversionEfficiency);
+        }
+        if !(self.counter_waste_fraction.is_finite()
+            && (0.0..=1.0).contains(&self.counter_waste_fraction))
+        {
+            errors.push(SimConfigError::InvalidCounterWasteFraction);
+        }
+        if !(self.counter_basal_cost.is_finite() && self.counter_basal_cost >= 0.0) {
+            errors.push(SimConfigError::InvalidCounterBasalCost);
+        }
+        if !(self.energy_sharing_rate.is_finite() && self.energy_sharing_rate >= 0.0) {
+            errors.push(SimConfigError::InvalidEnergySharingRate);
+        }
+        if !(self.energy_sharing_radius.is_finite() && self.energy_sharing_radius >= 0.0) {
+            errors.push(SimConfigError::InvalidEnergySharingRadius);
         }
         if !(self.setpoint_pid_base.is_finite() && (0.0..=1.0).contains(&self.setpoint_pid_base)) {
-            return Err(SimConfigError::InvalidSetpointPidBase);
+            errors.push(SimConfigError::InvalidSetpointPidBase);
         }
         if !(self.setpoint_pid_energy_scale.is_finite() && self.setpoint_pid_energy_scale >= 0.0) {
-            return Err(SimConfigError::InvalidSetpointPidEnergyScale);
+            errors.push(SimConfigError::InvalidSetpointPidEnergyScale);
         }
         if !(self.setpoint_pid_kp.is_finite() && self.setpoint_pid_kp >= 0.0) {
-            return Err(SimConfigError::InvalidSetpointPidKp);
+            errors.push(SimConfigError::InvalidSetpointPidKp);
         }
         if !(self.spatial_hull_repair_base.is_finite() && self.spatial_hull_repair_base >= 0.0) {
-            return Err(SimConfigError::InvalidSpatialHullRepairBase);
+            errors.push(SimConfigError::InvalidSpatialHullRepairBase);
         }
         if !(self.spatial_hull_repair_cohesion_scale.is_finite()
             && self.spatial_hull_repair_cohesion_scale >= 0.0)
         {
-            return Err(SimConfigError::InvalidSpatialHullRepairCohesionScale);
+            errors.push(SimConfigError::InvalidSpatialHullRepairCohesionScale);
         }
         if !(self.spatial_hull_decay_base.is_finite() && self.spatial_hull_decay_base >= 0.0) {
-            return Err(SimConfigError::InvalidSpatialHullDecayBase);
+            errors.push(SimConfigError::InvalidSpatialHullDecayBase);
         }
         if !(self.spatial_hull_decay_cohesion_scale.is_finite()
             && self.spatial_hull_decay_cohesion_scale >= 0.0)
         {
-            return Err(SimConfigError::InvalidSpatialHullDecayCohesionScale);
+            errors.push(SimConfigError::InvalidSpatialHullDecayCohesionScale);
         }
         if !(self.spatial_hull_decay_min.is_finite() && self.spatial_hull_decay_min >= 0.0) {
-            return Err(SimConfigError::InvalidSpatialHullDecayMin);
+            errors.push(SimConfigError::InvalidSpatialHullDecayMin);
         }
-        Ok(())
     }
 
-    fn validate_boundary(&self) -> Result<(), SimConfigError> {
+    fn validate_boundary(&self, errors: &mut Vec<SimConfigError>) {
         if !(self.boundary_decay_base_rate.is_finite() && self.boundary_decay_base_rate >= 0.0) {
-            return Err(SimConfigError::InvalidBoundaryDecayBaseRate);
+            errors.push(SimConfigError::InvalidBoundaryDecayBaseRate);
         }
         if !(self.boundary_decay_energy_scale.is_finite()
             && self.boundary_decay_energy_scale >= 0.0)
         {
-            return Err(SimConfigError::InvalidBoundaryDecayEnergyScale);
+            errors.push(SimConfigError::InvalidBoundaryDecayEnergyScale);
         }
         if !(self.boundary_waste_pressure_scale.is_finite()
             && self.boundary_waste_pressure_scale >= 0.0)
         {
-            return Err(SimConfigError::InvalidBoundaryWastePressureScale);
+            errors.push(SimConfigError::InvalidBoundaryWastePressureScale);
         }
         if !(self.boundary_repair_waste_penalty_scale.is_finite()
             && self.boundary_repair_waste_penalty_scale >= 0.0)
         {
-            return Err(SimConfigError::InvalidBoundaryRepairWastePenaltyScale);
+            errors.push(SimConfigError::InvalidBoundaryRepairWastePenaltyScale);
         }
         if !(self.boundary_repair_rate.is_finite() && self.boundary_repair_rate >= 0.0) {
-            return Err(SimConfigError::InvalidBoundaryRepairRate);
+            errors.push(SimConfigError::InvalidBoundaryRepairRate);
         }
         if !(self.boundary_collapse_threshold.is_finite()
             && (0.0..=1.0).contains(&self.boundary_collapse_threshold))
         {
-            return Err(SimConfigError::InvalidBoundaryCollapseThreshold);
+            errors.push(SimConfigError::InvalidBoundaryCollapseThreshold);
+        }
+        if !(self.cohesion_boundary_coupling.is_finite() && self.cohesion_boundary_coupling >= 0.0)
+        {
+            errors.push(SimConfigError::InvalidCohesionBoundaryCoupling);
+        }
+        if !(self.boundary_cohesion_threshold.is_finite()
+            && (0.0..=1.0).contains(&self.boundary_cohesion_threshold))
+        {
+            errors.push(SimConfigError::InvalidBoundaryCohesionThreshold);
         }
-        Ok(())
     }
 
-    fn validate_death(&self) -> Result<(), SimConfigError> {
+    fn validate_death(&self, errors: &mut Vec<SimConfigError>) {
         if !(self.death_energy_threshold.is_finite() && self.death_energy_threshold >= 0.0) {
-            return Err(SimConfigError::InvalidDeathEnergyThreshold);
+            errors.push(SimConfigError::InvalidDeathEnergyThreshold);
         }
         if !(self.death_boundary_threshold.is_finite()
             && (0.0..=1.0).contains(&self.death_boundary_threshold))
         {
-            return Err(SimConfigError::InvalidDeathBoundaryThreshold);
+            errors.push(SimConfigError::InvalidDeathBoundaryThreshold);
+        }
+        if !(self.dormancy_entry_threshold.is_finite() && self.dormancy_entry_threshold >= 0.0) {
+            errors.push(SimConfigError::InvalidDormancyEntryThreshold);
+        }
+        if !(self.dormancy_metabolic_scale.is_finite()
+            && (0.0..=1.0).contains(&self.dormancy_metabolic_scale))
+        {
+            errors.push(SimConfigError::InvalidDormancyMetabolicScale);
+        }
+        if !(self.dormancy_exit_resource.is_finite() && self.dormancy_exit_resource >= 0.0) {
+            errors.push(SimConfigError::InvalidDormancyExitResource);
         }
-        Ok(())
     }
 
-    fn validate_reproduction(&self) -> Result<(), SimConfigError> {
-        if !(self.reproduction_min_energy.is_finite() && self.reproduction_min_energy >= 0.0) {
-            return Err(SimConfigError::InvalidReproductionMinEnergy);
+    fn validate_reproduction(&self, errors: &mut Vec<SimConfigError>) {
+        let min_energy_valid =
+            self.reproduction_min_energy.is_finite() && self.reproduction_min_energy >= 0.0;
+        if !min_energy_valid {
+            errors.push(SimConfigError::InvalidReproductionMinEnergy);
         }
         if !(self.reproduction_min_boundary.is_finite()
             && (0.0..=1.0).contains(&self.reproduction_min_boundary))
         {
-            return Err(SimConfigError::InvalidReproductionMinBoundary);
+            errors.push(SimConfigError::InvalidReproductionMinBoundary);
         }
-        if !(self.reproduction_energy_cost.is_finite() && self.reproduction_energy_cost > 0.0) {
-            return Err(SimConfigError::InvalidReproductionEnergyCost);
+        let energy_cost_valid =
+            self.reproduction_energy_cost.is_finite() && self.reproduction_energy_cost > 0.0;
+        if !energy_cost_valid {
+            errors.push(SimConfigError::InvalidReproductionEnergyCost);
         }
-        if self.reproduction_min_energy < self.reproduction_energy_cost {
-            return Err(SimConfigError::InvalidReproductionEnergyBalance);
+        if min_energy_valid
+            && energy_cost_valid
+            && self.reproduction_min_energy < self.reproduction_energy_cost
+        {
+            errors.push(SimConfigError::InvalidReproductionEnergyBalance);
         }
         if self.reproduction_child_min_agents == 0 {
-            return Err(SimConfigError::InvalidReproductionChildMinAgents);
+            errors.push(SimConfigError::InvalidReproductionChildMinAgents);
         }
         if !(self.reproduction_spawn_radius.is_finite() && self.reproduction_spawn_radius >= 0.0) {
-            return Err(SimConfigError::InvalidReproductionSpawnRadius);
+            errors.push(SimConfigError::InvalidReproductionSpawnRadius);
         }
-        Ok(())
     }
 
-    fn validate_crowding(&self) -> Result<(), SimConfigError> {
+    fn validate_crowding(&self, errors: &mut Vec<SimConfigError>) {
         if !(self.crowding_neighbor_threshold.is_finite()
             && self.crowding_neighbor_threshold >= 0.0)
         {
-            return Err(SimConfigError::InvalidCrowdingNeighborThreshold);
+            errors.push(SimConfigError::InvalidCrowdingNeighborThreshold);
         }
         if !(self.crowding_boundary_decay.is_finite() && self.crowding_boundary_decay >= 0.0) {
-            return Err(SimConfigError::InvalidCrowdingBoundaryDecay);
+            errors.push(SimConfigError::InvalidCrowdingBoundaryDecay);
+        }
+        if !(self.crowding_energy_cost.is_finite() && self.crowding_energy_cost >= 0.0) {
+            errors.push(SimConfigError::InvalidCrowdingEnergyCost);
         }
-        Ok(())
     }
 
-    fn validate_simulation_steps(&self) -> Result<(), SimConfigError> {
-        if self.max_organism_age_steps == 0 {
-            return Err(SimConfigError::InvalidMaxOrganismAgeSteps);
+    fn validate_predation(&self, errors: &mut Vec<SimConfigError>) {
+        if !(self.predation_radius.is_finite() && self.predation_radius >= 0.0) {
+            errors.push(SimConfigError::InvalidPredationRadius);
         }
-        if self.compaction_interval_steps == 0 {
-            return Err(SimConfigError::InvalidCompactionIntervalSteps);
+        if !(self.predation_energy_advantage.is_finite() && self.predation_energy_advantage >= 0.0)
+        {
+            errors.push(SimConfigError::InvalidPredationEnergyAdvantage);
         }
-        Ok(())
-    }
-
-    fn validate_mutation(&self) -> Result<(), SimConfigError> {
-        if !(self.mutation_point_rate.is_finite()
-            && (0.0..=1.0).contains(&self.mutation_point_rate))
+        if !(self.predation_transfer.is_finite() && self.predation_transfer >= 0.0) {
+            errors.push(SimConfigError::InvalidPredationTransfer);
+        }
+        if !(self.predation_boundary_damage.is_finite()
+            && (0.0..=1.0).contains(&self.predation_boundary_damage))
         {
-            return Err(SimConfigError::InvalidMutationPointRate);
+            errors.push(SimConfigError::InvalidPredationBoundaryDamage);
         }
-        if !(self.mutation_point_scale.is_finite() && self.mutation_point_scale >= 0.0) {
-            return Err(SimConfigError::InvalidMutationPointScale);
+        if !(self.fusion_radius.is_finite() && self.fusion_radius >= 0.0) {
+            errors.push(SimConfigError::InvalidFusionRadius);
         }
-        if !(self.mutation_reset_rate.is_finite()
-            && (0.0..=1.0).contains(&self.mutation_reset_rate))
+        if !(self.fusion_compatibility_threshold.is_finite()
+            && self.fusion_compatibility_threshold >= 0.0)
         {
-            return Err(SimConfigError::InvalidMutationResetRate);
+            errors.push(SimConfigError::InvalidFusionCompatibilityThreshold);
         }
-        if !(self.mutation_scale_rate.is_finite()
-            && (0.0..=1.0).contains(&self.mutation_scale_rate))
+        if !(self.corpse_recycling_fraction.is_finite()
+            && (0.0..=1.0).contains(&self.corpse_recycling_fraction))
         {
-            return Err(SimConfigError::InvalidMutationScaleRate);
+            errors.push(SimConfigError::InvalidCorpseRecyclingFraction);
         }
-        if !(self.mutation_scale_min.is_finite()
-            && self.mutation_scale_max.is_finite()
-            && self.mutation_scale_min > 0.0
-            && self.mutation_scale_max > 0.0
-            && self.mutation_scale_min <= self.mutation_scale_max)
+        if !(self.corpse_waste_recycle_scale.is_finite() && self.corpse_waste_recycle_scale >= 0.0)
         {
-            return Err(SimConfigError::InvalidMutationScaleBounds);
+            errors.push(SimConfigError::InvalidCorpseWasteRecycleScale);
         }
-        if !(self.mutation_value_limit.is_finite() && self.mutation_value_limit > 0.0) {
-            return Err(SimConfigError::InvalidMutationValueLimit);
+        if !(self.corpse_deposit_cell_cap.is_finite() && self.corpse_deposit_cell_cap >= 0.0) {
+            errors.push(SimConfigError::InvalidCorpseDepositCellCap);
         }
-        let mutation_budget =
-            self.mutation_point_rate + self.mutation_reset_rate + self.mutation_scale_rate;
-        if mutation_budget > 1.0 + f32::EPSILON {
-            return Err(SimConfigError::InvalidMutationProbabilityBudget);
+        if !(self.waste_field_decay_rate.is_finite() && self.waste_field_decay_rate >= 0.0) {
+            errors.push(SimConfigError::InvalidWasteFieldDecayRate);
         }
-        Ok(())
-    }
-
-    fn validate_homeostasis(&self) -> Result<(), SimConfigError> {
+        if !(self.waste_uptake_efficiency.is_finite()
+            && (0.0..=1.0).contains(&self.waste_uptake_efficiency))
+        {
+            errors.push(SimConfigError::InvalidWasteUptakeEfficiency);
+        }
+        if !(self.waste_field_toxicity.is_finite() && self.waste_field_toxicity >= 0.0) {
+            errors.push(SimConfigError::InvalidWasteFieldToxicity);
+        }
+        if !(self.toxin_resistance_cost.is_finite() && self.toxin_resistance_cost >= 0.0) {
+            errors.push(SimConfigError::InvalidToxinResistanceCost);
+        }
+    }
+
+    fn validate_simulation_steps(&self, errors: &mut Vec<SimConfigError>) {
+        if self.max_organism_age_steps == 0 {
+            errors.push(SimConfigError::InvalidMaxOrganismAgeSteps);
+        }
+        if !(self.senescence_rate.is_finite() && self.senescence_rate >= 0.0) {
+            errors.push(SimConfigError::InvalidSenescenceRate);
+        }
+        if self.compaction_interval_steps == 0 {
+            errors.push(SimConfigError::InvalidCompactionIntervalSteps);
+        }
+        if self.max_cohesion_pairs == 0 {
+            errors.push(SimConfigError::InvalidMaxCohesionPairs);
+        }
+        if self.genome_diversity.max_pairs == 0 {
+            errors.push(SimConfigError::InvalidGenomeDiversityMaxPairs);
+        }
+        if self.spatial_entropy_grid > Self::MAX_SPATIAL_ENTROPY_GRID {
+            errors.push(SimConfigError::SpatialEntropyGridTooLarge {
+                max: Self::MAX_SPATIAL_ENTROPY_GRID,
+                actual: self.spatial_entropy_grid,
+            });
+        }
+        if !(Self::MIN_EARLY_WARNING_WINDOW..=Self::MAX_EARLY_WARNING_WINDOW)
+            .contains(&self.early_warning_window)
+        {
+            errors.push(SimConfigError::EarlyWarningWindowOutOfRange {
+                min: Self::MIN_EARLY_WARNING_WINDOW,
+                max: Self::MAX_EARLY_WARNING_WINDOW,
+                actual: self.early_warning_window,
+            });
+        }
+        if self.early_warning_every == 0 {
+            errors.push(SimConfigError::InvalidEarlyWarningEvery);
+        }
+        if !self.metrics_selection.contains(MetricsSelection::CORE) {
+            errors.push(SimConfigError::MetricsSelectionWithoutCore);
+        }
+        if !(self.speciation_distance_threshold.is_finite()
+            && self.speciation_distance_threshold > 0.0)
+        {
+            errors.push(SimConfigError::InvalidSpeciationDistanceThreshold);
+        }
+    }
+
+    fn validate_mutation(&self, errors: &mut Vec<SimConfigError>) {
+        let is_probability = |r: f32| r.is_finite() && (0.0..=1.0).contains(&r);
+        if !is_probability(self.mutation_point_rate) {
+            errors.push(SimConfigError::InvalidMutationPointRate);
+        }
+        if !(self.mutation_point_scale.is_finite() && self.mutation_point_scale >= 0.0) {
+            errors.push(SimConfigError::InvalidMutationPointScale);
+        }
+        if !is_probability(self.mutation_reset_rate) {
+            errors.push(SimConfigError::InvalidMutationResetRate);
+        }
+        if !is_probability(self.mutation_scale_rate) {
+            errors.push(SimConfigError::InvalidMutationScaleRate);
+        }
+        if !(self.mutation_scale_min.is_finite()
+            && self.mutation_scale_max.is_finite()
+            && self.mutation_scale_min > 0.0
+            && self.mutation_scale_max > 0.0
+            && self.mutation_scale_min <= self.mutation_scale_max)
+        {
+            errors.push(SimConfigError::InvalidMutationScaleBounds);
+        }
+        if !(self.mutation_value_limit.is_finite() && self.mutation_value_limit > 0.0) {
+            errors.push(SimConfigError::InvalidMutationValueLimit);
+        }
+        if !is_probability(self.mutation_duplication_rate) {
+            errors.push(SimConfigError::InvalidMutationDuplicationRate);
+        }
+        if !is_probability(self.mutation_deletion_rate) {
+            errors.push(SimConfigError::InvalidMutationDeletionRate);
+        }
+        // The budget is only checked once each rate is individually valid, so one bad
+        // rate is not reported twice.
+        let rates = [
+            self.mutation_point_rate,
+            self.mutation_reset_rate,
+            self.mutation_scale_rate,
+        ];
+        if rates.into_iter().all(is_probability) && rates.iter().sum::<f32>() > 1.0 + f32::EPSILON {
+            errors.push(SimConfigError::InvalidMutationProbabilityBudget);
+        }
+        let overrides = &self.mutation_segment_overrides;
+        let override_rates =
+            |o: &SegmentMutationOverride| [o.point_rate, o.reset_rate, o.scale_rate];
+        if overrides
+            .iter()
+            .any(|o| !override_rates(o).into_iter().all(is_probability))
+        {
+            errors.push(SimConfigError::InvalidMutationSegmentRate);
+        }
+        if overrides.iter().map(override_rates).any(|r| {
+            r.into_iter().all(is_probability) && r.iter().sum::<f32>() > 1.0 + f32::EPSILON
+        }) {
+            errors.push(SimConfigError::InvalidMutationSegmentBudget);
+        }
+        if overrides
+            .iter()
+            .enumerate()
+            .any(|(i, o)| overrides[..i].iter().any(|prev| prev.segment == o.segment))
+        {
+            errors.push(SimConfigError::DuplicateMutationSegmentOverride);
+        }
+    }
+
+    fn validate_homeostasis(&self, errors: &mut Vec<SimConfigError>) {
         if !(self.homeostasis_decay_rate.is_finite() && self.homeostasis_decay_rate >= 0.0) {
-            return Err(SimConfigError::InvalidHomeostasisDecayRate);
+            errors.push(SimConfigError::InvalidHomeostasisDecayRate);
+        }
+        if self.enable_genetic_homeostasis && self.homeostasis_mode != HomeostasisMode::NnRegulator
+        {
+            errors.push(SimConfigError::GeneticHomeostasisRequiresNnRegulator);
+        }
+        let shocks = &self.homeostasis_shock_schedule;
+        if shocks.windows(2).any(|w| w[0].0 >= w[1].0)
+            || shocks.iter().any(|&(_, amount)| !amount.is_finite())
+        {
+            errors.push(SimConfigError::InvalidHomeostasisShockSchedule);
+        }
+        if !(self.homeostasis_recovery_epsilon.is_finite()
+            && self.homeostasis_recovery_epsilon > 0.0)
+        {
+            errors.push(SimConfigError::InvalidHomeostasisRecoveryEpsilon);
         }
-        Ok(())
     }
 
-    fn validate_growth(&self) -> Result<(), SimConfigError> {
+    fn validate_growth(&self, errors: &mut Vec<SimConfigError>) {
         if self.growth_maturation_steps == 0 {
-            return Err(SimConfigError::InvalidGrowthMaturationSteps);
+            errors.push(SimConfigError::InvalidGrowthMaturationSteps);
         }
         if !(self.growth_immature_metabolic_efficiency.is_finite()
             && (0.0..=1.0).contains(&self.growth_immature_metabolic_efficiency))
         {
-            return Err(SimConfigError::InvalidGrowthImmatureMetabolicEfficiency);
+            errors.push(SimConfigError::InvalidGrowthImmatureMetabolicEfficiency);
+        }
+    }
+
+    fn resource_init_is_valid(&self) -> bool {
+        let non_negative = |v: f32| v.is_finite() && v >= 0.0;
+        match self.resource_init {
+            ResourceInit::Uniform => true,
+            ResourceInit::Patches {
+                count,
+                radius,
+                peak,
+                background,
+            } => {
+                count > 0
+                    && radius.is_finite()
+                    && radius > 0.0
+                    && non_negative(peak)
+                    && non_negative(background)
+            }
+            ResourceInit::Gradient { low, high, .. } => non_negative(low) && non_negative(high),
+            ResourceInit::Noise { mean, amplitude } => {
+                non_negative(mean) && non_negative(amplitude)
+            }
         }
-        Ok(())
     }
 
-    fn validate_environment(&self) -> Result<(), SimConfigError> {
+    fn validate_environment(&self, errors: &mut Vec<SimConfigError>) {
         if !(self.resource_regeneration_rate.is_finite() && self.resource_regeneration_rate >= 0.0)
         {
-            return Err(SimConfigError::InvalidResourceRegenerationRate);
+            errors.push(SimConfigError::InvalidResourceRegenerationRate);
+        }
+        if !self.resource_init_is_valid() {
+            errors.push(SimConfigError::InvalidResourceInit);
+        }
+        if self
+            .resource_cell_capacity
+            .is_some_and(|cap| !(cap.is_finite() && cap > 0.0))
+        {
+            errors.push(SimConfigError::InvalidResourceCellCapacity);
+        }
+        if let ResourceDynamics::MovingHotspots {
+            count,
+            radius,
+            speed,
+            peak,
+        } = self.resource_dynamics
+        {
+            if !((1..=Self::MAX_RESOURCE_HOTSPOTS).contains(&count)
+                && radius.is_finite()
+                && radius > 0.0
+                && speed.is_finite()
+                && speed >= 0.0
+                && peak.is_finite()
+                && peak > 0.0)
+            {
+                errors.push(SimConfigError::InvalidResourceDynamics {
+                    max_count: Self::MAX_RESOURCE_HOTSPOTS,
+                });
+            }
+        }
+        if !(1..=crate::resource::MAX_RESOURCE_CHANNELS).contains(&self.resource_channels) {
+            errors.push(SimConfigError::InvalidResourceChannels);
+        }
+        if !(self.resource_noise_amplitude.is_finite() && self.resource_noise_amplitude >= 0.0) {
+            errors.push(SimConfigError::InvalidResourceNoiseAmplitude);
+        }
+        if let ResourceNoiseKind::SpatiallyCorrelated { length_scale } = self.resource_noise_kind {
+            if !(length_scale.is_finite() && length_scale > 0.0) {
+                errors.push(SimConfigError::InvalidResourceNoiseKind);
+            }
+        }
+        if self.resource_noise_refresh_steps == 0 {
+            errors.push(SimConfigError::InvalidResourceNoiseRefreshSteps);
+        }
+        let valid_radius = |radius: f64| radius.is_finite() && radius >= 0.0;
+        let valid_placement = match self.placement {
+            PlacementStrategy::ClusteredPerOrganism {
+                radius: Some(radius),
+            } => valid_radius(radius),
+            PlacementStrategy::GridOfClusters { spacing, radius } => {
+                spacing.is_finite() && spacing > 0.0 && valid_radius(radius)
+            }
+            _ => true,
+        };
+        if !valid_placement {
+            errors.push(SimConfigError::InvalidPlacement);
+        }
+        if self.resource_channels > 1
+            && (self.resource_uptake_mode != ResourceUptakeMode::Center
+                || self.resource_sampling != ResourceSamplingMode::Nearest)
+        {
+            errors.push(SimConfigError::MultiChannelUptakeUnsupported);
         }
         if !(self.environment_shift_resource_rate.is_finite()
             && self.environment_shift_resource_rate >= 0.0)
         {
-            return Err(SimConfigError::InvalidEnvironmentShiftResourceRate);
+            errors.push(SimConfigError::InvalidEnvironmentShiftResourceRate);
         }
         if !(self.environment_cycle_low_rate.is_finite() && self.environment_cycle_low_rate >= 0.0)
         {
-            return Err(SimConfigError::InvalidEnvironmentCycleLowRate);
+            errors.push(SimConfigError::InvalidEnvironmentCycleLowRate);
         }
         if self.environment_shift_step > 0 && self.environment_cycle_period > 0 {
-            return Err(SimConfigError::ConflictingEnvironmentFeatures);
+            errors.push(SimConfigError::ConflictingEnvironmentFeatures);
+        }
+        if !self.environment_schedule.is_empty() {
+            if self.environment_shift_step > 0 || self.environment_cycle_period > 0 {
+                errors.push(SimConfigError::ConflictingEnvironmentSchedule);
+            }
+            if self
+                .environment_schedule
+                .windows(2)
+                .any(|w| w[0].0 >= w[1].0)
+            {
+                errors.push(SimConfigError::UnsortedEnvironmentSchedule);
+            }
+            if self
+                .environment_schedule
+                .iter()
+                .any(|&(_, rate)| !(rate.is_finite() && rate >= 0.0))
+            {
+                errors.push(SimConfigError::InvalidEnvironmentScheduleRate);
+            }
+        }
+    }
+
+    /// Regeneration schedule in effect, with the legacy shift/cycle fields translated
+    /// into breakpoints: a shift becomes one breakpoint, a cycle becomes a high/low pair
+    /// repeating every `2 * environment_cycle_period` steps.
+    pub fn effective_environment_schedule(&self) -> EnvironmentSchedule<'_> {
+        let base_rate = self.resource_regeneration_rate;
+        if !self.environment_schedule.is_empty() {
+            return EnvironmentSchedule {
+                base_rate,
+                breakpoints: Cow::Borrowed(&self.environment_schedule),
+                period: None,
+            };
+        }
+        if self.environment_cycle_period > 0 {
+            return EnvironmentSchedule {
+                base_rate,
+                breakpoints: Cow::Owned(vec![
+                    (0, base_rate),
+                    (
+                        self.environment_cycle_period,
+                        self.environment_cycle_low_rate,
+                    ),
+                ]),
+                period: self.environment_cycle_period.checked_mul(2),
+            };
+        }
+        let breakpoints = if self.environment_shift_step > 0 {
+            vec![(
+                self.environment_shift_step,
+                self.environment_shift_resource_rate,
+            )]
+        } else {
+            Vec::new()
+        };
+        EnvironmentSchedule {
+            base_rate,
+            breakpoints: Cow::Owned(breakpoints),
+            period: None,
+        }
+    }
+}
+
+/// Piecewise-constant resource regeneration rate over simulation steps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnvironmentSchedule<'a> {
+    /// Rate before the first breakpoint.
+    pub base_rate: f32,
+    /// `(step, rate)` pairs sorted by strictly increasing step.
+    pub breakpoints: Cow<'a, [(usize, f32)]>,
+    /// When set, steps are reduced modulo this period before lookup.
+    pub period: Option<usize>,
+}
+
+impl EnvironmentSchedule<'_> {
+    /// Regeneration rate applied at `step`.
+    pub fn rate_at(&self, step: usize) -> f32 {
+        let step = self.reduce(step);
+        let idx = self.breakpoints.partition_point(|&(s, _)| s <= step);
+        idx.checked_sub(1)
+            .map_or(self.base_rate, |i| self.breakpoints[i].1)
+    }
+
+    /// Rate of the breakpoint that starts at `step`, or `None` when `step` is not a
+    /// breakpoint.
+    pub fn rate_change_at(&self, step: usize) -> Option<f32> {
+        let step = self.reduce(step);
+        self.breakpoints
+            .binary_search_by_key(&step, |&(s, _)| s)
+            .ok()
+            .map(|i| self.breakpoints[i].1)
+    }
+
+    /// Copy of the schedule that no longer borrows from its config.
+    pub fn into_owned(self) -> EnvironmentSchedule<'static> {
+        EnvironmentSchedule {
+            base_rate: self.base_rate,
+            breakpoints: Cow::Owned(self.breakpoints.into_owned()),
+            period: self.period,
+        }
+    }
+
+    fn reduce(&self, step: usize) -> usize {
+        match self.period {
+            Some(period) if period > 0 => step % period,
+            _ => step,
         }
-        Ok(())
     }
 }
 
@@ -611,18 +2125,63 @@ mod tests {
             world_size: -1.0,
             ..SimConfig::default()
         };
-        assert_eq!(config.validate(), Err(SimConfigError::InvalidWorldSize));
+        assert_eq!(
+            config.validate_first(),
+            Err(SimConfigError::InvalidWorldSize)
+        );
 
         let config = SimConfig {
             world_size: SimConfig::MAX_WORLD_SIZE + 1.0,
             ..SimConfig::default()
         };
         assert!(matches!(
-            config.validate(),
+            config.validate_first(),
             Err(SimConfigError::WorldSizeTooLarge { .. })
         ));
     }
 
+    #[test]
+    fn validate_rejects_invalid_mutation_segment_overrides() {
+        let over_budget = SimConfig {
+            mutation_segment_overrides: vec![SegmentMutationOverride {
+                segment: GenomeSegment::Metabolic,
+                point_rate: 0.6,
+                reset_rate: 0.3,
+                scale_rate: 0.2,
+            }],
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            over_budget.validate_first(),
+            Err(SimConfigError::InvalidMutationSegmentBudget)
+        );
+        let frozen = SegmentMutationOverride {
+            segment: GenomeSegment::Metabolic,
+            point_rate: 0.0,
+            reset_rate: 0.0,
+            scale_rate: 0.0,
+        };
+        let duplicated = SimConfig {
+            mutation_segment_overrides: vec![frozen, frozen],
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            duplicated.validate_first(),
+            Err(SimConfigError::DuplicateMutationSegmentOverride)
+        );
+        let negative = SimConfig {
+            mutation_segment_overrides: vec![SegmentMutationOverride {
+                point_rate: -0.1,
+                ..frozen
+            }],
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            negative.validate_first(),
+            Err(SimConfigError::InvalidMutationSegmentRate)
+        );
+    }
+
     #[test]
     fn validate_rejects_invalid_mutation_budget() {
         let config = SimConfig {
@@ -632,11 +2191,574 @@ mod tests {
             ..SimConfig::default()
         };
         assert_eq!(
-            config.validate(),
+            config.validate_first(),
             Err(SimConfigError::InvalidMutationProbabilityBudget)
         );
     }
 
+    #[test]
+    fn validate_rejects_invalid_resource_init() {
+        let patches = |count, radius| SimConfig {
+            resource_init: ResourceInit::Patches {
+                count,
+                radius,
+                peak: 2.0,
+                background: 0.1,
+            },
+            ..SimConfig::default()
+        };
+        assert_eq!(patches(2, 3.0).validate_first(), Ok(()));
+        for config in [
+            patches(0, 3.0),
+            patches(2, 0.0),
+            SimConfig {
+                resource_init: ResourceInit::Noise {
+                    mean: 1.0,
+                    amplitude: f32::NAN,
+                },
+                ..SimConfig::default()
+            },
+        ] {
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidResourceInit)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_resource_cell_capacity() {
+        for cap in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let cfg = SimConfig {
+                resource_cell_capacity: Some(cap),
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                cfg.validate_first(),
+                Err(SimConfigError::InvalidResourceCellCapacity),
+                "{cap}"
+            );
+        }
+        let parsed: SimConfig = serde_json::from_str(
+            r#"{"regeneration_model": "logistic", "resource_cell_capacity": 1.5}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.regeneration_model, RegenerationModel::Logistic);
+        assert_eq!(parsed.resource_cell_capacity, Some(1.5));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_resource_dynamics() {
+        let hotspots = |count, radius, speed| SimConfig {
+            resource_dynamics: ResourceDynamics::MovingHotspots {
+                count,
+                radius,
+                speed,
+                peak: 3.0,
+            },
+            ..SimConfig::default()
+        };
+        assert_eq!(hotspots(3, 5.0, 0.0).validate_first(), Ok(()));
+        for config in [
+            hotspots(0, 5.0, 1.0),
+            hotspots(65, 5.0, 1.0),
+            hotspots(3, 0.0, 1.0),
+            hotspots(3, 5.0, f64::NAN),
+        ] {
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidResourceDynamics { max_count: 64 })
+            );
+        }
+        let parsed: SimConfig = serde_json::from_str(
+            r#"{"resource_dynamics": {"kind": "moving_hotspots", "count": 2, "radius": 4.0, "speed": 0.5, "peak": 2.0}}"#,
+        )
+        .expect("moving hotspots should parse");
+        assert_eq!(
+            parsed.resource_dynamics,
+            ResourceDynamics::MovingHotspots {
+                count: 2,
+                radius: 4.0,
+                speed: 0.5,
+                peak: 2.0,
+            }
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_hebbian_parameters() {
+        let cases = [
+            (
+                SimConfig {
+                    hebbian_learning_rate: -0.1,
+                    ..SimConfig::default()
+                },
+                SimConfigError::InvalidHebbianLearningRate,
+            ),
+            (
+                SimConfig {
+                    hebbian_decay: 1.5,
+                    ..SimConfig::default()
+                },
+                SimConfigError::InvalidHebbianDecay,
+            ),
+            (
+                SimConfig {
+                    hebbian_max_delta: f32::NAN,
+                    ..SimConfig::default()
+                },
+                SimConfigError::InvalidHebbianMaxDelta,
+            ),
+        ];
+        for (config, expected) in cases {
+            assert_eq!(config.validate_first(), Err(expected));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_nn_output_gain() {
+        for gain in [0.0, -2.0, f32::INFINITY] {
+            let config = SimConfig {
+                nn_output_gain: gain,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidNnOutputGain)
+            );
+        }
+    }
+
+    #[test]
+    fn criteria_mask_round_trips_through_enable_flags() {
+        assert_eq!(SimConfig::default().criteria_mask(), CriteriaMask::all());
+        for bits in 0..=CriteriaMask::all().bits() {
+            let mask = CriteriaMask::from_bits(bits).unwrap();
+            assert_eq!(SimConfig::with_criteria(mask).criteria_mask(), mask);
+        }
+
+        let ablated = SimConfig::ablate(CriteriaMask::GROWTH);
+        assert!(!ablated.enable_growth);
+        assert_eq!(
+            ablated.criteria_mask(),
+            CriteriaMask::all() - CriteriaMask::GROWTH
+        );
+        assert_eq!(SimConfig::ablate(AblationTarget::Growth), ablated);
+        assert_eq!(
+            SimConfig::ablate(CriteriaMask::BOUNDARY | CriteriaMask::RESPONSE).criteria_mask(),
+            CriteriaMask::METABOLISM
+                | CriteriaMask::HOMEOSTASIS
+                | CriteriaMask::GROWTH
+                | CriteriaMask::REPRODUCTION
+                | CriteriaMask::EVOLUTION
+        );
+
+        let mask = CriteriaMask::METABOLISM | CriteriaMask::EVOLUTION;
+        let json = serde_json::to_string(&mask).unwrap();
+        assert_eq!(serde_json::from_str::<CriteriaMask>(&json).unwrap(), mask);
+    }
+
+    #[test]
+    fn warnings_flag_contradictory_criteria() {
+        assert_eq!(
+            SimConfig::default().validate_with_warnings(),
+            Ok(Vec::new())
+        );
+        assert_eq!(
+            SimConfig::ablate(CriteriaMask::METABOLISM).validate_with_warnings(),
+            Ok(vec![SimConfigWarning::ReproductionWithoutMetabolism])
+        );
+        assert_eq!(
+            SimConfig::ablate(CriteriaMask::REPRODUCTION).warnings(),
+            vec![SimConfigWarning::EvolutionWithoutReproduction]
+        );
+
+        let mut scheduled = SimConfig::ablate(CriteriaMask::GROWTH);
+        scheduled.ablation_targets = vec![AblationTarget::Growth, AblationTarget::Response];
+        assert!(
+            scheduled.warnings().is_empty(),
+            "targets unused without ablation_step"
+        );
+        scheduled.ablation_step = 10;
+        assert_eq!(
+            scheduled.warnings(),
+            vec![SimConfigWarning::AblationTargetAlreadyDisabled(
+                AblationTarget::Growth
+            )]
+        );
+
+        let invalid = SimConfig {
+            world_size: -1.0,
+            ..SimConfig::ablate(CriteriaMask::METABOLISM)
+        };
+        assert!(invalid.validate_with_warnings().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_dormancy_parameters() {
+        let config = SimConfig {
+            dormancy_entry_threshold: -0.1,
+            dormancy_metabolic_scale: 1.5,
+            dormancy_exit_resource: f32::INFINITY,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err().errors().collect::<Vec<_>>(),
+            vec![
+                &SimConfigError::InvalidDormancyEntryThreshold,
+                &SimConfigError::InvalidDormancyMetabolicScale,
+                &SimConfigError::InvalidDormancyExitResource,
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_cohesion_boundary_parameters() {
+        let config = SimConfig {
+            cohesion_boundary_coupling: f32::NAN,
+            boundary_cohesion_threshold: 1.5,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err().errors().collect::<Vec<_>>(),
+            vec![
+                &SimConfigError::InvalidCohesionBoundaryCoupling,
+                &SimConfigError::InvalidBoundaryCohesionThreshold,
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_waste_field_parameters() {
+        let config = SimConfig {
+            waste_field_decay_rate: -0.1,
+            waste_uptake_efficiency: 1.5,
+            waste_field_toxicity: f32::NAN,
+            toxin_resistance_cost: -1.0,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err().errors().collect::<Vec<_>>(),
+            vec![
+                &SimConfigError::InvalidWasteFieldDecayRate,
+                &SimConfigError::InvalidWasteUptakeEfficiency,
+                &SimConfigError::InvalidWasteFieldToxicity,
+                &SimConfigError::InvalidToxinResistanceCost,
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_energy_sharing_parameters() {
+        let config = SimConfig {
+            energy_sharing_rate: -0.5,
+            energy_sharing_radius: f64::NAN,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err().errors().collect::<Vec<_>>(),
+            vec![
+                &SimConfigError::InvalidEnergySharingRate,
+                &SimConfigError::InvalidEnergySharingRadius,
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_early_warning_parameters() {
+        let config = SimConfig {
+            early_warning_window: 2,
+            early_warning_every: 0,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err().errors().collect::<Vec<_>>(),
+            vec![
+                &SimConfigError::EarlyWarningWindowOutOfRange {
+                    min: 3,
+                    max: 10_000,
+                    actual: 2,
+                },
+                &SimConfigError::InvalidEarlyWarningEvery,
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_metrics_selection_without_core() {
+        let config = SimConfig {
+            metrics_selection: MetricsSelection::STD_DEVS,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate_first(),
+            Err(SimConfigError::MetricsSelectionWithoutCore)
+        );
+        let json = serde_json::to_value(SimConfig {
+            metrics_selection: MetricsSelection::CORE | MetricsSelection::STD_DEVS,
+            ..SimConfig::default()
+        })
+        .unwrap();
+        assert_eq!(json["metrics_selection"], "CORE | STD_DEVS");
+    }
+
+    #[test]
+    fn validate_rejects_invalid_speciation_threshold() {
+        for threshold in [0.0, -1.0, f32::NAN] {
+            let config = SimConfig {
+                speciation_distance_threshold: threshold,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidSpeciationDistanceThreshold)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_unencodable_metabolic_genome_size() {
+        for size in [0, 8, 257] {
+            let config = SimConfig {
+                metabolic_genome_size: size,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::MetabolicGenomeSizeOutOfRange {
+                    min: 9,
+                    max: 256,
+                    actual: size,
+                })
+            );
+        }
+        for size in [9, 16, 32, 64, 256] {
+            let config = SimConfig {
+                metabolic_genome_size: size,
+                ..SimConfig::default()
+            };
+            assert!(config.validate().is_ok(), "size {size} should be accepted");
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_homeostasis_shocks() {
+        for schedule in [vec![(10, 0.1), (10, 0.2)], vec![(10, f32::NAN)]] {
+            let config = SimConfig {
+                homeostasis_shock_schedule: schedule,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidHomeostasisShockSchedule)
+            );
+        }
+        let config = SimConfig {
+            homeostasis_recovery_epsilon: 0.0,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate_first(),
+            Err(SimConfigError::InvalidHomeostasisRecoveryEpsilon)
+        );
+        let config = SimConfig {
+            homeostasis_shock_schedule: vec![(10, -0.3), (20, 0.3)],
+            ..SimConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_agent_role_count() {
+        for count in [1, 5] {
+            let config = SimConfig {
+                agent_role_count: count,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidAgentRoleCount { max: 4 })
+            );
+        }
+        for count in [0, 2, 3, 4] {
+            let config = SimConfig {
+                agent_role_count: count,
+                ..SimConfig::default()
+            };
+            assert!(
+                config.validate().is_ok(),
+                "count {count} should be accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_structural_mutation_rates() {
+        for rate in [-0.1, 1.5, f32::NAN] {
+            let config = SimConfig {
+                mutation_duplication_rate: rate,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidMutationDuplicationRate)
+            );
+            let config = SimConfig {
+                mutation_deletion_rate: rate,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidMutationDeletionRate)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_sensing_cost_per_radius() {
+        for cost in [-0.1, f32::NAN, f32::INFINITY] {
+            let config = SimConfig {
+                sensing_cost_per_radius: cost,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidSensingCostPerRadius)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_crowding_energy_cost() {
+        for cost in [-0.1, f32::NAN, f32::INFINITY] {
+            let config = SimConfig {
+                crowding_energy_cost: cost,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidCrowdingEnergyCost)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_rejects_zero_k_nearest() {
+        let config = SimConfig {
+            sensing_mode: SensingMode::KNearest { k: 0 },
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate_first(),
+            Err(SimConfigError::InvalidSensingMode)
+        );
+        let parsed: SimConfig =
+            serde_json::from_str(r#"{"sensing_mode": {"kind": "k_nearest", "k": 6}}"#)
+                .expect("k_nearest sensing mode should parse");
+        assert_eq!(parsed.sensing_mode, SensingMode::KNearest { k: 6 });
+        assert!(parsed.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_resource_noise() {
+        for amplitude in [-0.1, f32::NAN] {
+            let config = SimConfig {
+                resource_noise_amplitude: amplitude,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidResourceNoiseAmplitude)
+            );
+        }
+        for length_scale in [0.0, -1.0, f64::INFINITY] {
+            let config = SimConfig {
+                resource_noise_kind: ResourceNoiseKind::SpatiallyCorrelated { length_scale },
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidResourceNoiseKind)
+            );
+        }
+        let config = SimConfig {
+            resource_noise_refresh_steps: 0,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate_first(),
+            Err(SimConfigError::InvalidResourceNoiseRefreshSteps)
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_placement() {
+        for placement in [
+            PlacementStrategy::ClusteredPerOrganism { radius: Some(-1.0) },
+            PlacementStrategy::ClusteredPerOrganism {
+                radius: Some(f64::NAN),
+            },
+            PlacementStrategy::GridOfClusters {
+                spacing: 0.0,
+                radius: 1.0,
+            },
+            PlacementStrategy::GridOfClusters {
+                spacing: 10.0,
+                radius: f64::INFINITY,
+            },
+        ] {
+            let config = SimConfig {
+                placement,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidPlacement),
+                "{placement:?}"
+            );
+        }
+        let parsed: SimConfig = serde_json::from_str(
+            r#"{"placement": {"kind": "grid_of_clusters", "spacing": 20.0, "radius": 3.0}}"#,
+        )
+        .expect("grid_of_clusters placement should parse");
+        assert_eq!(
+            parsed.placement,
+            PlacementStrategy::GridOfClusters {
+                spacing: 20.0,
+                radius: 3.0
+            }
+        );
+        assert!(parsed.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_resource_channels() {
+        for channels in [0, 4] {
+            let config = SimConfig {
+                resource_channels: channels,
+                ..SimConfig::default()
+            };
+            assert_eq!(
+                config.validate_first(),
+                Err(SimConfigError::InvalidResourceChannels)
+            );
+        }
+        let config = SimConfig {
+            resource_channels: 2,
+            resource_sampling: ResourceSamplingMode::Bilinear,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate_first(),
+            Err(SimConfigError::MultiChannelUptakeUnsupported)
+        );
+        let config = SimConfig {
+            resource_channels: 3,
+            ..SimConfig::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn deserialize_rejects_unknown_ablation_target() {
         let invalid_json = r#"{
@@ -666,10 +2788,36 @@ mod tests {
         }"#;
         let cfg: SimConfig = serde_json::from_str(legacy_json).expect("legacy config should parse");
         assert_eq!(cfg.metabolism_mode, MetabolismMode::Toy);
+        assert_eq!(cfg.metabolic_genome_size, 16);
+        assert_eq!(cfg.agent_role_count, 0);
+        assert_eq!(cfg.locomotion, Locomotion::Holonomic);
+        assert!((cfg.heading_drag - 0.5).abs() < f64::EPSILON);
+        assert!((cfg.heading_max_turn_rate - std::f64::consts::PI).abs() < f64::EPSILON);
+        assert!((cfg.counter_uptake_rate - 0.4).abs() < f32::EPSILON);
+        assert!((cfg.counter_conversion_efficiency - 0.5).abs() < f32::EPSILON);
+        assert!(cfg.counter_waste_fraction.abs() < f32::EPSILON);
+        assert!(cfg.counter_basal_cost.abs() < f32::EPSILON);
         assert!(cfg.boundary_decay_base_rate > 0.0);
         assert!(cfg.reproduction_min_energy > 0.0);
         assert!(cfg.max_organism_age_steps > 0);
+        assert_eq!(cfg.senescence_mode, SenescenceMode::Off);
+        assert_eq!(cfg.senescence_onset_steps, 5_000);
+        assert!((cfg.senescence_rate - 0.0002).abs() < f32::EPSILON);
+        assert!(!cfg.senescence_evolvable);
+        assert!(!cfg.enable_resource_gradient_sensing);
+        assert!((cfg.resource_gradient_norm - 0.1).abs() < f32::EPSILON);
+        assert!(cfg.corpse_recycling_fraction.abs() < f32::EPSILON);
+        assert!(cfg.corpse_waste_recycle_scale.abs() < f32::EPSILON);
+        assert!((cfg.corpse_deposit_cell_cap - 2.0).abs() < f32::EPSILON);
+        assert!(!cfg.enable_waste_field);
+        assert!((cfg.waste_field_decay_rate - 0.05).abs() < f32::EPSILON);
+        assert!((cfg.waste_uptake_efficiency - 0.3).abs() < f32::EPSILON);
+        assert_eq!(cfg.waste_field_toxicity, 0.0);
+        assert_eq!(cfg.toxin_resistance_cost, 0.0);
         assert!(cfg.compaction_interval_steps > 0);
+        assert!(!cfg.recycle_agent_ids);
+        assert_eq!(cfg.capacity_policy, CapacityPolicy::FirstComeFirstServed);
+        assert_eq!(cfg.rng_mode, RngMode::Global);
         assert!(cfg.mutation_value_limit > 0.0);
         // New ablation toggles must default to true for backward compatibility
         assert!(cfg.enable_homeostasis);
@@ -677,10 +2825,14 @@ mod tests {
         assert!(cfg.enable_reproduction);
         assert!(cfg.enable_evolution);
         assert!(cfg.enable_growth);
+        assert!(!cfg.enable_maturity_gating);
+        assert!(!cfg.maturity_gating_evolvable);
         assert_eq!(cfg.ablation_step, 0);
         assert!(cfg.ablation_targets.is_empty());
         assert_eq!(cfg.boundary_mode, BoundaryMode::ScalarRepair);
+        assert_eq!(cfg.boundary_exposure, BoundaryExposure::Lagged);
         assert_eq!(cfg.homeostasis_mode, HomeostasisMode::NnRegulator);
+        assert!(!cfg.enable_genetic_homeostasis);
         assert_eq!(cfg.setpoint_pid_base, 0.45);
         assert_eq!(cfg.setpoint_pid_energy_scale, 0.1);
         assert_eq!(cfg.setpoint_pid_kp, 0.5);
@@ -689,6 +2841,150 @@ mod tests {
         assert_eq!(cfg.spatial_hull_decay_base, 1.2);
         assert_eq!(cfg.spatial_hull_decay_cohesion_scale, 0.5);
         assert_eq!(cfg.spatial_hull_decay_min, 0.5);
+        assert_eq!(cfg.cohesion_boundary_coupling, 0.0);
+        assert_eq!(cfg.boundary_cohesion_threshold, 0.5);
+        assert!(!cfg.enable_self_adaptive_mutation);
+        assert!(!cfg.enable_structural_mutation);
+        assert_eq!(cfg.mutation_duplication_rate, 0.01);
+        assert_eq!(cfg.mutation_deletion_rate, 0.01);
+        assert!(!cfg.enable_predation);
+        assert!(!cfg.enable_fusion);
+        assert_eq!(cfg.fusion_radius, 2.0);
+        assert_eq!(cfg.fusion_compatibility_threshold, 1.0);
+        assert_eq!(cfg.resource_sampling, ResourceSamplingMode::Nearest);
+        assert_eq!(cfg.resource_init, ResourceInit::Uniform);
+        assert_eq!(cfg.resource_dynamics, ResourceDynamics::Static);
+        assert_eq!(cfg.regeneration_model, RegenerationModel::Linear);
+        assert_eq!(cfg.resource_cell_capacity, None);
+        assert_eq!(cfg.resource_channels, 1);
+        assert_eq!(cfg.resource_reset_policy, ResourceResetPolicy::Preserve);
+        assert_eq!(cfg.resource_noise_amplitude, 0.0);
+        assert_eq!(cfg.resource_noise_kind, ResourceNoiseKind::White);
+        assert_eq!(
+            cfg.placement,
+            PlacementStrategy::ClusteredPerOrganism { radius: None }
+        );
+        assert_eq!(cfg.resource_noise_refresh_steps, 50);
+        assert_eq!(cfg.crowding_energy_cost, 0.0);
+        assert!(!cfg.enable_genetic_sensing);
+        assert_eq!(cfg.sensing_cost_per_radius, 0.001);
+        assert_eq!(cfg.sensing_mode, SensingMode::Radius);
+        assert!(!cfg.enable_hebbian_plasticity);
+        assert_eq!(cfg.nn_hidden_activation, Activation::Tanh);
+        assert_eq!(cfg.nn_output_gain, 1.0);
+        assert_eq!(cfg.hebbian_learning_rate, 0.01);
+        assert_eq!(cfg.hebbian_decay, 0.01);
+        assert_eq!(cfg.hebbian_max_delta, 0.5);
+        assert!(!cfg.enable_event_log);
+        assert_eq!(cfg.lineage_flush_interval_steps, 100);
+        assert!(!cfg.collect_timings);
+        assert!(!cfg.parallel_organism_phases);
+        assert!(!cfg.audit_conservation);
+        assert_eq!(cfg.max_total_agents, SimConfig::MAX_TOTAL_AGENTS);
+        assert_eq!(cfg.max_experiment_steps, SimConfig::MAX_EXPERIMENT_STEPS);
+        assert_eq!(
+            cfg.max_experiment_samples,
+            SimConfig::MAX_EXPERIMENT_SAMPLES
+        );
+        assert_eq!(cfg.memory_limit_bytes, None);
+        assert!(!cfg.collect_contact_metrics);
+        assert!(!cfg.snapshot_decoded_params);
+        assert!(!cfg.collect_early_warning);
+        assert_eq!(cfg.metrics_selection, MetricsSelection::all());
+        assert_eq!(cfg.early_warning_window, 20);
+        assert_eq!(cfg.early_warning_every, 1);
+        assert_eq!(cfg.speciation_metric_every, 0);
+        assert_eq!(cfg.speciation_distance_threshold, 1.0);
+        assert_eq!(
+            cfg.speciation_genome_scope,
+            SpeciationGenomeScope::NnWeights
+        );
+        assert!(!cfg.enable_agent_energy_reserves);
+        assert_eq!(cfg.energy_sharing_rate, 0.5);
+        assert_eq!(cfg.energy_sharing_radius, 2.0);
+        assert_eq!(cfg.genome_diversity, GenomeDiversityConfig::default());
+        assert_eq!(cfg.genome_diversity.max_pairs, 50);
+        assert!(!cfg.genome_diversity.per_segment);
+        assert_eq!(cfg.spatial_entropy_grid, 0);
+        assert!(!cfg.enable_dormancy);
+        assert_eq!(cfg.dormancy_entry_threshold, 0.1);
+        assert_eq!(cfg.dormancy_metabolic_scale, 0.1);
+        assert_eq!(cfg.dormancy_exit_resource, 0.5);
+        assert!(cfg.environment_schedule.is_empty());
+        assert!(cfg.homeostasis_shock_schedule.is_empty());
+        assert!((cfg.homeostasis_recovery_epsilon - 0.05).abs() < f32::EPSILON);
+        assert!(cfg.movement_energy_cost.abs() < f32::EPSILON);
+        assert!(cfg.mutation_segment_overrides.is_empty());
+    }
+
+    #[test]
+    fn validate_reports_every_invalid_field() {
+        let config = SimConfig {
+            world_size: -5.0,
+            mutation_point_rate: 1.5,
+            resource_channels: 0,
+            ..SimConfig::default()
+        };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(
+            errors.errors().cloned().collect::<Vec<_>>(),
+            vec![
+                SimConfigError::InvalidWorldSize,
+                SimConfigError::InvalidMutationPointRate,
+                SimConfigError::InvalidResourceChannels,
+            ]
+        );
+        let fields: Vec<_> = errors
+            .violations()
+            .iter()
+            .map(|v| (v.field.as_deref(), v.value.as_deref()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (Some("world_size"), Some("-5.0")),
+                (Some("mutation_point_rate"), Some("1.5")),
+                (Some("resource_channels"), Some("0")),
+            ]
+        );
+        assert_eq!(
+            errors.to_string(),
+            "3 invalid config values:\n  \
+             - world_size must be positive and finite (got -5.0)\n  \
+             - mutation_point_rate must be finite and within [0,1] (got 1.5)\n  \
+             - resource_channels must be between 1 and 3 (got 0)"
+        );
+        assert_eq!(
+            config.validate_first(),
+            Err(SimConfigError::InvalidWorldSize)
+        );
+    }
+
+    #[test]
+    fn single_violation_displays_without_header() {
+        let config = SimConfig {
+            dt: f64::NAN,
+            ..SimConfig::default()
+        };
+        assert_eq!(
+            config.validate().unwrap_err().to_string(),
+            "dt must be positive and finite (got non-finite)"
+        );
+        let relational = SimConfig {
+            mutation_point_rate: 0.6,
+            mutation_reset_rate: 0.6,
+            ..SimConfig::default()
+        };
+        let errors = relational.validate().unwrap_err();
+        assert_eq!(
+            errors.first(),
+            &SimConfigError::InvalidMutationProbabilityBudget
+        );
+        assert_eq!(
+            errors.violations()[0].field.as_deref(),
+            Some("mutation_point_rate")
+        );
+        assert_eq!(errors.violations()[0].value, None);
     }
 
     #[test]
@@ -697,7 +2993,10 @@ mod tests {
             num_organisms: 0,
             ..SimConfig::default()
         };
-        assert_eq!(config.validate(), Err(SimConfigError::InvalidNumOrganisms));
+        assert_eq!(
+            config.validate_first(),
+            Err(SimConfigError::InvalidNumOrganisms)
+        );
 
         let config = SimConfig {
             num_organisms: 1,
@@ -705,7 +3004,7 @@ mod tests {
             ..SimConfig::default()
         };
         assert_eq!(
-            config.validate(),
+            config.validate_first(),
             Err(SimConfigError::InvalidAgentsPerOrganism)
         );
 
@@ -714,10 +3013,24 @@ mod tests {
             agents_per_organism: 1,
             ..SimConfig::default()
         };
-        match config.validate() {
+        match config.validate_first() {
             Err(SimConfigError::TooManyAgents { .. }) => (),
             _ => panic!("Expected TooManyAgents error"),
         }
+
+        let config = SimConfig {
+            max_experiment_steps: 0,
+            max_experiment_samples: 0,
+            ..SimConfig::default()
+        };
+        let errors: Vec<_> = config.validate().unwrap_err().errors().cloned().collect();
+        assert_eq!(
+            errors,
+            [
+                SimConfigError::InvalidMaxExperimentSteps,
+                SimConfigError::InvalidMaxExperimentSamples
+            ]
+        );
     }
 
     #[test]
@@ -751,6 +3064,18 @@ mod tests {
                 SimConfigError::InvalidMaxSpeed,
                 "max_speed must be positive and finite",
             ),
+            (
+                SimConfigError::InvalidHeadingDrag,
+                "heading_drag must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidHeadingMaxTurnRate,
+                "heading_max_turn_rate must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidMovementEnergyCost,
+                "movement_energy_cost must be finite and non-negative",
+            ),
             (
                 SimConfigError::InvalidSensingRadius,
                 "sensing_radius must be non-negative and finite",
@@ -759,6 +3084,34 @@ mod tests {
                 SimConfigError::InvalidNeighborNorm,
                 "neighbor_norm must be positive and finite",
             ),
+            (
+                SimConfigError::InvalidSensingCostPerRadius,
+                "sensing_cost_per_radius must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidSensingMode,
+                "sensing_mode k must be greater than 0",
+            ),
+            (
+                SimConfigError::InvalidHebbianLearningRate,
+                "hebbian_learning_rate must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidHebbianDecay,
+                "hebbian_decay must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidHebbianMaxDelta,
+                "hebbian_max_delta must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidNnOutputGain,
+                "nn_output_gain must be finite and positive",
+            ),
+            (
+                SimConfigError::InvalidResourceGradientNorm,
+                "resource_gradient_norm must be positive and finite",
+            ),
             (
                 SimConfigError::InvalidMetabolicViabilityFloor,
                 "metabolic_viability_floor must be finite and non-negative",
@@ -787,6 +3140,14 @@ mod tests {
                 SimConfigError::InvalidBoundaryCollapseThreshold,
                 "boundary_collapse_threshold must be finite and within [0,1]",
             ),
+            (
+                SimConfigError::InvalidCohesionBoundaryCoupling,
+                "cohesion_boundary_coupling must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidBoundaryCohesionThreshold,
+                "boundary_cohesion_threshold must be finite and within [0,1]",
+            ),
             (
                 SimConfigError::InvalidDeathEnergyThreshold,
                 "death_energy_threshold must be finite and non-negative",
@@ -795,6 +3156,26 @@ mod tests {
                 SimConfigError::InvalidDeathBoundaryThreshold,
                 "death_boundary_threshold must be finite and within [0,1]",
             ),
+            (
+                SimConfigError::InvalidDormancyEntryThreshold,
+                "dormancy_entry_threshold must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidDormancyMetabolicScale,
+                "dormancy_metabolic_scale must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidDormancyExitResource,
+                "dormancy_exit_resource must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidEnergySharingRate,
+                "energy_sharing_rate must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidEnergySharingRadius,
+                "energy_sharing_radius must be finite and non-negative",
+            ),
             (
                 SimConfigError::InvalidReproductionMinEnergy,
                 "reproduction_min_energy must be finite and non-negative",
@@ -827,10 +3208,46 @@ mod tests {
                 SimConfigError::InvalidCrowdingBoundaryDecay,
                 "crowding_boundary_decay must be finite and non-negative",
             ),
+            (
+                SimConfigError::InvalidCrowdingEnergyCost,
+                "crowding_energy_cost must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidCorpseRecyclingFraction,
+                "corpse_recycling_fraction must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidCorpseWasteRecycleScale,
+                "corpse_waste_recycle_scale must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidCorpseDepositCellCap,
+                "corpse_deposit_cell_cap must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidWasteFieldDecayRate,
+                "waste_field_decay_rate must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidWasteUptakeEfficiency,
+                "waste_uptake_efficiency must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidWasteFieldToxicity,
+                "waste_field_toxicity must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidToxinResistanceCost,
+                "toxin_resistance_cost must be finite and non-negative",
+            ),
             (
                 SimConfigError::InvalidMaxOrganismAgeSteps,
                 "max_organism_age_steps must be positive",
             ),
+            (
+                SimConfigError::InvalidSenescenceRate,
+                "senescence_rate must be finite and non-negative",
+            ),
             (
                 SimConfigError::InvalidCompactionIntervalSteps,
                 "compaction_interval_steps must be positive",
@@ -863,10 +3280,26 @@ mod tests {
                 SimConfigError::InvalidMutationProbabilityBudget,
                 "mutation_point_rate + mutation_reset_rate + mutation_scale_rate must be <= 1.0",
             ),
+            (
+                SimConfigError::InvalidMutationDuplicationRate,
+                "mutation_duplication_rate must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidMutationDeletionRate,
+                "mutation_deletion_rate must be finite and within [0,1]",
+            ),
             (
                 SimConfigError::InvalidHomeostasisDecayRate,
                 "homeostasis_decay_rate must be finite and non-negative",
             ),
+            (
+                SimConfigError::InvalidHomeostasisShockSchedule,
+                "homeostasis_shock_schedule steps must be strictly increasing with finite amounts",
+            ),
+            (
+                SimConfigError::InvalidHomeostasisRecoveryEpsilon,
+                "homeostasis_recovery_epsilon must be finite and positive",
+            ),
             (
                 SimConfigError::InvalidGrowthMaturationSteps,
                 "growth_maturation_steps must be positive",
@@ -879,6 +3312,46 @@ mod tests {
                 SimConfigError::InvalidResourceRegenerationRate,
                 "resource_regeneration_rate must be finite and non-negative",
             ),
+            (
+                SimConfigError::InvalidResourceDynamics { max_count: 64 },
+                "resource_dynamics moving_hotspots needs a count in 1..=64, positive finite radius and peak, and finite non-negative speed",
+            ),
+            (
+                SimConfigError::InvalidResourceCellCapacity,
+                "resource_cell_capacity must be finite and positive",
+            ),
+            (
+                SimConfigError::InvalidResourceInit,
+                "resource_init parameters must be finite and non-negative, with positive patch count and radius",
+            ),
+            (
+                SimConfigError::InvalidResourceChannels,
+                "resource_channels must be between 1 and 3",
+            ),
+            (
+                SimConfigError::InvalidResourceNoiseAmplitude,
+                "resource_noise_amplitude must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidResourceNoiseKind,
+                "resource_noise_kind length_scale must be finite and positive",
+            ),
+            (
+                SimConfigError::InvalidResourceNoiseRefreshSteps,
+                "resource_noise_refresh_steps must be positive",
+            ),
+            (
+                SimConfigError::InvalidPlacement,
+                "placement radius must be finite and non-negative, and grid spacing finite and positive",
+            ),
+            (
+                SimConfigError::GeneticHomeostasisRequiresNnRegulator,
+                "enable_genetic_homeostasis requires homeostasis_mode = nn_regulator",
+            ),
+            (
+                SimConfigError::MultiChannelUptakeUnsupported,
+                "resource_channels > 1 requires resource_uptake_mode = center and resource_sampling = nearest",
+            ),
             (
                 SimConfigError::InvalidEnvironmentShiftResourceRate,
                 "environment_shift_resource_rate must be finite and non-negative",
@@ -887,10 +3360,121 @@ mod tests {
                 SimConfigError::InvalidMetabolismEfficiencyMultiplier,
                 "metabolism_efficiency_multiplier must be finite and within [0,1]",
             ),
+            (
+                SimConfigError::MetabolicGenomeSizeOutOfRange {
+                    min: 9,
+                    max: 256,
+                    actual: 8,
+                },
+                "metabolic_genome_size (8) must be between 9 and 256",
+            ),
+            (
+                SimConfigError::InvalidAgentRoleCount { max: 4 },
+                "agent_role_count must be 0 or between 2 and 4",
+            ),
+            (
+                SimConfigError::InvalidCounterUptakeRate,
+                "counter_uptake_rate must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidCounterConversionEfficiency,
+                "counter_conversion_efficiency must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidCounterWasteFraction,
+                "counter_waste_fraction must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidCounterBasalCost,
+                "counter_basal_cost must be finite and non-negative",
+            ),
             (
                 SimConfigError::InvalidEnvironmentCycleLowRate,
                 "environment_cycle_low_rate must be finite and non-negative",
             ),
+            (
+                SimConfigError::InvalidPredationRadius,
+                "predation_radius must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidPredationEnergyAdvantage,
+                "predation_energy_advantage must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidPredationTransfer,
+                "predation_transfer must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidPredationBoundaryDamage,
+                "predation_boundary_damage must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidFusionRadius,
+                "fusion_radius must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidFusionCompatibilityThreshold,
+                "fusion_compatibility_threshold must be finite and non-negative",
+            ),
+            (
+                SimConfigError::InvalidMaxCohesionPairs,
+                "max_cohesion_pairs must be positive",
+            ),
+            (
+                SimConfigError::InvalidGenomeDiversityMaxPairs,
+                "genome_diversity.max_pairs must be positive",
+            ),
+            (
+                SimConfigError::SpatialEntropyGridTooLarge {
+                    max: 2048,
+                    actual: 4096,
+                },
+                "spatial_entropy_grid (4096) exceeds supported maximum (2048)",
+            ),
+            (
+                SimConfigError::EarlyWarningWindowOutOfRange {
+                    min: 3,
+                    max: 10_000,
+                    actual: 2,
+                },
+                "early_warning_window (2) must be between 3 and 10000",
+            ),
+            (
+                SimConfigError::InvalidEarlyWarningEvery,
+                "early_warning_every must be positive",
+            ),
+            (
+                SimConfigError::MetricsSelectionWithoutCore,
+                "metrics_selection must include CORE",
+            ),
+            (
+                SimConfigError::InvalidSpeciationDistanceThreshold,
+                "speciation_distance_threshold must be finite and positive",
+            ),
+            (
+                SimConfigError::InvalidMutationSegmentRate,
+                "mutation_segment_overrides rates must be finite and within [0,1]",
+            ),
+            (
+                SimConfigError::InvalidMutationSegmentBudget,
+                "mutation_segment_overrides point_rate + reset_rate + scale_rate must be <= 1.0 for every segment",
+            ),
+            (
+                SimConfigError::DuplicateMutationSegmentOverride,
+                "mutation_segment_overrides must list each segment at most once",
+            ),
+            (
+                SimConfigError::UnsortedEnvironmentSchedule,
+                "environment_schedule steps must be strictly increasing",
+            ),
+            (
+                SimConfigError::InvalidEnvironmentScheduleRate,
+                "environment_schedule rates must be finite and non-negative",
+            ),
+            (
+                SimConfigError::ConflictingEnvironmentSchedule,
+                "environment_schedule cannot be combined with environment_shift_step or environment_cycle_period",
+            ),
             (
                 SimConfigError::ConflictingEnvironmentFeatures,
                 "environment_shift_step and environment_cycle_period are mutually exclusive",
@@ -0,0 +1,340 @@
+//! TOML loading for [`SimConfig`].
+//!
+//! Unlike the JSON binding path, TOML files are meant to be hand-edited, so unknown
+//! keys are rejected and every failure is reported with the offending key and line.
+
+use crate::config::{SimConfig, SimConfigError};
+use std::path::{Path, PathBuf};
+use std::{error::Error, fmt};
+
+#[derive(Debug)]
+pub enum ConfigFileError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        key: Option<String>,
+        line: Option<usize>,
+        message: String,
+    },
+    UnknownKey {
+        key: String,
+        line: usize,
+    },
+    Invalid {
+        key: Option<String>,
+        line: Option<usize>,
+        source: SimConfigError,
+    },
+    /// Several [`ConfigFileError::Invalid`] entries, in document check order.
+    Multiple(Vec<ConfigFileError>),
+}
+
+impl ConfigFileError {
+    /// 1-based line of the offending entry, when it can be located.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ConfigFileError::Io { .. } => None,
+            ConfigFileError::UnknownKey { line, .. } => Some(*line),
+            ConfigFileError::Parse { line, .. } | ConfigFileError::Invalid { line, .. } => *line,
+            ConfigFileError::Multiple(errors) => errors.first().and_then(Self::line),
+        }
+    }
+
+    /// Config key the failure is attributed to, when known.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            ConfigFileError::Io { .. } => None,
+            ConfigFileError::UnknownKey { key, .. } => Some(key),
+            ConfigFileError::Parse { key, .. } | ConfigFileError::Invalid { key, .. } => {
+                key.as_deref()
+            }
+            ConfigFileError::Multiple(errors) => errors.first().and_then(Self::key),
+        }
+    }
+}
+
+fn write_location(
+    f: &mut fmt::Formatter<'_>,
+    key: Option<&str>,
+    line: Option<usize>,
+) -> fmt::Result {
+    match (line, key) {
+        (Some(line), Some(key)) => write!(f, "line {line}, key `{key}`: "),
+        (Some(line), None) => write!(f, "line {line}: "),
+        (None, Some(key)) => write!(f, "key `{key}`: "),
+        (None, None) => Ok(()),
+    }
+}
+
+impl fmt::Display for ConfigFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigFileError::Io { path, source } => {
+                write!(f, "failed to read {}: {source}", path.display())
+            }
+            ConfigFileError::Parse { key, line, message } => {
+                write_location(f, key.as_deref(), *line)?;
+                write!(f, "{message}")
+            }
+            ConfigFileError::UnknownKey { key, line } => {
+                write!(f, "line {line}: unknown config key `{key}`")
+            }
+            ConfigFileError::Invalid { key, line, source } => {
+                write_location(f, key.as_deref(), *line)?;
+                write!(f, "{source}")
+            }
+            ConfigFileError::Multiple(errors) => {
+                write!(f, "{} invalid config values:", errors.len())?;
+                for error in errors {
+                    write!(f, "\n  - {error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ConfigFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigFileError::Io { source, .. } => Some(source),
+            ConfigFileError::Invalid { source, .. } => Some(source),
+            ConfigFileError::Multiple(errors) => errors.first().and_then(Error::source),
+            _ => None,
+        }
+    }
+}
+
+/// Top-level keys of the document with the byte span of each entry (key through value).
+struct KeySpans(Vec<(String, std::ops::Range<usize>)>);
+
+impl KeySpans {
+    fn parse(input: &str) -> Result<Self, ConfigFileError> {
+        let table = toml::de::DeTable::parse(input).map_err(|e| ConfigFileError::Parse {
+            key: None,
+            line: e.span().map(|span| line_of(input, span.start)),
+            message: e.message().to_string(),
+        })?;
+        let spans = table
+            .get_ref()
+            .iter()
+            .map(|(key, value)| {
+                (
+                    key.get_ref().to_string(),
+                    key.span().start..value.span().end,
+                )
+            })
+            .collect();
+        Ok(Self(spans))
+    }
+
+    fn find(&self, key: &str) -> Option<&std::ops::Range<usize>> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, span)| span)
+    }
+
+    fn containing(&self, offset: usize) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(_, span)| span.contains(&offset))
+            .map(|(k, _)| k.as_str())
+    }
+}
+
+fn line_of(input: &str, offset: usize) -> usize {
+    input[..offset.min(input.len())].matches('\n').count() + 1
+}
+
+/// Field names as serialized, i.e. the accepted top-level TOML keys.
+fn known_keys() -> Vec<String> {
+    toml::Table::try_from(SimConfig::default())
+        .map(|table| table.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+impl SimConfig {
+    /// Parse a TOML document, rejecting unknown keys and applying [`SimConfig::validate`].
+    /// Every validation failure is reported, each with its key and line.
+    ///
+    /// Missing keys take their default values, matching the JSON path.
+    pub fn from_toml_str(input: &str) -> Result<Self, ConfigFileError> {
+        let spans = KeySpans::parse(input)?;
+        let known = known_keys();
+        if let Some((key, span)) = spans.0.iter().find(|(k, _)| !known.contains(k)) {
+            return Err(ConfigFileError::UnknownKey {
+                key: key.clone(),
+                line: line_of(input, span.start),
+            });
+        }
+        let config: SimConfig = toml::from_str(input).map_err(|e| {
+            let start = e.span().map(|span| span.start);
+            ConfigFileError::Parse {
+                key: start.and_then(|s| spans.containing(s)).map(str::to_string),
+                line: start.map(|s| line_of(input, s)),
+                message: e.message().to_string(),
+            }
+        })?;
+        config.validate().map_err(|errors| {
+            let mut invalid: Vec<_> = errors
+                .violations()
+                .iter()
+                .map(|violation| {
+                    let key = violation.field.clone();
+                    let line = key
+                        .as_deref()
+                        .and_then(|k| spans.find(k))
+                        .map(|span| line_of(input, span.start));
+                    ConfigFileError::Invalid {
+                        key,
+                        line,
+                        source: violation.error.clone(),
+                    }
+                })
+                .collect();
+            if invalid.len() == 1 {
+                invalid.remove(0)
+            } else {
+                ConfigFileError::Multiple(invalid)
+            }
+        })?;
+        Ok(config)
+    }
+
+    /// Read and parse a TOML config file. See [`SimConfig::from_toml_str`].
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, ConfigFileError> {
+        let path = path.as_ref();
+        let input = std::fs::read_to_string(path).map_err(|source| ConfigFileError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::from_toml_str(&input)
+    }
+
+    /// Serialize to a TOML document accepted by [`SimConfig::from_toml_str`].
+    pub fn to_toml_string(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AblationTarget, BoundaryMode, ResourceInit};
+
+    #[test]
+    fn toml_round_trip_preserves_config() {
+        let config = SimConfig {
+            seed: 9,
+            mutation_point_scale: 0.17,
+            boundary_mode: BoundaryMode::SpatialHullFeedback,
+            ablation_step: 100,
+            ablation_targets: vec![AblationTarget::Metabolism, AblationTarget::Growth],
+            ..SimConfig::default()
+        };
+        let text = config.to_toml_string().expect("config should serialize");
+        let parsed = SimConfig::from_toml_str(&text).expect("round trip should parse");
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn toml_round_trip_preserves_resource_init_table() {
+        let config = SimConfig {
+            resource_init: ResourceInit::Patches {
+                count: 3,
+                radius: 4.0,
+                peak: 2.0,
+                background: 0.25,
+            },
+            ..SimConfig::default()
+        };
+        let text = config.to_toml_string().expect("config should serialize");
+        let parsed = SimConfig::from_toml_str(&text).expect("round trip should parse");
+        assert_eq!(parsed, config);
+        let inline = SimConfig::from_toml_str(
+            "resource_init = { kind = \"noise\", mean = 1.0, amplitude = 0.5 }\n",
+        )
+        .expect("inline table should parse");
+        assert_eq!(
+            inline.resource_init,
+            ResourceInit::Noise {
+                mean: 1.0,
+                amplitude: 0.5
+            }
+        );
+    }
+
+    #[test]
+    fn missing_keys_take_defaults() {
+        let config = SimConfig::from_toml_str("seed = 3\n").expect("partial config should load");
+        assert_eq!(config.seed, 3);
+        assert_eq!(config.world_size, SimConfig::default().world_size);
+    }
+
+    #[test]
+    fn unknown_key_reports_key_and_line() {
+        let err = SimConfig::from_toml_str("seed = 1\n\nworld_sise = 10.0\n").unwrap_err();
+        assert!(matches!(err, ConfigFileError::UnknownKey { .. }));
+        assert_eq!(err.key(), Some("world_sise"));
+        assert_eq!(err.line(), Some(3));
+    }
+
+    #[test]
+    fn type_error_reports_key_and_line() {
+        let err = SimConfig::from_toml_str("seed = 1\ndt = \"fast\"\n").unwrap_err();
+        assert!(matches!(err, ConfigFileError::Parse { .. }));
+        assert_eq!(err.key(), Some("dt"));
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn syntax_error_reports_line() {
+        let err = SimConfig::from_toml_str("seed = 1\nworld_size = = 3\n").unwrap_err();
+        assert!(matches!(err, ConfigFileError::Parse { .. }));
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn validation_error_reports_key_and_line() {
+        let err = SimConfig::from_toml_str("seed = 1\nmax_speed = -2.0\n").unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigFileError::Invalid {
+                source: SimConfigError::InvalidMaxSpeed,
+                ..
+            }
+        ));
+        assert_eq!(err.key(), Some("max_speed"));
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(
+            err.to_string(),
+            "line 2, key `max_speed`: max_speed must be positive and finite"
+        );
+    }
+
+    #[test]
+    fn multiple_validation_errors_each_report_key_and_line() {
+        let err = SimConfig::from_toml_str(
+            "max_speed = -2.0
+seed = 1
+dt = 0.0
+",
+        )
+        .unwrap_err();
+        let ConfigFileError::Multiple(errors) = &err else {
+            panic!("expected every violation, got {err}");
+        };
+        let located: Vec<_> = errors.iter().map(|e| (e.key(), e.line())).collect();
+        assert_eq!(
+            located,
+            vec![(Some("dt"), Some(3)), (Some("max_speed"), Some(1))]
+        );
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(
+            err.to_string(),
+            "2 invalid config values:\n  \
+             - line 3, key `dt`: dt must be positive and finite\n  \
+             - line 1, key `max_speed`: max_speed must be positive and finite"
+        );
+    }
+}
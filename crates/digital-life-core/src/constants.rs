@@ -5,6 +5,14 @@ pub const MAX_WORLD_SIZE: f64 = 2048.0;
 /// Chosen so streams for consecutive organism IDs have minimal overlap.
 pub const RNG_DERIVATION_PRIME: u64 = 7919;
 
-/// Maximum number of genome pairs sampled when estimating population diversity.
-/// Caps the O(n²) pairwise computation; sampling is deterministic per step.
+/// Default number of genome pairs sampled when estimating population diversity.
+/// Caps the O(n²) pairwise computation; sampling is deterministic per run and step.
 pub const GENOME_DIVERSITY_MAX_PAIRS: usize = 50;
+
+/// Most genomes clustered by the speciation metric. Larger populations are
+/// subsampled deterministically per run and step to bound the O(n²) pass.
+pub const SPECIATION_MAX_GENOMES: usize = 200;
+
+/// Most hidden-activation records [`crate::world::World`] keeps between
+/// `take_activation_log` calls (about 80 MB); later records are dropped.
+pub const MAX_ACTIVATION_RECORDS: usize = 1_000_000;
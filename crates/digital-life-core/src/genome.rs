@@ -1,4 +1,54 @@
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::{error::Error, fmt};
+
+/// Sizes of the genome segments that can vary between runs. Every genome in a
+/// world shares one layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GenomeLayout {
+    /// Length of the metabolic network segment (1); see
+    /// [`crate::metabolism::decode_metabolic_graph`] for how it is decoded.
+    pub metabolic_size: usize,
+    /// Agent role archetypes appended to the developmental segment (3), each
+    /// [`Genome::ROLE_ARCHETYPE_SIZE`] genes; see
+    /// [`crate::organism::RoleArchetype`].
+    pub agent_roles: usize,
+    /// Whether [`Genome::MATURITY_GATING_SIZE`] maturity gating genes sit between
+    /// the developmental program and the role archetypes in segment 3; see
+    /// [`crate::organism::DevelopmentalProgram`].
+    pub maturity_gating_genes: bool,
+}
+
+impl Default for GenomeLayout {
+    fn default() -> Self {
+        Self {
+            metabolic_size: Genome::METABOLIC_SIZE,
+            agent_roles: 0,
+            maturity_gating_genes: false,
+        }
+    }
+}
+
+impl GenomeLayout {
+    /// Length of a genome in this layout with `nn_len` NN weights.
+    pub fn total_len(&self, nn_len: usize) -> usize {
+        nn_len + self.segment_sizes().iter().sum::<usize>()
+    }
+
+    fn segment_sizes(&self) -> [usize; 6] {
+        [
+            self.metabolic_size,
+            Genome::HOMEOSTASIS_SIZE,
+            Genome::DEVELOPMENTAL_SIZE
+                + usize::from(self.maturity_gating_genes) * Genome::MATURITY_GATING_SIZE
+                + self.agent_roles * Genome::ROLE_ARCHETYPE_SIZE,
+            Genome::REPRODUCTION_SIZE,
+            Genome::SENSORY_SIZE,
+            Genome::EVOLUTION_SIZE,
+        ]
+    }
+}
 
 /// Variable-length genome encoding all 7 criteria.
 /// Only NN weights are active initially; other segments are zero-initialized
@@ -12,31 +62,41 @@ pub struct Genome {
     /// 3 = developmental program, 4 = reproduction params, 5 = sensory params,
     /// 6 = evolution/mutation params
     segments: [(usize, usize); 7],
+    /// Genes in use at the front of each segment. Equal to the segment's allocated
+    /// length unless structural mutation shrank it; the unused tail stays zero.
+    lengths: [usize; 7],
 }
 
 impl Genome {
+    /// Default metabolic segment size.
     pub const METABOLIC_SIZE: usize = 16;
     pub const HOMEOSTASIS_SIZE: usize = 8;
     pub const DEVELOPMENTAL_SIZE: usize = 8;
+    /// Genes per agent role archetype in the developmental segment.
+    pub const ROLE_ARCHETYPE_SIZE: usize = 4;
+    /// Maturity gating genes in the developmental segment of layouts with
+    /// `maturity_gating_genes`. Not a multiple of [`Genome::ROLE_ARCHETYPE_SIZE`], so
+    /// [`Genome::layout`] can tell the two apart.
+    pub const MATURITY_GATING_SIZE: usize = 3;
     pub const REPRODUCTION_SIZE: usize = 4;
     pub const SENSORY_SIZE: usize = 4;
     pub const EVOLUTION_SIZE: usize = 4;
+    /// Segments whose length [`Genome::mutate_structure`] may change: the metabolic
+    /// network (1) and the developmental program (3). The others have a fixed
+    /// gene-per-parameter meaning.
+    pub const STRUCTURAL_SEGMENTS: [usize; 2] = [1, 3];
 
-    const SEGMENT_SIZES: [usize; 6] = [
-        Self::METABOLIC_SIZE,
-        Self::HOMEOSTASIS_SIZE,
-        Self::DEVELOPMENTAL_SIZE,
-        Self::REPRODUCTION_SIZE,
-        Self::SENSORY_SIZE,
-        Self::EVOLUTION_SIZE,
-    ];
+    /// Create a genome with only NN weights active (segment 0), in the default layout.
+    pub fn with_nn_weights(nn_weights: Vec<f32>) -> Self {
+        Self::with_layout(nn_weights, GenomeLayout::default())
+    }
 
     /// Create a genome with only NN weights active (segment 0).
-    pub fn with_nn_weights(nn_weights: Vec<f32>) -> Self {
+    pub fn with_layout(nn_weights: Vec<f32>, layout: GenomeLayout) -> Self {
         let nn_len = nn_weights.len();
-        let placeholder_sizes = Self::SEGMENT_SIZES;
+        let placeholder_sizes = layout.segment_sizes();
 
-        let total_len: usize = nn_len + placeholder_sizes.iter().sum::<usize>();
+        let total_len = layout.total_len(nn_len);
         let mut data = Vec::with_capacity(total_len);
         data.extend_from_slice(&nn_weights);
         // Zero-fill remaining segments
@@ -50,14 +110,49 @@ impl Genome {
             offset += size;
         }
 
-        Self { data, segments }
+        Self {
+            data,
+            segments,
+            lengths: segments.map(|(_, len)| len),
+        }
+    }
+
+    /// Rebuild a default-layout genome from its full flat `data()`, e.g. one
+    /// exported from a run. Everything before the regulatory segments is NN
+    /// weights; returns `None` if `data` is too short to hold those segments.
+    /// Every segment comes back at its full allocated length.
+    pub fn from_data(data: Vec<f32>) -> Option<Self> {
+        Self::from_data_with_layout(data, GenomeLayout::default())
+    }
+
+    /// [`Genome::from_data`] for a genome in `layout`.
+    pub fn from_data_with_layout(data: Vec<f32>, layout: GenomeLayout) -> Option<Self> {
+        let nn_len = data
+            .len()
+            .checked_sub(layout.segment_sizes().iter().sum::<usize>())?;
+        let mut genome = Self::with_layout(vec![0.0; nn_len], layout);
+        genome.data = data;
+        Some(genome)
+    }
+
+    pub fn layout(&self) -> GenomeLayout {
+        let extra = self.segments[3].1.saturating_sub(Self::DEVELOPMENTAL_SIZE);
+        let maturity_gating_genes = extra % Self::ROLE_ARCHETYPE_SIZE
+            == Self::MATURITY_GATING_SIZE % Self::ROLE_ARCHETYPE_SIZE;
+        let gating = usize::from(maturity_gating_genes) * Self::MATURITY_GATING_SIZE;
+        GenomeLayout {
+            metabolic_size: self.segments[1].1,
+            agent_roles: (extra - gating) / Self::ROLE_ARCHETYPE_SIZE,
+            maturity_gating_genes,
+        }
     }
 
     pub fn nn_weights(&self) -> &[f32] {
         self.segment_data(0)
     }
 
-    /// Returns the parameter slice for a criterion segment (0..=6).
+    /// Returns the parameter slice for a criterion segment (0..=6), at its full
+    /// allocated length.
     pub fn segment_data(&self, criterion: usize) -> &[f32] {
         assert!(
             criterion < self.segments.len(),
@@ -67,6 +162,32 @@ impl Genome {
         &self.data[start..start + len]
     }
 
+    /// Genes in use in segment `criterion`; below the allocated length only after
+    /// a structural deletion.
+    pub fn segment_len(&self, criterion: usize) -> usize {
+        self.lengths[criterion]
+    }
+
+    /// The in-use front of segment `criterion` (see [`Genome::segment_len`]).
+    pub fn active_segment_data(&self, criterion: usize) -> &[f32] {
+        let start = self.segments[criterion].0;
+        &self.data[start..start + self.lengths[criterion]]
+    }
+
+    /// [`Genome::active_segment_data`] zero-padded to at least `min_len` genes, for
+    /// decoders that need a minimum encoding. Borrows when no padding is needed.
+    pub fn padded_segment_data(&self, criterion: usize, min_len: usize) -> Cow<'_, [f32]> {
+        let active = self.active_segment_data(criterion);
+        if active.len() >= min_len {
+            return Cow::Borrowed(active);
+        }
+        let mut padded = active.to_vec();
+        padded.resize(min_len, 0.0);
+        Cow::Owned(padded)
+    }
+
+    /// Overwrite segment `criterion` at its full allocated length, which becomes
+    /// its in-use length again.
     pub fn set_segment_data(&mut self, criterion: usize, data: &[f32]) {
         assert!(
             criterion < self.segments.len(),
@@ -75,33 +196,212 @@ impl Genome {
         let (start, len) = self.segments[criterion];
         assert_eq!(data.len(), len, "data length must match segment size");
         self.data[start..start + len].copy_from_slice(data);
+        self.lengths[criterion] = len;
     }
 
     pub fn data(&self) -> &[f32] {
         &self.data
     }
 
+    /// Drop every gene and collapse all segments to empty, freeing the data.
+    /// For genomes that are never read again, such as those of dead organisms.
+    pub fn release(&mut self) {
+        self.data = Vec::new();
+        self.segments = [(0, 0); 7];
+        self.lengths = [0; 7];
+    }
+
     pub fn segments(&self) -> &[(usize, usize); 7] {
         &self.segments
     }
 
+    /// Mutate every in-use gene, using each segment's override from
+    /// `rates.per_segment` when present and the global probabilities otherwise.
     pub fn mutate<R: Rng + ?Sized>(&mut self, rng: &mut R, rates: &MutationRates) {
-        debug_assert!(
-            rates.point_rate + rates.reset_rate + rates.scale_rate <= 1.0,
-            "mutation probabilities should sum to <= 1.0"
-        );
-        for v in &mut self.data {
-            let r = rng.random::<f32>();
-            if r < rates.point_rate {
-                let delta = rng.random_range(-rates.point_scale..=rates.point_scale);
-                *v = (*v + delta).clamp(-rates.value_limit, rates.value_limit);
-            } else if r < rates.point_rate + rates.reset_rate {
-                *v = 0.0;
-            } else if r < rates.point_rate + rates.reset_rate + rates.scale_rate {
-                let factor = rng.random_range(rates.scale_min..=rates.scale_max);
-                *v = (*v * factor).clamp(-rates.value_limit, rates.value_limit);
+        for (criterion, (&(start, _), &len)) in self.segments.iter().zip(&self.lengths).enumerate()
+        {
+            let rates = rates.for_segment(criterion);
+            debug_assert!(
+                rates.point_rate + rates.reset_rate + rates.scale_rate <= 1.0,
+                "mutation probabilities should sum to <= 1.0"
+            );
+            for v in &mut self.data[start..start + len] {
+                let r = rng.random::<f32>();
+                if r < rates.point_rate {
+                    let delta = rng.random_range(-rates.point_scale..=rates.point_scale);
+                    *v = finite_clamp(*v + delta, rates.value_limit);
+                } else if r < rates.point_rate + rates.reset_rate {
+                    *v = 0.0;
+                } else if r < rates.point_rate + rates.reset_rate + rates.scale_rate {
+                    let factor = rng.random_range(rates.scale_min..=rates.scale_max);
+                    *v = finite_clamp(*v * factor, rates.value_limit);
+                }
+            }
+        }
+    }
+
+    /// Structural mutation of each of [`Genome::STRUCTURAL_SEGMENTS`]: with
+    /// probability `rates.duplication_rate` a random run of up to half the in-use
+    /// genes is copied in right after itself, and with probability
+    /// `rates.deletion_rate` such a run is removed. Segments keep their allocated
+    /// space, so a duplication pushes genes past the end of the segment out and a
+    /// deletion leaves a zeroed tail; at least one gene always stays in use.
+    pub fn mutate_structure<R: Rng + ?Sized>(&mut self, rng: &mut R, rates: &MutationRates) {
+        for criterion in Self::STRUCTURAL_SEGMENTS {
+            let (start, capacity) = self.segments[criterion];
+            let segment = &mut self.data[start..start + capacity];
+            let mut genes = segment[..self.lengths[criterion]].to_vec();
+            if !genes.is_empty() && rng.random::<f32>() < rates.duplication_rate {
+                let run = rng.random_range(1..=genes.len().div_ceil(2));
+                let at = rng.random_range(0..=genes.len() - run);
+                let copy = genes[at..at + run].to_vec();
+                genes.splice(at + run..at + run, copy);
+                genes.truncate(capacity);
+            }
+            if genes.len() > 1 && rng.random::<f32>() < rates.deletion_rate {
+                let run = rng.random_range(1..=genes.len() / 2);
+                let at = rng.random_range(0..=genes.len() - run);
+                genes.drain(at..at + run);
+            }
+            segment[..genes.len()].copy_from_slice(&genes);
+            segment[genes.len()..].fill(0.0);
+            self.lengths[criterion] = genes.len();
+        }
+    }
+
+    /// Child taking each gene from `self` or `other` with equal probability. Each
+    /// segment's in-use length is the longer of the parents'.
+    pub fn crossover_uniform<R: Rng + ?Sized>(
+        &self,
+        other: &Genome,
+        rng: &mut R,
+    ) -> Result<Genome, CrossoverError> {
+        self.check_crossover_layout(other)?;
+        let mut child = self.crossover_child(other);
+        for (gene, &theirs) in child.data.iter_mut().zip(&other.data) {
+            if rng.random::<bool>() {
+                *gene = theirs;
+            }
+        }
+        Ok(child)
+    }
+
+    /// Child taking the genes before a random cut point from `self` and the rest
+    /// from `other`; the cut leaves at least one gene from each parent. Each
+    /// segment's in-use length is the longer of the parents'.
+    pub fn crossover_one_point<R: Rng + ?Sized>(
+        &self,
+        other: &Genome,
+        rng: &mut R,
+    ) -> Result<Genome, CrossoverError> {
+        self.check_crossover_layout(other)?;
+        let mut child = self.crossover_child(other);
+        let len = child.data.len();
+        if len >= 2 {
+            let cut = rng.random_range(1..len);
+            child.data[cut..].copy_from_slice(&other.data[cut..]);
+        }
+        Ok(child)
+    }
+
+    /// Child taking each whole segment, with its in-use length, from `self` or
+    /// `other` with equal probability, so no segment mixes the parents' genes.
+    pub fn crossover_segmentwise<R: Rng + ?Sized>(
+        &self,
+        other: &Genome,
+        rng: &mut R,
+    ) -> Result<Genome, CrossoverError> {
+        self.check_crossover_layout(other)?;
+        let mut child = self.clone();
+        for (criterion, &(start, len)) in self.segments.iter().enumerate() {
+            if rng.random::<bool>() {
+                child.data[start..start + len].copy_from_slice(&other.data[start..start + len]);
+                child.lengths[criterion] = other.lengths[criterion];
             }
         }
+        Ok(child)
+    }
+
+    fn check_crossover_layout(&self, other: &Genome) -> Result<(), CrossoverError> {
+        for (segment, (&(_, left), &(_, right))) in
+            self.segments.iter().zip(&other.segments).enumerate()
+        {
+            if left != right {
+                return Err(CrossoverError::SegmentLengthMismatch {
+                    segment,
+                    left,
+                    right,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy of `self` whose in-use lengths cover both parents' in-use genes.
+    fn crossover_child(&self, other: &Genome) -> Genome {
+        let mut child = self.clone();
+        for (len, &theirs) in child.lengths.iter_mut().zip(&other.lengths) {
+            *len = (*len).max(theirs);
+        }
+        child
+    }
+}
+
+/// Parents passed to a [`Genome`] crossover operator have different layouts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CrossoverError {
+    /// Segment `segment` holds `left` genes in the first parent and `right` in the
+    /// second.
+    SegmentLengthMismatch {
+        segment: usize,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl fmt::Display for CrossoverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossoverError::SegmentLengthMismatch {
+                segment,
+                left,
+                right,
+            } => write!(
+                f,
+                "cannot cross genomes with different layouts: segment {segment} has {left} genes in the first parent and {right} in the second"
+            ),
+        }
+    }
+}
+
+impl Error for CrossoverError {}
+
+/// Mutation probabilities for a single genome segment, replacing the global ones.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SegmentMutationRates {
+    pub point_rate: f32,
+    pub reset_rate: f32,
+    pub scale_rate: f32,
+}
+
+impl SegmentMutationRates {
+    /// Apply per-organism multipliers, keeping each probability in [0,1] and the
+    /// combined probability budget at or below 1.0.
+    fn scaled(&self, modifiers: &MutationRateModifiers) -> Self {
+        let mut point_rate = (self.point_rate * modifiers.point_rate).clamp(0.0, 1.0);
+        let mut reset_rate = (self.reset_rate * modifiers.reset_rate).clamp(0.0, 1.0);
+        let mut scale_rate = (self.scale_rate * modifiers.scale_rate).clamp(0.0, 1.0);
+        let budget = point_rate + reset_rate + scale_rate;
+        if budget > 1.0 {
+            point_rate /= budget;
+            reset_rate /= budget;
+            scale_rate /= budget;
+        }
+        Self {
+            point_rate,
+            reset_rate,
+            scale_rate,
+        }
     }
 }
 
@@ -114,6 +414,12 @@ pub struct MutationRates {
     pub scale_min: f32,
     pub scale_max: f32,
     pub value_limit: f32,
+    /// Optional probability overrides indexed like `Genome::segments`.
+    pub per_segment: [Option<SegmentMutationRates>; 7],
+    /// Per-segment probability of a duplication in [`Genome::mutate_structure`].
+    pub duplication_rate: f32,
+    /// Per-segment probability of a deletion in [`Genome::mutate_structure`].
+    pub deletion_rate: f32,
 }
 
 impl Default for MutationRates {
@@ -126,8 +432,111 @@ impl Default for MutationRates {
             scale_min: 0.8,
             scale_max: 1.2,
             value_limit: 2.0,
+            per_segment: [None; 7],
+            duplication_rate: 0.0,
+            deletion_rate: 0.0,
+        }
+    }
+}
+
+impl MutationRates {
+    /// Apply per-organism multipliers, keeping each probability in [0,1] and the
+    /// combined probability budget at or below 1.0.
+    /// Segment overrides are scaled the same way.
+    pub fn scaled(&self, modifiers: &MutationRateModifiers) -> Self {
+        let global = SegmentMutationRates {
+            point_rate: self.point_rate,
+            reset_rate: self.reset_rate,
+            scale_rate: self.scale_rate,
+        }
+        .scaled(modifiers);
+        Self {
+            point_rate: global.point_rate,
+            point_scale: self.point_scale * modifiers.point_scale,
+            reset_rate: global.reset_rate,
+            scale_rate: global.scale_rate,
+            per_segment: self
+                .per_segment
+                .map(|rates| rates.map(|r| r.scaled(modifiers))),
+            ..*self
+        }
+    }
+
+    /// Effective rates for genome segment `criterion` (0..=6).
+    pub fn for_segment(&self, criterion: usize) -> Self {
+        match self.per_segment.get(criterion).copied().flatten() {
+            Some(rates) => Self {
+                point_rate: rates.point_rate,
+                reset_rate: rates.reset_rate,
+                scale_rate: rates.scale_rate,
+                ..*self
+            },
+            None => *self,
+        }
+    }
+}
+
+/// Decoded self-adaptive mutation modifiers from genome segment 6 (4 floats).
+///
+/// Each gene is sigmoid-squashed into [`MIN_MULTIPLIER`, `MAX_MULTIPLIER`], so an
+/// all-zero segment decodes to 1.0 (global rates unchanged). Because segment 6 is
+/// mutated along with the rest of the genome, mutation rates can themselves evolve.
+///
+/// [`MIN_MULTIPLIER`]: MutationRateModifiers::MIN_MULTIPLIER
+/// [`MAX_MULTIPLIER`]: MutationRateModifiers::MAX_MULTIPLIER
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MutationRateModifiers {
+    /// g[0]: multiplier on `point_rate`.
+    pub point_rate: f32,
+    /// g[1]: multiplier on `point_scale`.
+    pub point_scale: f32,
+    /// g[2]: multiplier on `reset_rate`.
+    pub reset_rate: f32,
+    /// g[3]: multiplier on `scale_rate`.
+    pub scale_rate: f32,
+}
+
+impl MutationRateModifiers {
+    pub const MIN_MULTIPLIER: f32 = 0.25;
+    pub const MAX_MULTIPLIER: f32 = 1.75;
+
+    fn squash(x: f32) -> f32 {
+        let sig = 1.0 / (1.0 + (-x).exp());
+        Self::MIN_MULTIPLIER + sig * (Self::MAX_MULTIPLIER - Self::MIN_MULTIPLIER)
+    }
+
+    /// Decode genome segment 6 (4 floats) into mutation rate multipliers.
+    pub fn decode(segment: &[f32]) -> Self {
+        assert!(segment.len() >= 4, "evolution segment needs ≥4 floats");
+        Self {
+            point_rate: Self::squash(segment[0]),
+            point_scale: Self::squash(segment[1]),
+            reset_rate: Self::squash(segment[2]),
+            scale_rate: Self::squash(segment[3]),
         }
     }
+
+    /// Mean of the four multipliers; 1.0 for an all-zero segment.
+    pub fn mean(&self) -> f32 {
+        (self.point_rate + self.point_scale + self.reset_rate + self.scale_rate) * 0.25
+    }
+}
+
+impl Default for MutationRateModifiers {
+    /// Default matches decode of an all-zero genome segment (all multipliers 1.0).
+    fn default() -> Self {
+        Self::decode(&[0.0; Genome::EVOLUTION_SIZE])
+    }
+}
+
+/// Clamp a mutated gene to `[-limit, limit]`; `f32::clamp` passes NaN through, so a
+/// NaN gene is reset to zero instead.
+fn finite_clamp(v: f32, limit: f32) -> f32 {
+    if v.is_nan() {
+        0.0
+    } else {
+        v.clamp(-limit, limit)
+    }
 }
 
 #[cfg(test)]
@@ -149,6 +558,179 @@ mod tests {
         assert_eq!(a.data(), b.data());
     }
 
+    #[test]
+    fn mutation_leaves_only_finite_genes() {
+        let mut g = Genome::with_nn_weights(vec![f32::NAN, f32::INFINITY, -f32::INFINITY, 0.5]);
+        let rates = MutationRates {
+            point_rate: 1.0,
+            reset_rate: 0.0,
+            scale_rate: 0.0,
+            ..MutationRates::default()
+        };
+        g.mutate(&mut ChaCha12Rng::seed_from_u64(9), &rates);
+        let weights = &g.data()[..4];
+        assert!(weights.iter().all(|w| w.is_finite()), "{weights:?}");
+        assert!(weights.iter().all(|w| w.abs() <= rates.value_limit));
+    }
+
+    fn crossover_parents() -> (Genome, Genome) {
+        let mut a = Genome::with_nn_weights((0..24).map(|i| i as f32 * 0.01).collect());
+        let mut b = Genome::with_nn_weights((0..24).map(|i| -1.0 - i as f32 * 0.01).collect());
+        a.set_segment_data(2, &[0.5; Genome::HOMEOSTASIS_SIZE]);
+        b.set_segment_data(2, &[-0.5; Genome::HOMEOSTASIS_SIZE]);
+        b.set_segment_data(4, &[1.0, 2.0, 3.0, 4.0]);
+        (a, b)
+    }
+
+    type Crossover = fn(&Genome, &Genome, &mut ChaCha12Rng) -> Result<Genome, CrossoverError>;
+
+    const CROSSOVERS: [(&str, Crossover); 3] = [
+        ("uniform", Genome::crossover_uniform),
+        ("one_point", Genome::crossover_one_point),
+        ("segmentwise", Genome::crossover_segmentwise),
+    ];
+
+    #[test]
+    fn crossover_is_deterministic_and_every_locus_comes_from_a_parent() {
+        let (a, b) = crossover_parents();
+        for (name, cross) in CROSSOVERS {
+            let first = cross(&a, &b, &mut ChaCha12Rng::seed_from_u64(5)).unwrap();
+            let again = cross(&a, &b, &mut ChaCha12Rng::seed_from_u64(5)).unwrap();
+            assert_eq!(first.data(), again.data(), "{name}");
+            assert_eq!(first.segments(), a.segments(), "{name}");
+            for (i, gene) in first.data().iter().enumerate() {
+                assert!(
+                    *gene == a.data()[i] || *gene == b.data()[i],
+                    "{name} locus {i}"
+                );
+            }
+            let mixed = (0..20u64).any(|seed| {
+                let child = cross(&a, &b, &mut ChaCha12Rng::seed_from_u64(seed)).unwrap();
+                child.data() != a.data() && child.data() != b.data()
+            });
+            assert!(mixed, "{name} should recombine the parents");
+        }
+    }
+
+    #[test]
+    fn crossover_rejects_mismatched_layouts() {
+        let (a, _) = crossover_parents();
+        let wider = Genome::with_layout(
+            vec![0.0; 24],
+            GenomeLayout {
+                metabolic_size: 20,
+                ..GenomeLayout::default()
+            },
+        );
+        for (name, cross) in CROSSOVERS {
+            let err = cross(&a, &wider, &mut ChaCha12Rng::seed_from_u64(1)).unwrap_err();
+            assert_eq!(
+                err,
+                CrossoverError::SegmentLengthMismatch {
+                    segment: 1,
+                    left: 16,
+                    right: 20
+                },
+                "{name}"
+            );
+        }
+        let err = Genome::with_nn_weights(vec![0.0; 10])
+            .crossover_uniform(&a, &mut ChaCha12Rng::seed_from_u64(1))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot cross genomes with different layouts: segment 0 has 10 genes in the first parent and 24 in the second"
+        );
+    }
+
+    #[test]
+    fn crossover_child_keeps_the_longer_in_use_length() {
+        let (mut a, b) = crossover_parents();
+        let rates = MutationRates {
+            deletion_rate: 1.0,
+            duplication_rate: 0.0,
+            ..MutationRates::default()
+        };
+        a.mutate_structure(&mut ChaCha12Rng::seed_from_u64(3), &rates);
+        assert!(a.segment_len(1) < Genome::METABOLIC_SIZE);
+        let child = a
+            .crossover_one_point(&b, &mut ChaCha12Rng::seed_from_u64(3))
+            .unwrap();
+        assert_eq!(child.segment_len(1), Genome::METABOLIC_SIZE);
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_segmentwise_crossover_never_straddles_segment_boundaries(
+            seed: u64,
+            nn_len in 1usize..40,
+            metabolic_size in 4usize..24,
+        ) {
+            let layout = GenomeLayout { metabolic_size, ..GenomeLayout::default() };
+            let a = Genome::with_layout(vec![1.0; nn_len], layout);
+            let mut b = Genome::with_layout(vec![-1.0; nn_len], layout);
+            for criterion in 1..7 {
+                let len = b.segment_data(criterion).len();
+                b.set_segment_data(criterion, &vec![-1.0; len]);
+            }
+            let child = a
+                .crossover_segmentwise(&b, &mut ChaCha12Rng::seed_from_u64(seed))
+                .unwrap();
+            for criterion in 0..7 {
+                let segment = child.segment_data(criterion);
+                prop_assert!(
+                    segment == a.segment_data(criterion) || segment == b.segment_data(criterion),
+                    "segment {} mixes parents: {:?}", criterion, segment
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn from_data_round_trips_segment_layout() {
+        let mut g = Genome::with_nn_weights(vec![0.25; 12]);
+        g.set_segment_data(4, &[1.0, 2.0, 3.0, 4.0]);
+        let rebuilt = Genome::from_data(g.data().to_vec()).unwrap();
+        assert_eq!(rebuilt.segments(), g.segments());
+        assert_eq!(rebuilt.data(), g.data());
+        assert!(Genome::from_data(vec![0.0; 3]).is_none());
+    }
+
+    #[test]
+    fn agent_roles_extend_the_developmental_segment() {
+        let layout = GenomeLayout {
+            agent_roles: 3,
+            ..GenomeLayout::default()
+        };
+        let g = Genome::with_layout(vec![0.25; 12], layout);
+        assert_eq!(
+            g.segment_data(3).len(),
+            Genome::DEVELOPMENTAL_SIZE + 3 * Genome::ROLE_ARCHETYPE_SIZE
+        );
+        assert_eq!(g.layout(), layout);
+        let rebuilt = Genome::from_data_with_layout(g.data().to_vec(), layout).unwrap();
+        assert_eq!(rebuilt.segments(), g.segments());
+    }
+
+    #[test]
+    fn maturity_gating_genes_precede_agent_roles() {
+        for agent_roles in [0, 3] {
+            let layout = GenomeLayout {
+                agent_roles,
+                maturity_gating_genes: true,
+                ..GenomeLayout::default()
+            };
+            let g = Genome::with_layout(vec![0.25; 12], layout);
+            assert_eq!(
+                g.segment_data(3).len(),
+                Genome::DEVELOPMENTAL_SIZE
+                    + Genome::MATURITY_GATING_SIZE
+                    + agent_roles * Genome::ROLE_ARCHETYPE_SIZE
+            );
+            assert_eq!(g.layout(), layout);
+        }
+    }
+
     #[test]
     fn mutation_respects_value_bounds() {
         let mut g = Genome::with_nn_weights(vec![1.5; 32]);
@@ -228,6 +810,154 @@ mod tests {
         assert!(non_nn_changed, "mutation should affect non-NN segments too");
     }
 
+    #[test]
+    fn zero_segment_override_freezes_only_that_segment() {
+        let mut g = Genome::with_nn_weights(vec![0.5; 212]);
+        g.set_segment_data(1, &[0.25; Genome::METABOLIC_SIZE]);
+        let mut per_segment = [None; 7];
+        per_segment[1] = Some(SegmentMutationRates {
+            point_rate: 0.0,
+            reset_rate: 0.0,
+            scale_rate: 0.0,
+        });
+        let rates = MutationRates {
+            point_rate: 0.5,
+            point_scale: 1.0,
+            per_segment,
+            ..MutationRates::default()
+        };
+        let mut rng = ChaCha12Rng::seed_from_u64(5);
+        for _ in 0..20 {
+            g.mutate(&mut rng, &rates);
+        }
+        assert!(g.segment_data(1).iter().all(|&v| v == 0.25));
+        assert!(g.nn_weights().iter().any(|&v| v != 0.5));
+    }
+
+    #[test]
+    fn segment_overrides_are_scaled_within_budget() {
+        let mut per_segment = [None; 7];
+        per_segment[3] = Some(SegmentMutationRates {
+            point_rate: 0.7,
+            reset_rate: 0.2,
+            scale_rate: 0.1,
+        });
+        let rates = MutationRates {
+            per_segment,
+            ..MutationRates::default()
+        };
+        let scaled = rates.scaled(&MutationRateModifiers::decode(&[100.0; 4]));
+        let seg = scaled.for_segment(3);
+        let budget = seg.point_rate + seg.reset_rate + seg.scale_rate;
+        assert!(budget <= 1.0 + f32::EPSILON, "budget {budget} exceeds 1.0");
+        assert!((scaled.for_segment(0).point_rate - scaled.point_rate).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn release_empties_data_and_segments() {
+        let mut genome = Genome::with_nn_weights(vec![0.5; 10]);
+        genome.release();
+        assert!(genome.data().is_empty());
+        assert!(genome.nn_weights().is_empty());
+        assert_eq!(genome.segments(), &[(0, 0); 7]);
+        assert_eq!(genome.padded_segment_data(1, 4).as_ref(), &[0.0; 4]);
+    }
+
+    #[test]
+    fn structural_mutation_keeps_layout_intact() {
+        use crate::metabolism::{decode_graph_metabolism, MIN_METABOLIC_SEGMENT_SIZE};
+        use crate::organism::DevelopmentalProgram;
+
+        let layout = GenomeLayout {
+            agent_roles: 2,
+            ..GenomeLayout::default()
+        };
+        let mut g = Genome::with_layout(vec![0.1; 212], layout);
+        let segments = *g.segments();
+        let total = g.data().len();
+        let mut rng = ChaCha12Rng::seed_from_u64(5);
+        let rates = MutationRates {
+            point_rate: 0.3,
+            duplication_rate: 0.5,
+            deletion_rate: 0.5,
+            ..MutationRates::default()
+        };
+        let (mut shrank, mut regrew) = (false, false);
+        for _ in 0..2_000 {
+            let before = g.segment_len(1);
+            g.mutate(&mut rng, &rates);
+            g.mutate_structure(&mut rng, &rates);
+            shrank |= g.segment_len(1) < before;
+            regrew |= g.segment_len(1) > before;
+
+            assert_eq!(g.data().len(), total);
+            assert_eq!(g.segments(), &segments);
+            assert_eq!(g.layout(), layout);
+            for pair in g.segments().windows(2) {
+                assert_eq!(pair[0].0 + pair[0].1, pair[1].0, "segments must tile");
+            }
+            for (criterion, &(_, capacity)) in segments.iter().enumerate() {
+                let len = g.segment_len(criterion);
+                assert!((1..=capacity).contains(&len));
+                if !Genome::STRUCTURAL_SEGMENTS.contains(&criterion) {
+                    assert_eq!(len, capacity);
+                }
+                let tail = &g.segment_data(criterion)[len..];
+                assert!(tail.iter().all(|&v| v == 0.0), "unused genes stay zero");
+            }
+            decode_graph_metabolism(&g.padded_segment_data(1, MIN_METABOLIC_SEGMENT_SIZE));
+            let program = DevelopmentalProgram::decode(g.active_segment_data(3));
+            assert!(program.roles.len() <= 2);
+        }
+        assert!(shrank && regrew);
+    }
+
+    #[test]
+    fn zero_evolution_segment_decodes_to_unit_multipliers() {
+        let m = MutationRateModifiers::decode(&[0.0; Genome::EVOLUTION_SIZE]);
+        assert_eq!(m, MutationRateModifiers::default());
+        assert!((m.mean() - 1.0).abs() < f32::EPSILON);
+        let rates = MutationRates::default();
+        let scaled = rates.scaled(&m);
+        assert!((scaled.point_rate - rates.point_rate).abs() < f32::EPSILON);
+        assert!((scaled.point_scale - rates.point_scale).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn mutation_rate_modifiers_are_capped() {
+        let high = MutationRateModifiers::decode(&[100.0; 4]);
+        let low = MutationRateModifiers::decode(&[-100.0; 4]);
+        for v in [
+            high.point_rate,
+            high.point_scale,
+            high.reset_rate,
+            high.scale_rate,
+        ] {
+            assert!(v <= MutationRateModifiers::MAX_MULTIPLIER);
+        }
+        for v in [
+            low.point_rate,
+            low.point_scale,
+            low.reset_rate,
+            low.scale_rate,
+        ] {
+            assert!(v >= MutationRateModifiers::MIN_MULTIPLIER);
+        }
+    }
+
+    #[test]
+    fn scaled_rates_keep_probability_budget() {
+        let rates = MutationRates {
+            point_rate: 0.6,
+            reset_rate: 0.3,
+            scale_rate: 0.1,
+            ..MutationRates::default()
+        };
+        let scaled = rates.scaled(&MutationRateModifiers::decode(&[100.0; 4]));
+        let budget = scaled.point_rate + scaled.reset_rate + scaled.scale_rate;
+        assert!(budget <= 1.0 + f32::EPSILON, "budget {budget} exceeds 1.0");
+    }
+
     proptest! {
         #[test]
         fn proptest_mutation_always_stays_within_limit(seed: u64, steps in 1usize..64) {
@@ -240,10 +970,14 @@ mod tests {
                 scale_min: 0.2,
                 scale_max: 2.5,
                 value_limit: 1.5,
+                per_segment: [None; 7],
+                duplication_rate: 0.3,
+                deletion_rate: 0.3,
             };
             let mut rng = ChaCha12Rng::seed_from_u64(seed);
             for _ in 0..steps {
                 g.mutate(&mut rng, &rates);
+                g.mutate_structure(&mut rng, &rates);
             }
             prop_assert!(g
                 .data()
@@ -0,0 +1,190 @@
+//! PNG rasters of the resource field and agent density, behind the `image-export`
+//! feature.
+//!
+//! Values are min-max normalized per image unless a fixed `(min, max)` range is
+//! given, which keeps frames of one run on a shared scale; values outside a fixed
+//! range are clamped. Grid row 0 is the top row of the image.
+
+use crate::resource::ResourceField;
+use image::{GrayImage, ImageError, Luma, Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::{error::Error, fmt};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Colormap {
+    /// 8-bit grayscale, black at the minimum.
+    #[default]
+    Grayscale,
+    /// Perceptually uniform dark purple → yellow, as RGB.
+    Viridis,
+}
+
+/// Viridis sampled at nine evenly spaced points, interpolated linearly between.
+const VIRIDIS: [[u8; 3]; 9] = [
+    [68, 1, 84],
+    [71, 44, 122],
+    [59, 81, 139],
+    [44, 113, 142],
+    [33, 144, 141],
+    [39, 173, 129],
+    [92, 200, 99],
+    [170, 220, 50],
+    [253, 231, 37],
+];
+
+#[derive(Debug)]
+pub enum ImageExportError {
+    /// A density grid needs at least one cell per side.
+    InvalidGridCells,
+    /// A fixed range needs finite bounds with `min < max`.
+    InvalidRange {
+        min: f32,
+        max: f32,
+    },
+    Image(ImageError),
+}
+
+impl fmt::Display for ImageExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageExportError::InvalidGridCells => write!(f, "grid_cells must be positive"),
+            ImageExportError::InvalidRange { min, max } => write!(
+                f,
+                "image range must be finite with min < max, got [{min}, {max}]"
+            ),
+            ImageExportError::Image(err) => write!(f, "failed to write image: {err}"),
+        }
+    }
+}
+
+impl Error for ImageExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImageExportError::Image(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ImageError> for ImageExportError {
+    fn from(err: ImageError) -> Self {
+        ImageExportError::Image(err)
+    }
+}
+
+/// Write the row-major `width` × `height` grid `values` to `path` as a PNG.
+pub fn write_grid_png(
+    path: impl AsRef<Path>,
+    values: &[f32],
+    width: usize,
+    height: usize,
+    colormap: Colormap,
+    range: Option<(f32, f32)>,
+) -> Result<(), ImageExportError> {
+    assert_eq!(values.len(), width * height, "grid size mismatch");
+    let (min, max) = match range {
+        Some((min, max)) if !(min.is_finite() && max.is_finite() && min < max) => {
+            return Err(ImageExportError::InvalidRange { min, max });
+        }
+        Some(range) => range,
+        None => values
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            }),
+    };
+    // A flat grid has no range to spread and renders at the minimum.
+    let span = max - min;
+    let level = |i: usize| -> f32 {
+        if span > 0.0 {
+            ((values[i] - min) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    };
+    let (w, h) = (width as u32, height as u32);
+    let index = |x: u32, y: u32| y as usize * width + x as usize;
+    match colormap {
+        Colormap::Grayscale => GrayImage::from_fn(w, h, |x, y| {
+            Luma([(level(index(x, y)) * 255.0).round() as u8])
+        })
+        .save(path)?,
+        Colormap::Viridis => {
+            RgbImage::from_fn(w, h, |x, y| Rgb(viridis(level(index(x, y))))).save(path)?
+        }
+    }
+    Ok(())
+}
+
+fn viridis(level: f32) -> [u8; 3] {
+    let position = level * (VIRIDIS.len() - 1) as f32;
+    let lower = (position.floor() as usize).min(VIRIDIS.len() - 2);
+    let t = position - lower as f32;
+    let (a, b) = (VIRIDIS[lower], VIRIDIS[lower + 1]);
+    std::array::from_fn(|c| (a[c] as f32 + t * (b[c] as f32 - a[c] as f32)).round() as u8)
+}
+
+impl ResourceField {
+    /// Write the first channel as a `width` × `height` PNG, one pixel per cell.
+    pub fn to_png(
+        &self,
+        path: impl AsRef<Path>,
+        colormap: Colormap,
+        range: Option<(f32, f32)>,
+    ) -> Result<(), ImageExportError> {
+        write_grid_png(
+            path,
+            self.channel_data(0),
+            self.width(),
+            self.height(),
+            colormap,
+            range,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_hot_cell_is_the_only_bright_pixel() {
+        let dir = std::env::temp_dir().join(format!("dl_png_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut data = vec![0.0f32; 12];
+        data[7] = 3.0;
+        let field = ResourceField::from_grid(4, 3, 1.0, data).unwrap();
+
+        let gray = dir.join("field_gray.png");
+        field.to_png(&gray, Colormap::Grayscale, None).unwrap();
+        let image = image::open(&gray).unwrap().to_luma8();
+        assert_eq!(image.dimensions(), (4, 3));
+        let bright: Vec<_> = image
+            .enumerate_pixels()
+            .filter(|(_, _, p)| p.0[0] > 0)
+            .map(|(x, y, p)| (x, y, p.0[0]))
+            .collect();
+        assert_eq!(bright, vec![(3, 1, 255)]);
+
+        // A fixed range shared across frames dims the same cell.
+        field
+            .to_png(&gray, Colormap::Grayscale, Some((0.0, 6.0)))
+            .unwrap();
+        let image = image::open(&gray).unwrap().to_luma8();
+        assert_eq!(image.get_pixel(3, 1).0[0], 128);
+
+        let color = dir.join("field_viridis.png");
+        field.to_png(&color, Colormap::Viridis, None).unwrap();
+        let image = image::open(&color).unwrap().to_rgb8();
+        assert_eq!(image.get_pixel(3, 1).0, VIRIDIS[8]);
+        assert_eq!(image.get_pixel(0, 0).0, VIRIDIS[0]);
+
+        assert!(matches!(
+            field.to_png(&gray, Colormap::Grayscale, Some((1.0, 1.0))),
+            Err(ImageExportError::InvalidRange { .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,17 +1,34 @@
+pub mod ablation;
 pub mod agent;
+pub mod analysis;
+pub mod assay;
 pub mod config;
+pub mod config_file;
 pub mod constants;
 pub mod genome;
+#[cfg(feature = "image-export")]
+pub mod image_export;
+pub mod lineage;
 pub mod metabolism;
 pub mod metrics;
 pub mod nn;
 pub mod organism;
+pub mod paired;
+#[cfg(feature = "arrow-export")]
+pub mod parquet_export;
+pub mod presets;
 pub mod resource;
 pub mod rng;
+pub mod snapshot_file;
 pub mod spatial;
+pub mod sweep;
+pub mod trajectory_file;
 pub mod world;
 
 pub use constants::MAX_WORLD_SIZE;
+pub use lineage::LineageTree;
 pub use metrics::{
-    LineageEvent, OrganismSnapshot, PopulationStats, RunSummary, SnapshotFrame, StepMetrics,
+    DeathCause, EventLog, FusionEvent, LifespanSummary, LineageEvent, OrganismSnapshot,
+    PopulationStats, RunSummary, SchemaError, SnapshotFrame, SnapshotSchedule, StepMetrics,
+    WorldEvent,
 };
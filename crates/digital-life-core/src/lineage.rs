@@ -0,0 +1,412 @@
+//! Phylogeny reconstruction from `LineageEvent` logs.
+//!
+//! Builds a forest keyed by organism `stable_id` and exports it as Newick or
+//! GraphViz DOT. Traversals are iterative so deep lineages cannot overflow the stack,
+//! and track visited organisms so malformed logs that link an organism back to its
+//! own ancestry fail with [`LineageCycleError`] instead of looping.
+
+use crate::metrics::LineageEvent;
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fmt::{self, Write};
+
+#[derive(Clone, Debug)]
+struct LineageNode {
+    parent: Option<u64>,
+    /// Step at which the organism was born; `None` when it predates the log
+    /// (a parent first seen only through its children).
+    birth_step: Option<usize>,
+    children: Vec<u64>,
+}
+
+/// Parent/child forest reconstructed from lineage events.
+///
+/// Roots are founders (born at step 0), immigrants, and parents that were never
+/// logged as children, e.g. because they were born before the run's lineage log
+/// started.
+#[derive(Clone, Debug, Default)]
+pub struct LineageTree {
+    nodes: BTreeMap<u64, LineageNode>,
+}
+
+/// A traversal reached an organism twice: the lineage events form a cycle (or log
+/// one child under two parents), so they do not describe a forest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineageCycleError {
+    /// An organism on the cycle.
+    pub stable_id: u64,
+}
+
+impl fmt::Display for LineageCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "lineage events form a cycle through organism {}",
+            self.stable_id
+        )
+    }
+}
+
+impl Error for LineageCycleError {}
+
+enum NewickFrame {
+    Enter(u64),
+    Separator,
+    Exit(u64),
+}
+
+impl LineageTree {
+    /// Build the forest from `events` plus explicitly known founders.
+    ///
+    /// Events may arrive in any order; children are kept sorted by stable id so
+    /// exports are deterministic.
+    pub fn from_events(events: &[LineageEvent], founder_ids: &[u64]) -> Self {
+        let mut nodes: BTreeMap<u64, LineageNode> = BTreeMap::new();
+        for &id in founder_ids {
+            nodes.insert(
+                id,
+                LineageNode {
+                    parent: None,
+                    birth_step: Some(0),
+                    children: Vec::new(),
+                },
+            );
+        }
+        for event in events {
+            if event.parent_stable_id == LineageEvent::NO_PARENT {
+                // Immigrants root their own tree from the step they were added.
+                nodes
+                    .entry(event.child_stable_id)
+                    .or_insert_with(|| LineageNode {
+                        parent: None,
+                        birth_step: None,
+                        children: Vec::new(),
+                    })
+                    .birth_step = Some(event.step);
+                continue;
+            }
+            let child = nodes
+                .entry(event.child_stable_id)
+                .or_insert_with(|| LineageNode {
+                    parent: None,
+                    birth_step: None,
+                    children: Vec::new(),
+                });
+            child.parent = Some(event.parent_stable_id);
+            child.birth_step = Some(event.step);
+            nodes
+                .entry(event.parent_stable_id)
+                .or_insert_with(|| LineageNode {
+                    parent: None,
+                    birth_step: None,
+                    children: Vec::new(),
+                })
+                .children
+                .push(event.child_stable_id);
+        }
+        for node in nodes.values_mut() {
+            node.children.sort_unstable();
+            node.children.dedup();
+        }
+        Self { nodes }
+    }
+
+    /// Number of organisms in the forest.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Root stable ids in ascending order.
+    pub fn roots(&self) -> Vec<u64> {
+        self.nodes
+            .iter()
+            .filter(|(_, n)| n.parent.is_none())
+            .map(|(&id, _)| id)
+            .collect()
+    }
+
+    pub fn parent(&self, id: u64) -> Option<u64> {
+        self.nodes.get(&id).and_then(|n| n.parent)
+    }
+
+    pub fn children(&self, id: u64) -> &[u64] {
+        self.nodes.get(&id).map_or(&[], |n| n.children.as_slice())
+    }
+
+    /// Branch length to the parent in steps, when both birth steps are known.
+    fn branch_length(&self, id: u64) -> Option<usize> {
+        let node = self.nodes.get(&id)?;
+        let parent = self.nodes.get(&node.parent?)?;
+        Some(node.birth_step?.saturating_sub(parent.birth_step?))
+    }
+
+    /// Check that walking down from the roots reaches every organism exactly once.
+    fn check_acyclic(&self) -> Result<(), LineageCycleError> {
+        let mut visited = HashSet::with_capacity(self.nodes.len());
+        let mut stack = self.roots();
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                return Err(LineageCycleError { stable_id: id });
+            }
+            stack.extend_from_slice(self.children(id));
+        }
+        // Organisms unreachable from any root only have ancestors on a cycle.
+        match self.nodes.keys().find(|id| !visited.contains(*id)) {
+            Some(&stable_id) => Err(LineageCycleError { stable_id }),
+            None => Ok(()),
+        }
+    }
+
+    /// Export the forest as Newick, one `;`-terminated tree per root and line.
+    ///
+    /// Labels are stable ids; branch lengths are step differences between a
+    /// child's and its parent's birth. Lengths are omitted when the parent's
+    /// birth step is unknown.
+    pub fn to_newick(&self) -> Result<String, LineageCycleError> {
+        self.check_acyclic()?;
+        let mut out = String::new();
+        for root in self.roots() {
+            let mut stack = vec![NewickFrame::Enter(root)];
+            while let Some(frame) = stack.pop() {
+                match frame {
+                    NewickFrame::Enter(id) => {
+                        let children = self.children(id);
+                        if children.is_empty() {
+                            self.write_newick_label(&mut out, id);
+                            continue;
+                        }
+                        out.push('(');
+                        stack.push(NewickFrame::Exit(id));
+                        for (i, &child) in children.iter().enumerate().rev() {
+                            stack.push(NewickFrame::Enter(child));
+                            if i > 0 {
+                                stack.push(NewickFrame::Separator);
+                            }
+                        }
+                    }
+                    NewickFrame::Separator => out.push(','),
+                    NewickFrame::Exit(id) => {
+                        out.push(')');
+                        self.write_newick_label(&mut out, id);
+                    }
+                }
+            }
+            out.push_str(";\n");
+        }
+        Ok(out)
+    }
+
+    fn write_newick_label(&self, out: &mut String, id: u64) {
+        let _ = write!(out, "{id}");
+        if let Some(len) = self.branch_length(id) {
+            let _ = write!(out, ":{len}");
+        }
+    }
+
+    /// Export the forest as a GraphViz `digraph`, edges labelled with birth steps.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph lineage {\n");
+        for (&id, node) in &self.nodes {
+            match node.birth_step {
+                Some(step) => {
+                    let _ = writeln!(out, "  {id} [label=\"{id}\\nstep {step}\"];");
+                }
+                None => {
+                    let _ = writeln!(out, "  {id} [label=\"{id}\"];");
+                }
+            }
+        }
+        for (&id, node) in &self.nodes {
+            for &child in &node.children {
+                let _ = writeln!(out, "  {id} -> {child};");
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Roots whose subtree contains at least one organism in `alive_ids`.
+    pub fn surviving_lineages(&self, alive_ids: &[u64]) -> Result<Vec<u64>, LineageCycleError> {
+        let mut roots = alive_ids
+            .iter()
+            .filter(|id| self.nodes.contains_key(id))
+            .map(|&id| self.root_of(id))
+            .collect::<Result<Vec<u64>, _>>()?;
+        roots.sort_unstable();
+        roots.dedup();
+        Ok(roots)
+    }
+
+    /// Root of the tree holding `id`; `id` itself when it is absent or a root.
+    pub fn root_of(&self, mut id: u64) -> Result<u64, LineageCycleError> {
+        let mut visited = HashSet::new();
+        while let Some(parent) = self.parent(id) {
+            if !visited.insert(id) {
+                return Err(LineageCycleError { stable_id: id });
+            }
+            id = parent;
+        }
+        Ok(id)
+    }
+
+    /// Largest number of parent→child edges from any root to a descendant.
+    pub fn max_depth(&self) -> Result<usize, LineageCycleError> {
+        self.check_acyclic()?;
+        let mut max_depth = 0;
+        let mut stack: Vec<(u64, usize)> = self.roots().into_iter().map(|r| (r, 0)).collect();
+        while let Some((id, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+            stack.extend(self.children(id).iter().map(|&c| (c, depth + 1)));
+        }
+        Ok(max_depth)
+    }
+
+    /// Deepest organism that is an ancestor of (or equal to) both `a` and `b`.
+    pub fn most_recent_common_ancestor(
+        &self,
+        a: u64,
+        b: u64,
+    ) -> Result<Option<u64>, LineageCycleError> {
+        if !self.nodes.contains_key(&a) || !self.nodes.contains_key(&b) {
+            return Ok(None);
+        }
+        let mut ancestors = HashSet::new();
+        let mut cursor = Some(a);
+        while let Some(id) = cursor {
+            if !ancestors.insert(id) {
+                return Err(LineageCycleError { stable_id: id });
+            }
+            cursor = self.parent(id);
+        }
+        let mut visited = HashSet::new();
+        let mut cursor = Some(b);
+        while let Some(id) = cursor {
+            if ancestors.contains(&id) {
+                return Ok(Some(id));
+            }
+            if !visited.insert(id) {
+                return Err(LineageCycleError { stable_id: id });
+            }
+            cursor = self.parent(id);
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(step: usize, parent: u64, child: u64, generation: u32) -> LineageEvent {
+        LineageEvent {
+            step,
+            parent_stable_id: parent,
+            child_stable_id: child,
+            generation,
+        }
+    }
+
+    /// Founders 0 and 1; 0 → {2, 3}; 2 → {4}; 3 → {5}.
+    fn three_generation_tree() -> LineageTree {
+        let events = vec![
+            event(10, 0, 2, 1),
+            event(15, 0, 3, 1),
+            event(30, 2, 4, 2),
+            event(42, 3, 5, 2),
+        ];
+        LineageTree::from_events(&events, &[0, 1])
+    }
+
+    #[test]
+    fn newick_matches_expected_string() {
+        let tree = three_generation_tree();
+        assert_eq!(tree.to_newick().unwrap(), "((4:20)2:10,(5:27)3:15)0;\n1;\n");
+    }
+
+    #[test]
+    fn dot_contains_all_edges() {
+        let dot = three_generation_tree().to_dot();
+        assert!(dot.starts_with("digraph lineage {"));
+        for edge in ["0 -> 2;", "0 -> 3;", "2 -> 4;", "3 -> 5;"] {
+            assert!(dot.contains(edge), "missing edge {edge}");
+        }
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn parent_missing_from_log_becomes_root_without_branch_length() {
+        let tree = LineageTree::from_events(&[event(50, 7, 8, 4)], &[]);
+        assert_eq!(tree.roots(), vec![7]);
+        assert_eq!(tree.to_newick().unwrap(), "(8)7;\n");
+    }
+
+    #[test]
+    fn immigrant_roots_its_own_tree_with_branch_lengths() {
+        let events = [
+            event(300, LineageEvent::NO_PARENT, 9, 0),
+            event(320, 9, 10, 1),
+        ];
+        let tree = LineageTree::from_events(&events, &[0]);
+        assert_eq!(tree.roots(), vec![0, 9]);
+        assert_eq!(tree.to_newick().unwrap(), "0;\n(10:20)9;\n");
+        assert_eq!(tree.surviving_lineages(&[10]).unwrap(), vec![9]);
+    }
+
+    #[test]
+    fn surviving_lineages_reports_roots_of_alive_organisms() {
+        let tree = three_generation_tree();
+        assert_eq!(tree.surviving_lineages(&[4, 5]).unwrap(), vec![0]);
+        assert_eq!(tree.surviving_lineages(&[1, 5]).unwrap(), vec![0, 1]);
+        assert!(tree.surviving_lineages(&[99]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn max_depth_counts_generations() {
+        assert_eq!(three_generation_tree().max_depth().unwrap(), 2);
+        assert_eq!(LineageTree::default().max_depth().unwrap(), 0);
+    }
+
+    #[test]
+    fn most_recent_common_ancestor_walks_parents() {
+        let tree = three_generation_tree();
+        assert_eq!(tree.most_recent_common_ancestor(4, 5), Ok(Some(0)));
+        assert_eq!(tree.most_recent_common_ancestor(4, 2), Ok(Some(2)));
+        assert_eq!(tree.most_recent_common_ancestor(4, 1), Ok(None));
+        assert_eq!(tree.most_recent_common_ancestor(4, 99), Ok(None));
+    }
+
+    #[test]
+    fn cyclic_events_are_rejected_instead_of_looping() {
+        // 1 and 2 are each other's parent; neither is a root.
+        let detached = LineageTree::from_events(&[event(1, 1, 2, 1), event(2, 2, 1, 1)], &[]);
+        assert_eq!(
+            detached.to_newick(),
+            Err(LineageCycleError { stable_id: 1 })
+        );
+        assert!(detached.max_depth().is_err());
+        assert!(detached.surviving_lineages(&[2]).is_err());
+        assert!(detached.most_recent_common_ancestor(1, 2).is_err());
+
+        // Founder 0 → 1 → 2 → 1: the cycle hangs below a root.
+        let events = [event(1, 0, 1, 1), event(2, 1, 2, 2), event(3, 2, 1, 3)];
+        let rooted = LineageTree::from_events(&events, &[0]);
+        assert!(rooted.to_newick().is_err());
+        assert!(rooted.max_depth().is_err());
+        assert!(rooted.root_of(2).is_err());
+        assert_eq!(rooted.root_of(0), Ok(0));
+    }
+
+    #[test]
+    fn deep_lineage_does_not_overflow_stack() {
+        let events: Vec<LineageEvent> = (0..100_000u64)
+            .map(|i| event(i as usize + 1, i, i + 1, i as u32 + 1))
+            .collect();
+        let tree = LineageTree::from_events(&events, &[0]);
+        assert_eq!(tree.max_depth().unwrap(), 100_000);
+        assert!(tree.to_newick().unwrap().ends_with("0;\n"));
+    }
+}
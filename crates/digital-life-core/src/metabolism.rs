@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use crate::resource::MAX_RESOURCE_CHANNELS;
+use std::fmt;
 use std::sync::OnceLock;
 
 /// Per-organism metabolic state.
@@ -9,6 +10,8 @@ pub struct MetabolicState {
     pub waste: f32,
     // Per-organism carry-over pool for graph intermediates between simulation steps.
     pub graph_pool: Vec<f32>,
+    /// Buffer the graph engine swaps with `graph_pool` each step; holds no state.
+    pub graph_scratch: Vec<f32>,
 }
 
 impl Default for MetabolicState {
@@ -18,15 +21,31 @@ impl Default for MetabolicState {
             resource: 5.0,
             waste: 0.0,
             graph_pool: Vec::new(),
+            graph_scratch: Vec::new(),
         }
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct MetabolismFlux {
     pub consumed_external: f32,
     pub consumed_total: f32,
     pub produced_waste: f32,
+    /// Mass removed from the organism's internal stock (energy, resource, waste and
+    /// graph intermediates) without being excreted: conversion losses, basal and
+    /// energy-loss costs, waste decay and anything clamped away. Together with
+    /// `consumed_external` it closes the organism's balance for the step.
+    pub dissipated: f32,
+}
+
+impl MetabolismFlux {
+    /// Add `other` to these running totals.
+    pub fn accumulate(&mut self, other: &MetabolismFlux) {
+        self.consumed_external += other.consumed_external;
+        self.consumed_total += other.consumed_total;
+        self.produced_waste += other.produced_waste;
+        self.dissipated += other.dissipated;
+    }
 }
 
 /// Future-facing node definition for genetically encoded graph metabolism.
@@ -95,7 +114,19 @@ impl ToyMetabolism {
         external_resource: f32,
         dt: f32,
     ) -> MetabolismFlux {
-        let params = MetabolismParams::from_toy(self);
+        self.step_with_basal_scale(state, external_resource, dt, 1.0)
+    }
+
+    /// [`ToyMetabolism::step`] with the energy loss rate multiplied by `basal_scale`.
+    pub fn step_with_basal_scale(
+        &self,
+        state: &mut MetabolicState,
+        external_resource: f32,
+        dt: f32,
+        basal_scale: f32,
+    ) -> MetabolismFlux {
+        let mut params = MetabolismParams::from_toy(self);
+        params.energy_loss_rate *= basal_scale;
         apply_metabolism_step(
             params,
             self.conversion_efficiency,
@@ -109,10 +140,7 @@ impl ToyMetabolism {
 const DEFAULT_EDGE_TRANSFER_EFFICIENCY: f32 = 0.98;
 
 // Genome decoding constants
-const MIN_NODE_COUNT: f32 = 2.0;
-const MAX_NODE_COUNT: f32 = 4.0;
-const NODE_COUNT_SCALE: f32 = 2.0;
-const NODE_COUNT_OFFSET: f32 = 2.0;
+const MIN_NODE_COUNT: usize = 2;
 const CATALYTIC_EFF_SCALE: f32 = 0.9;
 const CATALYTIC_EFF_OFFSET: f32 = 0.1;
 const EDGE_EXISTENCE_THRESHOLD: f32 = 0.3;
@@ -127,7 +155,67 @@ fn sigmoid(x: f32) -> f32 {
     1.0 / (1.0 + (-x).exp())
 }
 
-/// Decode a 16-float genome segment into entry node ID for a graph with `node_count` nodes.
+/// Length of the metabolic segment encoding graphs of up to `max_nodes` nodes:
+/// node count, entry node, one efficiency per node, one weight per node pair and
+/// four engine parameters.
+pub const fn metabolic_segment_size(max_nodes: usize) -> usize {
+    6 + max_nodes + max_nodes * (max_nodes - 1) / 2
+}
+
+/// Smallest metabolic segment that still encodes a graph: two nodes and one edge.
+pub const MIN_METABOLIC_SEGMENT_SIZE: usize = metabolic_segment_size(MIN_NODE_COUNT);
+
+/// Largest node count fully encoded by a segment of `len` floats. Trailing floats
+/// beyond that encoding are carried by the genome but not decoded.
+///
+/// # Panics
+///
+/// Panics if `len < MIN_METABOLIC_SEGMENT_SIZE`.
+pub fn max_nodes_for_segment(len: usize) -> usize {
+    assert!(
+        len >= MIN_METABOLIC_SEGMENT_SIZE,
+        "metabolic segment must have at least {MIN_METABOLIC_SEGMENT_SIZE} elements"
+    );
+    let mut max_nodes = MIN_NODE_COUNT;
+    while metabolic_segment_size(max_nodes + 1) <= len {
+        max_nodes += 1;
+    }
+    max_nodes
+}
+
+/// Offsets of each field in a metabolic segment encoding up to `max_nodes` nodes.
+struct SegmentLayout {
+    max_nodes: usize,
+}
+
+impl SegmentLayout {
+    fn catalytic(&self, node: usize) -> usize {
+        2 + node
+    }
+
+    fn edges(&self) -> usize {
+        2 + self.max_nodes
+    }
+
+    fn transfer_efficiency(&self) -> usize {
+        self.edges() + self.max_nodes * (self.max_nodes - 1) / 2
+    }
+
+    fn conversion_efficiency(&self) -> usize {
+        self.transfer_efficiency() + 1
+    }
+
+    fn channel_affinities(&self) -> usize {
+        self.transfer_efficiency() + 2
+    }
+
+    /// First float past the graph encoding; only present in segments with spare floats.
+    fn waste_affinity(&self) -> usize {
+        metabolic_segment_size(self.max_nodes)
+    }
+}
+
+/// Decode a metabolic genome segment into entry node ID for a graph with `node_count` nodes.
 ///
 /// # Panics
 ///
@@ -138,30 +226,40 @@ pub fn decode_entry_node_id(segment: &[f32], node_count: usize) -> u16 {
     raw.min(node_count.saturating_sub(1)) as u16
 }
 
-/// Decode a 16-float genome segment into a `MetabolicGraph`.
+/// Decode a metabolic genome segment into a `MetabolicGraph`.
 ///
-/// Encoding scheme (16 floats):
-/// - [0]: node count → clamp(round(sigmoid(x)*2+2), 2, 4)
+/// The maximum node count N is the largest that fits the segment
+/// (see [`max_nodes_for_segment`]): 16 floats give N = 4, 32 give 6, 64 give 10.
+/// Encoding scheme (6 + N + N(N-1)/2 floats; the 16-float layout for N = 4 is shown):
+/// - [0]: node count → clamp(round(sigmoid(x)*(N-2)+2), 2, N)
 /// - [1]: entry node → floor(sigmoid(x)*node_count)
-/// - [2-5]: catalytic efficiency per node → sigmoid(x)*0.9+0.1 → [0.1, 1.0]
-/// - [6-11]: edge weights for pairs (0,1)(0,2)(0,3)(1,2)(1,3)(2,3)
+/// - [2..2+N] ([2-5]): catalytic efficiency per node → sigmoid(x)*0.9+0.1 → [0.1, 1.0]
+/// - next N(N-1)/2 ([6-11]): edge weights for pairs (i,j), i<j, in lexicographic order
+///   (0,1)(0,2)(0,3)(1,2)(1,3)(2,3);
 ///   abs(x)>0.3 → edge exists; sign → direction; clamp(abs(x),0.1,1.0) → flux_ratio
-/// - [12]: edge transfer efficiency → sigmoid(x)*0.3+0.7 → [0.7, 1.0]
-/// - [13]: conversion efficiency → sigmoid(x)*0.7+0.3 → [0.3, 1.0]
-/// - 14-15: reserved
+/// - next ([12]): edge transfer efficiency → sigmoid(x)*0.3+0.7 → [0.7, 1.0]
+/// - next ([13]): conversion efficiency → sigmoid(x)*0.7+0.3 → [0.3, 1.0]
+/// - next two ([14-15]): uptake affinity logits for resource channels 1 and 2
+///   (channel 0 is fixed at 0)
+/// - optional next ([16], only in segments with a spare float): waste affinity →
+///   clamp(x, 0, 1), read by [`decode_graph_metabolism`]; 0 means no waste uptake
+///
+/// # Panics
+///
+/// Panics if `segment.len() < MIN_METABOLIC_SEGMENT_SIZE`.
 pub fn decode_metabolic_graph(segment: &[f32]) -> MetabolicGraph {
-    assert!(
-        segment.len() >= 16,
-        "segment must have at least 16 elements"
-    );
-
-    let node_count = (sigmoid(segment[0]) * NODE_COUNT_SCALE + NODE_COUNT_OFFSET)
+    let layout = SegmentLayout {
+        max_nodes: max_nodes_for_segment(segment.len()),
+    };
+    let node_count = (sigmoid(segment[0]) * (layout.max_nodes - MIN_NODE_COUNT) as f32
+        + MIN_NODE_COUNT as f32)
         .round()
-        .clamp(MIN_NODE_COUNT, MAX_NODE_COUNT) as usize;
+        .clamp(MIN_NODE_COUNT as f32, layout.max_nodes as f32) as usize;
 
     let nodes: Vec<MetabolicNode> = (0..node_count)
         .map(|i| {
-            let eff = sigmoid(segment[2 + i]) * CATALYTIC_EFF_SCALE + CATALYTIC_EFF_OFFSET;
+            let eff =
+                sigmoid(segment[layout.catalytic(i)]) * CATALYTIC_EFF_SCALE + CATALYTIC_EFF_OFFSET;
             MetabolicNode {
                 id: i as u16,
                 catalytic_efficiency: eff,
@@ -169,14 +267,14 @@ pub fn decode_metabolic_graph(segment: &[f32]) -> MetabolicGraph {
         })
         .collect();
 
-    // Edge pairs for up to 4 nodes: (0,1)(0,2)(0,3)(1,2)(1,3)(2,3) at slots 6..12
-    let edge_pairs: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+    let edge_pairs =
+        (0..layout.max_nodes).flat_map(|i| (i + 1..layout.max_nodes).map(move |j| (i, j)));
     let mut edges = Vec::new();
-    for (slot, &(i, j)) in edge_pairs.iter().enumerate() {
+    for (slot, (i, j)) in edge_pairs.enumerate() {
         if i >= node_count || j >= node_count {
             continue;
         }
-        let val = segment[6 + slot];
+        let val = segment[layout.edges() + slot];
         if val.abs() <= EDGE_EXISTENCE_THRESHOLD {
             continue;
         }
@@ -192,26 +290,44 @@ pub fn decode_metabolic_graph(segment: &[f32]) -> MetabolicGraph {
     MetabolicGraph { nodes, edges }
 }
 
-/// Decode a 16-float genome segment into a full `GraphMetabolism` engine.
+/// Decode a metabolic genome segment into a full `GraphMetabolism` engine, with
+/// its step buffers sized for the decoded graph.
+///
+/// See [`decode_metabolic_graph`] for the encoding.
 pub fn decode_graph_metabolism(segment: &[f32]) -> GraphMetabolism {
     let graph = decode_metabolic_graph(segment);
+    let layout = SegmentLayout {
+        max_nodes: max_nodes_for_segment(segment.len()),
+    };
     let entry_node_id = decode_entry_node_id(segment, graph.nodes.len());
-    let edge_transfer_efficiency =
-        sigmoid(segment[12]) * EDGE_TRANSFER_EFF_SCALE + EDGE_TRANSFER_EFF_OFFSET;
-    let conversion_efficiency = sigmoid(segment[13]) * CONVERSION_EFF_SCALE + CONVERSION_EFF_OFFSET;
+    let edge_transfer_efficiency = sigmoid(segment[layout.transfer_efficiency()])
+        * EDGE_TRANSFER_EFF_SCALE
+        + EDGE_TRANSFER_EFF_OFFSET;
+    let conversion_efficiency = sigmoid(segment[layout.conversion_efficiency()])
+        * CONVERSION_EFF_SCALE
+        + CONVERSION_EFF_OFFSET;
+    let affinities = layout.channel_affinities();
+    let channel_affinity_logits = [segment[affinities], segment[affinities + 1]];
+    let waste_affinity = segment
+        .get(layout.waste_affinity())
+        .map_or(0.0, |&x| x.clamp(0.0, 1.0));
 
     assert!(
         validate_metabolic_graph(&graph, entry_node_id),
         "decoded graph must be structurally valid"
     );
 
-    GraphMetabolism {
+    let engine = GraphMetabolism {
         graph,
         entry_node_id,
         edge_transfer_efficiency,
         conversion_efficiency,
+        channel_affinity_logits,
+        waste_affinity,
         ..GraphMetabolism::default()
-    }
+    };
+    engine.topology();
+    engine
 }
 
 /// Validate a decoded metabolic graph for structural correctness.
@@ -239,7 +355,43 @@ pub fn validate_metabolic_graph(graph: &MetabolicGraph, entry_node_id: u16) -> b
     true
 }
 
-#[derive(Debug)]
+/// Index-based view of a `GraphMetabolism` graph, built once so steps need no
+/// id lookups or allocation.
+#[derive(Clone, Debug)]
+struct CompiledTopology {
+    entry_idx: usize,
+    /// Outgoing edges of node `i` are `out_edges[offsets[i]..offsets[i + 1]]`,
+    /// in graph order.
+    offsets: Vec<usize>,
+    /// `(target node index, flux ratio clamped to [0, 1])`; edges to unknown
+    /// nodes or with zero ratio carry no flux and are omitted.
+    out_edges: Vec<(usize, f32)>,
+}
+
+impl CompiledTopology {
+    fn compile(graph: &MetabolicGraph, entry_node_id: u16) -> Self {
+        let index_of = |id: u16| graph.nodes.iter().rposition(|node| node.id == id);
+        let mut offsets = Vec::with_capacity(graph.nodes.len() + 1);
+        let mut out_edges = Vec::new();
+        offsets.push(0);
+        for node in &graph.nodes {
+            for edge in graph.edges.iter().filter(|edge| edge.from == node.id) {
+                let ratio = edge.flux_ratio.clamp(0.0, 1.0);
+                if let Some(to_idx) = index_of(edge.to).filter(|_| ratio > 0.0) {
+                    out_edges.push((to_idx, ratio));
+                }
+            }
+            offsets.push(out_edges.len());
+        }
+        Self {
+            entry_idx: index_of(entry_node_id).unwrap_or(0),
+            offsets,
+            out_edges,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct GraphMetabolism {
     pub graph: MetabolicGraph,
     pub entry_node_id: u16,
@@ -251,7 +403,11 @@ pub struct GraphMetabolism {
     pub waste_decay_rate: f32,
     pub max_waste: f32,
     pub edge_transfer_efficiency: f32,
-    node_index_cache: OnceLock<HashMap<u16, usize>>,
+    /// Uptake preference logits for resource channels 1 and 2, relative to channel 0.
+    pub channel_affinity_logits: [f32; MAX_RESOURCE_CHANNELS - 1],
+    /// Fraction of `uptake_rate` drawn from the environmental waste field, in [0, 1].
+    pub waste_affinity: f32,
+    topology: OnceLock<CompiledTopology>,
 }
 
 impl Default for GraphMetabolism {
@@ -268,39 +424,54 @@ impl Default for GraphMetabolism {
             waste_decay_rate: toy.waste_decay_rate,
             max_waste: toy.max_waste,
             edge_transfer_efficiency: DEFAULT_EDGE_TRANSFER_EFFICIENCY,
-            node_index_cache: OnceLock::new(),
+            channel_affinity_logits: [0.0; MAX_RESOURCE_CHANNELS - 1],
+            waste_affinity: 0.0,
+            topology: OnceLock::new(),
         }
     }
 }
 
 impl GraphMetabolism {
-    fn node_index_by_id(&self) -> &HashMap<u16, usize> {
-        self.node_index_cache.get_or_init(|| {
-            self.graph
-                .nodes
-                .iter()
-                .enumerate()
-                .map(|(idx, node)| (node.id, idx))
-                .collect()
-        })
+    /// Compiled on first use from `graph` and `entry_node_id`, which must not
+    /// change afterwards.
+    fn topology(&self) -> &CompiledTopology {
+        self.topology
+            .get_or_init(|| CompiledTopology::compile(&self.graph, self.entry_node_id))
     }
-}
 
-impl Clone for GraphMetabolism {
-    fn clone(&self) -> Self {
-        Self {
-            graph: self.graph.clone(),
-            entry_node_id: self.entry_node_id,
-            uptake_rate: self.uptake_rate,
-            conversion_efficiency: self.conversion_efficiency,
-            waste_ratio: self.waste_ratio,
-            energy_loss_rate: self.energy_loss_rate,
-            max_energy: self.max_energy,
-            waste_decay_rate: self.waste_decay_rate,
-            max_waste: self.max_waste,
-            edge_transfer_efficiency: self.edge_transfer_efficiency,
-            node_index_cache: OnceLock::new(),
+    /// Relative uptake affinity for each of the first `channels` resource channels.
+    ///
+    /// Affinities are `exp(logit - max_logit)`, so the preferred channel is taken at
+    /// full strength and the others are discounted; unused slots are zero.
+    pub fn uptake_affinities(&self, channels: usize) -> [f32; MAX_RESOURCE_CHANNELS] {
+        let channels = channels.clamp(1, MAX_RESOURCE_CHANNELS);
+        let mut logits = [0.0f32; MAX_RESOURCE_CHANNELS];
+        logits[1..].copy_from_slice(&self.channel_affinity_logits);
+        let max = logits[..channels]
+            .iter()
+            .copied()
+            .fold(f32::NEG_INFINITY, f32::max);
+        let mut affinities = [0.0f32; MAX_RESOURCE_CHANNELS];
+        for (a, &l) in affinities.iter_mut().zip(&logits).take(channels) {
+            *a = (l - max).exp();
         }
+        affinities
+    }
+
+    /// Take up to `waste_affinity * uptake_rate * dt` of `available_waste` as a
+    /// secondary input, converting it to energy at `efficiency`. Returns the amount
+    /// taken, which the caller removes from the waste field.
+    pub fn consume_waste(
+        &self,
+        state: &mut MetabolicState,
+        available_waste: f32,
+        efficiency: f32,
+        dt: f32,
+    ) -> f32 {
+        let cap = (self.waste_affinity.clamp(0.0, 1.0) * self.uptake_rate * dt).max(0.0);
+        let taken = available_waste.max(0.0).min(cap);
+        state.energy = (state.energy + taken * efficiency).clamp(0.0, self.max_energy);
+        taken
     }
 }
 
@@ -310,9 +481,21 @@ impl GraphMetabolism {
         state: &mut MetabolicState,
         external_resource: f32,
         dt: f32,
+    ) -> MetabolismFlux {
+        self.step_with_basal_scale(state, external_resource, dt, 1.0)
+    }
+
+    /// [`GraphMetabolism::step`] with the energy loss rate multiplied by `basal_scale`.
+    pub fn step_with_basal_scale(
+        &self,
+        state: &mut MetabolicState,
+        external_resource: f32,
+        dt: f32,
+        basal_scale: f32,
     ) -> MetabolismFlux {
         if self.graph.nodes.is_empty() {
-            let params = MetabolismParams::from_graph(self);
+            let mut params = MetabolismParams::from_graph(self);
+            params.energy_loss_rate *= basal_scale;
             return apply_metabolism_step(
                 params,
                 self.conversion_efficiency.clamp(0.0, 1.0),
@@ -331,16 +514,15 @@ impl GraphMetabolism {
 
         let node_count = self.graph.nodes.len();
         if state.graph_pool.len() != node_count {
-            state.graph_pool = vec![0.0; node_count];
+            state.graph_pool.clear();
+            state.graph_pool.resize(node_count, 0.0);
         }
-        let node_index_by_id = self.node_index_by_id();
-        let entry_idx = node_index_by_id
-            .get(&self.entry_node_id)
-            .copied()
-            .unwrap_or(0);
-        state.graph_pool[entry_idx] += uptake;
-        let current = std::mem::take(&mut state.graph_pool);
-        let mut next = vec![0.0f32; node_count];
+        let topology = self.topology();
+        state.graph_pool[topology.entry_idx] += uptake;
+        let current = &state.graph_pool;
+        let next = &mut state.graph_scratch;
+        next.clear();
+        next.resize(node_count, 0.0);
 
         let mut terminal_product = 0.0f32;
         let mut inefficiency_loss = 0.0f32;
@@ -355,42 +537,42 @@ impl GraphMetabolism {
             inefficiency_loss += substrate - produced;
 
             let mut allocated = 0.0f32;
-            for edge in self.graph.edges.iter().filter(|edge| edge.from == node.id) {
-                if let Some(&to_idx) = node_index_by_id.get(&edge.to) {
-                    if allocated >= produced {
-                        break;
-                    }
-                    let ratio = edge.flux_ratio.clamp(0.0, 1.0);
-                    if ratio == 0.0 {
-                        continue;
-                    }
-                    let desired = produced * ratio;
-                    let flow = desired.min(produced - allocated);
-                    let transferred = flow * self.edge_transfer_efficiency.clamp(0.0, 1.0);
-                    next[to_idx] += transferred;
-                    allocated += flow;
-                    inefficiency_loss += flow - transferred;
+            let out_edges = &topology.out_edges[topology.offsets[idx]..topology.offsets[idx + 1]];
+            for &(to_idx, ratio) in out_edges {
+                if allocated >= produced {
+                    break;
                 }
+                let desired = produced * ratio;
+                let flow = desired.min(produced - allocated);
+                let transferred = flow * self.edge_transfer_efficiency.clamp(0.0, 1.0);
+                next[to_idx] += transferred;
+                allocated += flow;
+                inefficiency_loss += flow - transferred;
             }
 
             terminal_product += produced - allocated;
         }
 
-        state.graph_pool = next;
+        std::mem::swap(&mut state.graph_pool, &mut state.graph_scratch);
 
         state.energy += terminal_product * self.conversion_efficiency.clamp(0.0, 1.0);
         let produced_waste = inefficiency_loss
             + terminal_product * (1.0 - self.conversion_efficiency.clamp(0.0, 1.0));
         state.waste += produced_waste;
+        let waste_before_decay = state.waste;
         state.waste = (state.waste - self.waste_decay_rate * dt).clamp(0.0, self.max_waste);
 
-        let retained = (1.0 - self.energy_loss_rate * dt).clamp(0.0, 1.0);
+        let energy_before_loss = state.energy;
+        let retained = (1.0 - self.energy_loss_rate * basal_scale * dt).clamp(0.0, 1.0);
         state.energy = (state.energy * retained).clamp(0.0, self.max_energy);
 
+        // Every intermediate ends up as next-step pool, energy or waste, so only
+        // decay and loss leave the organism.
         MetabolismFlux {
             consumed_external,
             consumed_total: uptake,
             produced_waste,
+            dissipated: (waste_before_decay - state.waste) + (energy_before_loss - state.energy),
         }
     }
 }
@@ -398,7 +580,7 @@ impl GraphMetabolism {
 /// Minimal single-step metabolism for proxy control experiments.
 ///
 /// Converts external resource to energy with a flat efficiency.
-/// No graph intermediates, no waste dynamics beyond baseline decay.
+/// No graph intermediates; waste is only produced when `waste_fraction > 0`.
 /// Serves as the simplest possible metabolism satisfying "dynamic + resource-consuming".
 #[derive(Clone, Debug)]
 pub struct CounterMetabolism {
@@ -408,6 +590,10 @@ pub struct CounterMetabolism {
     pub waste_decay_rate: f32,
     pub max_waste: f32,
     pub uptake_rate: f32,
+    /// Fraction of consumed external resource emitted as waste.
+    pub waste_fraction: f32,
+    /// Energy debited per unit time regardless of intake.
+    pub basal_cost: f32,
 }
 
 impl Default for CounterMetabolism {
@@ -420,6 +606,8 @@ impl Default for CounterMetabolism {
             waste_decay_rate: toy.waste_decay_rate,
             max_waste: toy.max_waste,
             uptake_rate: toy.uptake_rate,
+            waste_fraction: 0.0,
+            basal_cost: 0.0,
         }
     }
 }
@@ -430,32 +618,159 @@ impl CounterMetabolism {
         state: &mut MetabolicState,
         external_resource: f32,
         dt: f32,
+    ) -> MetabolismFlux {
+        self.step_with_basal_scale(state, external_resource, dt, 1.0)
+    }
+
+    /// [`CounterMetabolism::step`] with the basal cost and energy loss rate multiplied
+    /// by `basal_scale`.
+    pub fn step_with_basal_scale(
+        &self,
+        state: &mut MetabolicState,
+        external_resource: f32,
+        dt: f32,
+        basal_scale: f32,
     ) -> MetabolismFlux {
         // Single-step: consume external resource, add energy directly
         let external_cap = (self.uptake_rate * dt).max(0.0);
         let consumed_external = external_resource.max(0.0).min(external_cap);
-        state.energy += consumed_external * self.flat_efficiency;
+        let energy_before = state.energy;
+        let gained = consumed_external * self.flat_efficiency;
+        state.energy += gained - self.basal_cost * basal_scale * dt;
 
         // Same energy loss rate as other modes for fairness
-        let retained = (1.0 - self.energy_loss_rate * dt).clamp(0.0, 1.0);
+        let retained = (1.0 - self.energy_loss_rate * basal_scale * dt).clamp(0.0, 1.0);
         state.energy = (state.energy * retained).clamp(0.0, self.max_energy);
 
-        // Waste decays but is not produced (no multi-step processing)
-        state.waste = (state.waste - self.waste_decay_rate * dt).clamp(0.0, self.max_waste);
+        // No multi-step processing: waste is a flat fraction of intake, then decays
+        let produced_waste = consumed_external * self.waste_fraction;
+        let waste_before = state.waste;
+        state.waste =
+            (state.waste + produced_waste - self.waste_decay_rate * dt).clamp(0.0, self.max_waste);
 
+        // Intake that is neither energy nor waste is conversion loss; the rest is
+        // basal cost, energy loss, waste decay and clamping.
         MetabolismFlux {
             consumed_external,
             consumed_total: consumed_external,
-            produced_waste: 0.0,
+            produced_waste,
+            dissipated: consumed_external * (1.0 - self.flat_efficiency - self.waste_fraction)
+                + (energy_before + gained - state.energy)
+                + (waste_before + produced_waste - state.waste),
         }
     }
 }
 
+/// Kinetic model that turns external resource into energy and waste; the extension
+/// point behind [`MetabolismEngine::Custom`].
+///
+/// Custom engines live only in the running world: they are not part of
+/// [`SimConfig`](crate::config::SimConfig), so rebuilding a world from its config
+/// drops them.
+///
+/// ```
+/// use digital_life_core::metabolism::{MetabolicState, Metabolism, MetabolismFlux};
+///
+/// /// Loses a fixed amount of energy per unit time and never feeds.
+/// #[derive(Clone, Debug)]
+/// struct ConstantDrain(f32);
+///
+/// impl Metabolism for ConstantDrain {
+///     fn step(&self, state: &mut MetabolicState, _external: f32, dt: f32) -> MetabolismFlux {
+///         state.energy = (state.energy - self.0 * dt).max(0.0);
+///         MetabolismFlux::default()
+///     }
+///
+///     fn clone_box(&self) -> Box<dyn Metabolism> {
+///         Box::new(self.clone())
+///     }
+/// }
+///
+/// let mut state = MetabolicState::default();
+/// ConstantDrain(0.1).step(&mut state, 1.0, 1.0);
+/// assert!((state.energy - 0.4).abs() < 1e-6);
+/// ```
+pub trait Metabolism: fmt::Debug + Send + Sync {
+    fn step(&self, state: &mut MetabolicState, external_resource: f32, dt: f32) -> MetabolismFlux;
+
+    /// Step with basal energy costs multiplied by `basal_scale`, as for dormant
+    /// organisms. Engines without a basal cost can keep the default, which ignores
+    /// the scale.
+    fn step_with_basal_scale(
+        &self,
+        state: &mut MetabolicState,
+        external_resource: f32,
+        dt: f32,
+        _basal_scale: f32,
+    ) -> MetabolismFlux {
+        self.step(state, external_resource, dt)
+    }
+
+    /// Energy ceiling the engine clamps to. Gains from outside the engine, such as
+    /// predation, respect it too. Engines without one keep the unbounded default.
+    fn max_energy(&self) -> f32 {
+        f32::INFINITY
+    }
+
+    fn clone_box(&self) -> Box<dyn Metabolism>;
+}
+
+impl Clone for Box<dyn Metabolism> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+macro_rules! impl_builtin_metabolism {
+    ($($engine:ty),*) => {$(
+        impl Metabolism for $engine {
+            fn step(
+                &self,
+                state: &mut MetabolicState,
+                external_resource: f32,
+                dt: f32,
+            ) -> MetabolismFlux {
+                <$engine>::step(self, state, external_resource, dt)
+            }
+
+            fn step_with_basal_scale(
+                &self,
+                state: &mut MetabolicState,
+                external_resource: f32,
+                dt: f32,
+                basal_scale: f32,
+            ) -> MetabolismFlux {
+                <$engine>::step_with_basal_scale(self, state, external_resource, dt, basal_scale)
+            }
+
+            fn max_energy(&self) -> f32 {
+                self.max_energy
+            }
+
+            fn clone_box(&self) -> Box<dyn Metabolism> {
+                Box::new(self.clone())
+            }
+        }
+    )*};
+}
+
+impl_builtin_metabolism!(ToyMetabolism, GraphMetabolism, CounterMetabolism);
+
 #[derive(Clone, Debug)]
 pub enum MetabolismEngine {
     Toy(ToyMetabolism),
     Graph(GraphMetabolism),
     Counter(CounterMetabolism),
+    /// User-supplied engine, installed with
+    /// [`World::set_metabolism_engine`](crate::world::World::set_metabolism_engine).
+    Custom(Box<dyn Metabolism>),
+}
+
+/// Any engine, built-in ones included, converts to [`MetabolismEngine::Custom`].
+impl<M: Metabolism + 'static> From<M> for MetabolismEngine {
+    fn from(engine: M) -> Self {
+        Self::Custom(Box::new(engine))
+    }
 }
 
 impl Default for MetabolismEngine {
@@ -470,11 +785,42 @@ impl MetabolismEngine {
         state: &mut MetabolicState,
         external_resource: f32,
         dt: f32,
+    ) -> MetabolismFlux {
+        self.step_with_basal_scale(state, external_resource, dt, 1.0)
+    }
+
+    /// Step with basal energy costs multiplied by `basal_scale`; uptake and conversion
+    /// are unaffected. Used for dormant organisms.
+    pub fn step_with_basal_scale(
+        &self,
+        state: &mut MetabolicState,
+        external_resource: f32,
+        dt: f32,
+        basal_scale: f32,
     ) -> MetabolismFlux {
         match self {
-            MetabolismEngine::Toy(engine) => engine.step(state, external_resource, dt),
-            MetabolismEngine::Graph(engine) => engine.step(state, external_resource, dt),
-            MetabolismEngine::Counter(engine) => engine.step(state, external_resource, dt),
+            MetabolismEngine::Toy(engine) => {
+                engine.step_with_basal_scale(state, external_resource, dt, basal_scale)
+            }
+            MetabolismEngine::Graph(engine) => {
+                engine.step_with_basal_scale(state, external_resource, dt, basal_scale)
+            }
+            MetabolismEngine::Counter(engine) => {
+                engine.step_with_basal_scale(state, external_resource, dt, basal_scale)
+            }
+            MetabolismEngine::Custom(engine) => {
+                engine.step_with_basal_scale(state, external_resource, dt, basal_scale)
+            }
+        }
+    }
+
+    /// See [`Metabolism::max_energy`].
+    pub fn max_energy(&self) -> f32 {
+        match self {
+            MetabolismEngine::Toy(engine) => engine.max_energy,
+            MetabolismEngine::Graph(engine) => engine.max_energy,
+            MetabolismEngine::Counter(engine) => engine.max_energy,
+            MetabolismEngine::Custom(engine) => engine.max_energy(),
         }
     }
 }
@@ -528,9 +874,11 @@ fn apply_metabolism_step(
     state.resource -= uptake;
     state.energy += uptake * conversion_efficiency;
     state.waste += uptake * params.waste_ratio;
+    let waste_before_decay = state.waste;
     state.waste = (state.waste - params.waste_decay_rate * dt).clamp(0.0, params.max_waste);
 
     // Minimal thermodynamic loss to avoid unbounded free energy growth.
+    let energy_before_loss = state.energy;
     let retained = (1.0 - params.energy_loss_rate * dt).clamp(0.0, 1.0);
     state.energy = (state.energy * retained).clamp(0.0, params.max_energy);
 
@@ -538,6 +886,9 @@ fn apply_metabolism_step(
         consumed_external,
         consumed_total: uptake,
         produced_waste: uptake * params.waste_ratio,
+        dissipated: uptake * (1.0 - conversion_efficiency - params.waste_ratio)
+            + (waste_before_decay - state.waste)
+            + (energy_before_loss - state.energy),
     }
 }
 
@@ -766,6 +1117,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_derives_graph_size_from_segment_length() {
+        for (len, max_nodes, zero_nodes) in [(16, 4, 3), (32, 6, 4), (64, 10, 6)] {
+            assert_eq!(max_nodes_for_segment(len), max_nodes);
+
+            let zero = decode_graph_metabolism(&vec![0.0; len]);
+            assert_eq!(zero.graph.nodes.len(), zero_nodes, "len {len}");
+            assert!(zero.graph.edges.is_empty());
+
+            let mut segment = vec![0.5f32; len];
+            segment[0] = 20.0;
+            let full = decode_graph_metabolism(&segment);
+            assert_eq!(full.graph.nodes.len(), max_nodes, "len {len}");
+            assert_eq!(full.graph.edges.len(), max_nodes * (max_nodes - 1) / 2);
+            assert!(validate_metabolic_graph(&full.graph, full.entry_node_id));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 9 elements")]
+    fn decode_rejects_segment_below_minimum_encoding() {
+        decode_metabolic_graph(&[0.0; MIN_METABOLIC_SEGMENT_SIZE - 1]);
+    }
+
+    #[test]
+    fn graph_step_reuses_buffers() {
+        let mut segment: Vec<f32> = (0..64).map(|i| ((i * 7 % 11) as f32 - 5.0) / 4.0).collect();
+        segment[0] = 20.0;
+        let metabolism = decode_graph_metabolism(&segment);
+        let mut state = MetabolicState::default();
+        metabolism.step(&mut state, 1.0, 0.1);
+        let buffers = |state: &MetabolicState| {
+            let mut ptrs = [state.graph_pool.as_ptr(), state.graph_scratch.as_ptr()];
+            ptrs.sort();
+            (
+                ptrs,
+                state.graph_pool.capacity(),
+                state.graph_scratch.capacity(),
+            )
+        };
+        let initial = buffers(&state);
+        for _ in 0..1000 {
+            metabolism.step(&mut state, 1.0, 0.1);
+            assert_eq!(buffers(&state), initial);
+        }
+        assert_eq!(state.graph_pool.len(), 10);
+    }
+
     #[test]
     fn decode_clamps_values() {
         // Extreme values should produce valid clamped ranges
@@ -832,6 +1231,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_graph_metabolism_reads_channel_affinities() {
+        let mut segment = [0.0f32; 16];
+        let neutral = decode_graph_metabolism(&segment);
+        assert_eq!(neutral.uptake_affinities(1), [1.0, 0.0, 0.0]);
+        assert_eq!(neutral.uptake_affinities(3), [1.0, 1.0, 1.0]);
+        segment[14] = 2.0;
+        let prefers_second = decode_graph_metabolism(&segment);
+        let [a0, a1, a2] = prefers_second.uptake_affinities(2);
+        assert!((a0 - (-2.0f32).exp()).abs() < 1e-6);
+        assert!((a1 - 1.0).abs() < f32::EPSILON);
+        assert_eq!(a2, 0.0);
+        // A single-channel world ignores the affinity genes.
+        assert_eq!(prefers_second.uptake_affinities(1), [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn decode_graph_metabolism_reads_waste_affinity_from_spare_float() {
+        // The default 16-float segment has no spare float, so it never takes waste.
+        assert_eq!(decode_graph_metabolism(&[1.0; 16]).waste_affinity, 0.0);
+        let mut segment = [0.0f32; 17];
+        segment[16] = 0.6;
+        assert!((decode_graph_metabolism(&segment).waste_affinity - 0.6).abs() < f32::EPSILON);
+        segment[16] = -3.0;
+        assert_eq!(decode_graph_metabolism(&segment).waste_affinity, 0.0);
+        segment[16] = 3.0;
+        assert_eq!(decode_graph_metabolism(&segment).waste_affinity, 1.0);
+    }
+
+    #[test]
+    fn consume_waste_is_capped_by_affinity_and_availability() {
+        let gm = GraphMetabolism {
+            uptake_rate: 0.4,
+            waste_affinity: 0.5,
+            ..GraphMetabolism::default()
+        };
+        let mut state = MetabolicState {
+            energy: 0.2,
+            ..MetabolicState::default()
+        };
+        let taken = gm.consume_waste(&mut state, 1.0, 0.5, 1.0);
+        assert!((taken - 0.2).abs() < 1e-6);
+        assert!((state.energy - 0.3).abs() < 1e-6);
+        assert!((gm.consume_waste(&mut state, 0.05, 0.5, 1.0) - 0.05).abs() < 1e-6);
+        let none = GraphMetabolism::default();
+        assert_eq!(none.consume_waste(&mut state, 1.0, 0.5, 1.0), 0.0);
+    }
+
     // ── validate_metabolic_graph tests ──
 
     #[test]
@@ -947,6 +1394,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn counter_waste_fraction_produces_waste() {
+        let mut state = MetabolicState {
+            waste: 0.0,
+            ..MetabolicState::default()
+        };
+        let metabolism = CounterMetabolism {
+            waste_fraction: 0.5,
+            waste_decay_rate: 0.0,
+            ..CounterMetabolism::default()
+        };
+        let flux = metabolism.step(&mut state, 1.0, 1.0);
+        assert!((flux.produced_waste - flux.consumed_external * 0.5).abs() < f32::EPSILON);
+        assert!((state.waste - flux.produced_waste).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn counter_basal_cost_drains_energy_without_intake() {
+        let mut state = MetabolicState {
+            energy: 0.5,
+            ..MetabolicState::default()
+        };
+        let metabolism = CounterMetabolism {
+            basal_cost: 0.1,
+            energy_loss_rate: 0.0,
+            ..CounterMetabolism::default()
+        };
+        metabolism.step(&mut state, 0.0, 1.0);
+        assert!((state.energy - 0.4).abs() < 1e-6, "energy {}", state.energy);
+    }
+
+    #[test]
+    fn basal_scale_reduces_energy_loss_for_every_engine() {
+        let engines = [
+            MetabolismEngine::Toy(ToyMetabolism::default()),
+            MetabolismEngine::Counter(CounterMetabolism {
+                basal_cost: 0.1,
+                ..CounterMetabolism::default()
+            }),
+        ];
+        for engine in engines {
+            let drained = |basal_scale: f32| {
+                let mut state = MetabolicState {
+                    energy: 0.5,
+                    resource: 0.0,
+                    ..MetabolicState::default()
+                };
+                engine.step_with_basal_scale(&mut state, 0.0, 1.0, basal_scale);
+                0.5 - state.energy
+            };
+            assert!(drained(1.0) > 0.0);
+            assert!(drained(0.1) < 0.2 * drained(1.0));
+            assert_eq!(drained(0.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn dissipation_closes_the_per_step_balance_for_every_engine() {
+        let stock = |state: &MetabolicState| {
+            state.energy + state.resource + state.waste + state.graph_pool.iter().sum::<f32>()
+        };
+        let segment: Vec<f32> = (0..16).map(|i| (i as f32 * 0.37).sin()).collect();
+        let engines = [
+            MetabolismEngine::Toy(ToyMetabolism::default()),
+            MetabolismEngine::Graph(decode_graph_metabolism(&segment)),
+            MetabolismEngine::Counter(CounterMetabolism {
+                waste_fraction: 0.2,
+                basal_cost: 0.05,
+                ..CounterMetabolism::default()
+            }),
+        ];
+        for engine in engines {
+            let mut state = MetabolicState {
+                energy: 0.5,
+                resource: 0.5,
+                waste: 0.3,
+                ..MetabolicState::default()
+            };
+            for step in 0..50 {
+                let before = stock(&state);
+                let external = if step % 3 == 0 { 0.0 } else { 0.4 };
+                let flux = engine.step(&mut state, external, 1.0);
+                let residual = stock(&state) - before - flux.consumed_external + flux.dissipated;
+                assert!(residual.abs() < 1e-5, "{engine:?} step {step}: {residual}");
+                assert!(flux.dissipated >= -1e-6);
+            }
+        }
+    }
+
     #[test]
     fn graph_uses_explicit_entry_node_id() {
         let mut state = MetabolicState {
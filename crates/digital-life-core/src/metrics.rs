@@ -1,9 +1,18 @@
 use crate::agent::Agent;
-use crate::organism::OrganismRuntime;
+use crate::config::{
+    CohesionMetricMode, CriteriaMask, GenomeDiversityConfig, MetabolismMode, MetricsSelection,
+};
+use crate::constants::SPECIATION_MAX_GENOMES;
+use crate::genome::{Genome, MutationRateModifiers};
+use crate::metabolism::MetabolismFlux;
+use crate::organism::{OrganismRuntime, SensoryProgram, StageFactors};
+use crate::rng::derive_step_rng;
 use rand::Rng;
 use rand::SeedableRng;
 use rand_chacha::ChaCha12Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::{error::Error, fmt};
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -14,12 +23,30 @@ pub struct StepMetrics {
     pub boundary_mean: f32,
     pub alive_count: usize,
     pub resource_total: f64,
+    /// Population variance of resource cell values across every cell and channel.
+    pub resource_variance: f64,
     pub birth_count: usize,
     pub death_count: usize,
+    /// Organisms absorbed by fusion this step; not included in `death_count`.
+    pub fusion_count: usize,
     pub population_size: usize,
     pub mean_generation: f32,
     pub mean_genome_drift: f32,
     pub agent_id_exhaustion_events: usize,
+    /// Reproduction halts caused by the live population filling the `u16` organism id space.
+    pub organism_id_exhaustion_events: usize,
+    /// Eligible parents that could not reproduce because the agent cap was reached.
+    pub reproduction_suppressed_by_capacity: usize,
+    /// Agents whose NN outputs were non-finite and replaced with zero deltas.
+    pub nonfinite_output_events: usize,
+    /// Organisms whose energy or waste was non-finite or negative after metabolism
+    /// and was reset to zero.
+    pub metabolic_state_violations: usize,
+    /// Accumulated mass-balance residual since `audit_conservation` was enabled; see
+    /// [`ConservationLedger`](crate::world::ConservationLedger). Present only while
+    /// auditing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conservation_residual: Option<f64>,
     // Extended metrics for peer review response
     pub energy_std: f32,
     pub waste_std: f32,
@@ -31,6 +58,195 @@ pub struct StepMetrics {
     pub max_generation: usize,
     pub maturity_mean: f32,
     pub spatial_cohesion_mean: f32,
+    /// Mean decoded genome segment 6 multiplier across alive organisms.
+    pub mean_mutation_rate_multiplier: f32,
+    /// Resource regeneration rate applied during the sampled step.
+    pub current_resource_rate: f32,
+    /// Fraction of within-radius neighbor pairs whose agents share an organism, from the
+    /// step's sensing queries. Present only when `collect_contact_metrics` is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub same_organism_neighbor_fraction: Option<f32>,
+    /// Per-channel resource totals; empty (and omitted from JSON) for single-channel fields.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub resource_channel_totals: Vec<f64>,
+    /// Alive organisms currently dormant. Present only when `enable_dormancy` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dormant_count: Option<usize>,
+    /// Total waste in the environmental waste field. Present only when
+    /// `enable_waste_field` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waste_field_total: Option<f64>,
+    /// Field waste taken up by organisms during the sampled step. Present only when
+    /// `enable_waste_field` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waste_cross_feeding_uptake: Option<f32>,
+    /// Mean toxin resistance (homeostasis gene g[4]) of alive organisms; present only
+    /// with `enable_waste_field`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean_toxin_resistance: Option<f32>,
+    /// Rolling collapse indicators over the run's recent samples. Present only when
+    /// `collect_early_warning` is set, on every `early_warning_every`-th sample once
+    /// `early_warning_window` samples have been taken.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub early_warning: Option<EarlyWarningMetrics>,
+    /// Shannon entropy of alive agents' occupancy over a G×G grid, normalized by
+    /// `ln(G²)`: 1.0 for an even spread, 0.0 when every agent shares one cell.
+    pub spatial_entropy: f32,
+    /// Fraction of the G×G grid cells holding at least one alive agent.
+    pub occupied_cell_fraction: f32,
+    /// `genome_diversity` restricted to each genome segment, in `Genome::segments`
+    /// order; set when `GenomeDiversityConfig::per_segment` is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub genome_diversity_by_segment: Option<[f32; 7]>,
+    /// Fraction of alive agents holding each role, indexed by role; empty (and
+    /// omitted from JSON) unless `agent_role_count` is set.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub role_fractions: Vec<f32>,
+    /// Steps since the latest homeostasis shock; absent before the first one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steps_since_last_shock: Option<usize>,
+    /// Mean over alive organisms of the external resource taken up this step.
+    pub external_uptake_mean: f32,
+    /// Mean over alive organisms of the internal pool converted this step.
+    pub internal_conversion_mean: f32,
+    /// Mean over alive organisms of the metabolic waste produced this step.
+    pub waste_produced_mean: f32,
+    /// Mean over alive organisms of the neighbor count above
+    /// `crowding_neighbor_threshold` (0 for uncrowded organisms).
+    pub crowding_excess_mean: f32,
+    /// Present on samples that ran a speciation clustering pass.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speciation: Option<SpeciationMetrics>,
+}
+
+/// Species structure of the alive population from single-linkage clustering of
+/// genomes, computed every `SimConfig::speciation_metric_every` samples.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SpeciationMetrics {
+    pub species_count: usize,
+    pub largest_species_fraction: f32,
+    /// Mean L2 distance between genomes of different species; 0.0 with one species.
+    pub mean_inter_cluster_distance: f32,
+    /// Genomes clustered, at most [`SPECIATION_MAX_GENOMES`].
+    pub genomes_clustered: usize,
+}
+
+/// Leading indicators of population collapse over the last `window` samples.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct EarlyWarningMetrics {
+    pub window: usize,
+    /// Standard deviation of `energy_mean` divided by its mean; 0.0 when the mean is 0.
+    pub energy_cv: f32,
+    /// Lag-1 autocorrelation of `alive_count`; 0.0 when the count is constant.
+    pub alive_autocorrelation: f32,
+    /// Least-squares slope of `boundary_mean` per sample.
+    pub boundary_trend: f32,
+}
+
+/// Ring buffer of the last `capacity` samples' energy mean, alive count and boundary
+/// mean, with running sums so each push updates the indicators in O(1).
+#[derive(Clone, Debug)]
+pub struct EarlyWarningWindow {
+    capacity: usize,
+    samples: VecDeque<[f64; 3]>,
+    energy_sum: f64,
+    energy_sq_sum: f64,
+    alive_sum: f64,
+    alive_sq_sum: f64,
+    /// Sum of products of consecutive alive counts.
+    alive_lag_sum: f64,
+    boundary_sum: f64,
+    /// Sum of `i * boundary_i`, with `i` the position in the window (oldest = 0).
+    boundary_index_sum: f64,
+}
+
+impl EarlyWarningWindow {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+            energy_sum: 0.0,
+            energy_sq_sum: 0.0,
+            alive_sum: 0.0,
+            alive_sq_sum: 0.0,
+            alive_lag_sum: 0.0,
+            boundary_sum: 0.0,
+            boundary_index_sum: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, metrics: &StepMetrics) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            let [energy, alive, boundary] = self.samples.pop_front().expect("window is full");
+            self.energy_sum -= energy;
+            self.energy_sq_sum -= energy * energy;
+            self.alive_sum -= alive;
+            self.alive_sq_sum -= alive * alive;
+            if let Some(next) = self.samples.front() {
+                self.alive_lag_sum -= alive * next[1];
+            }
+            self.boundary_sum -= boundary;
+            // Every remaining sample moves one position closer to the front.
+            self.boundary_index_sum -= self.boundary_sum;
+        }
+        let energy = metrics.energy_mean as f64;
+        let alive = metrics.alive_count as f64;
+        let boundary = metrics.boundary_mean as f64;
+        if let Some(prev) = self.samples.back() {
+            self.alive_lag_sum += prev[1] * alive;
+        }
+        self.energy_sum += energy;
+        self.energy_sq_sum += energy * energy;
+        self.alive_sum += alive;
+        self.alive_sq_sum += alive * alive;
+        self.boundary_index_sum += self.samples.len() as f64 * boundary;
+        self.boundary_sum += boundary;
+        self.samples.push_back([energy, alive, boundary]);
+    }
+
+    /// Indicators over the current window, or `None` until it is full.
+    pub fn metrics(&self) -> Option<EarlyWarningMetrics> {
+        let n = self.samples.len();
+        if n < self.capacity.max(2) {
+            return None;
+        }
+        let nf = n as f64;
+
+        let energy_mean = self.energy_sum / nf;
+        let energy_var = (self.energy_sq_sum - nf * energy_mean * energy_mean) / (nf - 1.0);
+        let energy_cv = if energy_mean.abs() > f64::EPSILON {
+            energy_var.max(0.0).sqrt() / energy_mean
+        } else {
+            0.0
+        };
+
+        let alive_mean = self.alive_sum / nf;
+        let first = self.samples.front().expect("window is non-empty")[1];
+        let last = self.samples.back().expect("window is non-empty")[1];
+        let alive_var = self.alive_sq_sum - nf * alive_mean * alive_mean;
+        let alive_cov = self.alive_lag_sum - alive_mean * (2.0 * self.alive_sum - first - last)
+            + (nf - 1.0) * alive_mean * alive_mean;
+        let alive_autocorrelation = if alive_var > 1e-9 {
+            alive_cov / alive_var
+        } else {
+            0.0
+        };
+
+        let index_sum = nf * (nf - 1.0) / 2.0;
+        let index_sq_sum = (nf - 1.0) * nf * (2.0 * nf - 1.0) / 6.0;
+        let boundary_trend = (nf * self.boundary_index_sum - index_sum * self.boundary_sum)
+            / (nf * index_sq_sum - index_sum * index_sum);
+
+        Some(EarlyWarningMetrics {
+            window: n,
+            energy_cv: energy_cv as f32,
+            alive_autocorrelation: alive_autocorrelation as f32,
+            boundary_trend: boundary_trend as f32,
+        })
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -41,7 +257,113 @@ pub struct LineageEvent {
     pub generation: u32,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl LineageEvent {
+    /// `parent_stable_id` of an organism added by [`crate::world::World::add_organism`].
+    pub const NO_PARENT: u64 = u64::MAX;
+}
+
+/// One organism absorbing another under `enable_fusion`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FusionEvent {
+    pub step: usize,
+    /// Stable id of the organism that kept its identity and gained the agents.
+    pub absorber_stable_id: u64,
+    /// Stable id of the organism that ended as [`DeathCause::Fused`].
+    pub absorbed_stable_id: u64,
+}
+
+/// Terminal check that removed an organism.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeathCause {
+    /// Energy fell to `death_energy_threshold`.
+    EnergyDepletion,
+    /// Boundary integrity fell to the terminal threshold.
+    BoundaryCollapse,
+    /// Age exceeded `max_organism_age_steps`.
+    Age,
+    /// Boundary collapse caused by predation damage.
+    Predation,
+    /// Removed by [`crate::world::World::kill_organism`].
+    Intervention,
+    /// Absorbed by another organism under `enable_fusion`; counted as a fusion, not
+    /// a death.
+    Fused,
+    #[default]
+    Unknown,
+}
+
+/// Discrete birth, death or immigration event recorded when `enable_event_log` is set.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WorldEvent {
+    Birth {
+        step: usize,
+        parent_stable_id: u64,
+        child_stable_id: u64,
+        center: [f64; 2],
+    },
+    Death {
+        step: usize,
+        stable_id: u64,
+        age: usize,
+        cause: DeathCause,
+        center: [f64; 2],
+        final_energy: f32,
+    },
+    /// Organism added by [`crate::world::World::add_organism`].
+    Immigration {
+        step: usize,
+        stable_id: u64,
+        center: [f64; 2],
+    },
+}
+
+/// Bounded event buffer; events past `max_entries` are dropped and flagged.
+#[derive(Clone, Debug, Default)]
+pub struct EventLog {
+    events: Vec<WorldEvent>,
+    max_entries: usize,
+    truncated: bool,
+}
+
+impl EventLog {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            events: Vec::new(),
+            max_entries,
+            truncated: false,
+        }
+    }
+
+    pub fn push(&mut self, event: WorldEvent) {
+        if self.events.len() < self.max_entries {
+            self.events.push(event);
+        } else {
+            self.truncated = true;
+        }
+    }
+
+    pub fn events(&self) -> &[WorldEvent] {
+        &self.events
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries;
+    }
+
+    /// Drain recorded events and reset the truncation flag, keeping the bound.
+    pub fn take(&mut self) -> (Vec<WorldEvent>, bool) {
+        let truncated = std::mem::replace(&mut self.truncated, false);
+        (std::mem::take(&mut self.events), truncated)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct OrganismSnapshot {
     pub stable_id: u64,
     pub generation: u32,
@@ -53,12 +375,128 @@ pub struct OrganismSnapshot {
     pub center_x: f64,
     pub center_y: f64,
     pub n_agents: usize,
+    #[serde(default)]
+    pub dormant: bool,
+    /// External resource taken up since the previous snapshot frame.
+    #[serde(default)]
+    pub external_uptake: f32,
+    /// Internal pool converted by the metabolism since the previous frame.
+    #[serde(default)]
+    pub internal_conversion: f32,
+    /// Metabolic waste produced since the previous frame.
+    #[serde(default)]
+    pub waste_produced: f32,
+    /// Species label from a speciation pass run at this frame's step; `None` when
+    /// no pass ran or the organism was not in the clustered subsample.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub species_id: Option<u32>,
+    /// Genome-decoded parameters; present only under `snapshot_decoded_params`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded_params: Option<DecodedParams>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Phenotypic parameters decoded from an organism's genome, as the lifecycle code
+/// reads them. Every field defaults when absent, so frames written before a field
+/// was added still load.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DecodedParams {
+    /// Developmental segment g[0]: maturation speed modifier.
+    pub maturation_rate_modifier: f32,
+    /// Developmental segment g[7]: senescence pace modifier.
+    pub aging_rate_modifier: f32,
+    /// Maturity at which the juvenile stage gives way to the adolescent stage.
+    pub adolescent_threshold: f32,
+    /// Stage factors throughout the juvenile stage.
+    pub juvenile_factors: StageFactors,
+    /// Stage factors on entering the adolescent stage, before they ramp to 1.0.
+    pub adolescent_factors: StageFactors,
+    /// Stage factors at the organism's maturity when the frame was taken.
+    pub stage_factors: StageFactors,
+    /// Segment 6 multipliers applied to this organism's offspring's mutation rates
+    /// under `enable_self_adaptive_mutation`.
+    pub mutation_rate_modifiers: MutationRateModifiers,
+    /// Segment 5 multipliers, applied under `enable_genetic_sensing`.
+    pub sensory: SensoryProgram,
+}
+
+impl DecodedParams {
+    pub fn from_organism(org: &OrganismRuntime) -> Self {
+        let program = &org.developmental_program;
+        Self {
+            maturation_rate_modifier: program.maturation_rate_modifier,
+            aging_rate_modifier: program.aging_rate_modifier,
+            adolescent_threshold: program.adolescent_threshold,
+            juvenile_factors: program.stage_factors(0.0),
+            adolescent_factors: program.stage_factors(program.adolescent_threshold),
+            stage_factors: program.stage_factors(org.maturity),
+            mutation_rate_modifiers: MutationRateModifiers::decode(org.genome.segment_data(6)),
+            sensory: org.sensory_program,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SnapshotFrame {
     pub step: usize,
     pub organisms: Vec<OrganismSnapshot>,
+    /// Regeneration hotspot centers under `ResourceDynamics::MovingHotspots`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_hotspots: Vec<[f64; 2]>,
+}
+
+/// Steps at which `World::try_run_experiment_with_snapshot_schedule` records a
+/// [`SnapshotFrame`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotSchedule {
+    /// Exactly these steps, in any order; duplicates record one frame.
+    Steps(Vec<usize>),
+    /// Every `n`th step: `n`, `2n`, ...
+    Every(usize),
+    /// Geometric steps `first`, then each previous step times `factor`, rounded
+    /// and at least one step later, up to `max` inclusive. `first = 10,
+    /// factor = 2.0` gives 10, 20, 40, 80, ...
+    LogSpaced {
+        first: usize,
+        factor: f64,
+        max: usize,
+    },
+}
+
+impl SnapshotSchedule {
+    /// Scheduled steps in `1..=last_step`, ascending and distinct.
+    pub fn steps_through(&self, last_step: usize) -> Vec<usize> {
+        match *self {
+            SnapshotSchedule::Steps(ref steps) => {
+                let mut steps: Vec<usize> = steps
+                    .iter()
+                    .copied()
+                    .filter(|step| (1..=last_step).contains(step))
+                    .collect();
+                steps.sort_unstable();
+                steps.dedup();
+                steps
+            }
+            SnapshotSchedule::Every(0) => Vec::new(),
+            SnapshotSchedule::Every(n) => (n..=last_step).step_by(n).collect(),
+            SnapshotSchedule::LogSpaced { first, factor, max } => {
+                let last = last_step.min(max);
+                let mut steps = Vec::new();
+                let mut step = first.max(1);
+                while step <= last {
+                    steps.push(step);
+                    let next = (step as f64 * factor).round();
+                    step = if next.is_finite() && next < usize::MAX as f64 {
+                        (next as usize).max(step + 1)
+                    } else {
+                        usize::MAX
+                    };
+                }
+                steps
+            }
+        }
+    }
 }
 
 fn default_schema_version() -> u32 {
@@ -75,12 +513,337 @@ pub struct RunSummary {
     pub samples: Vec<StepMetrics>,
     #[serde(default)]
     pub lifespans: Vec<usize>,
+    /// Distribution of `lifespans`, kept even when the raw vector is not.
+    #[serde(default)]
+    pub lifespan_summary: LifespanSummary,
+    /// Homeostasis shocks that resolved during the run, followed by any still
+    /// unrecovered at its end.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shock_recoveries: Vec<ShockRecovery>,
     #[serde(default)]
     pub total_reproduction_events: usize,
     #[serde(default)]
     pub lineage_events: Vec<LineageEvent>,
+    /// True when lineage went to `World::set_lineage_writer` instead, leaving
+    /// `lineage_events` empty.
+    #[serde(default)]
+    pub lineage_streamed: bool,
+    /// Fusions recorded during the run (empty unless `enable_fusion`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fusion_events: Vec<FusionEvent>,
+    /// Births indexed by child generation, counted even when lineage is
+    /// streamed; index 0 (the founders and immigrants) is always zero.
+    #[serde(default)]
+    pub births_per_generation: Vec<usize>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub organism_snapshots: Vec<SnapshotFrame>,
+    /// `World::state_hash` after the final step; compare across runs to detect
+    /// trajectory changes.
+    #[serde(default)]
+    pub final_state_hash: u64,
+    /// Birth/death/immigration events recorded during the run (empty unless `enable_event_log`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<WorldEvent>,
+    /// True when `events` hit `event_log_max_entries` and later events were dropped.
+    #[serde(default)]
+    pub events_truncated: bool,
+    /// Steps actually run; below `steps` when the run was cancelled.
+    #[serde(default)]
+    pub steps_executed: usize,
+    /// True when an `ExperimentObserver` stopped the run before `steps`.
+    #[serde(default)]
+    pub cancelled: bool,
+    /// Metabolism engine the run used, so analyses can confirm which arm ran.
+    #[serde(default)]
+    pub metabolism_mode: MetabolismMode,
+    /// Criteria enabled when the run started, before any scheduled ablation. `None`
+    /// for summaries written before the field existed, whose condition is unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub criteria_mask: Option<CriteriaMask>,
+    /// Metric groups computed for `samples`; fields outside it are defaults, not
+    /// measurements.
+    #[serde(default)]
+    pub metrics_selection: MetricsSelection,
+    /// Per-phase step timings (present only when `collect_timings` is set).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<TimingSummary>,
+    /// Schema version the document was written with, when
+    /// [`RunSummary::from_json_value`] had to migrate it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub migrated_from: Option<u32>,
+    /// Seed shared with the other run of a [`crate::paired::run_paired`] pair.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paired_seed: Option<u64>,
+    /// Schedule `organism_snapshots` (or a streamed snapshot file) was recorded on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_schedule: Option<SnapshotSchedule>,
+}
+
+/// Failure to load a [`RunSummary`] through [`RunSummary::from_json_value`].
+#[derive(Debug)]
+pub enum SchemaError {
+    NotAnObject,
+    InvalidVersion(serde_json::Value),
+    /// Written by a newer build; refusing beats silently dropping fields.
+    UnsupportedVersion {
+        found: u32,
+        latest: u32,
+    },
+    Deserialize {
+        version: u32,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::NotAnObject => write!(f, "run summary must be a JSON object"),
+            SchemaError::InvalidVersion(value) => {
+                write!(f, "schema_version must be a positive integer, got {value}")
+            }
+            SchemaError::UnsupportedVersion { found, latest } => write!(
+                f,
+                "run summary schema_version {found} is newer than the latest supported version {latest}"
+            ),
+            SchemaError::Deserialize { version, source } => {
+                write!(f, "invalid schema v{version} run summary: {source}")
+            }
+        }
+    }
+}
+
+impl Error for SchemaError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SchemaError::Deserialize { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+type SummaryObject = serde_json::Map<String, serde_json::Value>;
+
+fn insert_missing(object: &mut SummaryObject, key: &str, value: serde_json::Value) {
+    object.entry(key).or_insert(value);
+}
+
+/// v1 → v2: v2 only adds `migrated_from`, but v1 spans documents written before
+/// most optional fields existed, so every field v1 could omit is filled here
+/// rather than left to serde defaults:
+///
+/// - `steps_executed` = `steps` (cancellation postdates those documents, so
+///   they always ran to completion) and `cancelled` = `false`.
+/// - `lifespans`, `lineage_events` = `[]`; `total_reproduction_events` = `0`.
+/// - `final_state_hash` = `0` (not recorded by those runs).
+/// - `events_truncated` = `false`; `metabolism_mode` = `"toy"`, the only engine
+///   before the field was added.
+fn migrate_v1_to_v2(object: &mut SummaryObject) {
+    let steps = object
+        .get("steps")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    insert_missing(object, "steps_executed", steps);
+    insert_missing(object, "cancelled", false.into());
+    insert_missing(object, "lifespans", serde_json::json!([]));
+    insert_missing(object, "lineage_events", serde_json::json!([]));
+    insert_missing(object, "total_reproduction_events", 0.into());
+    insert_missing(object, "final_state_hash", 0.into());
+    insert_missing(object, "events_truncated", false.into());
+    insert_missing(object, "metabolism_mode", "toy".into());
+}
+
+/// Migration from version `n` to `n + 1` lives at index `n - 1`.
+const MIGRATIONS: [fn(&mut SummaryObject); 1] = [migrate_v1_to_v2];
+
+impl RunSummary {
+    /// Version written by this build.
+    pub const SCHEMA_VERSION: u32 = 2;
+
+    /// Load a summary written by any supported schema version.
+    ///
+    /// Documents without `schema_version` are v1. Older versions are migrated
+    /// step by step to [`RunSummary::SCHEMA_VERSION`] and record the original
+    /// version in `migrated_from`; newer versions are rejected outright.
+    pub fn from_json_value(value: serde_json::Value) -> Result<RunSummary, SchemaError> {
+        let serde_json::Value::Object(mut object) = value else {
+            return Err(SchemaError::NotAnObject);
+        };
+        let version = match object.get("schema_version") {
+            None => 1,
+            Some(raw) => raw
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .filter(|&v| v >= 1)
+                .ok_or_else(|| SchemaError::InvalidVersion(raw.clone()))?,
+        };
+        if version > Self::SCHEMA_VERSION {
+            return Err(SchemaError::UnsupportedVersion {
+                found: version,
+                latest: Self::SCHEMA_VERSION,
+            });
+        }
+        for migrate in &MIGRATIONS[version as usize - 1..] {
+            migrate(&mut object);
+        }
+        object.insert("schema_version".into(), Self::SCHEMA_VERSION.into());
+        let mut summary: RunSummary = serde_json::from_value(serde_json::Value::Object(object))
+            .map_err(|source| SchemaError::Deserialize { version, source })?;
+        summary.migrated_from = (version < Self::SCHEMA_VERSION).then_some(version);
+        Ok(summary)
+    }
+
+    /// Counts of `lifespans` in bins `[i * bin_width, (i + 1) * bin_width)`, up to
+    /// the bin holding the longest lifespan.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bin_width` is zero.
+    pub fn lifespan_histogram(&self, bin_width: usize) -> Vec<usize> {
+        assert!(bin_width > 0, "bin_width must be positive");
+        let mut bins = Vec::new();
+        for &lifespan in &self.lifespans {
+            let bin = lifespan / bin_width;
+            if bin >= bins.len() {
+                bins.resize(bin + 1, 0);
+            }
+            bins[bin] += 1;
+        }
+        bins
+    }
+}
+
+/// Recovery from one homeostasis shock, scheduled or applied with
+/// `World::apply_homeostasis_shock`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ShockRecovery {
+    /// Step at which the shock was applied.
+    pub step: usize,
+    pub amount: f32,
+    /// Steps after `step` until the mean of internal-state channels 0 and 1 were
+    /// both within `homeostasis_recovery_epsilon` of their pre-shock values; `None`
+    /// if that had not happened by the next shock or the end of the run.
+    pub recovery_steps: Option<usize>,
+}
+
+/// Summary statistics of organism lifespans (in steps) over a run. Quantiles
+/// interpolate linearly between the sorted lifespans; all fields are zero when
+/// no organism died.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LifespanSummary {
+    pub count: usize,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub p10: f64,
+    pub p25: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+impl LifespanSummary {
+    pub fn from_lifespans(lifespans: &[usize]) -> Self {
+        if lifespans.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = lifespans.to_vec();
+        sorted.sort_unstable();
+        let quantile = |q: f64| {
+            let pos = q * (sorted.len() - 1) as f64;
+            let lo = pos.floor() as usize;
+            let hi = pos.ceil() as usize;
+            sorted[lo] as f64 + (sorted[hi] - sorted[lo]) as f64 * (pos - lo as f64)
+        };
+        Self {
+            count: sorted.len(),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            mean: sorted.iter().sum::<usize>() as f64 / sorted.len() as f64,
+            median: quantile(0.5),
+            p10: quantile(0.1),
+            p25: quantile(0.25),
+            p75: quantile(0.75),
+            p90: quantile(0.9),
+        }
+    }
+}
+
+/// Births indexed by child generation. Immigration events are not births.
+pub fn births_per_generation(events: &[LineageEvent]) -> Vec<usize> {
+    let mut births = Vec::new();
+    for event in events {
+        count_birth(&mut births, event);
+    }
+    births
+}
+
+/// Add one event to a [`births_per_generation`] tally.
+pub fn count_birth(births: &mut Vec<usize>, event: &LineageEvent) {
+    if event.parent_stable_id == LineageEvent::NO_PARENT {
+        return;
+    }
+    let generation = event.generation as usize;
+    if generation >= births.len() {
+        births.resize(generation + 1, 0);
+    }
+    births[generation] += 1;
+}
+
+/// Wall-clock statistics for one step phase across a run, in microseconds.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct PhaseTimingStats {
+    pub total_us: u64,
+    pub mean_us: f64,
+    pub max_us: u64,
+}
+
+impl PhaseTimingStats {
+    fn record(&mut self, us: u64, steps: usize) {
+        self.total_us += us;
+        self.max_us = self.max_us.max(us);
+        self.mean_us = self.total_us as f64 / steps as f64;
+    }
+}
+
+/// Aggregated `StepTimings` for a run, with the population size at the slowest
+/// step so spikes can be attributed.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct TimingSummary {
+    pub steps: usize,
+    pub spatial_build: PhaseTimingStats,
+    pub nn_query: PhaseTimingStats,
+    pub state_update: PhaseTimingStats,
+    pub total: PhaseTimingStats,
+    /// Run step (1-based) with the largest `total` time.
+    pub max_total_step: usize,
+    pub agents_at_max_total: usize,
+    pub organisms_at_max_total: usize,
+}
+
+impl TimingSummary {
+    /// Fold one step's timings in; `agents`/`organisms` are the entity counts after it.
+    pub fn record(
+        &mut self,
+        step: usize,
+        timings: &crate::world::StepTimings,
+        agents: usize,
+        organisms: usize,
+    ) {
+        self.steps += 1;
+        if self.steps == 1 || timings.total_us > self.total.max_us {
+            self.max_total_step = step;
+            self.agents_at_max_total = agents;
+            self.organisms_at_max_total = organisms;
+        }
+        self.spatial_build
+            .record(timings.spatial_build_us, self.steps);
+        self.nn_query.record(timings.nn_query_us, self.steps);
+        self.state_update
+            .record(timings.state_update_us, self.steps);
+        self.total.record(timings.total_us, self.steps);
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -89,10 +852,46 @@ pub struct PopulationStats {
     pub alive_count: usize,
     pub total_births: usize,
     pub total_deaths: usize,
+    #[serde(default)]
+    pub total_fusions: usize,
     pub mean_generation: f32,
 }
 
-fn l2_distance(a: &[f32], b: &[f32]) -> f32 {
+/// Streaming mean/variance accumulator (Welford's algorithm).
+///
+/// Accumulates in f64 so the single-pass result matches the two-pass f32
+/// computation within float tolerance without buffering samples.
+#[derive(Clone, Copy, Debug, Default)]
+struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    fn push(&mut self, value: f32) {
+        self.count += 1;
+        let value = value as f64;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// Mean of pushed values, or 0.0 when empty.
+    fn mean(&self) -> f32 {
+        self.mean as f32
+    }
+
+    /// Bessel-corrected standard deviation, or 0.0 with fewer than two values.
+    fn sample_std(&self) -> f32 {
+        if self.count < 2 {
+            return 0.0;
+        }
+        (self.m2 / (self.count - 1) as f64).sqrt() as f32
+    }
+}
+
+pub(crate) fn l2_distance(a: &[f32], b: &[f32]) -> f32 {
     a.iter()
         .zip(b.iter())
         .map(|(x, y)| (x - y).powi(2))
@@ -115,88 +914,283 @@ fn genome_drift(org: &OrganismRuntime) -> f32 {
     sum_abs / len as f32
 }
 
-fn compute_genome_diversity(organisms: &[OrganismRuntime], step_index: usize) -> f32 {
-    let alive_genomes: Vec<&[f32]> = organisms
-        .iter()
-        .filter(|o| o.alive)
-        .map(|o| o.genome.data())
-        .collect();
-    let n = alive_genomes.len();
+/// Mean pairwise L2 distance between `genomes`, plus the same mean per genome
+/// segment when `config.per_segment` is set. See [`GenomeDiversityConfig`].
+fn compute_genome_diversity(
+    genomes: &[&Genome],
+    seed: u64,
+    step_index: usize,
+    config: &GenomeDiversityConfig,
+) -> (f32, Option<[f32; 7]>) {
+    let n = genomes.len();
+    let empty_segments = config.per_segment.then_some([0.0; 7]);
     if n < 2 {
-        return 0.0;
+        return (0.0, empty_segments);
     }
 
-    // Sample up to GENOME_DIVERSITY_MAX_PAIRS random pairs to avoid O(n^2) cost
-    let max_pairs = crate::constants::GENOME_DIVERSITY_MAX_PAIRS;
-    let total_pairs = n * (n - 1) / 2;
+    let mut sum = 0.0f32;
+    let mut segment_sums = [0.0f32; 7];
+    let mut add_pair = |a: &Genome, b: &Genome| {
+        sum += l2_distance(a.data(), b.data());
+        if config.per_segment {
+            for (segment, segment_sum) in segment_sums.iter_mut().enumerate() {
+                *segment_sum += l2_distance(a.segment_data(segment), b.segment_data(segment));
+            }
+        }
+    };
 
-    if total_pairs <= max_pairs {
-        // Enumerate all pairs
-        let mut sum = 0.0f32;
+    let total_pairs = n * (n - 1) / 2;
+    let pair_count = if n < config.exact_below || total_pairs <= config.max_pairs {
         for i in 0..n {
             for j in (i + 1)..n {
-                sum += l2_distance(alive_genomes[i], alive_genomes[j]);
+                add_pair(genomes[i], genomes[j]);
             }
         }
-        sum / total_pairs as f32
+        total_pairs
     } else {
-        // Use deterministic sampling based on step_index for reproducibility
-        let mut sample_rng = ChaCha12Rng::seed_from_u64(step_index as u64);
-        let mut sum = 0.0f32;
-        for _ in 0..max_pairs {
+        let mut sample_rng = derive_step_rng(seed, step_index);
+        for _ in 0..config.max_pairs {
             let i = sample_rng.random_range(0..n);
             let mut j = sample_rng.random_range(0..n - 1);
             if j >= i {
                 j += 1;
             }
-            sum += l2_distance(alive_genomes[i], alive_genomes[j]);
+            add_pair(genomes[i], genomes[j]);
+        }
+        config.max_pairs
+    };
+    let denom = pair_count as f32;
+    (
+        sum / denom,
+        empty_segments.map(|_| segment_sums.map(|s| s / denom)),
+    )
+}
+
+/// Single-linkage clustering of `genomes`: two genomes share a species when a chain
+/// of genomes joins them with every link at most `threshold` apart. Populations
+/// above [`SPECIATION_MAX_GENOMES`] are subsampled with a stream seeded from the
+/// run seed and step index. Returns the metrics and one label per input genome,
+/// numbered in input order and `None` for genomes left out of the subsample.
+pub(crate) fn cluster_species(
+    genomes: &[&[f32]],
+    threshold: f32,
+    seed: u64,
+    step_index: usize,
+) -> (SpeciationMetrics, Vec<Option<u32>>) {
+    let n = genomes.len();
+    let clustered: Vec<usize> = if n > SPECIATION_MAX_GENOMES {
+        let mut sample_rng = derive_step_rng(seed, step_index);
+        let mut picked =
+            rand::seq::index::sample(&mut sample_rng, n, SPECIATION_MAX_GENOMES).into_vec();
+        picked.sort_unstable();
+        picked
+    } else {
+        (0..n).collect()
+    };
+    let m = clustered.len();
+    let mut labels = vec![None; n];
+    if m == 0 {
+        return (SpeciationMetrics::default(), labels);
+    }
+
+    // Union-find over the clustered genomes, keeping each pair's distance for the
+    // inter-species mean.
+    let mut parent: Vec<usize> = (0..m).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
         }
-        sum / max_pairs as f32
+        i
     }
+    let mut distances = Vec::with_capacity(m * (m - 1) / 2);
+    for i in 0..m {
+        for j in (i + 1)..m {
+            let distance = l2_distance(genomes[clustered[i]], genomes[clustered[j]]);
+            distances.push(distance);
+            if distance <= threshold {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut species_of_root = vec![u32::MAX; m];
+    let mut species = vec![0u32; m];
+    let mut sizes: Vec<usize> = Vec::new();
+    for i in 0..m {
+        let r = root(&mut parent, i);
+        if species_of_root[r] == u32::MAX {
+            species_of_root[r] = sizes.len() as u32;
+            sizes.push(0);
+        }
+        species[i] = species_of_root[r];
+        sizes[species[i] as usize] += 1;
+        labels[clustered[i]] = Some(species[i]);
+    }
+
+    let mut inter_sum = 0.0f64;
+    let mut inter_pairs = 0usize;
+    let mut pair = 0;
+    for i in 0..m {
+        for j in (i + 1)..m {
+            if species[i] != species[j] {
+                inter_sum += f64::from(distances[pair]);
+                inter_pairs += 1;
+            }
+            pair += 1;
+        }
+    }
+    let metrics = SpeciationMetrics {
+        species_count: sizes.len(),
+        largest_species_fraction: sizes.iter().copied().max().unwrap_or(0) as f32 / m as f32,
+        mean_inter_cluster_distance: if inter_pairs > 0 {
+            (inter_sum / inter_pairs as f64) as f32
+        } else {
+            0.0
+        },
+        genomes_clustered: m,
+    };
+    (metrics, labels)
+}
+
+/// Normalized occupancy entropy and occupied-cell fraction of alive agents binned
+/// into a `grid`×`grid` lattice. `occupancy` is scratch space reused across calls.
+fn compute_spatial_entropy(
+    agents: &[Agent],
+    organisms: &[OrganismRuntime],
+    world_size: f64,
+    grid: usize,
+    occupancy: &mut Vec<u32>,
+) -> (f32, f32) {
+    let cells = grid * grid;
+    occupancy.clear();
+    occupancy.resize(cells, 0);
+    let bin = |coord: f64| ((coord / world_size * grid as f64).floor() as usize).min(grid - 1);
+    let mut total = 0u32;
+    for agent in agents {
+        if !organisms
+            .get(agent.organism_id as usize)
+            .is_some_and(|o| o.alive)
+        {
+            continue;
+        }
+        let [x, y] = agent.position;
+        occupancy[bin(y) * grid + bin(x)] += 1;
+        total += 1;
+    }
+    if total == 0 {
+        return (0.0, 0.0);
+    }
+    let total = total as f64;
+    let mut entropy = 0.0f64;
+    let mut occupied = 0usize;
+    for &count in occupancy.iter().filter(|&&c| c > 0) {
+        let p = count as f64 / total;
+        entropy -= p * p.ln();
+        occupied += 1;
+    }
+    let max_entropy = (cells as f64).ln();
+    let normalized = if max_entropy > 0.0 {
+        entropy / max_entropy
+    } else {
+        0.0
+    };
+    (normalized as f32, occupied as f32 / cells as f32)
+}
+
+fn toroidal_distance(a: [f64; 2], b: [f64; 2], world_size: f64) -> f64 {
+    crate::spatial::toroidal_distance_sq(a, b, world_size).sqrt()
 }
 
-/// Compute mean pairwise agent distance per alive organism (toroidal-aware).
-/// Lower values indicate tighter spatial cohesion.
-fn compute_spatial_cohesion(
+fn toroidal_center(positions: &[[f64; 2]], world_size: f64) -> [f64; 2] {
+    let tau_over_world = std::f64::consts::TAU / world_size;
+    let mut sums = [0.0f64; 4];
+    for p in positions {
+        let (sin_x, cos_x) = (p[0] * tau_over_world).sin_cos();
+        let (sin_y, cos_y) = (p[1] * tau_over_world).sin_cos();
+        sums[0] += sin_x;
+        sums[1] += cos_x;
+        sums[2] += sin_y;
+        sums[3] += cos_y;
+    }
+    let coord = |sum_sin: f64, sum_cos: f64| {
+        (sum_sin.atan2(sum_cos).rem_euclid(std::f64::consts::TAU) / std::f64::consts::TAU)
+            * world_size
+    };
+    [coord(sums[0], sums[1]), coord(sums[2], sums[3])]
+}
+
+/// Compute the per-organism cohesion distance averaged over alive organisms
+/// (toroidal-aware). Lower values indicate tighter spatial cohesion.
+///
+/// See [`CohesionMetricMode`] for the estimators and their bias.
+pub(crate) fn compute_spatial_cohesion(
     agents: &[Agent],
     organisms: &[OrganismRuntime],
     world_size: f64,
+    mode: CohesionMetricMode,
+    max_pairs: usize,
+    step_index: usize,
 ) -> f32 {
-    let half = world_size * 0.5;
-    let mut org_cohesions = Vec::new();
+    let mut positions_by_org: Vec<Vec<[f64; 2]>> = vec![Vec::new(); organisms.len()];
+    for agent in agents {
+        let idx = agent.organism_id as usize;
+        if organisms.get(idx).is_some_and(|o| o.alive) {
+            positions_by_org[idx].push(agent.position);
+        }
+    }
 
-    for org in organisms.iter().filter(|o| o.alive) {
-        let positions: Vec<[f64; 2]> = agents
-            .iter()
-            .filter(|a| a.organism_id == org.id)
-            .map(|a| a.position)
-            .collect();
+    // Deterministic sampling based on step_index for reproducibility
+    let mut sample_rng = ChaCha12Rng::seed_from_u64(step_index as u64);
+    let mut cohesion_sum = 0.0f64;
+    let mut cohesion_count = 0usize;
+
+    for positions in &positions_by_org {
         let n = positions.len();
         if n < 2 {
             continue;
         }
-        let mut dist_sum = 0.0f64;
-        let pairs = n * (n - 1) / 2;
-        for i in 0..n {
-            for j in (i + 1)..n {
-                let mut dx = (positions[i][0] - positions[j][0]).abs();
-                if dx > half {
-                    dx = world_size - dx;
+        let total_pairs = n * (n - 1) / 2;
+        let cohesion = match mode {
+            CohesionMetricMode::Center => {
+                let center = toroidal_center(positions, world_size);
+                positions
+                    .iter()
+                    .map(|&p| toroidal_distance(p, center, world_size))
+                    .sum::<f64>()
+                    / n as f64
+            }
+            CohesionMetricMode::Sampled if total_pairs > max_pairs => {
+                let mut dist_sum = 0.0f64;
+                for _ in 0..max_pairs {
+                    let i = sample_rng.random_range(0..n);
+                    let mut j = sample_rng.random_range(0..n - 1);
+                    if j >= i {
+                        j += 1;
+                    }
+                    dist_sum += toroidal_distance(positions[i], positions[j], world_size);
                 }
-                let mut dy = (positions[i][1] - positions[j][1]).abs();
-                if dy > half {
-                    dy = world_size - dy;
+                dist_sum / max_pairs as f64
+            }
+            CohesionMetricMode::Exact | CohesionMetricMode::Sampled => {
+                let mut dist_sum = 0.0f64;
+                for i in 0..n {
+                    for j in (i + 1)..n {
+                        dist_sum += toroidal_distance(positions[i], positions[j], world_size);
+                    }
                 }
-                dist_sum += (dx * dx + dy * dy).sqrt();
+                dist_sum / total_pairs as f64
             }
-        }
-        org_cohesions.push((dist_sum / pairs as f64) as f32);
+        };
+        cohesion_sum += cohesion;
+        cohesion_count += 1;
     }
 
-    if org_cohesions.is_empty() {
+    if cohesion_count == 0 {
         0.0
     } else {
-        org_cohesions.iter().sum::<f32>() / org_cohesions.len() as f32
+        (cohesion_sum / cohesion_count as f64) as f32
     }
 }
 
@@ -204,125 +1198,485 @@ fn compute_spatial_cohesion(
 pub fn collect_step_metrics(
     step: usize,
     step_index: usize,
+    seed: u64,
+    selection: MetricsSelection,
+    genome_diversity: &GenomeDiversityConfig,
     world_size: f64,
-    resource_total: f64,
+    cohesion_mode: CohesionMetricMode,
+    max_cohesion_pairs: usize,
+    entropy_grid: usize,
+    occupancy: &mut Vec<u32>,
+    resource_channel_totals: &[f64],
+    resource_variance: f64,
+    current_resource_rate: f32,
+    same_organism_neighbor_fraction: Option<f32>,
+    count_dormant: bool,
+    role_count: usize,
+    steps_since_last_shock: Option<usize>,
+    waste_field: Option<(f64, f32)>,
     birth_count: usize,
     death_count: usize,
+    fusion_count: usize,
     exhaustion_events: usize,
+    organism_id_exhaustion_events: usize,
+    reproduction_suppressed_by_capacity: usize,
+    nonfinite_output_events: usize,
+    metabolic_state_violations: usize,
+    conservation_residual: Option<f64>,
     organisms: &[OrganismRuntime],
     agents: &[Agent],
 ) -> StepMetrics {
-    let alive = organisms.iter().filter(|o| o.alive).count();
-    let denom = alive.max(1) as f32;
-
-    let mut energy_sum = 0.0f32;
-    let mut waste_sum = 0.0f32;
-    let mut boundary_sum = 0.0f32;
+    let mut alive = 0usize;
+    let mut energy_stats = RunningStats::default();
+    let mut waste_stats = RunningStats::default();
+    let mut boundary_stats = RunningStats::default();
     let mut generation_sum = 0.0f32;
     let mut drift_sum = 0.0f32;
     let mut age_sum = 0.0f32;
     let mut maturity_sum = 0.0f32;
+    let mut mutation_multiplier_sum = 0.0f32;
     let mut max_gen: usize = 0;
-
-    // Collect values for SD computation
-    let mut energies = Vec::with_capacity(alive);
-    let mut wastes = Vec::with_capacity(alive);
-    let mut boundaries = Vec::with_capacity(alive);
+    let mut dormant = 0usize;
+    let mut flux = MetabolismFlux::default();
+    let mut crowding_excess_sum = 0.0f32;
+    let mut toxin_resistance_sum = 0.0f32;
 
     for org in organisms.iter().filter(|o| o.alive) {
-        energy_sum += org.metabolic_state.energy;
-        waste_sum += org.metabolic_state.waste;
-        boundary_sum += org.boundary_integrity;
+        alive += 1;
+        toxin_resistance_sum += org.homeostasis_program.toxin_resistance;
+        dormant += usize::from(org.dormant);
+        flux.accumulate(&org.last_step_flux);
+        crowding_excess_sum += org.crowding_excess;
+        energy_stats.push(org.metabolic_state.energy);
+        waste_stats.push(org.metabolic_state.waste);
+        boundary_stats.push(org.boundary_integrity);
         generation_sum += org.generation as f32;
         drift_sum += genome_drift(org);
         age_sum += org.age_steps as f32;
         maturity_sum += org.maturity;
+        mutation_multiplier_sum += MutationRateModifiers::decode(org.genome.segment_data(6)).mean();
         max_gen = max_gen.max(org.generation as usize);
-
-        energies.push(org.metabolic_state.energy);
-        wastes.push(org.metabolic_state.waste);
-        boundaries.push(org.boundary_integrity);
     }
+    let denom = alive.max(1) as f32;
 
-    let energy_mean = energy_sum / denom;
-    let waste_mean = waste_sum / denom;
-    let boundary_mean = boundary_sum / denom;
-
-    let std_dev = |vals: &[f32], mean: f32| -> f32 {
-        if vals.len() < 2 {
-            return 0.0;
-        }
-        let var = vals.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / (vals.len() - 1) as f32;
-        var.sqrt()
-    };
-
-    // Internal state: mean and SD across all alive agents (single-pass collection)
-    let alive_states: Vec<[f32; 4]> = agents
-        .iter()
-        .filter(|a| {
+    // Internal state: mean and SD across all alive agents (single streaming pass)
+    let mut internal_state_stats = [RunningStats::default(); 4];
+    let mut role_counts = vec![0usize; role_count];
+    if selection.contains(MetricsSelection::INTERNAL_STATE) {
+        for agent in agents.iter().filter(|a| {
             organisms
                 .get(a.organism_id as usize)
-                .map(|o| o.alive)
-                .unwrap_or(false)
-        })
-        .map(|a| a.internal_state)
-        .collect();
-
-    let is_count = alive_states.len();
-    let is_denom = is_count.max(1) as f32;
-    let mut is_sums = [0.0f32; 4];
-    for state in &alive_states {
-        for (s, &v) in is_sums.iter_mut().zip(state) {
-            *s += v;
-        }
-    }
-    let internal_state_mean = [
-        is_sums[0] / is_denom,
-        is_sums[1] / is_denom,
-        is_sums[2] / is_denom,
-        is_sums[3] / is_denom,
-    ];
-
-    let mut is_var = [0.0f32; 4];
-    if is_count >= 2 {
-        for state in &alive_states {
-            for ((v, &s), &m) in is_var.iter_mut().zip(state).zip(&internal_state_mean) {
-                *v += (s - m).powi(2);
+                .is_some_and(|o| o.alive)
+        }) {
+            for (stats, &v) in internal_state_stats.iter_mut().zip(&agent.internal_state) {
+                stats.push(v);
+            }
+            if let Some(count) = role_counts.get_mut(usize::from(agent.role)) {
+                *count += 1;
             }
         }
-        for v in &mut is_var {
-            *v = (*v / (is_count - 1) as f32).sqrt();
-        }
+    } else {
+        role_counts.clear();
     }
+    let role_total = role_counts.iter().sum::<usize>().max(1) as f32;
+    let internal_state_mean = internal_state_stats.map(|s| s.mean());
+    let internal_state_std = internal_state_stats.map(|s| s.sample_std());
 
     // Genome diversity: mean L2 distance between sampled pairs of alive organism genomes
-    let genome_diversity = compute_genome_diversity(organisms, step_index);
+    let (genome_diversity_mean, genome_diversity_by_segment) =
+        if selection.contains(MetricsSelection::GENOME_DIVERSITY) {
+            let alive_genomes: Vec<&Genome> = organisms
+                .iter()
+                .filter(|o| o.alive)
+                .map(|o| &o.genome)
+                .collect();
+            compute_genome_diversity(&alive_genomes, seed, step_index, genome_diversity)
+        } else {
+            (0.0, None)
+        };
 
-    // Spatial cohesion: mean pairwise agent distance per organism (toroidal-aware)
-    let spatial_cohesion_mean = compute_spatial_cohesion(agents, organisms, world_size);
+    let (spatial_entropy, occupied_cell_fraction, spatial_cohesion_mean) =
+        if selection.contains(MetricsSelection::SPATIAL_COHESION) {
+            let (entropy, occupied) =
+                compute_spatial_entropy(agents, organisms, world_size, entropy_grid, occupancy);
+            // Spatial cohesion: mean agent distance per organism (toroidal-aware)
+            let cohesion = compute_spatial_cohesion(
+                agents,
+                organisms,
+                world_size,
+                cohesion_mode,
+                max_cohesion_pairs,
+                step_index,
+            );
+            (entropy, occupied, cohesion)
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+    let std_devs = selection.contains(MetricsSelection::STD_DEVS);
 
     StepMetrics {
         step,
-        energy_mean,
-        waste_mean,
-        boundary_mean,
+        energy_mean: energy_stats.mean(),
+        waste_mean: waste_stats.mean(),
+        boundary_mean: boundary_stats.mean(),
         alive_count: alive,
-        resource_total,
+        resource_total: resource_channel_totals.iter().sum(),
+        resource_variance,
         birth_count,
         death_count,
+        fusion_count,
         population_size: organisms.len(),
         mean_generation: generation_sum / denom,
         mean_genome_drift: drift_sum / denom,
         agent_id_exhaustion_events: exhaustion_events,
-        energy_std: std_dev(&energies, energy_mean),
-        waste_std: std_dev(&wastes, waste_mean),
-        boundary_std: std_dev(&boundaries, boundary_mean),
+        organism_id_exhaustion_events,
+        reproduction_suppressed_by_capacity,
+        nonfinite_output_events,
+        metabolic_state_violations,
+        conservation_residual,
+        energy_std: if std_devs {
+            energy_stats.sample_std()
+        } else {
+            0.0
+        },
+        waste_std: if std_devs {
+            waste_stats.sample_std()
+        } else {
+            0.0
+        },
+        boundary_std: if std_devs {
+            boundary_stats.sample_std()
+        } else {
+            0.0
+        },
         mean_age: age_sum / denom,
         internal_state_mean,
-        internal_state_std: is_var,
-        genome_diversity,
+        internal_state_std,
+        genome_diversity: genome_diversity_mean,
+        genome_diversity_by_segment,
+        spatial_entropy,
+        occupied_cell_fraction,
         max_generation: max_gen,
         maturity_mean: maturity_sum / denom,
         spatial_cohesion_mean,
+        mean_mutation_rate_multiplier: if alive > 0 {
+            mutation_multiplier_sum / denom
+        } else {
+            0.0
+        },
+        current_resource_rate,
+        same_organism_neighbor_fraction,
+        resource_channel_totals: if resource_channel_totals.len() > 1 {
+            resource_channel_totals.to_vec()
+        } else {
+            Vec::new()
+        },
+        dormant_count: count_dormant.then_some(dormant),
+        waste_field_total: waste_field.map(|(total, _)| total),
+        waste_cross_feeding_uptake: waste_field.map(|(_, uptake)| uptake),
+        mean_toxin_resistance: waste_field.map(|_| toxin_resistance_sum / denom),
+        early_warning: None,
+        role_fractions: role_counts
+            .iter()
+            .map(|&count| count as f32 / role_total)
+            .collect(),
+        steps_since_last_shock,
+        external_uptake_mean: flux.consumed_external / denom,
+        internal_conversion_mean: flux.consumed_total / denom,
+        waste_produced_mean: flux.produced_waste / denom,
+        crowding_excess_mean: crowding_excess_sum / denom,
+        speciation: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1_UNVERSIONED: &str = include_str!("../tests/fixtures/run_summary_v1_unversioned.json");
+    const V1: &str = include_str!("../tests/fixtures/run_summary_v1.json");
+
+    fn load(json: &str) -> Result<RunSummary, SchemaError> {
+        RunSummary::from_json_value(serde_json::from_str(json).expect("fixture must be valid JSON"))
+    }
+
+    #[test]
+    fn unversioned_v1_fixture_migrates_with_documented_defaults() {
+        let summary = load(V1_UNVERSIONED).expect("v1 fixture must keep loading");
+        assert_eq!(summary.schema_version, RunSummary::SCHEMA_VERSION);
+        assert_eq!(summary.migrated_from, Some(1));
+        assert_eq!(summary.steps, 20);
+        assert_eq!(summary.samples.len(), 2);
+        assert_eq!(summary.steps_executed, summary.steps);
+        assert!(!summary.cancelled);
+        assert!(summary.lifespans.is_empty());
+        assert!(summary.lineage_events.is_empty());
+        assert_eq!(summary.total_reproduction_events, 0);
+        assert_eq!(summary.final_state_hash, 0);
+        assert!(!summary.events_truncated);
+        assert_eq!(summary.metabolism_mode, MetabolismMode::Toy);
+    }
+
+    #[test]
+    fn versioned_v1_fixture_keeps_recorded_fields() {
+        let summary = load(V1).expect("v1 fixture must keep loading");
+        assert_eq!(summary.schema_version, RunSummary::SCHEMA_VERSION);
+        assert_eq!(summary.migrated_from, Some(1));
+        assert_eq!(summary.final_state_hash, 11_161_104_417_776_700_033);
+        assert_eq!(summary.total_reproduction_events, 2);
+        assert_eq!(summary.steps_executed, 20);
+        assert_eq!(summary.samples[1].population_size, 4);
+    }
+
+    #[test]
+    fn current_version_round_trips_without_migration() {
+        let mut summary = load(V1).unwrap();
+        summary.migrated_from = None;
+        let value = serde_json::to_value(&summary).unwrap();
+        assert!(value.get("migrated_from").is_none());
+        let reloaded = RunSummary::from_json_value(value).unwrap();
+        assert_eq!(reloaded.migrated_from, None);
+        assert_eq!(reloaded.final_state_hash, summary.final_state_hash);
+    }
+
+    #[test]
+    fn from_json_value_rejects_future_and_invalid_versions() {
+        let with_version = |version: serde_json::Value| {
+            let mut value: serde_json::Value = serde_json::from_str(V1).unwrap();
+            value["schema_version"] = version;
+            RunSummary::from_json_value(value)
+        };
+        assert!(matches!(
+            with_version(3.into()),
+            Err(SchemaError::UnsupportedVersion {
+                found: 3,
+                latest: RunSummary::SCHEMA_VERSION
+            })
+        ));
+        assert!(matches!(
+            with_version(0.into()),
+            Err(SchemaError::InvalidVersion(_))
+        ));
+        assert!(matches!(
+            with_version("2".into()),
+            Err(SchemaError::InvalidVersion(_))
+        ));
+        assert!(matches!(
+            RunSummary::from_json_value(serde_json::json!([1, 2])),
+            Err(SchemaError::NotAnObject)
+        ));
+        assert!(matches!(
+            RunSummary::from_json_value(serde_json::json!({"schema_version": 2})),
+            Err(SchemaError::Deserialize { version: 2, .. })
+        ));
+    }
+
+    /// One NN weight plus the fixed-size regulatory segments, zero except where set.
+    fn diversity_genomes() -> [Genome; 3] {
+        let base = Genome::with_nn_weights(vec![0.0]);
+        let mut b = base.clone();
+        b.set_segment_data(0, &[3.0]);
+        let mut c = b.clone();
+        let mut metabolic = vec![0.0; Genome::METABOLIC_SIZE];
+        metabolic[0] = 3.0;
+        metabolic[1] = 4.0;
+        c.set_segment_data(1, &metabolic);
+        c.set_segment_data(6, &[2.0, 0.0, 0.0, 0.0]);
+        [base, b, c]
+    }
+
+    #[test]
+    fn births_per_generation_counts_children_by_generation() {
+        let event = |generation| LineageEvent {
+            step: 1,
+            parent_stable_id: 0,
+            child_stable_id: 1,
+            generation,
+        };
+        let events = [event(1), event(3), event(1), event(2), event(1)];
+        assert_eq!(births_per_generation(&events), vec![0, 3, 1, 1]);
+        assert!(births_per_generation(&[]).is_empty());
+        let immigrant = LineageEvent {
+            parent_stable_id: LineageEvent::NO_PARENT,
+            ..event(0)
+        };
+        assert!(births_per_generation(&[immigrant]).is_empty());
+    }
+
+    #[test]
+    fn lifespan_summary_defaults_for_old_documents_and_empty_runs() {
+        let summary = load(V1).unwrap();
+        assert_eq!(summary.lifespan_summary, LifespanSummary::default());
+        assert!(summary.births_per_generation.is_empty());
+        assert_eq!(
+            LifespanSummary::from_lifespans(&[]),
+            LifespanSummary::default()
+        );
+        let single = LifespanSummary::from_lifespans(&[7]);
+        assert_eq!((single.min, single.max, single.median), (7, 7, 7.0));
+    }
+
+    #[test]
+    fn species_clustering_chains_links_and_caps_genome_count() {
+        // 0 -- 0.8 -- 1.6 chain into one species; 5.0 stands alone.
+        let points = [[0.0f32], [0.8], [5.0], [1.6]];
+        let genomes: Vec<&[f32]> = points.iter().map(|p| p.as_slice()).collect();
+        let (metrics, labels) = cluster_species(&genomes, 1.0, 1, 0);
+        assert_eq!(labels, [Some(0), Some(0), Some(1), Some(0)]);
+        assert_eq!(metrics.species_count, 2);
+        assert_eq!(metrics.largest_species_fraction, 0.75);
+        assert!((metrics.mean_inter_cluster_distance - (5.0 + 4.2 + 3.4) / 3.0).abs() < 1e-5);
+
+        let many: Vec<[f32; 1]> = (0..SPECIATION_MAX_GENOMES + 50)
+            .map(|i| [i as f32 * 10.0])
+            .collect();
+        let genomes: Vec<&[f32]> = many.iter().map(|p| p.as_slice()).collect();
+        let (metrics, labels) = cluster_species(&genomes, 1.0, 7, 3);
+        assert_eq!(metrics.genomes_clustered, SPECIATION_MAX_GENOMES);
+        assert_eq!(metrics.species_count, SPECIATION_MAX_GENOMES);
+        assert_eq!(labels.iter().flatten().count(), SPECIATION_MAX_GENOMES);
+        assert_eq!(cluster_species(&genomes, 1.0, 7, 3).1, labels);
+    }
+
+    #[test]
+    fn genome_diversity_reports_exact_per_segment_distances() {
+        let genomes = diversity_genomes();
+        let refs: Vec<&Genome> = genomes.iter().collect();
+        let config = GenomeDiversityConfig {
+            per_segment: true,
+            ..GenomeDiversityConfig::default()
+        };
+        let (mean, by_segment) = compute_genome_diversity(&refs, 1, 0, &config);
+        // Pairs (a,b), (a,c), (b,c):
+        // NN:         3, 3, 0          -> 2
+        // metabolic:  0, 5, 5          -> 10/3
+        // evolution:  0, 2, 2          -> 4/3
+        // full:       3, sqrt(38), sqrt(29)
+        let by_segment = by_segment.expect("per-segment diversity requested");
+        let expected = [2.0, 10.0 / 3.0, 0.0, 0.0, 0.0, 0.0, 4.0 / 3.0];
+        for (got, want) in by_segment.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-5, "{by_segment:?}");
+        }
+        let full = (3.0 + 38.0f32.sqrt() + 29.0f32.sqrt()) / 3.0;
+        assert!((mean - full).abs() < 1e-5);
+
+        let (plain, none) =
+            compute_genome_diversity(&refs, 1, 0, &GenomeDiversityConfig::default());
+        assert_eq!(plain, mean);
+        assert!(none.is_none());
+    }
+
+    #[test]
+    fn genome_diversity_sampling_depends_on_seed_and_exact_below_enumerates() {
+        let genomes: Vec<Genome> = (0..12)
+            .map(|i| Genome::with_nn_weights(vec![(i * i) as f32]))
+            .collect();
+        let refs: Vec<&Genome> = genomes.iter().collect();
+        let sampled = GenomeDiversityConfig {
+            max_pairs: 5,
+            ..GenomeDiversityConfig::default()
+        };
+        let (seed_a, _) = compute_genome_diversity(&refs, 1, 10, &sampled);
+        let (seed_a_again, _) = compute_genome_diversity(&refs, 1, 10, &sampled);
+        let (seed_b, _) = compute_genome_diversity(&refs, 2, 10, &sampled);
+        assert_eq!(seed_a, seed_a_again);
+        assert_ne!(seed_a, seed_b);
+
+        let exact = GenomeDiversityConfig {
+            exact_below: 13,
+            ..sampled
+        };
+        let pairs: Vec<f32> = (0..12)
+            .flat_map(|i| ((i + 1)..12).map(move |j| (j * j - i * i) as f32))
+            .collect();
+        let expected = pairs.iter().sum::<f32>() / pairs.len() as f32;
+        let (seed_a_exact, _) = compute_genome_diversity(&refs, 1, 10, &exact);
+        let (seed_b_exact, _) = compute_genome_diversity(&refs, 2, 10, &exact);
+        assert!((seed_a_exact - expected).abs() < 1e-3);
+        assert_eq!(seed_a_exact, seed_b_exact);
+    }
+
+    /// Two-pass reference for the indicators over `window`.
+    fn reference_early_warning(window: &[(f32, usize, f32)]) -> [f64; 3] {
+        let n = window.len() as f64;
+        let energy: Vec<f64> = window.iter().map(|s| s.0 as f64).collect();
+        let alive: Vec<f64> = window.iter().map(|s| s.1 as f64).collect();
+        let boundary: Vec<f64> = window.iter().map(|s| s.2 as f64).collect();
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+
+        let em = mean(&energy);
+        let energy_std = (energy.iter().map(|e| (e - em).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+
+        let am = mean(&alive);
+        let lag: f64 = alive.windows(2).map(|p| (p[0] - am) * (p[1] - am)).sum();
+        let var: f64 = alive.iter().map(|a| (a - am).powi(2)).sum();
+
+        let tm = (n - 1.0) / 2.0;
+        let bm = mean(&boundary);
+        let cov: f64 = (0..window.len())
+            .map(|i| (i as f64 - tm) * (boundary[i] - bm))
+            .sum();
+        let tvar: f64 = (0..window.len()).map(|i| (i as f64 - tm).powi(2)).sum();
+        [energy_std / em, lag / var, cov / tvar]
+    }
+
+    #[test]
+    fn early_warning_window_matches_two_pass_reference_after_wrapping() {
+        let mut rng = ChaCha12Rng::seed_from_u64(9);
+        let history: Vec<(f32, usize, f32)> = (0..40)
+            .map(|_| {
+                (
+                    rng.random_range(0.2f32..0.8),
+                    rng.random_range(10usize..50),
+                    rng.random_range(0.0f32..1.0),
+                )
+            })
+            .collect();
+        let mut window = EarlyWarningWindow::new(7);
+        for (i, &(energy_mean, alive_count, boundary_mean)) in history.iter().enumerate() {
+            window.push(&StepMetrics {
+                energy_mean,
+                alive_count,
+                boundary_mean,
+                ..StepMetrics::default()
+            });
+            let Some(metrics) = window.metrics() else {
+                assert!(i < 6, "window should report once it holds 7 samples");
+                continue;
+            };
+            assert_eq!(metrics.window, 7);
+            let [cv, autocorrelation, trend] = reference_early_warning(&history[i - 6..=i]);
+            assert!((metrics.energy_cv as f64 - cv).abs() < 1e-5, "step {i}");
+            assert!(
+                (metrics.alive_autocorrelation as f64 - autocorrelation).abs() < 1e-5,
+                "step {i}"
+            );
+            assert!(
+                (metrics.boundary_trend as f64 - trend).abs() < 1e-5,
+                "step {i}"
+            );
+        }
+    }
+
+    #[test]
+    fn early_warning_window_handles_flat_series() {
+        let mut window = EarlyWarningWindow::new(3);
+        for _ in 0..5 {
+            window.push(&StepMetrics {
+                energy_mean: 0.0,
+                alive_count: 4,
+                boundary_mean: 0.5,
+                ..StepMetrics::default()
+            });
+        }
+        assert_eq!(
+            window.metrics(),
+            Some(EarlyWarningMetrics {
+                window: 3,
+                energy_cv: 0.0,
+                alive_autocorrelation: 0.0,
+                boundary_trend: 0.0,
+            })
+        );
     }
 }
@@ -1,12 +1,30 @@
-//! Trivial feedforward neural network: 8 inputs → 16 hidden (tanh) → 4 outputs (tanh).
-//! Stack-allocated, no heap. 212 weights total.
+//! Trivial feedforward neural network: 8 inputs → 16 hidden → 4 outputs (tanh).
+//! Stack-allocated, no heap. 212 weights total. The hidden activation and a gain on
+//! the output sums come from [`NnParams`]; the defaults (tanh, gain 1) are the
+//! original network.
 //!
 //! Inputs:  position(2) + velocity(2) + internal_state(3) + neighbor_count(1) = 8
 //! Outputs: velocity_delta(2) + state_delta(2) = 4
+//!
+//! Networks built with `from_weights_with_sensors` also carry the extended sensor
+//! suite: resource gradient(2) + other-organism neighbor count(1) + agent role(1)
+//! inputs with 64 more weights, 276 in total. Under that suite the base neighbor input
+//! counts only the agent's own organism.
+//!
+//! `forward_batch` evaluates many inputs through one network, reading each weight row
+//! once per chunk of inputs; results are bit-identical to per-input `forward`.
+//!
+//! `forward_plastic` runs the network with per-organism Hebbian offsets on the
+//! input→hidden and hidden→output weights and updates those offsets afterwards.
+
+use crate::config::{Activation, SimConfig};
 
 const INPUT_SIZE: usize = 8;
-const HIDDEN_SIZE: usize = 16;
+pub const HIDDEN_SIZE: usize = 16;
 const OUTPUT_SIZE: usize = 4;
+pub const SENSOR_INPUT_SIZE: usize = 4;
+/// Inputs evaluated per pass over the weights in `forward_batch`; bounds its stack scratch.
+const BATCH_CHUNK: usize = 32;
 
 #[derive(Clone, Debug)]
 pub struct NeuralNet {
@@ -16,6 +34,117 @@ pub struct NeuralNet {
     pub b_h: [f32; HIDDEN_SIZE],                 // 16
     pub w_ho: [[f32; OUTPUT_SIZE]; HIDDEN_SIZE], // 16×4
     pub b_o: [f32; OUTPUT_SIZE],                 // 4
+    // Extended sensor suite: sensor→hidden (4×16), all zero unless `sensor_inputs`.
+    pub w_sh: [[f32; HIDDEN_SIZE]; SENSOR_INPUT_SIZE],
+    sensor_inputs: bool,
+}
+
+impl Activation {
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Activation::Tanh => x.tanh(),
+            Activation::Relu => x.max(0.0),
+            Activation::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Activation::Linear => x,
+        }
+    }
+}
+
+/// Evaluation settings shared by every forward pass of an organism's network,
+/// taken from the config rather than the genome.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NnParams {
+    pub hidden_activation: Activation,
+    /// Multiplies each output sum before its tanh.
+    pub output_gain: f32,
+}
+
+impl Default for NnParams {
+    fn default() -> Self {
+        Self {
+            hidden_activation: Activation::Tanh,
+            output_gain: 1.0,
+        }
+    }
+}
+
+impl NnParams {
+    pub fn from_config(config: &SimConfig) -> Self {
+        Self {
+            hidden_activation: config.nn_hidden_activation,
+            output_gain: config.nn_output_gain,
+        }
+    }
+
+    fn hidden(&self, h: &mut f32) {
+        *h = self.hidden_activation.apply(*h);
+    }
+
+    fn output(&self, o: &mut f32) {
+        *o = (*o * self.output_gain).tanh();
+    }
+}
+
+/// Parameters of the Hebbian rule applied by `NeuralNet::forward_plastic`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HebbianRule {
+    /// η: offset change per unit of pre·post activity.
+    pub learning_rate: f32,
+    /// Fraction of each offset lost per update, pulling weights back toward the genome.
+    pub decay: f32,
+    /// Offsets are clamped to `[-max_delta, max_delta]`.
+    pub max_delta: f32,
+}
+
+/// Within-lifetime weight offsets added to a network's genome-decoded weights.
+/// Belongs to one organism and is never written back to the genome.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HebbianTrace {
+    pub d_ih: [[f32; HIDDEN_SIZE]; INPUT_SIZE],
+    pub d_ho: [[f32; OUTPUT_SIZE]; HIDDEN_SIZE],
+}
+
+impl Default for HebbianTrace {
+    fn default() -> Self {
+        Self {
+            d_ih: [[0.0; HIDDEN_SIZE]; INPUT_SIZE],
+            d_ho: [[0.0; OUTPUT_SIZE]; HIDDEN_SIZE],
+        }
+    }
+}
+
+impl HebbianTrace {
+    /// `d ← clamp((1 - decay)·d + η·pre·post)` for every plastic connection.
+    fn update(
+        &mut self,
+        input: &[f32; INPUT_SIZE],
+        hidden: &[f32; HIDDEN_SIZE],
+        output: &[f32; OUTPUT_SIZE],
+        rule: &HebbianRule,
+    ) {
+        let keep = 1.0 - rule.decay;
+        let step = |d: &mut f32, pre: f32, post: f32| {
+            *d = (*d * keep + rule.learning_rate * pre * post)
+                .clamp(-rule.max_delta, rule.max_delta);
+        };
+        for (row, &pre) in self.d_ih.iter_mut().zip(input) {
+            for (d, &post) in row.iter_mut().zip(hidden) {
+                step(d, pre, post);
+            }
+        }
+        for (row, &pre) in self.d_ho.iter_mut().zip(hidden) {
+            for (d, &post) in row.iter_mut().zip(output) {
+                step(d, pre, post);
+            }
+        }
+    }
+
+    /// Sum of absolute offsets.
+    pub fn magnitude(&self) -> f32 {
+        let ih: f32 = self.d_ih.iter().flatten().map(|d| d.abs()).sum();
+        let ho: f32 = self.d_ho.iter().flatten().map(|d| d.abs()).sum();
+        ih + ho
+    }
 }
 
 impl NeuralNet {
@@ -56,33 +185,253 @@ impl NeuralNet {
             b_h,
             w_ho,
             b_o,
+            w_sh: [[0.0; HIDDEN_SIZE]; SENSOR_INPUT_SIZE],
+            sensor_inputs: false,
+        }
+    }
+
+    /// Create a NN with the extended sensor suite: `WEIGHT_COUNT` base weights in
+    /// `from_weights` order followed by `SENSOR_WEIGHT_COUNT` sensor weights.
+    /// Panics if fewer than `EXTENDED_WEIGHT_COUNT` values.
+    pub fn from_weights_with_sensors(mut weights: impl Iterator<Item = f32>) -> Self {
+        let mut nn = Self::from_weights(weights.by_ref());
+        for row in &mut nn.w_sh {
+            for w in row.iter_mut() {
+                *w = weights
+                    .next()
+                    .expect("insufficient weights: need EXTENDED_WEIGHT_COUNT (276) elements");
+            }
         }
+        nn.sensor_inputs = true;
+        nn
+    }
+
+    /// Inverse of [`NeuralNet::to_weight_vec`]: a base network from `WEIGHT_COUNT`
+    /// weights or a sensor network from `EXTENDED_WEIGHT_COUNT`, `None` for any
+    /// other length.
+    pub fn from_weight_vec(weights: &[f32]) -> Option<Self> {
+        let values = weights.iter().copied();
+        match weights.len() {
+            Self::WEIGHT_COUNT => Some(Self::from_weights(values)),
+            Self::EXTENDED_WEIGHT_COUNT => Some(Self::from_weights_with_sensors(values)),
+            _ => None,
+        }
+    }
+
+    /// Enable the extended sensor suite, keeping existing sensor weights (zero for a
+    /// network built with `from_weights`).
+    pub fn with_sensor_inputs(mut self) -> Self {
+        self.sensor_inputs = true;
+        self
+    }
+
+    pub fn has_sensor_inputs(&self) -> bool {
+        self.sensor_inputs
     }
 
     /// Forward pass. Returns [vel_dx, vel_dy, state_d0, state_d1].
-    pub fn forward(&self, input: &[f32; INPUT_SIZE]) -> [f32; OUTPUT_SIZE] {
-        // Hidden layer
+    pub fn forward(&self, input: &[f32; INPUT_SIZE], params: &NnParams) -> [f32; OUTPUT_SIZE] {
+        self.forward_with_hidden(input, params).0
+    }
+
+    /// [`NeuralNet::forward`] that also returns the hidden-layer activations.
+    pub fn forward_with_hidden(
+        &self,
+        input: &[f32; INPUT_SIZE],
+        params: &NnParams,
+    ) -> ([f32; OUTPUT_SIZE], [f32; HIDDEN_SIZE]) {
+        let hidden = self.hidden_activations(input, None, params);
+        (self.output_layer(&hidden, params), hidden)
+    }
+
+    /// Forward pass with the extended sensor inputs added to the hidden layer.
+    pub fn forward_with_sensors(
+        &self,
+        input: &[f32; INPUT_SIZE],
+        sensors: &[f32; SENSOR_INPUT_SIZE],
+        params: &NnParams,
+    ) -> [f32; OUTPUT_SIZE] {
+        let hidden = self.hidden_activations(input, Some(sensors), params);
+        self.output_layer(&hidden, params)
+    }
+
+    /// Activated hidden layer for `input`, plus `sensors` when given.
+    pub(crate) fn hidden_activations(
+        &self,
+        input: &[f32; INPUT_SIZE],
+        sensors: Option<&[f32; SENSOR_INPUT_SIZE]>,
+        params: &NnParams,
+    ) -> [f32; HIDDEN_SIZE] {
+        let mut hidden = self.hidden_pre_activation(input);
+        if let Some(sensors) = sensors {
+            for (i, &x) in sensors.iter().enumerate() {
+                for (j, h) in hidden.iter_mut().enumerate() {
+                    *h += x * self.w_sh[i][j];
+                }
+            }
+        }
+        for h in &mut hidden {
+            params.hidden(h);
+        }
+        hidden
+    }
+
+    /// Batched [`NeuralNet::forward`], appending one output per input to `outputs`.
+    ///
+    /// Loops over weight rows outside and inputs inside, accumulating every sum in the
+    /// same order as `forward`, so each output is bit-identical to the per-input pass.
+    pub fn forward_batch(
+        &self,
+        inputs: &[[f32; INPUT_SIZE]],
+        params: &NnParams,
+        outputs: &mut Vec<[f32; OUTPUT_SIZE]>,
+    ) {
+        self.forward_batch_impl(inputs, None, params, outputs);
+    }
+
+    /// Batched [`NeuralNet::forward_with_sensors`]; `sensors[k]` pairs with `inputs[k]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sensors.len() != inputs.len()`.
+    pub fn forward_batch_with_sensors(
+        &self,
+        inputs: &[[f32; INPUT_SIZE]],
+        sensors: &[[f32; SENSOR_INPUT_SIZE]],
+        params: &NnParams,
+        outputs: &mut Vec<[f32; OUTPUT_SIZE]>,
+    ) {
+        assert_eq!(inputs.len(), sensors.len(), "one sensor reading per input");
+        self.forward_batch_impl(inputs, Some(sensors), params, outputs);
+    }
+
+    fn forward_batch_impl(
+        &self,
+        inputs: &[[f32; INPUT_SIZE]],
+        sensors: Option<&[[f32; SENSOR_INPUT_SIZE]]>,
+        params: &NnParams,
+        outputs: &mut Vec<[f32; OUTPUT_SIZE]>,
+    ) {
+        outputs.reserve(inputs.len());
+        for (chunk_idx, chunk) in inputs.chunks(BATCH_CHUNK).enumerate() {
+            let n = chunk.len();
+            let mut hidden = [self.b_h; BATCH_CHUNK];
+            let hidden = &mut hidden[..n];
+            for (i, row) in self.w_ih.iter().enumerate() {
+                for (h, input) in hidden.iter_mut().zip(chunk) {
+                    let x = input[i];
+                    for (hj, &w) in h.iter_mut().zip(row) {
+                        *hj += x * w;
+                    }
+                }
+            }
+            if let Some(sensors) = sensors {
+                let chunk_sensors = &sensors[chunk_idx * BATCH_CHUNK..][..n];
+                for (i, row) in self.w_sh.iter().enumerate() {
+                    for (h, sensor) in hidden.iter_mut().zip(chunk_sensors) {
+                        let x = sensor[i];
+                        for (hj, &w) in h.iter_mut().zip(row) {
+                            *hj += x * w;
+                        }
+                    }
+                }
+            }
+            for h in hidden.iter_mut().flatten() {
+                params.hidden(h);
+            }
+
+            let mut output = [self.b_o; BATCH_CHUNK];
+            let output = &mut output[..n];
+            for (i, row) in self.w_ho.iter().enumerate() {
+                for (o, h) in output.iter_mut().zip(hidden.iter()) {
+                    let x = h[i];
+                    for (oj, &w) in o.iter_mut().zip(row) {
+                        *oj += x * w;
+                    }
+                }
+            }
+            for o in output.iter_mut().flatten() {
+                params.output(o);
+            }
+            outputs.extend_from_slice(output);
+        }
+    }
+
+    /// Forward pass with `trace` offsets added to the plastic weights, followed by a
+    /// Hebbian update of `trace` from this pass's activations. `sensors` feed the
+    /// extended sensor weights, which are not plastic.
+    pub fn forward_plastic(
+        &self,
+        input: &[f32; INPUT_SIZE],
+        sensors: Option<&[f32; SENSOR_INPUT_SIZE]>,
+        trace: &mut HebbianTrace,
+        rule: &HebbianRule,
+        params: &NnParams,
+    ) -> [f32; OUTPUT_SIZE] {
         let mut hidden = self.b_h;
         for (i, &x) in input.iter().enumerate() {
             for (j, h) in hidden.iter_mut().enumerate() {
-                *h += x * self.w_ih[i][j];
+                *h += x * (self.w_ih[i][j] + trace.d_ih[i][j]);
+            }
+        }
+        if let Some(sensors) = sensors {
+            for (i, &x) in sensors.iter().enumerate() {
+                for (j, h) in hidden.iter_mut().enumerate() {
+                    *h += x * self.w_sh[i][j];
+                }
             }
         }
-        // tanh activation
         for h in &mut hidden {
-            *h = h.tanh();
+            params.hidden(h);
+        }
+        let mut output = self.b_o;
+        for (i, &h) in hidden.iter().enumerate() {
+            for (j, o) in output.iter_mut().enumerate() {
+                *o += h * (self.w_ho[i][j] + trace.d_ho[i][j]);
+            }
+        }
+        for o in &mut output {
+            params.output(o);
+        }
+        trace.update(input, &hidden, &output, rule);
+        output
+    }
+
+    /// Copy of this network with `trace` folded into its weights.
+    pub fn with_trace(&self, trace: &HebbianTrace) -> Self {
+        let mut nn = self.clone();
+        for (row, d_row) in nn.w_ih.iter_mut().zip(&trace.d_ih) {
+            for (w, d) in row.iter_mut().zip(d_row) {
+                *w += d;
+            }
+        }
+        for (row, d_row) in nn.w_ho.iter_mut().zip(&trace.d_ho) {
+            for (w, d) in row.iter_mut().zip(d_row) {
+                *w += d;
+            }
+        }
+        nn
+    }
+
+    fn hidden_pre_activation(&self, input: &[f32; INPUT_SIZE]) -> [f32; HIDDEN_SIZE] {
+        let mut hidden = self.b_h;
+        for (i, &x) in input.iter().enumerate() {
+            for (j, h) in hidden.iter_mut().enumerate() {
+                *h += x * self.w_ih[i][j];
+            }
         }
+        hidden
+    }
 
-        // Output layer
+    fn output_layer(&self, hidden: &[f32; HIDDEN_SIZE], params: &NnParams) -> [f32; OUTPUT_SIZE] {
         let mut output = self.b_o;
         for (i, &h) in hidden.iter().enumerate() {
             for (j, o) in output.iter_mut().enumerate() {
                 *o += h * self.w_ho[i][j];
             }
         }
-        // tanh activation
         for o in &mut output {
-            *o = o.tanh();
+            params.output(o);
         }
 
         output
@@ -99,11 +448,20 @@ impl NeuralNet {
             out.extend_from_slice(row);
         }
         out.extend_from_slice(&self.b_o);
+        if self.sensor_inputs {
+            for row in &self.w_sh {
+                out.extend_from_slice(row);
+            }
+        }
         out
     }
 
     pub const WEIGHT_COUNT: usize =
         INPUT_SIZE * HIDDEN_SIZE + HIDDEN_SIZE + HIDDEN_SIZE * OUTPUT_SIZE + OUTPUT_SIZE;
+
+    pub const SENSOR_WEIGHT_COUNT: usize = SENSOR_INPUT_SIZE * HIDDEN_SIZE;
+
+    pub const EXTENDED_WEIGHT_COUNT: usize = Self::WEIGHT_COUNT + Self::SENSOR_WEIGHT_COUNT;
 }
 
 #[cfg(test)]
@@ -121,7 +479,7 @@ mod tests {
     fn forward_output_bounded_by_tanh() {
         let nn = NeuralNet::from_weights((0..NeuralNet::WEIGHT_COUNT).map(|i| (i as f32) * 0.01));
         let input = [1.0f32; INPUT_SIZE];
-        let output = nn.forward(&input);
+        let output = nn.forward(&input, &NnParams::default());
         for &o in &output {
             assert!((-1.0..=1.0).contains(&o), "output {o} outside tanh range");
         }
@@ -131,12 +489,50 @@ mod tests {
     fn zero_weights_produce_zero_output() {
         let nn = NeuralNet::from_weights(std::iter::repeat_n(0.0f32, NeuralNet::WEIGHT_COUNT));
         let input = [1.0f32; INPUT_SIZE];
-        let output = nn.forward(&input);
+        let output = nn.forward(&input, &NnParams::default());
         for &o in &output {
             assert!((o.abs()) < 1e-7, "expected ~0 with zero weights, got {o}");
         }
     }
 
+    #[test]
+    fn hidden_activations_and_output_gain_match_hand_computed_values() {
+        // Hidden pre-activations 0.5 and -1.0, both feeding output 0 with weight 1.
+        let mut nn = NeuralNet::from_weights(std::iter::repeat_n(0.0f32, NeuralNet::WEIGHT_COUNT));
+        nn.w_ih[0][0] = 2.0;
+        nn.b_h[0] = -0.5;
+        nn.b_h[1] = -1.0;
+        nn.w_ho[0][0] = 1.0;
+        nn.w_ho[1][0] = 1.0;
+        let mut input = [0.0f32; INPUT_SIZE];
+        input[0] = 0.5;
+        let cases = [
+            (Activation::Tanh, 1.0, -0.290_833_9), // tanh(tanh(0.5) + tanh(-1))
+            (Activation::Relu, 1.0, 0.462_117_2),  // tanh(0.5 + 0)
+            (Activation::Sigmoid, 1.0, 0.712_085), // tanh(σ(0.5) + σ(-1))
+            (Activation::Linear, 1.0, -0.462_117_2), // tanh(0.5 - 1)
+            (Activation::Linear, 2.0, -0.761_594_2), // tanh(2 × (0.5 - 1))
+        ];
+        for (hidden_activation, output_gain, expected) in cases {
+            let params = NnParams {
+                hidden_activation,
+                output_gain,
+            };
+            let output = nn.forward(&input, &params);
+            assert!(
+                (output[0] - expected).abs() < 1e-6,
+                "{hidden_activation:?} gain {output_gain}: {} vs {expected}",
+                output[0]
+            );
+            assert_eq!(output[1..], [0.0; 3]);
+            let (with_hidden, hidden) = nn.forward_with_hidden(&input, &params);
+            assert_eq!(with_hidden, output);
+            assert_eq!(hidden[0], hidden_activation.apply(0.5));
+            assert_eq!(hidden[1], hidden_activation.apply(-1.0));
+            assert_eq!(hidden[2..], [hidden_activation.apply(0.0); HIDDEN_SIZE - 2]);
+        }
+    }
+
     #[test]
     #[should_panic(expected = "insufficient weights")]
     fn from_weights_panics_on_short_iterator() {
@@ -148,7 +544,144 @@ mod tests {
         let nn = NeuralNet::from_weights((0..NeuralNet::WEIGHT_COUNT).map(|i| i as f32 * 0.01));
         let round_trip = NeuralNet::from_weights(nn.to_weight_vec().into_iter());
         let input = [0.25f32; INPUT_SIZE];
-        assert_eq!(nn.forward(&input), round_trip.forward(&input));
+        assert_eq!(
+            nn.forward(&input, &NnParams::default()),
+            round_trip.forward(&input, &NnParams::default())
+        );
+    }
+
+    #[test]
+    fn from_weight_vec_picks_layout_by_length() {
+        let base = vec![0.1f32; NeuralNet::WEIGHT_COUNT];
+        let extended = vec![0.1f32; NeuralNet::EXTENDED_WEIGHT_COUNT];
+        assert!(!NeuralNet::from_weight_vec(&base)
+            .unwrap()
+            .has_sensor_inputs());
+        assert!(NeuralNet::from_weight_vec(&extended)
+            .unwrap()
+            .has_sensor_inputs());
+        assert!(NeuralNet::from_weight_vec(&base[1..]).is_none());
+        assert!(NeuralNet::from_weight_vec(&[]).is_none());
+    }
+
+    #[test]
+    fn sensor_weights_round_trip_and_affect_output() {
+        let weights = (0..NeuralNet::EXTENDED_WEIGHT_COUNT).map(|i| (i % 7) as f32 * 0.05);
+        let nn = NeuralNet::from_weights_with_sensors(weights);
+        assert!(nn.has_sensor_inputs());
+        let flat = nn.to_weight_vec();
+        assert_eq!(flat.len(), NeuralNet::EXTENDED_WEIGHT_COUNT);
+        let round_trip = NeuralNet::from_weights_with_sensors(flat.into_iter());
+        let input = [0.25f32; INPUT_SIZE];
+        let sensors = [0.5, -0.5, 0.25, 1.0];
+        assert_eq!(
+            nn.forward_with_sensors(&input, &sensors, &NnParams::default()),
+            round_trip.forward_with_sensors(&input, &sensors, &NnParams::default())
+        );
+        assert_ne!(
+            nn.forward_with_sensors(&input, &sensors, &NnParams::default()),
+            nn.forward(&input, &NnParams::default())
+        );
+        assert_eq!(
+            nn.forward_with_sensors(&input, &[0.0; SENSOR_INPUT_SIZE], &NnParams::default()),
+            nn.forward(&input, &NnParams::default())
+        );
+    }
+
+    #[test]
+    fn base_network_excludes_sensor_weights() {
+        let nn = NeuralNet::from_weights(std::iter::repeat_n(0.1f32, NeuralNet::WEIGHT_COUNT));
+        assert!(!nn.has_sensor_inputs());
+        assert_eq!(nn.to_weight_vec().len(), NeuralNet::WEIGHT_COUNT);
+        let enabled = nn.with_sensor_inputs();
+        assert_eq!(
+            enabled.to_weight_vec().len(),
+            NeuralNet::EXTENDED_WEIGHT_COUNT
+        );
+    }
+
+    #[test]
+    fn plastic_forward_matches_plain_forward_until_trace_grows() {
+        let nn = NeuralNet::from_weights((0..NeuralNet::WEIGHT_COUNT).map(|i| (i as f32).sin()));
+        let input = [0.5f32; INPUT_SIZE];
+        let rule = HebbianRule {
+            learning_rate: 0.1,
+            decay: 0.0,
+            max_delta: 0.05,
+        };
+        let mut trace = HebbianTrace::default();
+        assert_eq!(
+            nn.forward_plastic(&input, None, &mut trace, &rule, &NnParams::default()),
+            nn.forward(&input, &NnParams::default())
+        );
+        assert!(trace.magnitude() > 0.0);
+        assert!(trace.d_ih.iter().flatten().all(|d| d.abs() <= 0.05));
+        // The second pass runs on the updated weights.
+        let plastic = nn.forward_plastic(&input, None, &mut trace, &rule, &NnParams::default());
+        let folded = nn.with_trace(&HebbianTrace::default());
+        assert_eq!(folded.to_weight_vec(), nn.to_weight_vec());
+        assert_ne!(plastic, nn.forward(&input, &NnParams::default()));
+    }
+
+    #[test]
+    fn forward_batch_is_bit_identical_to_forward() {
+        let nn = NeuralNet::from_weights_with_sensors(
+            (0..NeuralNet::EXTENDED_WEIGHT_COUNT).map(|i| ((i * 37 % 101) as f32 - 50.0) / 40.0),
+        );
+        // Spans several chunks, with a partial last one.
+        let inputs: Vec<[f32; INPUT_SIZE]> = (0..BATCH_CHUNK * 2 + 5)
+            .map(|k| std::array::from_fn(|i| ((k * 13 + i * 7) % 19) as f32 / 9.0 - 1.0))
+            .collect();
+        let sensors: Vec<[f32; SENSOR_INPUT_SIZE]> = (0..inputs.len())
+            .map(|k| {
+                [
+                    (k % 5) as f32 - 2.0,
+                    (k % 3) as f32 * 0.5,
+                    (k % 4) as f32 * 0.25,
+                    (k % 2) as f32,
+                ]
+            })
+            .collect();
+
+        let mut outputs = vec![[9.0; OUTPUT_SIZE]];
+        nn.forward_batch(&inputs, &NnParams::default(), &mut outputs);
+        assert_eq!(outputs.len(), inputs.len() + 1, "outputs are appended");
+        for (input, out) in inputs.iter().zip(&outputs[1..]) {
+            assert_eq!(
+                out.map(f32::to_bits),
+                nn.forward(input, &NnParams::default()).map(f32::to_bits)
+            );
+        }
+
+        outputs.clear();
+        nn.forward_batch_with_sensors(&inputs, &sensors, &NnParams::default(), &mut outputs);
+        for ((input, sensor), out) in inputs.iter().zip(&sensors).zip(&outputs) {
+            assert_eq!(
+                out.map(f32::to_bits),
+                nn.forward_with_sensors(input, sensor, &NnParams::default())
+                    .map(f32::to_bits)
+            );
+        }
+    }
+
+    #[test]
+    fn hebbian_decay_returns_trace_toward_zero() {
+        let nn = NeuralNet::from_weights(std::iter::repeat_n(0.0f32, NeuralNet::WEIGHT_COUNT));
+        let mut trace = HebbianTrace::default();
+        trace.d_ih[0][0] = 0.4;
+        let rule = HebbianRule {
+            learning_rate: 0.0,
+            decay: 0.5,
+            max_delta: 1.0,
+        };
+        let _ = nn.forward_plastic(
+            &[0.0; INPUT_SIZE],
+            None,
+            &mut trace,
+            &rule,
+            &NnParams::default(),
+        );
+        assert!((trace.d_ih[0][0] - 0.2).abs() < 1e-6);
     }
 
     proptest! {
@@ -159,7 +692,7 @@ mod tests {
         ) {
             let nn = NeuralNet::from_weights(weights.into_iter());
             let input: [f32; INPUT_SIZE] = inputs.try_into().expect("input size should match");
-            let output = nn.forward(&input);
+            let output = nn.forward(&input, &NnParams::default());
             prop_assert!(output.iter().all(|o| o.is_finite() && *o >= -1.0 && *o <= 1.0));
         }
     }
@@ -1,6 +1,7 @@
-use crate::genome::Genome;
-use crate::metabolism::{MetabolicState, MetabolismEngine};
-use crate::nn::NeuralNet;
+use crate::genome::{Genome, GenomeLayout};
+use crate::metabolism::{MetabolicState, MetabolismEngine, MetabolismFlux};
+use crate::nn::{HebbianTrace, NeuralNet, NnParams};
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug)]
 pub struct Organism {
@@ -54,11 +55,49 @@ impl Organism {
     }
 }
 
-/// Decoded developmental program from genome segment 3 (7 active floats of 8).
+/// Agent role archetype decoded from [`Genome::ROLE_ARCHETYPE_SIZE`] genes that
+/// follow the 8 developmental floats of segment 3.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoleArchetype {
+    /// g[0]: e^(g.clamp(-2,2)) — relative share of an organism's agents.
+    pub weight: f32,
+    /// g[1]: 2^(g.clamp(-1,1)) → [0.5, 2.0] — sensing radius multiplier.
+    pub sensing_multiplier: f32,
+    /// g[2]: 2^(g.clamp(-2,0)) → [0.25, 1.0] — share of the occupied resource cell
+    /// the agent feeds on (per-agent uptake only).
+    pub uptake_share: f32,
+    /// g[3]: 2^(g.clamp(-1,1)) → [0.5, 2.0] — gain on the agent's NN thrust.
+    pub movement_gain: f32,
+}
+
+impl RoleArchetype {
+    /// Modifiers of an agent without a role: every multiplier is 1.
+    pub const NEUTRAL: Self = Self {
+        weight: 1.0,
+        sensing_multiplier: 1.0,
+        uptake_share: 1.0,
+        movement_gain: 1.0,
+    };
+
+    pub fn decode(genes: &[f32]) -> Self {
+        Self {
+            weight: genes[0].clamp(-2.0, 2.0).exp(),
+            sensing_multiplier: 2.0f32.powf(genes[1].clamp(-1.0, 1.0)),
+            uptake_share: 2.0f32.powf(genes[2].clamp(-2.0, 0.0)),
+            movement_gain: 2.0f32.powf(genes[3].clamp(-1.0, 1.0)),
+        }
+    }
+}
+
+/// Decoded developmental program from genome segment 3 (8 floats).
 ///
 /// Encodes a 3-stage (juvenile → adolescent → adult) developmental trajectory
-/// that affects boundary repair, sensing radius, and metabolic efficiency.
-/// The 8th float in the segment is reserved for future use.
+/// that affects boundary repair, sensing radius, and metabolic efficiency, and
+/// under `enable_maturity_gating` movement speed and neighbor sensing. The 8th
+/// float sets the pace of senescence when it is genome-encoded. In layouts with
+/// `maturity_gating_genes`, the next [`Genome::MATURITY_GATING_SIZE`] floats encode
+/// the movement and neighbor-sensing curves; any further floats encode
+/// [`RoleArchetype`]s.
 #[derive(Clone, Debug)]
 pub struct DevelopmentalProgram {
     /// g[0]: 2^(g.clamp(-2,2)) → [0.25, 4.0] — maturation speed modifier.
@@ -75,6 +114,22 @@ pub struct DevelopmentalProgram {
     pub adolescent_sensing: f32,
     /// g[6]: sigmoid → [0.1, 0.5] — juvenile metabolic efficiency factor.
     pub juvenile_metabolic_efficiency: f32,
+    /// g[7]: 2^(g.clamp(-2,2)) → [0.25, 4.0] — aging pace; divides senescence onset
+    /// and multiplies its rate when `senescence_evolvable` is set.
+    pub aging_rate_modifier: f32,
+    /// g[8]: sigmoid → [0.3, 0.7] — juvenile movement speed factor. 0.5 without
+    /// maturity gating genes.
+    pub juvenile_movement: f32,
+    /// g[9]: sigmoid → [0.5, 1.0] — juvenile neighbor input factor. 0.75 without
+    /// maturity gating genes.
+    pub juvenile_neighbor_sensing: f32,
+    /// g[10]: sigmoid(g[3] + g) → [0.3, 0.7] — maturity at which movement and
+    /// neighbor sensing leave the juvenile stage. `adolescent_threshold` without
+    /// maturity gating genes.
+    pub maturity_gating_threshold: f32,
+    /// Remaining genes: agent role archetypes, empty unless `agent_role_count` is
+    /// set.
+    pub roles: Vec<RoleArchetype>,
 }
 
 impl DevelopmentalProgram {
@@ -88,60 +143,271 @@ impl DevelopmentalProgram {
         lo + sig * (hi - lo)
     }
 
-    /// Decode genome segment 3 (7 active of 8 floats) into developmental parameters.
+    /// Decode genome segment 3 of a default-layout genome, which has no maturity
+    /// gating genes; see [`DevelopmentalProgram::decode_in_layout`].
     pub fn decode(segment: &[f32]) -> Self {
-        assert!(segment.len() >= 7, "developmental segment needs ≥7 floats");
+        Self::decode_in_layout(segment, &GenomeLayout::default())
+    }
+
+    /// Decode genome segment 3 of a genome in `layout` into developmental
+    /// parameters. Genes missing from a segment shortened by structural mutation
+    /// read as zero, so the aging pace (g[7]) defaults to 1.0 for 7-float segments.
+    /// Without `maturity_gating_genes` the gating genes read as zero too.
+    pub fn decode_in_layout(segment: &[f32], layout: &GenomeLayout) -> Self {
         let s = Self::sigmoid;
         let m = Self::map_range;
+        let g = |i: usize| segment.get(i).copied().unwrap_or(0.0);
+        let gating_genes = if layout.maturity_gating_genes {
+            Genome::MATURITY_GATING_SIZE
+        } else {
+            0
+        };
+        let gating = |i: usize| {
+            if i < gating_genes {
+                g(Genome::DEVELOPMENTAL_SIZE + i)
+            } else {
+                0.0
+            }
+        };
         Self {
-            maturation_rate_modifier: 2.0f32.powf(segment[0].clamp(-2.0, 2.0)),
-            juvenile_boundary_repair: m(s(segment[1]), 0.2, 1.0),
-            juvenile_sensing: m(s(segment[2]), 0.3, 1.0),
-            adolescent_threshold: m(s(segment[3]), 0.3, 0.7),
-            adolescent_boundary_repair: m(s(segment[4]), 0.5, 1.0),
-            adolescent_sensing: m(s(segment[5]), 0.5, 1.0),
-            juvenile_metabolic_efficiency: m(s(segment[6]), 0.1, 0.5),
+            maturation_rate_modifier: 2.0f32.powf(g(0).clamp(-2.0, 2.0)),
+            juvenile_boundary_repair: m(s(g(1)), 0.2, 1.0),
+            juvenile_sensing: m(s(g(2)), 0.3, 1.0),
+            adolescent_threshold: m(s(g(3)), 0.3, 0.7),
+            adolescent_boundary_repair: m(s(g(4)), 0.5, 1.0),
+            adolescent_sensing: m(s(g(5)), 0.5, 1.0),
+            juvenile_metabolic_efficiency: m(s(g(6)), 0.1, 0.5),
+            aging_rate_modifier: 2.0f32.powf(g(7).clamp(-2.0, 2.0)),
+            juvenile_movement: m(s(gating(0)), 0.3, 0.7),
+            juvenile_neighbor_sensing: m(s(gating(1)), 0.5, 1.0),
+            maturity_gating_threshold: m(s(g(3) + gating(2)), 0.3, 0.7),
+            roles: segment
+                .get(Genome::DEVELOPMENTAL_SIZE + gating_genes..)
+                .unwrap_or_default()
+                .chunks_exact(Genome::ROLE_ARCHETYPE_SIZE)
+                .map(RoleArchetype::decode)
+                .collect(),
         }
     }
 
-    /// Returns (boundary_repair_factor, sensing_factor, metabolic_factor) for the
-    /// current maturity level. Adults (maturity ≥ 1.0) always return (1.0, 1.0, 1.0).
+    /// Role of agent `index` among an organism's `count` agents: roles take
+    /// contiguous blocks of agents in proportion to their weights. Always 0
+    /// without roles.
+    pub fn role_for_index(&self, index: usize, count: usize) -> u8 {
+        let total: f32 = self.roles.iter().map(|r| r.weight).sum();
+        let target = (index as f32 + 0.5) / count.max(1) as f32 * total;
+        let mut cumulative = 0.0;
+        for (role, archetype) in self.roles.iter().enumerate() {
+            cumulative += archetype.weight;
+            if target < cumulative {
+                return role as u8;
+            }
+        }
+        self.roles.len().saturating_sub(1) as u8
+    }
+
+    /// Modifiers of `role`; neutral when the organism has no such role.
+    pub fn role(&self, role: u8) -> &RoleArchetype {
+        self.roles
+            .get(usize::from(role))
+            .unwrap_or(&RoleArchetype::NEUTRAL)
+    }
+
+    /// NN role input: the role index scaled to [0, 1], or 0 without roles.
+    pub fn role_signal(&self, role: u8) -> f32 {
+        match self.roles.len() {
+            0 | 1 => 0.0,
+            n => f32::from(role) / (n - 1) as f32,
+        }
+    }
+
+    /// Stage factors for the current maturity level. Adults (maturity ≥ 1.0) always
+    /// get 1.0 for every factor. Movement and neighbor sensing change stage at
+    /// `maturity_gating_threshold`, the others at `adolescent_threshold`.
     ///
     /// Stage transitions may be discontinuous depending on genome-encoded parameters.
     /// With default (zero) genomes, adolescent factors are higher than juvenile,
     /// ensuring monotonic improvement. Evolved genomes may produce non-monotonic
     /// transitions, which is by design — natural development can have trade-offs.
-    pub fn stage_factors(&self, maturity: f32) -> (f32, f32, f32) {
+    pub fn stage_factors(&self, maturity: f32) -> StageFactors {
         if maturity >= 1.0 {
-            return (1.0, 1.0, 1.0);
+            return StageFactors::ADULT;
         }
-        if maturity < self.adolescent_threshold {
+        let gated = |juvenile: f32| match Self::adolescent_progress(
+            maturity,
+            self.maturity_gating_threshold,
+        ) {
+            None => juvenile,
+            Some(t) => Self::midway(juvenile, t),
+        };
+        let movement = gated(self.juvenile_movement);
+        let neighbor_sensing = gated(self.juvenile_neighbor_sensing);
+        match Self::adolescent_progress(maturity, self.adolescent_threshold) {
             // Juvenile stage
-            (
-                self.juvenile_boundary_repair,
-                self.juvenile_sensing,
-                self.juvenile_metabolic_efficiency,
-            )
-        } else {
+            None => StageFactors {
+                boundary_repair: self.juvenile_boundary_repair,
+                sensing: self.juvenile_sensing,
+                metabolic: self.juvenile_metabolic_efficiency,
+                movement,
+                neighbor_sensing,
+            },
             // Adolescent stage — interpolate toward adult (1.0) as maturity → 1.0
-            let t = (maturity - self.adolescent_threshold)
-                / (1.0 - self.adolescent_threshold).max(f32::EPSILON);
-            let t = t.clamp(0.0, 1.0);
-            let mid = (self.juvenile_metabolic_efficiency + 1.0) * 0.5;
-            (
-                self.adolescent_boundary_repair + t * (1.0 - self.adolescent_boundary_repair),
-                self.adolescent_sensing + t * (1.0 - self.adolescent_sensing),
-                mid + t * (1.0 - mid),
-            )
+            Some(t) => StageFactors {
+                boundary_repair: Self::toward_adult(self.adolescent_boundary_repair, t),
+                sensing: Self::toward_adult(self.adolescent_sensing, t),
+                metabolic: Self::midway(self.juvenile_metabolic_efficiency, t),
+                movement,
+                neighbor_sensing,
+            },
         }
     }
+
+    /// Progress in [0, 1] from `threshold` to adulthood, or `None` while juvenile.
+    fn adolescent_progress(maturity: f32, threshold: f32) -> Option<f32> {
+        (maturity >= threshold)
+            .then(|| ((maturity - threshold) / (1.0 - threshold).max(f32::EPSILON)).clamp(0.0, 1.0))
+    }
+
+    fn toward_adult(from: f32, t: f32) -> f32 {
+        from + t * (1.0 - from)
+    }
+
+    /// Adolescent factor starting halfway between the juvenile factor and 1.0.
+    fn midway(juvenile: f32, t: f32) -> f32 {
+        Self::toward_adult((juvenile + 1.0) * 0.5, t)
+    }
+}
+
+/// Multipliers a developmental stage applies to an organism's capabilities.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StageFactors {
+    pub boundary_repair: f32,
+    /// Sensing radius.
+    pub sensing: f32,
+    pub metabolic: f32,
+    /// Speed cap as a fraction of `max_speed`, under `enable_maturity_gating`.
+    pub movement: f32,
+    /// Neighbor NN input, under `enable_maturity_gating`.
+    pub neighbor_sensing: f32,
+}
+
+impl StageFactors {
+    pub const ADULT: Self = Self {
+        boundary_repair: 1.0,
+        sensing: 1.0,
+        metabolic: 1.0,
+        movement: 1.0,
+        neighbor_sensing: 1.0,
+    };
+}
+
+impl Default for StageFactors {
+    fn default() -> Self {
+        Self::ADULT
+    }
 }
 
 impl Default for DevelopmentalProgram {
     /// Default matches decode of an all-zero genome segment:
     /// sigmoid(0.0) = 0.5, maturation_rate_modifier = 2^0 = 1.0.
     fn default() -> Self {
-        Self::decode(&[0.0; 8])
+        Self::decode(&[0.0; Genome::DEVELOPMENTAL_SIZE])
+    }
+}
+
+/// Homeostatic set-points and gains decoded from genome segment 2 (6 active floats
+/// of 8), one pair per regulated internal-state channel (0 and 1), plus stress
+/// resistance to the waste field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HomeostasisProgram {
+    /// g[0], g[1]: sigmoid → [0, 1] — target value of each channel.
+    pub setpoints: [f32; 2],
+    /// g[2], g[3]: sigmoid → [0, 4] — proportional gain per unit time.
+    pub gains: [f32; 2],
+    /// g[4]: clamp → [0, 1] — toxin resistance; cuts the boundary decay caused by
+    /// `waste_field_toxicity` by up to [`HomeostasisProgram::MAX_TOXIN_PROTECTION`].
+    pub toxin_resistance: f32,
+    /// g[5]: 2^clamp(g, -1, 1) → [0.5, 2] — multiplier on `toxin_resistance_cost`.
+    pub resistance_cost_multiplier: f32,
+}
+
+impl HomeostasisProgram {
+    pub const MAX_GAIN: f32 = 4.0;
+    /// Fraction of toxin-driven boundary decay removed at full resistance.
+    pub const MAX_TOXIN_PROTECTION: f32 = 0.8;
+
+    /// Decode genome segment 2; an all-zero segment gives set-points 0.5, gains 2.0,
+    /// no toxin resistance and a cost multiplier of 1. Genes past the end of a short
+    /// segment read as zero.
+    pub fn decode(segment: &[f32]) -> Self {
+        assert!(segment.len() >= 4, "homeostasis segment needs ≥4 floats");
+        let s = DevelopmentalProgram::sigmoid;
+        let gene = |i: usize| segment.get(i).copied().unwrap_or(0.0);
+        Self {
+            setpoints: [s(segment[0]), s(segment[1])],
+            gains: [
+                s(segment[2]) * Self::MAX_GAIN,
+                s(segment[3]) * Self::MAX_GAIN,
+            ],
+            toxin_resistance: gene(4).clamp(0.0, 1.0),
+            resistance_cost_multiplier: 2f32.powf(gene(5).clamp(-1.0, 1.0)),
+        }
+    }
+
+    /// Scale on toxin-driven boundary decay: 1 without resistance, 0.2 at full.
+    pub fn toxin_exposure(&self) -> f32 {
+        1.0 - Self::MAX_TOXIN_PROTECTION * self.toxin_resistance
+    }
+
+    /// Energy per unit time spent maintaining resistance, given `toxin_resistance_cost`.
+    pub fn resistance_upkeep(&self, cost: f32) -> f32 {
+        self.toxin_resistance * self.resistance_cost_multiplier * cost
+    }
+
+    /// Rate of change pulling `state` toward the set-point of `channel`.
+    pub fn correction(&self, channel: usize, state: f32) -> f32 {
+        self.gains[channel] * (self.setpoints[channel] - state)
+    }
+}
+
+impl Default for HomeostasisProgram {
+    fn default() -> Self {
+        Self::decode(&[0.0; Genome::HOMEOSTASIS_SIZE])
+    }
+}
+
+/// Sensory modifiers decoded from genome segment 5 (4 floats), applied only with
+/// `SimConfig::enable_genetic_sensing`. Every gene maps through 2^clamp(g, -1, 1) to
+/// [0.5, 2.0], so an all-zero segment is neutral.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SensoryProgram {
+    /// g[0]: multiplier on the sensing radius.
+    pub radius_multiplier: f32,
+    /// g[1]: multiplier on `neighbor_norm` for the neighbor NN inputs.
+    pub neighbor_norm_multiplier: f32,
+    /// g[2]: gain on the extended suite's resource gradient inputs.
+    pub gradient_gain: f32,
+    /// g[3]: gain on the extended suite's other-organism neighbor input.
+    pub contact_gain: f32,
+}
+
+impl SensoryProgram {
+    /// Decode genome segment 5.
+    pub fn decode(segment: &[f32]) -> Self {
+        assert!(segment.len() >= 4, "sensory segment needs ≥4 floats");
+        let g = |i: usize| 2.0f32.powf(segment[i].clamp(-1.0, 1.0));
+        Self {
+            radius_multiplier: g(0),
+            neighbor_norm_multiplier: g(1),
+            gradient_gain: g(2),
+            contact_gain: g(3),
+        }
+    }
+}
+
+impl Default for SensoryProgram {
+    fn default() -> Self {
+        Self::decode(&[0.0; 4])
     }
 }
 
@@ -157,6 +423,8 @@ pub struct OrganismRuntime {
     pub genome: Genome,
     pub ancestor_genome: Genome,
     pub nn: NeuralNet,
+    /// Activation settings for `nn`, from the config.
+    pub nn_params: NnParams,
     pub agent_ids: Vec<u32>,
     /// Maturation level: 0.0 (seed) → 1.0 (fully mature).
     pub maturity: f32,
@@ -164,14 +432,89 @@ pub struct OrganismRuntime {
     pub metabolism_engine: Option<MetabolismEngine>,
     /// Decoded developmental program from genome segment 3.
     pub developmental_program: DevelopmentalProgram,
+    /// Decoded homeostatic set-points from genome segment 2.
+    pub homeostasis_program: HomeostasisProgram,
+    /// Decoded sensory modifiers from genome segment 5.
+    pub sensory_program: SensoryProgram,
     /// Stable ID of the parent organism (None for bootstrap organisms).
     pub parent_stable_id: Option<u64>,
+    /// Within-lifetime Hebbian weight offsets (Some only with plasticity enabled).
+    /// Never copied into the genome, so offspring start from their genome weights.
+    pub hebbian_trace: Option<Box<HebbianTrace>>,
+    /// Starvation dormancy (only ever set with `enable_dormancy`).
+    pub dormant: bool,
+    /// Metabolic flux of the latest metabolism phase.
+    pub last_step_flux: MetabolismFlux,
+    /// Flux summed since the last snapshot frame; reset when a frame is taken.
+    pub flux_since_snapshot: MetabolismFlux,
+    /// Mean neighbor count above `crowding_neighbor_threshold` in the latest growth
+    /// phase; 0 when uncrowded.
+    pub crowding_excess: f32,
+    /// Words consumed from this organism's `RngMode::PerOrganism` stream; stays 0
+    /// under `RngMode::Global`.
+    pub rng_word_pos: u128,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn decode_aging_rate_modifier_from_last_gene() {
+        let mut segment = [0.0f32; 8];
+        assert!((DevelopmentalProgram::decode(&segment).aging_rate_modifier - 1.0).abs() < 1e-6);
+        segment[7] = 1.0;
+        assert!((DevelopmentalProgram::decode(&segment).aging_rate_modifier - 2.0).abs() < 1e-6);
+        segment[7] = -10.0;
+        assert!((DevelopmentalProgram::decode(&segment).aging_rate_modifier - 0.25).abs() < 1e-6);
+        assert!(
+            (DevelopmentalProgram::decode(&segment[..7]).aging_rate_modifier - 1.0).abs() < 1e-6
+        );
+    }
+
+    #[test]
+    fn decode_sensory_program_within_bounds() {
+        assert_eq!(
+            SensoryProgram::default(),
+            SensoryProgram {
+                radius_multiplier: 1.0,
+                neighbor_norm_multiplier: 1.0,
+                gradient_gain: 1.0,
+                contact_gain: 1.0,
+            }
+        );
+        let program = SensoryProgram::decode(&[5.0, -5.0, 0.5, -0.5]);
+        assert_eq!(program.radius_multiplier, 2.0);
+        assert_eq!(program.neighbor_norm_multiplier, 0.5);
+        assert!((program.gradient_gain - 2.0f32.sqrt()).abs() < 1e-6);
+        assert!((program.contact_gain - 0.5f32.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn decode_role_archetypes_and_assign_agents_by_weight() {
+        let mut segment = vec![0.0f32; 8 + 2 * Genome::ROLE_ARCHETYPE_SIZE];
+        segment[12..].copy_from_slice(&[3.0f32.ln(), 1.0, -1.0, -1.0]);
+        let program = DevelopmentalProgram::decode(&segment);
+        assert_eq!(program.roles.len(), 2);
+        assert_eq!(program.roles[0], RoleArchetype::NEUTRAL);
+        let second = program.roles[1];
+        assert!((second.weight - 3.0).abs() < 1e-5);
+        assert!((second.sensing_multiplier - 2.0).abs() < 1e-6);
+        assert!((second.uptake_share - 0.5).abs() < 1e-6);
+        assert!((second.movement_gain - 0.5).abs() < 1e-6);
+
+        let roles: Vec<u8> = (0..8).map(|i| program.role_for_index(i, 8)).collect();
+        assert_eq!(roles, [0, 0, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(program.role_signal(0), 0.0);
+        assert_eq!(program.role_signal(1), 1.0);
+        assert_eq!(*program.role(7), RoleArchetype::NEUTRAL);
+
+        let plain = DevelopmentalProgram::default();
+        assert!(plain.roles.is_empty());
+        assert_eq!(plain.role_for_index(3, 8), 0);
+        assert_eq!(plain.role_signal(0), 0.0);
+    }
+
     #[test]
     fn decode_zero_genome_matches_default() {
         let from_decode = DevelopmentalProgram::decode(&[0.0; 8]);
@@ -207,35 +550,152 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_homeostasis_setpoints_and_gains() {
+        let zero = HomeostasisProgram::default();
+        assert_eq!(zero.setpoints, [0.5, 0.5]);
+        assert_eq!(zero.gains, [2.0, 2.0]);
+        let program = HomeostasisProgram::decode(&[4.0_f32.ln(), -(4.0_f32.ln()), 50.0, -50.0]);
+        assert!((program.setpoints[0] - 0.8).abs() < 1e-6);
+        assert!((program.setpoints[1] - 0.2).abs() < 1e-6);
+        assert!((program.gains[0] - HomeostasisProgram::MAX_GAIN).abs() < 1e-6);
+        assert!(program.gains[1].abs() < 1e-6);
+        assert!((program.correction(0, 0.3) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn decode_homeostasis_toxin_resistance_genes() {
+        let zero = HomeostasisProgram::default();
+        assert_eq!(zero.toxin_resistance, 0.0);
+        assert_eq!(zero.toxin_exposure(), 1.0);
+        assert_eq!(zero.resistance_upkeep(0.3), 0.0);
+        let program = HomeostasisProgram::decode(&[0.0, 0.0, 0.0, 0.0, 2.0, 1.0]);
+        assert_eq!(program.toxin_resistance, 1.0);
+        assert!((program.toxin_exposure() - 0.2).abs() < 1e-6);
+        assert!((program.resistance_upkeep(0.3) - 0.6).abs() < 1e-6);
+    }
+
     #[test]
     fn stage_factors_adult_returns_all_ones() {
         let dp = DevelopmentalProgram::default();
-        let (b, s, m) = dp.stage_factors(1.0);
-        assert!((b - 1.0).abs() < f32::EPSILON);
-        assert!((s - 1.0).abs() < f32::EPSILON);
-        assert!((m - 1.0).abs() < f32::EPSILON);
+        assert_eq!(dp.stage_factors(1.0), StageFactors::ADULT);
+    }
+
+    fn gating_layout() -> GenomeLayout {
+        GenomeLayout {
+            maturity_gating_genes: true,
+            ..GenomeLayout::default()
+        }
+    }
+
+    #[test]
+    fn decode_maturity_gating_from_dedicated_genes() {
+        let mut segment = [0.0f32; Genome::DEVELOPMENTAL_SIZE + Genome::MATURITY_GATING_SIZE];
+        let zero = DevelopmentalProgram::decode_in_layout(&segment, &gating_layout());
+        let plain = DevelopmentalProgram::default();
+        assert_eq!(zero.juvenile_movement, plain.juvenile_movement);
+        assert_eq!(
+            zero.juvenile_neighbor_sensing,
+            plain.juvenile_neighbor_sensing
+        );
+        assert_eq!(zero.maturity_gating_threshold, plain.adolescent_threshold);
+
+        segment[2] = 3.0;
+        segment[6] = 3.0;
+        let shifted = DevelopmentalProgram::decode_in_layout(&segment, &gating_layout());
+        assert!(shifted.juvenile_metabolic_efficiency > zero.juvenile_metabolic_efficiency);
+        assert_eq!(shifted.juvenile_movement, zero.juvenile_movement);
+        assert_eq!(
+            shifted.juvenile_neighbor_sensing,
+            zero.juvenile_neighbor_sensing
+        );
+
+        segment[8] = 3.0;
+        segment[9] = -3.0;
+        segment[10] = 2.0;
+        let evolved = DevelopmentalProgram::decode_in_layout(&segment, &gating_layout());
+        assert!(evolved.juvenile_movement > zero.juvenile_movement);
+        assert!(evolved.juvenile_neighbor_sensing < zero.juvenile_neighbor_sensing);
+        assert!(evolved.maturity_gating_threshold > evolved.adolescent_threshold);
+        // Between the two thresholds only the other factors have left the juvenile stage.
+        let maturity = (evolved.adolescent_threshold + evolved.maturity_gating_threshold) * 0.5;
+        let factors = evolved.stage_factors(maturity);
+        assert_eq!(factors.movement, evolved.juvenile_movement);
+        assert!(factors.boundary_repair >= evolved.adolescent_boundary_repair);
+        // Without the layout the gating genes are role genes, not curves.
+        assert_eq!(
+            DevelopmentalProgram::decode(&segment).juvenile_movement,
+            plain.juvenile_movement
+        );
+    }
+
+    #[test]
+    fn stage_factors_converge_to_one_at_maturity() {
+        let dp = DevelopmentalProgram::decode_in_layout(
+            &[0.5, -2.0, -2.0, 1.0, -2.0, -2.0, -2.0, 0.0, -2.0, -2.0, 0.0],
+            &gating_layout(),
+        );
+        let juvenile = dp.stage_factors(0.0);
+        assert!(juvenile.movement < 0.5, "{juvenile:?}");
+        assert!(juvenile.neighbor_sensing < 0.75, "{juvenile:?}");
+        let mut previous = juvenile;
+        for maturity in [dp.maturity_gating_threshold, 0.8, 0.9, 0.99, 0.9999] {
+            let factors = dp.stage_factors(maturity);
+            assert!(factors.movement > previous.movement, "{factors:?}");
+            assert!(factors.neighbor_sensing > previous.neighbor_sensing);
+            previous = factors;
+        }
+        assert!((previous.movement - 1.0).abs() < 1e-3, "{previous:?}");
+        assert!((previous.neighbor_sensing - 1.0).abs() < 1e-3);
+        assert!((previous.boundary_repair - 1.0).abs() < 1e-3);
+        assert_eq!(dp.stage_factors(1.0), StageFactors::ADULT);
     }
 
     #[test]
     fn stage_factors_juvenile_returns_reduced() {
         let dp = DevelopmentalProgram::default();
         // Default adolescent_threshold ≈ 0.5, so maturity=0.1 is juvenile
-        let (b, s, m) = dp.stage_factors(0.1);
-        assert!(b < 1.0, "juvenile boundary should be reduced: {b}");
-        assert!(s < 1.0, "juvenile sensing should be reduced: {s}");
-        assert!(m < 1.0, "juvenile metabolic should be reduced: {m}");
+        let f = dp.stage_factors(0.1);
+        assert!(
+            f.boundary_repair < 1.0,
+            "juvenile boundary should be reduced: {f:?}"
+        );
+        assert!(f.sensing < 1.0, "juvenile sensing should be reduced: {f:?}");
+        assert!(
+            f.metabolic < 1.0,
+            "juvenile metabolic should be reduced: {f:?}"
+        );
+        assert!(
+            f.movement < 1.0,
+            "juvenile movement should be reduced: {f:?}"
+        );
+        assert!(
+            f.neighbor_sensing < 1.0,
+            "juvenile neighbor input should be reduced: {f:?}"
+        );
     }
 
     #[test]
     fn stage_factors_adolescent_intermediate() {
         let dp = DevelopmentalProgram::default();
-        let (b_juv, s_juv, m_juv) = dp.stage_factors(0.1);
-        let (b_ado, s_ado, m_ado) = dp.stage_factors(0.7);
-        assert!(b_ado > b_juv, "adolescent boundary > juvenile");
-        assert!(s_ado > s_juv, "adolescent sensing > juvenile");
-        assert!(m_ado > m_juv, "adolescent metabolic > juvenile");
-        assert!(b_ado < 1.0, "adolescent boundary < adult");
-        assert!(s_ado < 1.0, "adolescent sensing < adult");
+        let juv = dp.stage_factors(0.1);
+        let ado = dp.stage_factors(0.7);
+        assert!(
+            ado.boundary_repair > juv.boundary_repair,
+            "adolescent boundary > juvenile"
+        );
+        assert!(ado.sensing > juv.sensing, "adolescent sensing > juvenile");
+        assert!(
+            ado.metabolic > juv.metabolic,
+            "adolescent metabolic > juvenile"
+        );
+        assert!(
+            ado.movement > juv.movement,
+            "adolescent movement > juvenile"
+        );
+        assert!(ado.boundary_repair < 1.0, "adolescent boundary < adult");
+        assert!(ado.sensing < 1.0, "adolescent sensing < adult");
+        assert!(ado.movement < 1.0, "adolescent movement < adult");
     }
 
     #[test]
@@ -0,0 +1,518 @@
+//! Parquet export of run samples and snapshot frames, behind the `arrow-export`
+//! feature.
+//!
+//! Samples become one row per [`StepMetrics`] and snapshots one row per organism per
+//! [`SnapshotFrame`]. Columns carry the serde field names, so a table loads with the
+//! same column names as the JSON summary after `pandas.json_normalize`: nested
+//! metrics are flattened as `speciation.species_count`, fixed and variable length
+//! arrays become list columns, and `Option` fields become nullable columns. `usize`
+//! counters are stored as `u64`. Snapshot rows skip `decoded_params` and frame-level
+//! `resource_hotspots`, as the binary snapshot format does.
+
+use crate::metrics::{OrganismSnapshot, RunSummary, SnapshotFrame, StepMetrics};
+use crate::snapshot_file::SnapshotSink;
+use arrow_array::types::{Float32Type, Float64Type};
+use arrow_array::{
+    ArrayRef, BooleanArray, Float32Array, Float64Array, ListArray, RecordBatch, UInt32Array,
+    UInt64Array,
+};
+use arrow_schema::{ArrowError, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::{error::Error, fmt};
+
+/// Rows buffered before a row group is written; bounds a streaming writer's memory.
+const MAX_ROW_GROUP_ROWS: usize = 65_536;
+
+#[derive(Debug)]
+pub enum ParquetExportError {
+    Io(io::Error),
+    Arrow(ArrowError),
+    Parquet(ParquetError),
+}
+
+impl fmt::Display for ParquetExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParquetExportError::Io(err) => write!(f, "parquet file I/O failed: {err}"),
+            ParquetExportError::Arrow(err) => write!(f, "failed to build arrow batch: {err}"),
+            ParquetExportError::Parquet(err) => write!(f, "failed to write parquet: {err}"),
+        }
+    }
+}
+
+impl Error for ParquetExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParquetExportError::Io(err) => Some(err),
+            ParquetExportError::Arrow(err) => Some(err),
+            ParquetExportError::Parquet(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for ParquetExportError {
+    fn from(err: io::Error) -> Self {
+        ParquetExportError::Io(err)
+    }
+}
+
+impl From<ArrowError> for ParquetExportError {
+    fn from(err: ArrowError) -> Self {
+        ParquetExportError::Arrow(err)
+    }
+}
+
+impl From<ParquetError> for ParquetExportError {
+    fn from(err: ParquetError) -> Self {
+        ParquetExportError::Parquet(err)
+    }
+}
+
+/// Column-by-column batch builder. Each column's type and nullability follow from
+/// the accessor alone, so batches of any length, including empty ones, share a
+/// schema.
+struct Columns<'a, T> {
+    rows: &'a [T],
+    fields: Vec<Field>,
+    arrays: Vec<ArrayRef>,
+}
+
+impl<'a, T> Columns<'a, T> {
+    fn new(rows: &'a [T]) -> Self {
+        Self {
+            rows,
+            fields: Vec::new(),
+            arrays: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, name: &str, array: ArrayRef, nullable: bool) {
+        self.fields
+            .push(Field::new(name, array.data_type().clone(), nullable));
+        self.arrays.push(array);
+    }
+
+    fn u64(&mut self, name: &str, value: impl Fn(&T) -> u64) {
+        let array = UInt64Array::from_iter_values(self.rows.iter().map(value));
+        self.push(name, Arc::new(array), false);
+    }
+
+    fn u32(&mut self, name: &str, value: impl Fn(&T) -> u32) {
+        let array = UInt32Array::from_iter_values(self.rows.iter().map(value));
+        self.push(name, Arc::new(array), false);
+    }
+
+    fn f32(&mut self, name: &str, value: impl Fn(&T) -> f32) {
+        let array = Float32Array::from_iter_values(self.rows.iter().map(value));
+        self.push(name, Arc::new(array), false);
+    }
+
+    fn f64(&mut self, name: &str, value: impl Fn(&T) -> f64) {
+        let array = Float64Array::from_iter_values(self.rows.iter().map(value));
+        self.push(name, Arc::new(array), false);
+    }
+
+    fn bool(&mut self, name: &str, value: impl Fn(&T) -> bool) {
+        let array: BooleanArray = self.rows.iter().map(|row| Some(value(row))).collect();
+        self.push(name, Arc::new(array), false);
+    }
+
+    fn opt_u64(&mut self, name: &str, value: impl Fn(&T) -> Option<u64>) {
+        let array: UInt64Array = self.rows.iter().map(value).collect();
+        self.push(name, Arc::new(array), true);
+    }
+
+    fn opt_u32(&mut self, name: &str, value: impl Fn(&T) -> Option<u32>) {
+        let array: UInt32Array = self.rows.iter().map(value).collect();
+        self.push(name, Arc::new(array), true);
+    }
+
+    fn opt_f32(&mut self, name: &str, value: impl Fn(&T) -> Option<f32>) {
+        let array: Float32Array = self.rows.iter().map(value).collect();
+        self.push(name, Arc::new(array), true);
+    }
+
+    fn opt_f64(&mut self, name: &str, value: impl Fn(&T) -> Option<f64>) {
+        let array: Float64Array = self.rows.iter().map(value).collect();
+        self.push(name, Arc::new(array), true);
+    }
+
+    fn f32_list(&mut self, name: &str, value: impl Fn(&T) -> Option<&[f32]>) {
+        let array = ListArray::from_iter_primitive::<Float32Type, _, _>(
+            self.rows
+                .iter()
+                .map(|row| value(row).map(|items| items.iter().copied().map(Some))),
+        );
+        self.push(name, Arc::new(array), true);
+    }
+
+    fn f64_list(&mut self, name: &str, value: impl Fn(&T) -> &[f64]) {
+        let array = ListArray::from_iter_primitive::<Float64Type, _, _>(
+            self.rows
+                .iter()
+                .map(|row| Some(value(row).iter().copied().map(Some))),
+        );
+        self.push(name, Arc::new(array), true);
+    }
+
+    fn finish(self) -> Result<RecordBatch, ArrowError> {
+        RecordBatch::try_new(Arc::new(Schema::new(self.fields)), self.arrays)
+    }
+}
+
+fn samples_batch(samples: &[StepMetrics]) -> Result<RecordBatch, ArrowError> {
+    let mut c = Columns::new(samples);
+    c.u64("step", |m| m.step as u64);
+    c.f32("energy_mean", |m| m.energy_mean);
+    c.f32("waste_mean", |m| m.waste_mean);
+    c.f32("boundary_mean", |m| m.boundary_mean);
+    c.u64("alive_count", |m| m.alive_count as u64);
+    c.f64("resource_total", |m| m.resource_total);
+    c.f64("resource_variance", |m| m.resource_variance);
+    c.u64("birth_count", |m| m.birth_count as u64);
+    c.u64("death_count", |m| m.death_count as u64);
+    c.u64("fusion_count", |m| m.fusion_count as u64);
+    c.u64("population_size", |m| m.population_size as u64);
+    c.f32("mean_generation", |m| m.mean_generation);
+    c.f32("mean_genome_drift", |m| m.mean_genome_drift);
+    c.u64("agent_id_exhaustion_events", |m| {
+        m.agent_id_exhaustion_events as u64
+    });
+    c.u64("organism_id_exhaustion_events", |m| {
+        m.organism_id_exhaustion_events as u64
+    });
+    c.u64("reproduction_suppressed_by_capacity", |m| {
+        m.reproduction_suppressed_by_capacity as u64
+    });
+    c.u64("nonfinite_output_events", |m| {
+        m.nonfinite_output_events as u64
+    });
+    c.u64("metabolic_state_violations", |m| {
+        m.metabolic_state_violations as u64
+    });
+    c.opt_f64("conservation_residual", |m| m.conservation_residual);
+    c.f32("energy_std", |m| m.energy_std);
+    c.f32("waste_std", |m| m.waste_std);
+    c.f32("boundary_std", |m| m.boundary_std);
+    c.f32("mean_age", |m| m.mean_age);
+    c.f32_list("internal_state_mean", |m| Some(&m.internal_state_mean));
+    c.f32_list("internal_state_std", |m| Some(&m.internal_state_std));
+    c.f32("genome_diversity", |m| m.genome_diversity);
+    c.u64("max_generation", |m| m.max_generation as u64);
+    c.f32("maturity_mean", |m| m.maturity_mean);
+    c.f32("spatial_cohesion_mean", |m| m.spatial_cohesion_mean);
+    c.f32("mean_mutation_rate_multiplier", |m| {
+        m.mean_mutation_rate_multiplier
+    });
+    c.f32("current_resource_rate", |m| m.current_resource_rate);
+    c.opt_f32("same_organism_neighbor_fraction", |m| {
+        m.same_organism_neighbor_fraction
+    });
+    c.f64_list("resource_channel_totals", |m| &m.resource_channel_totals);
+    c.opt_u64("dormant_count", |m| m.dormant_count.map(|n| n as u64));
+    c.opt_f64("waste_field_total", |m| m.waste_field_total);
+    c.opt_f32("waste_cross_feeding_uptake", |m| {
+        m.waste_cross_feeding_uptake
+    });
+    c.opt_f32("mean_toxin_resistance", |m| m.mean_toxin_resistance);
+    c.opt_u64("early_warning.window", |m| {
+        m.early_warning.as_ref().map(|ew| ew.window as u64)
+    });
+    c.opt_f32("early_warning.energy_cv", |m| {
+        m.early_warning.as_ref().map(|ew| ew.energy_cv)
+    });
+    c.opt_f32("early_warning.alive_autocorrelation", |m| {
+        m.early_warning.as_ref().map(|ew| ew.alive_autocorrelation)
+    });
+    c.opt_f32("early_warning.boundary_trend", |m| {
+        m.early_warning.as_ref().map(|ew| ew.boundary_trend)
+    });
+    c.f32("spatial_entropy", |m| m.spatial_entropy);
+    c.f32("occupied_cell_fraction", |m| m.occupied_cell_fraction);
+    c.f32_list("genome_diversity_by_segment", |m| {
+        m.genome_diversity_by_segment.as_ref().map(|d| d.as_slice())
+    });
+    c.f32_list("role_fractions", |m| Some(&m.role_fractions));
+    c.opt_u64("steps_since_last_shock", |m| {
+        m.steps_since_last_shock.map(|n| n as u64)
+    });
+    c.f32("external_uptake_mean", |m| m.external_uptake_mean);
+    c.f32("internal_conversion_mean", |m| m.internal_conversion_mean);
+    c.f32("waste_produced_mean", |m| m.waste_produced_mean);
+    c.f32("crowding_excess_mean", |m| m.crowding_excess_mean);
+    c.opt_u64("speciation.species_count", |m| {
+        m.speciation.as_ref().map(|s| s.species_count as u64)
+    });
+    c.opt_f32("speciation.largest_species_fraction", |m| {
+        m.speciation.as_ref().map(|s| s.largest_species_fraction)
+    });
+    c.opt_f32("speciation.mean_inter_cluster_distance", |m| {
+        m.speciation.as_ref().map(|s| s.mean_inter_cluster_distance)
+    });
+    c.opt_u64("speciation.genomes_clustered", |m| {
+        m.speciation.as_ref().map(|s| s.genomes_clustered as u64)
+    });
+    c.finish()
+}
+
+fn snapshot_batch(step: usize, organisms: &[OrganismSnapshot]) -> Result<RecordBatch, ArrowError> {
+    let mut c = Columns::new(organisms);
+    c.u64("step", |_| step as u64);
+    c.u64("stable_id", |o| o.stable_id);
+    c.u32("generation", |o| o.generation);
+    c.u64("age_steps", |o| o.age_steps as u64);
+    c.f32("energy", |o| o.energy);
+    c.f32("waste", |o| o.waste);
+    c.f32("boundary_integrity", |o| o.boundary_integrity);
+    c.f32("maturity", |o| o.maturity);
+    c.f64("center_x", |o| o.center_x);
+    c.f64("center_y", |o| o.center_y);
+    c.u64("n_agents", |o| o.n_agents as u64);
+    c.bool("dormant", |o| o.dormant);
+    c.f32("external_uptake", |o| o.external_uptake);
+    c.f32("internal_conversion", |o| o.internal_conversion);
+    c.f32("waste_produced", |o| o.waste_produced);
+    c.opt_u32("species_id", |o| o.species_id);
+    c.finish()
+}
+
+fn writer_properties() -> WriterProperties {
+    WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_max_row_group_row_count(Some(MAX_ROW_GROUP_ROWS))
+        .build()
+}
+
+/// Streams snapshot frames to `inner` as Parquet rows, one per organism.
+///
+/// At most one row group is buffered in memory. Call
+/// [`ParquetSnapshotWriter::finish`] once the last frame is written: a Parquet file
+/// is unreadable until its footer is.
+pub struct ParquetSnapshotWriter<W: Write + Send> {
+    inner: ArrowWriter<W>,
+}
+
+impl<W: Write + Send> ParquetSnapshotWriter<W> {
+    pub fn new(inner: W) -> Result<Self, ParquetExportError> {
+        let schema: SchemaRef = snapshot_batch(0, &[])?.schema();
+        let inner = ArrowWriter::try_new(inner, schema, Some(writer_properties()))?;
+        Ok(Self { inner })
+    }
+
+    pub fn write_frame(&mut self, frame: &SnapshotFrame) -> Result<(), ParquetExportError> {
+        self.inner
+            .write(&snapshot_batch(frame.step, &frame.organisms)?)?;
+        Ok(())
+    }
+
+    /// Write any buffered rows and the footer, returning the underlying writer.
+    pub fn finish(self) -> Result<W, ParquetExportError> {
+        Ok(self.inner.into_inner()?)
+    }
+}
+
+impl<W: Write + Send> SnapshotSink for ParquetSnapshotWriter<W> {
+    fn write_frame(&mut self, frame: &SnapshotFrame) -> io::Result<()> {
+        ParquetSnapshotWriter::write_frame(self, frame).map_err(io::Error::other)
+    }
+
+    /// Closes the current row group; the footer still needs
+    /// [`ParquetSnapshotWriter::finish`].
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().map_err(io::Error::other)
+    }
+}
+
+impl RunSummary {
+    /// Write `samples` to a Parquet file at `path`, one row per sample.
+    pub fn write_samples_parquet(&self, path: impl AsRef<Path>) -> Result<(), ParquetExportError> {
+        let batch = samples_batch(&self.samples)?;
+        let mut writer = ArrowWriter::try_new(
+            File::create(path)?,
+            batch.schema(),
+            Some(writer_properties()),
+        )?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+
+    /// Write `organism_snapshots` to a Parquet file at `path`, one row per organism
+    /// per frame.
+    pub fn write_snapshots_parquet(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ParquetExportError> {
+        let mut writer = ParquetSnapshotWriter::new(File::create(path)?)?;
+        for frame in &self.organism_snapshots {
+            writer.write_frame(frame)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SimConfig;
+    use crate::snapshot_file::ORGANISM_FIELDS;
+    use crate::world::World;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{UInt32Type, UInt64Type};
+    use arrow_array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dl_parquet_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    fn read_back(path: &Path) -> RecordBatch {
+        let mut batches: Vec<RecordBatch> =
+            ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+                .unwrap()
+                .with_batch_size(MAX_ROW_GROUP_ROWS)
+                .build()
+                .unwrap()
+                .map(Result::unwrap)
+                .collect();
+        assert_eq!(batches.len(), 1);
+        batches.remove(0)
+    }
+
+    fn config() -> SimConfig {
+        SimConfig {
+            seed: 5,
+            num_organisms: 6,
+            agents_per_organism: 4,
+            world_size: 40.0,
+            speciation_metric_every: 2,
+            collect_early_warning: true,
+            ..SimConfig::default()
+        }
+    }
+
+    #[test]
+    fn samples_round_trip_with_json_column_names() {
+        let mut world = World::from_config(config()).unwrap();
+        let summary = world.try_run_experiment(40, 10).unwrap();
+        let path = temp_path("samples.parquet");
+        summary.write_samples_parquet(&path).unwrap();
+        let batch = read_back(&path);
+        assert_eq!(batch.num_rows(), summary.samples.len());
+
+        // Every key of the JSON sample, flattened one level, is a column.
+        let sample = &summary.samples[3];
+        let json = serde_json::to_value(sample).unwrap();
+        for (key, value) in json.as_object().unwrap() {
+            match value.as_object() {
+                Some(nested) => {
+                    for inner in nested.keys() {
+                        let column = format!("{key}.{inner}");
+                        assert!(batch.column_by_name(&column).is_some(), "{column}");
+                    }
+                }
+                None => assert!(batch.column_by_name(key).is_some(), "{key}"),
+            }
+        }
+
+        let column = |name: &str| batch.column_by_name(name).unwrap();
+        assert_eq!(
+            column("step").as_primitive::<UInt64Type>().value(3),
+            sample.step as u64
+        );
+        assert_eq!(
+            column("energy_mean").as_primitive::<Float32Type>().value(3),
+            sample.energy_mean
+        );
+        assert_eq!(
+            column("resource_total")
+                .as_primitive::<Float64Type>()
+                .value(3),
+            sample.resource_total
+        );
+        let species = column("speciation.species_count").as_primitive::<UInt64Type>();
+        assert_eq!(
+            species.value(3),
+            sample.speciation.as_ref().unwrap().species_count as u64
+        );
+        let waste_field = column("waste_field_total");
+        assert!(waste_field.is_null(3));
+        let internal = column("internal_state_mean").as_list::<i32>().value(3);
+        assert_eq!(
+            internal.as_primitive::<Float32Type>().values().as_ref(),
+            sample.internal_state_mean.as_slice()
+        );
+    }
+
+    #[test]
+    fn snapshots_round_trip_and_streaming_matches_in_memory_frames() {
+        // Streamed frames are taken before the step's speciation pass labels them.
+        let config = SimConfig {
+            speciation_metric_every: 0,
+            ..config()
+        };
+        let mut world = World::from_config(config.clone()).unwrap();
+        let summary = world
+            .try_run_experiment_with_snapshots(30, 10, &[10, 20, 30])
+            .unwrap();
+        let path = temp_path("snapshots.parquet");
+        summary.write_snapshots_parquet(&path).unwrap();
+        let batch = read_back(&path);
+
+        let names: Vec<&str> = batch
+            .schema_ref()
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect();
+        let expected: Vec<&str> = std::iter::once("step")
+            .chain(ORGANISM_FIELDS.iter().map(|&(name, _)| name))
+            .collect();
+        assert_eq!(names, expected);
+        let frames = &summary.organism_snapshots;
+        let rows: usize = frames.iter().map(|f| f.organisms.len()).sum();
+        assert_eq!(batch.num_rows(), rows);
+
+        let row = frames[0].organisms.len() + 1;
+        let organism = &frames[1].organisms[1];
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        assert_eq!(column("step").as_primitive::<UInt64Type>().value(row), 20);
+        assert_eq!(
+            column("stable_id").as_primitive::<UInt64Type>().value(row),
+            organism.stable_id
+        );
+        assert_eq!(
+            column("generation").as_primitive::<UInt32Type>().value(row),
+            organism.generation
+        );
+        assert_eq!(
+            column("energy").as_primitive::<Float32Type>().value(row),
+            organism.energy
+        );
+        assert_eq!(
+            column("center_x").as_primitive::<Float64Type>().value(row),
+            organism.center_x
+        );
+        assert_eq!(column("dormant").as_boolean().value(row), organism.dormant);
+
+        let streamed_path = temp_path("streamed.parquet");
+        let mut streamed = World::from_config(config).unwrap();
+        let mut writer = ParquetSnapshotWriter::new(File::create(&streamed_path).unwrap()).unwrap();
+        streamed
+            .try_run_experiment_with_snapshot_writer(30, 10, 10, &mut writer)
+            .unwrap();
+        writer.finish().unwrap();
+        assert_eq!(read_back(&streamed_path), batch);
+    }
+}
@@ -0,0 +1,138 @@
+//! Named experiment configurations.
+//!
+//! Every ablation preset is [`baseline`] with exactly the flags named in its doc
+//! comment changed, so conditions cannot drift apart through hand-built configs.
+
+use crate::config::SimConfig;
+
+/// Preset names accepted by [`by_name`], in a stable order.
+pub const PRESET_NAMES: &[&str] = &[
+    "baseline",
+    "ablate_metabolism",
+    "ablate_boundary",
+    "ablate_homeostasis",
+    "ablate_response",
+    "ablate_reproduction",
+    "ablate_evolution",
+    "ablate_growth",
+    "sham_process",
+];
+
+/// Defaults plus the tuned values from `configs/tuned_baseline.json`
+/// (`metabolic_viability_floor = 0.1`, `crowding_neighbor_threshold = 50.0`).
+pub fn baseline() -> SimConfig {
+    SimConfig {
+        metabolic_viability_floor: 0.1,
+        crowding_neighbor_threshold: 50.0,
+        ..SimConfig::default()
+    }
+}
+
+/// Baseline with `enable_metabolism = false`.
+pub fn ablate_metabolism() -> SimConfig {
+    SimConfig {
+        enable_metabolism: false,
+        ..baseline()
+    }
+}
+
+/// Baseline with `enable_boundary_maintenance = false`.
+pub fn ablate_boundary() -> SimConfig {
+    SimConfig {
+        enable_boundary_maintenance: false,
+        ..baseline()
+    }
+}
+
+/// Baseline with `enable_homeostasis = false`.
+pub fn ablate_homeostasis() -> SimConfig {
+    SimConfig {
+        enable_homeostasis: false,
+        ..baseline()
+    }
+}
+
+/// Baseline with `enable_response = false`.
+pub fn ablate_response() -> SimConfig {
+    SimConfig {
+        enable_response: false,
+        ..baseline()
+    }
+}
+
+/// Baseline with `enable_reproduction = false`.
+pub fn ablate_reproduction() -> SimConfig {
+    SimConfig {
+        enable_reproduction: false,
+        ..baseline()
+    }
+}
+
+/// Baseline with `enable_evolution = false`.
+pub fn ablate_evolution() -> SimConfig {
+    SimConfig {
+        enable_evolution: false,
+        ..baseline()
+    }
+}
+
+/// Baseline with `enable_growth = false`.
+pub fn ablate_growth() -> SimConfig {
+    SimConfig {
+        enable_growth: false,
+        ..baseline()
+    }
+}
+
+/// Baseline with `enable_sham_process = true` (computational-cost control).
+pub fn sham_process() -> SimConfig {
+    SimConfig {
+        enable_sham_process: true,
+        ..baseline()
+    }
+}
+
+/// Look up a preset by name; see [`PRESET_NAMES`].
+pub fn by_name(name: &str) -> Option<SimConfig> {
+    let config = match name {
+        "baseline" => baseline(),
+        "ablate_metabolism" => ablate_metabolism(),
+        "ablate_boundary" => ablate_boundary(),
+        "ablate_homeostasis" => ablate_homeostasis(),
+        "ablate_response" => ablate_response(),
+        "ablate_reproduction" => ablate_reproduction(),
+        "ablate_evolution" => ablate_evolution(),
+        "ablate_growth" => ablate_growth(),
+        "sham_process" => sham_process(),
+        _ => return None,
+    };
+    Some(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_preset_passes_validation() {
+        for name in PRESET_NAMES {
+            let config = by_name(name).expect("listed preset should resolve");
+            assert!(config.validate().is_ok(), "preset {name} failed validation");
+        }
+        assert!(by_name("unknown").is_none());
+    }
+
+    #[test]
+    fn ablation_presets_differ_from_baseline_in_one_key() {
+        let base = toml::Table::try_from(baseline()).unwrap();
+        for name in PRESET_NAMES.iter().filter(|n| **n != "baseline") {
+            let preset = toml::Table::try_from(by_name(name).unwrap()).unwrap();
+            let changed: Vec<&String> = preset
+                .iter()
+                .filter(|(k, v)| base.get(*k) != Some(*v))
+                .map(|(k, _)| k)
+                .collect();
+            assert_eq!(changed.len(), 1, "preset {name} changed {changed:?}");
+        }
+    }
+}
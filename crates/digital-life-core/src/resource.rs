@@ -1,45 +1,470 @@
 /// 2D grid resource field stub.
 /// Each cell holds a resource concentration value.
+///
+/// A field may carry several independent substrate channels stored planar: channel
+/// `c` occupies `data[c * cells..(c + 1) * cells]`. Methods without a channel
+/// argument address channel 0, so single-channel fields behave as before.
+use crate::config::{GradientAxis, RegenerationModel};
+use rand::Rng;
+use std::error::Error;
+use std::fmt;
+
+/// Upper bound on resource channels; genome segment 1 has room for this many
+/// uptake affinities.
+pub const MAX_RESOURCE_CHANNELS: usize = 3;
+
+/// Fraction of a cell's capacity that logistic regeneration treats as the minimum
+/// stock, so fully depleted cells still recover.
+const LOGISTIC_SEED_FRACTION: f32 = 0.01;
+
+/// Rejected resource grid or cell value; the field is left unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResourceGridError {
+    /// The grid does not hold `width * height` values.
+    LengthMismatch { expected: usize, actual: usize },
+    /// The first NaN, infinite or negative value, at `(row, col)`.
+    InvalidValue { row: usize, col: usize, value: f32 },
+}
+
+impl fmt::Display for ResourceGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceGridError::LengthMismatch { expected, actual } => write!(
+                f,
+                "resource grid must hold {expected} values, got {actual}"
+            ),
+            ResourceGridError::InvalidValue { row, col, value } => write!(
+                f,
+                "resource value at index ({row}, {col}) must be finite and non-negative, got {value}"
+            ),
+        }
+    }
+}
+
+impl Error for ResourceGridError {}
 
 #[derive(Clone, Debug)]
 pub struct ResourceField {
     width: usize,
     height: usize,
     cell_size: f64,
+    channels: usize,
     data: Vec<f32>,
-    total: f64,
-    initial_value: f32,
+    /// Running total per channel.
+    totals: Vec<f64>,
+    /// Per-cell regeneration ceiling: the value each cell was initialized to.
+    capacity: Vec<f32>,
 }
 
 impl ResourceField {
     pub fn new(world_size: f64, cell_size: f64, initial_value: f32) -> Self {
+        Self::with_channels(world_size, cell_size, initial_value, 1)
+    }
+
+    /// Field with `channels` independent substrates, each starting at `initial_value`.
+    pub fn with_channels(
+        world_size: f64,
+        cell_size: f64,
+        initial_value: f32,
+        channels: usize,
+    ) -> Self {
         assert!(world_size > 0.0, "world_size must be positive");
         assert!(cell_size > 0.0, "cell_size must be positive");
+        assert!(
+            (1..=MAX_RESOURCE_CHANNELS).contains(&channels),
+            "channels must be in 1..={MAX_RESOURCE_CHANNELS}"
+        );
         // The simulation world is currently square; use a square resource grid for parity.
         let width = (world_size / cell_size).ceil() as usize;
         let height = width;
-        let data = vec![initial_value; width * height];
+        let data = vec![initial_value; channels * width * height];
         let total = initial_value as f64 * (width * height) as f64;
         Self {
             width,
             height,
             cell_size,
+            channels,
+            capacity: data.clone(),
+            data,
+            totals: vec![total; channels],
+        }
+    }
+
+    /// Single-channel field of `width` × `height` cells taken row-major from `data`.
+    /// The values also become the per-cell regeneration ceilings.
+    pub fn from_grid(
+        width: usize,
+        height: usize,
+        cell_size: f64,
+        data: Vec<f32>,
+    ) -> Result<Self, ResourceGridError> {
+        assert!(cell_size > 0.0, "cell_size must be positive");
+        assert!(width > 0 && height > 0, "grid dimensions must be positive");
+        validate_grid(width, width * height, &data)?;
+        let total = data.iter().map(|&v| v as f64).sum();
+        Ok(Self {
+            width,
+            height,
+            cell_size,
+            channels: 1,
+            capacity: data.clone(),
             data,
-            total,
-            initial_value,
+            totals: vec![total],
+        })
+    }
+
+    /// Replace every channel with the row-major `data` (`height` rows of `width`
+    /// values), which also become the per-cell regeneration ceilings.
+    pub fn set_grid(&mut self, data: &[f32]) -> Result<(), ResourceGridError> {
+        validate_grid(self.width, self.cells(), data)?;
+        let mut values = data.iter();
+        self.init_with(|_, _| *values.next().expect("grid length was validated"));
+        Ok(())
+    }
+
+    /// This field stretched onto the grid for `world_size`: each new cell center
+    /// samples the old field bilinearly at the proportionally scaled position,
+    /// wrapping toroidally, and each channel is then scaled so its total is
+    /// unchanged. Regeneration ceilings are resampled without that scaling.
+    pub fn rescaled(&self, world_size: f64) -> Self {
+        let mut field = Self::with_channels(world_size, self.cell_size, 0.0, self.channels);
+        let stretch_x = (self.width as f64) / (field.width as f64);
+        let stretch_y = (self.height as f64) / (field.height as f64);
+        let (old_cells, new_cells) = (self.cells(), field.cells());
+        for cy in 0..field.height {
+            for cx in 0..field.width {
+                let x = (cx as f64 + 0.5) * self.cell_size * stretch_x;
+                let y = (cy as f64 + 0.5) * self.cell_size * stretch_y;
+                let samples = self.bilinear_cells(x, y);
+                let idx = cy * field.width + cx;
+                for channel in 0..self.channels {
+                    let sample = |values: &[f32]| -> f32 {
+                        samples
+                            .iter()
+                            .map(|&(old, w)| values[channel * old_cells + old] * w)
+                            .sum()
+                    };
+                    field.data[channel * new_cells + idx] = sample(&self.data);
+                    field.capacity[channel * new_cells + idx] = sample(&self.capacity);
+                }
+            }
+        }
+        for channel in 0..self.channels {
+            let range = field.channel_range(channel);
+            let sampled: f64 = field.data[range.clone()].iter().map(|&v| v as f64).sum();
+            if sampled > 0.0 {
+                let factor = (self.totals[channel] / sampled) as f32;
+                field.data[range.clone()]
+                    .iter_mut()
+                    .for_each(|v| *v *= factor);
+            }
+            field.totals[channel] = field.data[range].iter().map(|&v| v as f64).sum();
+        }
+        field
+    }
+
+    /// Set the cell containing a position to `value` on every channel, and make it that
+    /// cell's regeneration ceiling. Coordinates wrap toroidally.
+    pub fn set_cell_value(&mut self, x: f64, y: f64, value: f32) -> Result<(), ResourceGridError> {
+        let idx = self.cell_index(x, y);
+        if !value.is_finite() || value < 0.0 {
+            return Err(ResourceGridError::InvalidValue {
+                row: idx / self.width,
+                col: idx % self.width,
+                value,
+            });
+        }
+        let cells = self.cells();
+        for channel in 0..self.channels {
+            let slot = channel * cells + idx;
+            self.totals[channel] += (value - self.data[slot]) as f64;
+            self.data[slot] = value;
+            self.capacity[slot] = value;
+        }
+        Ok(())
+    }
+
+    /// Reinitialize every cell from `value_at(cell_center_x, cell_center_y)`.
+    ///
+    /// Every channel receives the same layout. The new values also become the per-cell
+    /// regeneration ceilings.
+    fn init_with(&mut self, mut value_at: impl FnMut(f64, f64) -> f32) {
+        let cells = self.cells();
+        for cy in 0..self.height {
+            for cx in 0..self.width {
+                let x = (cx as f64 + 0.5) * self.cell_size;
+                let y = (cy as f64 + 0.5) * self.cell_size;
+                let value = value_at(x, y).max(0.0);
+                for channel in 0..self.channels {
+                    self.data[channel * cells + cy * self.width + cx] = value;
+                }
+            }
         }
+        self.capacity.clone_from(&self.data);
+        for channel in 0..self.channels {
+            self.totals[channel] = self.channel_data(channel).iter().map(|&v| v as f64).sum();
+        }
+    }
+
+    /// Place `n_patches` Gaussian bumps at uniformly drawn centers over a `background`
+    /// level. Each bump peaks at `peak` with standard deviation `patch_radius`, measured
+    /// toroidally; where bumps overlap the larger one wins, so no cell exceeds
+    /// `max(peak, background)`. Returns the patch centers in draw order.
+    pub fn init_patches(
+        &mut self,
+        rng: &mut impl Rng,
+        n_patches: usize,
+        patch_radius: f64,
+        peak: f32,
+        background: f32,
+    ) -> Vec<[f64; 2]> {
+        let extent_x = self.width as f64 * self.cell_size;
+        let extent_y = self.height as f64 * self.cell_size;
+        let centers: Vec<[f64; 2]> = (0..n_patches)
+            .map(|_| {
+                [
+                    rng.random_range(0.0..extent_x),
+                    rng.random_range(0.0..extent_y),
+                ]
+            })
+            .collect();
+        let two_sigma_sq = 2.0 * patch_radius * patch_radius;
+        self.init_with(|x, y| {
+            centers
+                .iter()
+                .map(|c| {
+                    let dx = toroidal_delta(x - c[0], extent_x);
+                    let dy = toroidal_delta(y - c[1], extent_y);
+                    let bump = (-(dx * dx + dy * dy) / two_sigma_sq).exp() as f32;
+                    background + (peak - background) * bump
+                })
+                .fold(background, f32::max)
+        });
+        centers
+    }
+
+    /// Linear gradient from `low` at the start of `axis` to `high` at its far edge,
+    /// sampled at cell centers.
+    pub fn init_gradient(&mut self, axis: GradientAxis, low: f32, high: f32) {
+        let extent = match axis {
+            GradientAxis::X => self.width as f64 * self.cell_size,
+            GradientAxis::Y => self.height as f64 * self.cell_size,
+        };
+        self.init_with(|x, y| {
+            let t = match axis {
+                GradientAxis::X => x / extent,
+                GradientAxis::Y => y / extent,
+            } as f32;
+            low + (high - low) * t
+        });
+    }
+
+    /// Independent uniform noise per cell in `[mean - amplitude, mean + amplitude]`,
+    /// clamped at zero.
+    pub fn init_noise(&mut self, rng: &mut impl Rng, mean: f32, amplitude: f32) {
+        self.init_with(|_, _| {
+            if amplitude > 0.0 {
+                mean + rng.random_range(-amplitude..=amplitude)
+            } else {
+                mean
+            }
+        });
     }
 
-    /// Regenerate resources toward the initial value at the given rate per step.
+    /// Regenerate resources toward each cell's initial value at the given rate per step.
     ///
-    /// Cells are capped at `initial_value`; cells already at or above it are unchanged.
+    /// Cells are capped at their initial value; cells already at or above it are unchanged.
+    /// Applies to every channel.
     pub fn regenerate(&mut self, rate: f32) {
+        for channel in 0..self.channels {
+            self.regenerate_channel(channel, rate);
+        }
+    }
+
+    /// [`ResourceField::regenerate`] restricted to one channel.
+    pub fn regenerate_channel(&mut self, channel: usize, rate: f32) {
+        self.regenerate_channel_with(channel, RegenerationModel::Linear, rate, None);
+    }
+
+    /// Regenerate every channel under `model` (see [`RegenerationModel`]), capping
+    /// each cell at the smaller of its initial value and `cell_capacity`.
+    pub fn regenerate_with(
+        &mut self,
+        model: RegenerationModel,
+        rate: f32,
+        cell_capacity: Option<f32>,
+    ) {
+        for channel in 0..self.channels {
+            self.regenerate_channel_with(channel, model, rate, cell_capacity);
+        }
+    }
+
+    fn regenerate_channel_with(
+        &mut self,
+        channel: usize,
+        model: RegenerationModel,
+        rate: f32,
+        cell_capacity: Option<f32>,
+    ) {
         debug_assert!(rate >= 0.0, "regeneration rate cannot be negative");
-        for cell in &mut self.data {
+        let limit = cell_capacity.unwrap_or(f32::INFINITY);
+        let range = self.channel_range(channel);
+        let mut added = 0.0f64;
+        for (cell, &initial) in self.data[range.clone()]
+            .iter_mut()
+            .zip(&self.capacity[range])
+        {
+            let cap = initial.min(limit);
+            if *cell >= cap {
+                continue;
+            }
             let before = *cell;
-            *cell = (*cell + rate).min(self.initial_value);
-            self.total += (*cell - before) as f64;
+            let growth = match model {
+                RegenerationModel::Linear => rate,
+                RegenerationModel::Logistic => {
+                    rate * before.max(cap * LOGISTIC_SEED_FRACTION) * (1.0 - before / cap)
+                }
+            };
+            *cell = (before + growth).min(cap);
+            added += (*cell - before) as f64;
         }
+        self.totals[channel] += added;
+    }
+
+    /// Regenerate only near `centers`: each channel receives the mass a fully depleted
+    /// field would get from [`ResourceField::regenerate`] (`rate` per cell), split evenly
+    /// over the cells whose centers lie within `radius` of any hotspot center (measured
+    /// toroidally) plus the cell containing each center.
+    ///
+    /// Cells are capped at `max(peak, initial value)`, so mass that would overflow a full
+    /// cell is not redistributed.
+    pub fn regenerate_hotspots(&mut self, centers: &[[f64; 2]], radius: f64, rate: f32, peak: f32) {
+        debug_assert!(rate >= 0.0, "regeneration rate cannot be negative");
+        let cells = self.cells();
+        let in_hotspot = |field: &Self, idx: usize| -> bool {
+            let (cx, cy) = (idx % field.width, idx / field.width);
+            let x = (cx as f64 + 0.5) * field.cell_size;
+            let y = (cy as f64 + 0.5) * field.cell_size;
+            let extent_x = field.width as f64 * field.cell_size;
+            let extent_y = field.height as f64 * field.cell_size;
+            centers.iter().any(|c| {
+                let dx = toroidal_delta(x - c[0], extent_x);
+                let dy = toroidal_delta(y - c[1], extent_y);
+                dx * dx + dy * dy <= radius * radius || field.cell_index(c[0], c[1]) == idx
+            })
+        };
+        let covered = (0..cells).filter(|&idx| in_hotspot(self, idx)).count();
+        if covered == 0 {
+            return;
+        }
+        let share = (rate as f64 * cells as f64 / covered as f64) as f32;
+        for idx in 0..cells {
+            if !in_hotspot(self, idx) {
+                continue;
+            }
+            let cap = peak.max(self.capacity[idx]);
+            for channel in 0..self.channels {
+                let slot = channel * cells + idx;
+                let before = self.data[slot];
+                if before >= cap {
+                    continue;
+                }
+                self.data[slot] = (before + share).min(cap);
+                self.totals[channel] += (self.data[slot] - before) as f64;
+            }
+        }
+    }
+
+    /// Add independent uniform noise in `[-amplitude, amplitude]` to every cell of
+    /// every channel, clamping at zero.
+    pub fn perturb_white(&mut self, rng: &mut impl Rng, amplitude: f32) {
+        self.perturb_with(|_| rng.random_range(-amplitude..=amplitude));
+    }
+
+    /// Add `amplitude * pattern[cell]` to every channel of each cell, clamping at
+    /// zero. `pattern` is one row-major value per cell.
+    pub fn perturb_pattern(&mut self, pattern: &[f32], amplitude: f32) {
+        debug_assert_eq!(pattern.len(), self.cells(), "pattern must cover the grid");
+        self.perturb_with(|idx| amplitude * pattern[idx]);
+    }
+
+    fn perturb_with(&mut self, mut delta: impl FnMut(usize) -> f32) {
+        for channel in 0..self.channels {
+            let range = self.channel_range(channel);
+            let mut total = 0.0f64;
+            for (idx, cell) in self.data[range].iter_mut().enumerate() {
+                *cell = (*cell + delta(idx)).max(0.0);
+                total += *cell as f64;
+            }
+            self.totals[channel] = total;
+        }
+    }
+
+    /// Population variance of cell values over every cell of every channel.
+    pub fn variance(&self) -> f64 {
+        let n = self.data.len() as f64;
+        let mean = self.total() / n;
+        self.data
+            .iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n
+    }
+
+    /// Multiply every cell of every channel by `factor`.
+    pub fn scale(&mut self, factor: f32) {
+        debug_assert!(factor >= 0.0, "scale factor cannot be negative");
+        for cell in &mut self.data {
+            *cell *= factor;
+        }
+        for channel in 0..self.channels {
+            self.totals[channel] = self.data[self.channel_range(channel)]
+                .iter()
+                .map(|&v| v as f64)
+                .sum();
+        }
+    }
+
+    /// Add up to `amount` to the cell containing a position without raising it above
+    /// `cell_cap`. Returns the amount actually deposited.
+    ///
+    /// The amount is split evenly across channels, each capped at `cell_cap`.
+    pub fn deposit(&mut self, x: f64, y: f64, amount: f32, cell_cap: f32) -> f32 {
+        let idx = self.cell_index(x, y);
+        let share = amount / self.channels as f32;
+        let cells = self.cells();
+        let mut deposited = 0.0;
+        for channel in 0..self.channels {
+            let slot = channel * cells + idx;
+            let added = share.min(cell_cap - self.data[slot]).max(0.0);
+            self.data[slot] += added;
+            self.totals[channel] += added as f64;
+            deposited += added;
+        }
+        deposited
+    }
+
+    /// Add a cone of resource to channel 0: `peak` at `center`, falling linearly to
+    /// zero at `radius`, evaluated at cell centers with toroidal distances. Cells may
+    /// rise above their regeneration ceiling. Returns the mass added.
+    pub fn add_cone(&mut self, center: [f64; 2], radius: f64, peak: f32) -> f64 {
+        let extent_x = self.width as f64 * self.cell_size;
+        let extent_y = self.height as f64 * self.cell_size;
+        let mut added = 0.0f64;
+        for idx in 0..self.cells() {
+            let (cx, cy) = (idx % self.width, idx / self.width);
+            let dx = toroidal_delta((cx as f64 + 0.5) * self.cell_size - center[0], extent_x);
+            let dy = toroidal_delta((cy as f64 + 0.5) * self.cell_size - center[1], extent_y);
+            let weight = 1.0 - (dx * dx + dy * dy).sqrt() / radius;
+            if weight <= 0.0 {
+                continue;
+            }
+            let amount = peak * weight as f32;
+            self.data[idx] += amount;
+            added += amount as f64;
+        }
+        self.totals[0] += added;
+        added
     }
 
     /// Get resource value at position. Coordinates wrap toroidally.
@@ -48,25 +473,134 @@ impl ResourceField {
         self.data[cy * self.width + cx]
     }
 
+    /// Bilinearly interpolated resource value at position.
+    ///
+    /// Cell values are treated as samples at cell centers; the four centers surrounding
+    /// the position are blended, wrapping toroidally across the grid edges.
+    pub fn get_bilinear(&self, x: f64, y: f64) -> f32 {
+        self.bilinear_cells(x, y)
+            .iter()
+            .map(|&(idx, w)| self.data[idx] * w)
+            .sum()
+    }
+
+    /// Remove up to `amount` resource from the four cells blended by `get_bilinear`,
+    /// in proportion to each cell's weighted contribution. Returns the amount withdrawn.
+    pub fn take_bilinear(&mut self, x: f64, y: f64, amount: f32) -> f32 {
+        let cells = self.bilinear_cells(x, y);
+        let available: f32 = cells.iter().map(|&(idx, w)| self.data[idx] * w).sum();
+        if available <= 0.0 || amount <= 0.0 {
+            return 0.0;
+        }
+        let fraction = (amount / available).min(1.0);
+        cells
+            .iter()
+            .map(|&(idx, w)| {
+                let share = self.data[idx] * w * fraction;
+                self.take_cell(idx, share)
+            })
+            .sum()
+    }
+
+    /// Flat indices and weights of the four cell centers surrounding a position.
+    /// Weights sum to one; duplicate indices can occur on grids narrower than two cells.
+    pub(crate) fn bilinear_cells(&self, x: f64, y: f64) -> [(usize, f32); 4] {
+        let gx = x / self.cell_size - 0.5;
+        let gy = y / self.cell_size - 0.5;
+        let (fx, fy) = (gx.floor(), gy.floor());
+        let (tx, ty) = ((gx - fx) as f32, (gy - fy) as f32);
+        let x0 = (fx as isize).rem_euclid(self.width as isize) as usize;
+        let y0 = (fy as isize).rem_euclid(self.height as isize) as usize;
+        let x1 = (x0 + 1) % self.width;
+        let y1 = (y0 + 1) % self.height;
+        [
+            (y0 * self.width + x0, (1.0 - tx) * (1.0 - ty)),
+            (y0 * self.width + x1, tx * (1.0 - ty)),
+            (y1 * self.width + x0, (1.0 - tx) * ty),
+            (y1 * self.width + x1, tx * ty),
+        ]
+    }
+
     /// Set resource value at position. Coordinates wrap toroidally.
     pub fn set(&mut self, x: f64, y: f64, value: f32) {
-        let (cx, cy) = self.wrap_coords(x, y);
-        let idx = cy * self.width + cx;
-        let old = self.data[idx];
-        self.data[idx] = value;
-        self.total += (value - old) as f64;
+        self.set_channel(0, x, y, value);
     }
 
     /// Remove up to `amount` resource from the addressed cell and return actual amount withdrawn.
     pub fn take(&mut self, x: f64, y: f64, amount: f32) -> f32 {
+        let idx = self.cell_index(x, y);
+        self.take_cell(idx, amount)
+    }
+
+    /// [`ResourceField::get`] on `channel`.
+    pub fn get_channel(&self, channel: usize, x: f64, y: f64) -> f32 {
+        self.get_cell_channel(channel, self.cell_index(x, y))
+    }
+
+    /// [`ResourceField::set`] on `channel`.
+    pub fn set_channel(&mut self, channel: usize, x: f64, y: f64, value: f32) {
+        let slot = self.channel_range(channel).start + self.cell_index(x, y);
+        let old = self.data[slot];
+        self.data[slot] = value;
+        self.totals[channel] += (value - old) as f64;
+    }
+
+    /// [`ResourceField::take`] on `channel`.
+    pub fn take_channel(&mut self, channel: usize, x: f64, y: f64, amount: f32) -> f32 {
+        let idx = self.cell_index(x, y);
+        self.take_cell_channel(channel, idx, amount)
+    }
+
+    /// Flat index of the cell containing a position. Coordinates wrap toroidally.
+    pub fn cell_index(&self, x: f64, y: f64) -> usize {
         let (cx, cy) = self.wrap_coords(x, y);
-        let idx = cy * self.width + cx;
-        let removed = self.data[idx].min(amount.max(0.0));
-        self.data[idx] -= removed;
-        self.total -= removed as f64;
+        cy * self.width + cx
+    }
+
+    /// Resource value of the cell at a flat index from `cell_index`.
+    pub fn get_cell(&self, idx: usize) -> f32 {
+        self.data[idx]
+    }
+
+    /// Remove up to `amount` from the cell at a flat index and return actual amount withdrawn.
+    pub fn take_cell(&mut self, idx: usize, amount: f32) -> f32 {
+        self.take_cell_channel(0, idx, amount)
+    }
+
+    /// [`ResourceField::get_cell`] on `channel`.
+    pub fn get_cell_channel(&self, channel: usize, idx: usize) -> f32 {
+        self.data[self.channel_range(channel).start + idx]
+    }
+
+    /// [`ResourceField::take_cell`] on `channel`.
+    pub fn take_cell_channel(&mut self, channel: usize, idx: usize, amount: f32) -> f32 {
+        let slot = self.channel_range(channel).start + idx;
+        let removed = self.data[slot].min(amount.max(0.0));
+        self.data[slot] -= removed;
+        self.totals[channel] -= removed as f64;
         removed
     }
 
+    /// Central-difference gradient (∂R/∂x, ∂R/∂y) at the cell with flat index `idx`,
+    /// in resource per world unit, summed over all channels. Neighbors wrap toroidally.
+    pub fn gradient_at_cell(&self, idx: usize) -> [f32; 2] {
+        let (cx, cy) = (idx % self.width, idx / self.width);
+        let left = (cx + self.width - 1) % self.width;
+        let right = (cx + 1) % self.width;
+        let down = (cy + self.height - 1) % self.height;
+        let up = (cy + 1) % self.height;
+        let span = (2.0 * self.cell_size) as f32;
+        let value = |x: usize, y: usize| -> f32 {
+            (0..self.channels)
+                .map(|c| self.get_cell_channel(c, y * self.width + x))
+                .sum()
+        };
+        [
+            (value(right, cy) - value(left, cy)) / span,
+            (value(cx, up) - value(cx, down)) / span,
+        ]
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -79,12 +613,40 @@ impl ResourceField {
         self.cell_size
     }
 
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// All channels, planar.
     pub fn data(&self) -> &[f32] {
         &self.data
     }
 
+    pub fn channel_data(&self, channel: usize) -> &[f32] {
+        &self.data[self.channel_range(channel)]
+    }
+
+    /// Total resource summed over all channels.
     pub fn total(&self) -> f64 {
-        self.total
+        self.totals.iter().sum()
+    }
+
+    pub fn channel_total(&self, channel: usize) -> f64 {
+        self.totals[channel]
+    }
+
+    pub fn channel_totals(&self) -> &[f64] {
+        &self.totals
+    }
+
+    fn cells(&self) -> usize {
+        self.width * self.height
+    }
+
+    fn channel_range(&self, channel: usize) -> std::ops::Range<usize> {
+        debug_assert!(channel < self.channels, "channel out of range");
+        let cells = self.cells();
+        channel * cells..(channel + 1) * cells
     }
 
     fn wrap_coords(&self, x: f64, y: f64) -> (usize, usize) {
@@ -92,18 +654,146 @@ impl ResourceField {
         let cy = ((y / self.cell_size).floor() as isize).rem_euclid(self.height as isize) as usize;
         (cx, cy)
     }
+}
 
-    #[allow(dead_code)]
-    fn clamp_coords(&self, x: f64, y: f64) -> (usize, usize) {
-        let cx = ((x / self.cell_size).max(0.0) as usize).min(self.width - 1);
-        let cy = ((y / self.cell_size).max(0.0) as usize).min(self.height - 1);
-        (cx, cy)
+/// Check that a row-major grid of `width`-wide rows holds `cells` finite,
+/// non-negative values.
+fn validate_grid(width: usize, cells: usize, data: &[f32]) -> Result<(), ResourceGridError> {
+    if data.len() != cells {
+        return Err(ResourceGridError::LengthMismatch {
+            expected: cells,
+            actual: data.len(),
+        });
+    }
+    match data.iter().position(|v| !v.is_finite() || *v < 0.0) {
+        Some(idx) => Err(ResourceGridError::InvalidValue {
+            row: idx / width,
+            col: idx % width,
+            value: data[idx],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Drifting regeneration hotspots for `ResourceDynamics::MovingHotspots`.
+///
+/// Each center starts at a seed-determined position and moves along a straight line
+/// at a fixed speed in its own seed-determined direction, wrapping toroidally.
+#[derive(Clone, Debug)]
+pub struct ResourceHotspots {
+    world_size: f64,
+    origins: Vec<[f64; 2]>,
+    velocities: Vec<[f64; 2]>,
+    centers: Vec<[f64; 2]>,
+}
+
+impl ResourceHotspots {
+    pub fn new(rng: &mut impl Rng, count: usize, speed: f64, world_size: f64) -> Self {
+        let mut origins = Vec::with_capacity(count);
+        let mut velocities = Vec::with_capacity(count);
+        for _ in 0..count {
+            origins.push([
+                rng.random_range(0.0..world_size),
+                rng.random_range(0.0..world_size),
+            ]);
+            let angle = rng.random_range(0.0..std::f64::consts::TAU);
+            velocities.push([speed * angle.cos(), speed * angle.sin()]);
+        }
+        Self {
+            world_size,
+            centers: origins.clone(),
+            origins,
+            velocities,
+        }
+    }
+
+    /// Move every center to its position at `time`.
+    pub fn update(&mut self, time: f64) {
+        for ((center, origin), velocity) in self
+            .centers
+            .iter_mut()
+            .zip(&self.origins)
+            .zip(&self.velocities)
+        {
+            *center = [
+                (origin[0] + velocity[0] * time).rem_euclid(self.world_size),
+                (origin[1] + velocity[1] * time).rem_euclid(self.world_size),
+            ];
+        }
+    }
+
+    /// Centers as of the last `update` (the origins before the first one).
+    pub fn centers(&self) -> &[[f64; 2]] {
+        &self.centers
+    }
+}
+
+/// Zero-mean pattern for `ResourceNoiseKind::SpatiallyCorrelated`: a weighted sum of
+/// [`CorrelatedNoise::MODES`] random toroidal cosine modes, staying within
+/// `[-1, 1]`. The pattern is tabulated per cell when drawn, so applying it
+/// every step allocates nothing.
+#[derive(Clone, Debug, Default)]
+pub struct CorrelatedNoise {
+    pattern: Vec<f32>,
+}
+
+impl CorrelatedNoise {
+    pub const MODES: usize = 4;
+
+    /// Draw new modes for `field`'s grid with wavelengths of at least `length_scale`
+    /// world units (or one full extent, whichever is shorter), reusing the table.
+    pub fn redraw(&mut self, rng: &mut impl Rng, field: &ResourceField, length_scale: f64) {
+        let (width, height) = (field.width, field.height);
+        let max_wavenumber =
+            |cells: usize| ((cells as f64 * field.cell_size / length_scale).floor() as i64).max(1);
+        let (max_kx, max_ky) = (max_wavenumber(width), max_wavenumber(height));
+        self.pattern.clear();
+        self.pattern.resize(width * height, 0.0);
+        for _ in 0..Self::MODES {
+            let mut kx = rng.random_range(0..=max_kx);
+            let ky = rng.random_range(-max_ky..=max_ky);
+            if kx == 0 && ky == 0 {
+                kx = 1;
+            }
+            let weight = rng.random_range(-1.0..=1.0) / Self::MODES as f64;
+            let phase = rng.random_range(0.0..std::f64::consts::TAU);
+            for (idx, value) in self.pattern.iter_mut().enumerate() {
+                let (x, y) = ((idx % width) as f64, (idx / width) as f64);
+                let angle = std::f64::consts::TAU
+                    * (kx as f64 * x / width as f64 + ky as f64 * y / height as f64)
+                    + phase;
+                *value += (weight * angle.cos()) as f32;
+            }
+        }
+    }
+
+    /// Whether a pattern has been drawn for a grid the size of `field`'s.
+    pub fn is_drawn_for(&self, field: &ResourceField) -> bool {
+        self.pattern.len() == field.cells()
+    }
+
+    /// Row-major per-cell values in `[-1, 1]`; empty before the first draw.
+    pub fn pattern(&self) -> &[f32] {
+        &self.pattern
+    }
+}
+
+/// Shortest signed offset equivalent to `delta` on a ring of length `extent`.
+fn toroidal_delta(delta: f64, extent: f64) -> f64 {
+    let d = delta.rem_euclid(extent);
+    if d > extent / 2.0 {
+        d - extent
+    } else {
+        d
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ResourceField;
+    use super::{CorrelatedNoise, ResourceField, ResourceGridError, ResourceHotspots};
+    use crate::config::{GradientAxis, RegenerationModel};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
 
     #[test]
     fn wraps_coordinates_toroidally() {
@@ -113,6 +803,47 @@ mod tests {
         assert!((field.get(19.0, 19.0) - 3.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn wraps_boundary_coordinates_symmetrically() {
+        let mut field = ResourceField::new(10.0, 1.0, 0.0);
+        field.set(9.5, 4.5, 2.0);
+        assert!((field.get(-0.5, 4.5) - field.get(9.5, 4.5)).abs() < f32::EPSILON);
+        assert!((field.get(-0.0001, 4.5) - 2.0).abs() < f32::EPSILON);
+        assert!((field.get(10.0, 4.5) - field.get(0.0, 4.5)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn bilinear_at_cell_corner_is_mean_of_four_cells() {
+        let mut field = ResourceField::new(10.0, 1.0, 0.0);
+        field.set(2.5, 2.5, 1.0);
+        field.set(3.5, 2.5, 2.0);
+        field.set(2.5, 3.5, 3.0);
+        field.set(3.5, 3.5, 4.0);
+        assert!((field.get_bilinear(3.0, 3.0) - 2.5).abs() < 1e-6);
+        // At a cell center the interpolation reproduces the cell value.
+        assert!((field.get_bilinear(3.5, 3.5) - 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bilinear_wraps_across_world_edge() {
+        let mut field = ResourceField::new(10.0, 1.0, 0.0);
+        field.set(9.5, 0.5, 4.0);
+        field.set(0.5, 0.5, 2.0);
+        // Midway between the last and first column centers.
+        assert!((field.get_bilinear(0.0, 0.5) - 3.0).abs() < 1e-6);
+        assert!((field.get_bilinear(10.0, 0.5) - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn take_bilinear_conserves_total() {
+        let mut field = ResourceField::new(10.0, 1.0, 1.0);
+        let before = field.total();
+        let taken = field.take_bilinear(3.2, 7.9, 0.4);
+        assert!((taken - 0.4).abs() < 1e-6);
+        assert!((field.total() - (before - 0.4)).abs() < 1e-5);
+        assert!(field.take_bilinear(3.2, 7.9, 100.0) <= 1.0 + 1e-6);
+    }
+
     #[test]
     fn take_withdraws_and_clamps_to_available() {
         let mut field = ResourceField::new(10.0, 1.0, 0.0);
@@ -133,6 +864,16 @@ mod tests {
         assert!((field.total() - (initial + 0.5)).abs() < 1e-6);
     }
 
+    #[test]
+    fn cell_index_addresses_same_cell_as_coordinates() {
+        let mut field = ResourceField::new(10.0, 1.0, 1.0);
+        let idx = field.cell_index(-0.5, 12.3);
+        assert_eq!(idx, field.cell_index(9.5, 2.3));
+        assert!((field.take_cell(idx, 0.25) - 0.25).abs() < f32::EPSILON);
+        assert!((field.get(9.5, 2.3) - 0.75).abs() < f32::EPSILON);
+        assert!((field.get_cell(idx) - 0.75).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn regenerate_restores_depleted_cells() {
         let mut field = ResourceField::new(10.0, 1.0, 1.0);
@@ -149,6 +890,73 @@ mod tests {
         assert!((field.get(0.0, 0.0) - 1.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn logistic_regeneration_asymptotes_to_cell_capacity() {
+        let mut field = ResourceField::new(10.0, 1.0, 3.0);
+        field.scale(0.0);
+        let mut previous_total = 0.0;
+        let mut previous_gain = f64::INFINITY;
+        for step in 0..400 {
+            field.regenerate_with(RegenerationModel::Logistic, 0.1, Some(1.0));
+            assert!(field.data().iter().all(|&v| v <= 1.0), "step {step}");
+            let gain = field.total() - previous_total;
+            assert!(gain >= 0.0);
+            // Well past the midpoint refill slows instead of staying linear.
+            if previous_total > 60.0 {
+                assert!(
+                    gain <= previous_gain + 1e-4,
+                    "step {step}: {gain} > {previous_gain}"
+                );
+            }
+            previous_total = field.total();
+            previous_gain = gain;
+        }
+        assert!(
+            field.data().iter().all(|&v| v > 0.99),
+            "{:?}",
+            &field.data()[..4]
+        );
+        let expected: f64 = field.data().iter().map(|&v| v as f64).sum();
+        assert!((field.total() - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn cell_capacity_caps_linear_regeneration_below_initial_value() {
+        let mut field = ResourceField::new(10.0, 1.0, 3.0);
+        field.scale(0.0);
+        for _ in 0..10 {
+            field.regenerate_with(RegenerationModel::Linear, 0.25, Some(1.0));
+        }
+        assert!(field.data().iter().all(|&v| v == 1.0));
+
+        let mut unlimited = ResourceField::new(10.0, 1.0, 3.0);
+        let mut plain = unlimited.clone();
+        unlimited.scale(0.5);
+        plain.scale(0.5);
+        unlimited.regenerate_with(RegenerationModel::Linear, 0.25, None);
+        plain.regenerate(0.25);
+        assert_eq!(unlimited.data(), plain.data());
+    }
+
+    #[test]
+    fn regenerate_leaves_cells_above_initial_value() {
+        let mut field = ResourceField::new(10.0, 1.0, 1.0);
+        field.set(3.0, 3.0, 1.5);
+        field.regenerate(0.25);
+        assert!((field.get(3.0, 3.0) - 1.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn deposit_respects_cell_cap_and_tracks_total() {
+        let mut field = ResourceField::new(10.0, 1.0, 1.0);
+        let before = field.total();
+        assert!((field.deposit(4.5, 4.5, 0.5, 2.0) - 0.5).abs() < f32::EPSILON);
+        assert!((field.deposit(4.5, 4.5, 1.0, 2.0) - 0.5).abs() < f32::EPSILON);
+        assert!(field.deposit(4.5, 4.5, 1.0, 2.0).abs() < f32::EPSILON);
+        assert!((field.get(4.5, 4.5) - 2.0).abs() < f32::EPSILON);
+        assert!((field.total() - (before + 1.0)).abs() < 1e-6);
+    }
+
     #[test]
     fn resource_total_tracks_regeneration() {
         let mut field = ResourceField::new(10.0, 1.0, 1.0);
@@ -157,4 +965,263 @@ mod tests {
         field.regenerate(0.5);
         assert!(field.total() > before);
     }
+
+    #[test]
+    fn patches_peak_at_centers_over_background() {
+        let mut field = ResourceField::new(40.0, 1.0, 1.0);
+        let mut rng = ChaCha12Rng::seed_from_u64(3);
+        let centers = field.init_patches(&mut rng, 3, 2.0, 2.0, 0.1);
+        assert_eq!(centers.len(), 3);
+        for c in &centers {
+            assert!(
+                field.get(c[0], c[1]) > 1.5,
+                "patch center should be near peak"
+            );
+        }
+        let min = field.data().iter().copied().fold(f32::INFINITY, f32::min);
+        let max = field.data().iter().copied().fold(0.0, f32::max);
+        assert!((min - 0.1).abs() < 1e-3);
+        assert!(max <= 2.0);
+        let mass: f64 = field.data().iter().map(|&v| v as f64).sum();
+        assert!((field.total() - mass).abs() < 1e-6);
+        assert!(mass > 0.1 * 1600.0 && mass < 2.0 * 1600.0);
+    }
+
+    #[test]
+    fn patches_are_deterministic_for_seed() {
+        let mut a = ResourceField::new(20.0, 1.0, 1.0);
+        let mut b = ResourceField::new(20.0, 1.0, 1.0);
+        a.init_patches(&mut ChaCha12Rng::seed_from_u64(5), 2, 1.5, 3.0, 0.0);
+        b.init_patches(&mut ChaCha12Rng::seed_from_u64(5), 2, 1.5, 3.0, 0.0);
+        assert_eq!(a.data(), b.data());
+    }
+
+    #[test]
+    fn patches_wrap_across_world_edge() {
+        let mut field = ResourceField::new(20.0, 1.0, 0.0);
+        let centers = field.init_patches(&mut ChaCha12Rng::seed_from_u64(1), 1, 3.0, 1.0, 0.0);
+        let [cx, cy] = centers[0];
+        // Points equally far from the center on either side agree across the seam.
+        let left = field.get(cx - 5.0, cy);
+        let right = field.get(cx + 5.0, cy);
+        assert!((left - right).abs() < 0.05, "{left} vs {right}");
+    }
+
+    #[test]
+    fn gradient_total_mass_matches_linear_mean() {
+        let mut field = ResourceField::new(10.0, 1.0, 1.0);
+        field.init_gradient(GradientAxis::X, 0.0, 2.0);
+        // Cell centers sample t = 0.05..0.95, so the mean value is exactly (low + high) / 2.
+        assert!((field.total() - 100.0).abs() < 1e-4);
+        assert!(field.get(0.5, 5.0) < field.get(9.5, 5.0));
+        assert!((field.get(3.5, 0.5) - field.get(3.5, 9.5)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn rescaled_keeps_layout_and_channel_mass() {
+        let mut field = ResourceField::with_channels(10.0, 1.0, 1.0, 2);
+        field.init_gradient(GradientAxis::X, 0.0, 2.0);
+        let _ = field.take_channel(1, 2.5, 2.5, 0.5);
+        for size in [17.0, 6.0] {
+            let rescaled = field.rescaled(size);
+            assert_eq!(rescaled.width(), size as usize);
+            for channel in 0..2 {
+                let (before, after) = (
+                    field.channel_total(channel),
+                    rescaled.channel_total(channel),
+                );
+                assert!(
+                    (after - before).abs() / before < 0.01,
+                    "{size}: {before} -> {after}"
+                );
+            }
+            assert!(rescaled.get(0.5, 1.0) < rescaled.get(size - 0.5, 1.0));
+            assert!(rescaled.get(size / 2.0, 0.5) - rescaled.get(size / 2.0, size - 0.5) < 1e-5);
+        }
+    }
+
+    #[test]
+    fn gradient_along_y_varies_with_y_only() {
+        let mut field = ResourceField::new(10.0, 1.0, 1.0);
+        field.init_gradient(GradientAxis::Y, 1.0, 0.0);
+        assert!(field.get(5.0, 0.5) > field.get(5.0, 9.5));
+        assert!((field.get(0.5, 4.5) - field.get(9.5, 4.5)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn noise_stays_within_amplitude_and_tracks_mass() {
+        let mut field = ResourceField::new(20.0, 1.0, 1.0);
+        field.init_noise(&mut ChaCha12Rng::seed_from_u64(9), 1.0, 0.25);
+        assert!(field.data().iter().all(|&v| (0.75..=1.25).contains(&v)));
+        let mass: f64 = field.data().iter().map(|&v| v as f64).sum();
+        assert!((field.total() - mass).abs() < 1e-6);
+        assert!((mass / 400.0 - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn gradient_at_cell_uses_wrapped_neighbors() {
+        let mut field = ResourceField::new(10.0, 1.0, 1.0);
+        field.init_gradient(GradientAxis::X, 0.0, 1.0);
+        let [gx, gy] = field.gradient_at_cell(field.cell_index(4.5, 4.5));
+        assert!((gx - 0.1).abs() < 1e-6);
+        assert!(gy.abs() < f32::EPSILON);
+        // At the seam the right neighbor wraps to the low end.
+        let [gx, _] = field.gradient_at_cell(field.cell_index(9.5, 4.5));
+        assert!(gx < 0.0);
+    }
+
+    #[test]
+    fn regenerate_caps_at_per_cell_initial_value() {
+        let mut field = ResourceField::new(10.0, 1.0, 1.0);
+        field.init_gradient(GradientAxis::X, 0.0, 1.0);
+        let initial = field.data().to_vec();
+        field.set(9.5, 0.5, 0.0);
+        for _ in 0..10 {
+            field.regenerate(0.5);
+        }
+        assert_eq!(field.data(), initial.as_slice());
+    }
+
+    #[test]
+    fn channels_are_independent_and_tracked_separately() {
+        let mut field = ResourceField::with_channels(10.0, 1.0, 1.0, 2);
+        assert_eq!(field.channels(), 2);
+        assert_eq!(field.data().len(), 200);
+        assert!((field.take_channel(1, 2.5, 2.5, 0.75) - 0.75).abs() < f32::EPSILON);
+        assert!((field.get(2.5, 2.5) - 1.0).abs() < f32::EPSILON);
+        assert!((field.get_channel(1, 2.5, 2.5) - 0.25).abs() < f32::EPSILON);
+        field.set_channel(0, 4.5, 4.5, 3.0);
+        assert!((field.channel_total(0) - 102.0).abs() < 1e-6);
+        assert!((field.channel_total(1) - 99.25).abs() < 1e-6);
+        assert!((field.total() - 201.25).abs() < 1e-6);
+        field.regenerate_channel(1, 1.0);
+        assert!((field.get_channel(1, 2.5, 2.5) - 1.0).abs() < f32::EPSILON);
+        assert!((field.get(4.5, 4.5) - 3.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn deposit_splits_evenly_across_channels() {
+        let mut field = ResourceField::with_channels(10.0, 1.0, 0.0, 2);
+        assert!((field.deposit(1.5, 1.5, 1.0, 2.0) - 1.0).abs() < f32::EPSILON);
+        assert!((field.get_channel(0, 1.5, 1.5) - 0.5).abs() < f32::EPSILON);
+        assert!((field.get_channel(1, 1.5, 1.5) - 0.5).abs() < f32::EPSILON);
+        assert_eq!(field.channel_totals(), &[0.5, 0.5]);
+    }
+
+    #[test]
+    fn hotspot_regeneration_matches_uniform_mass() {
+        let mut uniform = ResourceField::with_channels(20.0, 1.0, 1.0, 2);
+        let mut hotspots = uniform.clone();
+        uniform.scale(0.0);
+        hotspots.scale(0.0);
+        uniform.regenerate(0.1);
+        hotspots.regenerate_hotspots(&[[3.5, 3.5], [19.5, 10.5]], 3.0, 0.1, 4.0);
+        assert!((uniform.total() - hotspots.total()).abs() < 1e-3);
+        assert!(hotspots.get(3.5, 3.5) > 0.1);
+        // The second hotspot wraps across the x seam.
+        assert!((hotspots.get(0.5, 10.5) - hotspots.get(3.5, 3.5)).abs() < f32::EPSILON);
+        assert!(hotspots.get(10.5, 10.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn cone_peaks_at_center_and_wraps() {
+        let mut field = ResourceField::with_channels(20.0, 1.0, 0.0, 2);
+        let added = field.add_cone([0.5, 10.5], 4.0, 2.0);
+        assert!((field.get(0.5, 10.5) - 2.0).abs() < f32::EPSILON);
+        assert!((field.get(2.5, 10.5) - 1.0).abs() < 1e-6);
+        // Across the x seam, symmetric with the cell on the near side.
+        assert!((field.get(18.5, 10.5) - field.get(2.5, 10.5)).abs() < 1e-6);
+        assert!(field.get(10.5, 10.5).abs() < f32::EPSILON);
+        assert!((field.channel_total(0) - added).abs() < 1e-6);
+        assert!(field.channel_total(1).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn hotspots_drift_and_wrap_toroidally() {
+        let mut hotspots = ResourceHotspots::new(&mut ChaCha12Rng::seed_from_u64(2), 2, 1.0, 10.0);
+        let start = hotspots.centers().to_vec();
+        hotspots.update(25.0);
+        for (center, origin) in hotspots.centers().iter().zip(&start) {
+            assert!(center.iter().all(|v| (0.0..10.0).contains(v)));
+            assert_ne!(center, origin);
+        }
+        hotspots.update(0.0);
+        assert_eq!(hotspots.centers(), start.as_slice());
+    }
+
+    #[test]
+    fn from_grid_lays_out_rows_and_sets_ceilings() {
+        let mut field = ResourceField::from_grid(3, 2, 2.0, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0])
+            .expect("grid should be valid");
+        assert_eq!((field.width(), field.height()), (3, 2));
+        assert!((field.get(4.5, 0.5) - 2.0).abs() < f32::EPSILON);
+        assert!((field.get(0.5, 2.5) - 3.0).abs() < f32::EPSILON);
+        assert!((field.total() - 15.0).abs() < 1e-6);
+        field.scale(0.0);
+        field.regenerate(10.0);
+        assert_eq!(field.data(), &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(
+            ResourceField::from_grid(2, 2, 1.0, vec![1.0; 3]).unwrap_err(),
+            ResourceGridError::LengthMismatch {
+                expected: 4,
+                actual: 3
+            }
+        );
+    }
+
+    #[test]
+    fn set_grid_rejects_first_invalid_value_and_keeps_field() {
+        let mut field = ResourceField::with_channels(2.0, 1.0, 1.0, 2);
+        let err = field.set_grid(&[0.0, 1.0, f32::NAN, -1.0]).unwrap_err();
+        assert!(matches!(
+            err,
+            ResourceGridError::InvalidValue { row: 1, col: 0, .. }
+        ));
+        assert_eq!(field.data(), &[1.0; 8]);
+        field.set_grid(&[0.0, 1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(field.channel_data(1), &[0.0, 1.0, 2.0, 3.0]);
+        assert!((field.total() - 12.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn set_cell_value_updates_every_channel_and_ceiling() {
+        let mut field = ResourceField::with_channels(4.0, 1.0, 1.0, 2);
+        field.set_cell_value(1.5, 2.5, 0.0).unwrap();
+        field.regenerate(0.5);
+        assert!(field.get_channel(1, 1.5, 2.5).abs() < f32::EPSILON);
+        assert!((field.total() - 30.0).abs() < 1e-6);
+        assert!(field.set_cell_value(0.5, 0.5, -1.0).is_err());
+    }
+
+    #[test]
+    fn init_layout_applies_to_every_channel() {
+        let mut field = ResourceField::with_channels(10.0, 1.0, 1.0, 3);
+        field.init_gradient(GradientAxis::X, 0.0, 2.0);
+        assert_eq!(field.channel_data(0), field.channel_data(2));
+        assert!((field.total() - 300.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn perturbation_clamps_at_zero_and_updates_totals() {
+        let mut field = ResourceField::new(8.0, 1.0, 0.5);
+        let mut rng = ChaCha12Rng::seed_from_u64(3);
+        field.perturb_white(&mut rng, 2.0);
+        assert!(field.data().iter().all(|&v| v >= 0.0));
+        let sum: f32 = field.data().iter().sum();
+        assert!((field.total() - sum as f64).abs() < 1e-3);
+        assert!(field.variance() > 0.0);
+    }
+
+    #[test]
+    fn correlated_noise_pattern_is_bounded_and_redrawn_per_grid() {
+        let field = ResourceField::new(16.0, 1.0, 1.0);
+        let mut noise = CorrelatedNoise::default();
+        assert!(!noise.is_drawn_for(&field));
+        let mut rng = ChaCha12Rng::seed_from_u64(11);
+        noise.redraw(&mut rng, &field, 4.0);
+        assert!(noise.is_drawn_for(&field));
+        assert!(noise.pattern().iter().all(|v| v.abs() <= 1.0 + 1e-6));
+        let mean = noise.pattern().iter().sum::<f32>() / noise.pattern().len() as f32;
+        assert!(mean.abs() < 1e-3, "mean {mean}");
+    }
 }
@@ -6,9 +6,25 @@ pub fn create_rng(seed: u64) -> ChaCha12Rng {
     ChaCha12Rng::seed_from_u64(seed)
 }
 
+/// Derive a sub-RNG for metrics sampled at `step_index`, distinct across run seeds.
+pub fn derive_step_rng(base_seed: u64, step_index: usize) -> ChaCha12Rng {
+    ChaCha12Rng::seed_from_u64(base_seed ^ (step_index as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+}
+
 /// Derive a sub-RNG for a specific organism, ensuring independent streams.
 pub fn derive_organism_rng(base_seed: u64, organism_id: usize) -> ChaCha12Rng {
     ChaCha12Rng::seed_from_u64(
         base_seed.wrapping_add(organism_id as u64 * crate::constants::RNG_DERIVATION_PRIME),
     )
 }
+
+/// The `RngMode::PerOrganism` stream of the organism with `stable_id`, positioned
+/// at `word_pos` words in. Storing the position rather than the generator keeps
+/// each organism's stream state to a counter.
+pub fn organism_stream_rng(base_seed: u64, stable_id: u64, word_pos: u128) -> ChaCha12Rng {
+    let mut rng = ChaCha12Rng::seed_from_u64(
+        base_seed ^ stable_id.wrapping_mul(crate::constants::RNG_DERIVATION_PRIME),
+    );
+    rng.set_word_pos(word_pos);
+    rng
+}
@@ -0,0 +1,475 @@
+//! Compact binary storage for [`SnapshotFrame`] sequences.
+//!
+//! A file is a header followed by any number of frames, so frames can be streamed
+//! to disk while a run progresses and appended to later:
+//!
+//! - header: the magic `DLSNAP`, a `u16` format version, a `u16` field count, then
+//!   per organism field its name (`u8` length + UTF-8 bytes) and a type code.
+//! - frame: `u64` step, `u32` organism count, `u32` hotspot count, one fixed-width
+//!   record per organism with the header's fields in order, then the hotspot
+//!   centers as `f64` pairs.
+//!
+//! All integers and floats are little-endian. A `species_id` of `u32::MAX` means no
+//! species. Older versions wrote a prefix of the current fields and still load,
+//! with the missing fields zeroed or `None`. `decoded_params` is not stored and
+//! always reads back as `None`.
+
+use crate::metrics::{OrganismSnapshot, SnapshotFrame};
+use std::io::{self, Read, Write};
+use std::{error::Error, fmt};
+
+pub const SNAPSHOT_MAGIC: &[u8; 6] = b"DLSNAP";
+pub const SNAPSHOT_FORMAT_VERSION: u16 = 3;
+
+/// Storage type of a record field, written as its code in the header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Bool = 0,
+    U32 = 1,
+    U64 = 2,
+    F32 = 3,
+    F64 = 4,
+}
+
+impl FieldType {
+    pub const fn width(self) -> usize {
+        match self {
+            FieldType::Bool => 1,
+            FieldType::U32 | FieldType::F32 => 4,
+            FieldType::U64 | FieldType::F64 => 8,
+        }
+    }
+}
+
+/// Per-organism record layout, in storage order.
+pub const ORGANISM_FIELDS: [(&str, FieldType); 15] = [
+    ("stable_id", FieldType::U64),
+    ("generation", FieldType::U32),
+    ("age_steps", FieldType::U64),
+    ("energy", FieldType::F32),
+    ("waste", FieldType::F32),
+    ("boundary_integrity", FieldType::F32),
+    ("maturity", FieldType::F32),
+    ("center_x", FieldType::F64),
+    ("center_y", FieldType::F64),
+    ("n_agents", FieldType::U32),
+    ("dormant", FieldType::Bool),
+    ("external_uptake", FieldType::F32),
+    ("internal_conversion", FieldType::F32),
+    ("waste_produced", FieldType::F32),
+    ("species_id", FieldType::U32),
+];
+
+/// Number of [`ORGANISM_FIELDS`] written by each format version, from version 1.
+const VERSION_FIELD_COUNTS: [usize; SNAPSHOT_FORMAT_VERSION as usize] = [11, 14, 15];
+
+const NO_SPECIES: u32 = u32::MAX;
+
+/// Bytes per record holding the first `count` fields.
+const fn record_size(count: usize) -> usize {
+    let mut size = 0;
+    let mut i = 0;
+    while i < count {
+        size += ORGANISM_FIELDS[i].1.width();
+        i += 1;
+    }
+    size
+}
+
+/// Bytes per organism record.
+pub const ORGANISM_RECORD_SIZE: usize = record_size(ORGANISM_FIELDS.len());
+
+#[derive(Debug)]
+pub enum SnapshotFileError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u16),
+    /// The header's field list differs from [`ORGANISM_FIELDS`].
+    FieldMismatch,
+    /// The data ends inside a frame; frames before it were intact.
+    TruncatedFrame {
+        complete_frames: usize,
+    },
+}
+
+impl fmt::Display for SnapshotFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotFileError::Io(err) => write!(f, "snapshot file I/O failed: {err}"),
+            SnapshotFileError::BadMagic => write!(f, "not a snapshot file (bad magic)"),
+            SnapshotFileError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported snapshot format version {version} (expected {SNAPSHOT_FORMAT_VERSION})"
+            ),
+            SnapshotFileError::FieldMismatch => {
+                write!(f, "snapshot header field list does not match this version")
+            }
+            SnapshotFileError::TruncatedFrame { complete_frames } => write!(
+                f,
+                "snapshot data ends mid-frame after {complete_frames} complete frames"
+            ),
+        }
+    }
+}
+
+impl Error for SnapshotFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotFileError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotFileError {
+    fn from(err: io::Error) -> Self {
+        SnapshotFileError::Io(err)
+    }
+}
+
+/// Streams frames to `inner` in the binary snapshot format.
+pub struct SnapshotWriter<W: Write> {
+    inner: W,
+    record: Vec<u8>,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    /// Start a new file: writes the header immediately.
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        inner.write_all(SNAPSHOT_MAGIC)?;
+        inner.write_all(&SNAPSHOT_FORMAT_VERSION.to_le_bytes())?;
+        inner.write_all(&(ORGANISM_FIELDS.len() as u16).to_le_bytes())?;
+        for (name, ty) in ORGANISM_FIELDS {
+            inner.write_all(&[name.len() as u8])?;
+            inner.write_all(name.as_bytes())?;
+            inner.write_all(&[ty as u8])?;
+        }
+        Ok(Self::appending(inner))
+    }
+
+    /// Continue a file whose header was already written, e.g. one reopened in
+    /// append mode.
+    pub fn appending(inner: W) -> Self {
+        Self {
+            inner,
+            record: Vec::new(),
+        }
+    }
+
+    pub fn write_frame(&mut self, frame: &SnapshotFrame) -> io::Result<()> {
+        let buf = &mut self.record;
+        buf.clear();
+        buf.extend_from_slice(&(frame.step as u64).to_le_bytes());
+        buf.extend_from_slice(&(frame.organisms.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&(frame.resource_hotspots.len() as u32).to_le_bytes());
+        for org in &frame.organisms {
+            buf.extend_from_slice(&org.stable_id.to_le_bytes());
+            buf.extend_from_slice(&org.generation.to_le_bytes());
+            buf.extend_from_slice(&(org.age_steps as u64).to_le_bytes());
+            buf.extend_from_slice(&org.energy.to_le_bytes());
+            buf.extend_from_slice(&org.waste.to_le_bytes());
+            buf.extend_from_slice(&org.boundary_integrity.to_le_bytes());
+            buf.extend_from_slice(&org.maturity.to_le_bytes());
+            buf.extend_from_slice(&org.center_x.to_le_bytes());
+            buf.extend_from_slice(&org.center_y.to_le_bytes());
+            buf.extend_from_slice(&(org.n_agents as u32).to_le_bytes());
+            buf.push(org.dormant as u8);
+            buf.extend_from_slice(&org.external_uptake.to_le_bytes());
+            buf.extend_from_slice(&org.internal_conversion.to_le_bytes());
+            buf.extend_from_slice(&org.waste_produced.to_le_bytes());
+            buf.extend_from_slice(&org.species_id.unwrap_or(NO_SPECIES).to_le_bytes());
+        }
+        for [x, y] in &frame.resource_hotspots {
+            buf.extend_from_slice(&x.to_le_bytes());
+            buf.extend_from_slice(&y.to_le_bytes());
+        }
+        self.inner.write_all(buf)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Destination for frames streamed during a run by
+/// [`World::try_run_experiment_with_snapshot_writer`](crate::world::World::try_run_experiment_with_snapshot_writer).
+pub trait SnapshotSink {
+    fn write_frame(&mut self, frame: &SnapshotFrame) -> io::Result<()>;
+
+    /// Called once after the last frame of a run.
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+impl<W: Write> SnapshotSink for SnapshotWriter<W> {
+    fn write_frame(&mut self, frame: &SnapshotFrame) -> io::Result<()> {
+        SnapshotWriter::write_frame(self, frame)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        SnapshotWriter::flush(self)
+    }
+}
+
+/// Read a header and every frame after it.
+pub fn read_snapshot_frames(
+    mut reader: impl Read,
+) -> Result<Vec<SnapshotFrame>, SnapshotFileError> {
+    let record_size = record_size(read_header(&mut reader)?);
+    let mut frames = Vec::new();
+    let mut head = [0u8; 16];
+    loop {
+        match read_full(&mut reader, &mut head)? {
+            0 => return Ok(frames),
+            n if n < head.len() => {
+                return Err(SnapshotFileError::TruncatedFrame {
+                    complete_frames: frames.len(),
+                });
+            }
+            _ => {}
+        }
+        let step = u64::from_le_bytes(head[0..8].try_into().expect("8 bytes")) as usize;
+        let organisms = u32::from_le_bytes(head[8..12].try_into().expect("4 bytes")) as usize;
+        let hotspots = u32::from_le_bytes(head[12..16].try_into().expect("4 bytes")) as usize;
+        let mut body = vec![0u8; organisms * record_size + hotspots * 16];
+        if read_full(&mut reader, &mut body)? < body.len() {
+            return Err(SnapshotFileError::TruncatedFrame {
+                complete_frames: frames.len(),
+            });
+        }
+        let (records, centers) = body.split_at(organisms * record_size);
+        frames.push(SnapshotFrame {
+            step,
+            organisms: records
+                .chunks_exact(record_size)
+                .map(decode_record)
+                .collect(),
+            resource_hotspots: centers
+                .chunks_exact(16)
+                .map(|c| {
+                    [
+                        f64::from_le_bytes(c[0..8].try_into().expect("8 bytes")),
+                        f64::from_le_bytes(c[8..16].try_into().expect("8 bytes")),
+                    ]
+                })
+                .collect(),
+        });
+    }
+}
+
+/// Check the header and return how many of [`ORGANISM_FIELDS`] its records hold.
+fn read_header(reader: &mut impl Read) -> Result<usize, SnapshotFileError> {
+    let mut magic = [0u8; 6];
+    if read_full(reader, &mut magic)? < magic.len() || &magic != SNAPSHOT_MAGIC {
+        return Err(SnapshotFileError::BadMagic);
+    }
+    let version = read_u16(reader)?;
+    let Some(&field_count) = VERSION_FIELD_COUNTS.get(usize::from(version).wrapping_sub(1)) else {
+        return Err(SnapshotFileError::UnsupportedVersion(version));
+    };
+    if read_u16(reader)? as usize != field_count {
+        return Err(SnapshotFileError::FieldMismatch);
+    }
+    for (name, ty) in &ORGANISM_FIELDS[..field_count] {
+        let mut len = [0u8; 1];
+        reader.read_exact(&mut len)?;
+        let mut stored = vec![0u8; len[0] as usize + 1];
+        reader.read_exact(&mut stored)?;
+        let (stored_name, stored_ty) = stored.split_at(len[0] as usize);
+        if stored_name != name.as_bytes() || stored_ty[0] != *ty as u8 {
+            return Err(SnapshotFileError::FieldMismatch);
+        }
+    }
+    Ok(field_count)
+}
+
+fn read_u16(reader: &mut impl Read) -> io::Result<u16> {
+    let mut bytes = [0u8; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+/// Fill `buf` as far as the reader allows; returns the bytes read, short only at EOF.
+/// Shared with [`crate::trajectory_file`], which frames its stream the same way.
+pub(crate) fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+fn decode_record(record: &[u8]) -> OrganismSnapshot {
+    let mut at = 0;
+    let mut take = |n: usize| {
+        let bytes = &record[at..at + n];
+        at += n;
+        bytes
+    };
+    let mut snapshot = OrganismSnapshot {
+        stable_id: u64::from_le_bytes(take(8).try_into().expect("8 bytes")),
+        generation: u32::from_le_bytes(take(4).try_into().expect("4 bytes")),
+        age_steps: u64::from_le_bytes(take(8).try_into().expect("8 bytes")) as usize,
+        energy: f32::from_le_bytes(take(4).try_into().expect("4 bytes")),
+        waste: f32::from_le_bytes(take(4).try_into().expect("4 bytes")),
+        boundary_integrity: f32::from_le_bytes(take(4).try_into().expect("4 bytes")),
+        maturity: f32::from_le_bytes(take(4).try_into().expect("4 bytes")),
+        center_x: f64::from_le_bytes(take(8).try_into().expect("8 bytes")),
+        center_y: f64::from_le_bytes(take(8).try_into().expect("8 bytes")),
+        n_agents: u32::from_le_bytes(take(4).try_into().expect("4 bytes")) as usize,
+        dormant: take(1)[0] != 0,
+        external_uptake: 0.0,
+        internal_conversion: 0.0,
+        waste_produced: 0.0,
+        species_id: None,
+        decoded_params: None,
+    };
+    if record.len() >= record_size(VERSION_FIELD_COUNTS[1]) {
+        snapshot.external_uptake = f32::from_le_bytes(take(4).try_into().expect("4 bytes"));
+        snapshot.internal_conversion = f32::from_le_bytes(take(4).try_into().expect("4 bytes"));
+        snapshot.waste_produced = f32::from_le_bytes(take(4).try_into().expect("4 bytes"));
+    }
+    if record.len() >= record_size(VERSION_FIELD_COUNTS[2]) {
+        let species_id = u32::from_le_bytes(take(4).try_into().expect("4 bytes"));
+        snapshot.species_id = (species_id != NO_SPECIES).then_some(species_id);
+    }
+    snapshot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(step: usize, organisms: usize) -> SnapshotFrame {
+        SnapshotFrame {
+            step,
+            organisms: (0..organisms)
+                .map(|i| OrganismSnapshot {
+                    stable_id: 1_000 + i as u64,
+                    generation: i as u32,
+                    age_steps: step * 3 + i,
+                    energy: 0.25 * i as f32,
+                    waste: 0.125,
+                    boundary_integrity: 0.9,
+                    maturity: 1.0,
+                    center_x: 12.5 + i as f64,
+                    center_y: 40.0 / (i + 1) as f64,
+                    n_agents: 10 + i,
+                    dormant: i % 2 == 1,
+                    external_uptake: 0.5 * i as f32,
+                    internal_conversion: 0.375,
+                    waste_produced: 0.0625 * i as f32,
+                    species_id: (i != 1).then_some(i as u32 / 2),
+                    decoded_params: None,
+                })
+                .collect(),
+            resource_hotspots: if step.is_multiple_of(2) {
+                vec![[1.5, 2.5], [30.0, 0.25]]
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    #[test]
+    fn record_size_matches_field_list() {
+        assert_eq!(record_size(VERSION_FIELD_COUNTS[0]), 57);
+        assert_eq!(record_size(VERSION_FIELD_COUNTS[1]), 69);
+        assert_eq!(ORGANISM_RECORD_SIZE, 73);
+    }
+
+    #[test]
+    fn frames_round_trip_including_appended_ones() {
+        let frames = vec![frame(10, 3), frame(20, 0), frame(30, 5)];
+        let mut writer = SnapshotWriter::new(Vec::new()).unwrap();
+        writer.write_frame(&frames[0]).unwrap();
+        writer.write_frame(&frames[1]).unwrap();
+        let mut appending = SnapshotWriter::appending(writer.into_inner());
+        appending.write_frame(&frames[2]).unwrap();
+        let bytes = appending.into_inner();
+        assert_eq!(read_snapshot_frames(bytes.as_slice()).unwrap(), frames);
+    }
+
+    #[test]
+    fn truncated_and_foreign_data_are_rejected() {
+        let mut writer = SnapshotWriter::new(Vec::new()).unwrap();
+        writer.write_frame(&frame(1, 2)).unwrap();
+        writer.write_frame(&frame(2, 2)).unwrap();
+        let bytes = writer.into_inner();
+        assert!(matches!(
+            read_snapshot_frames(&bytes[..bytes.len() - 3]),
+            Err(SnapshotFileError::TruncatedFrame { complete_frames: 1 })
+        ));
+        assert!(matches!(
+            read_snapshot_frames(&b"{\"step\": 1}"[..]),
+            Err(SnapshotFileError::BadMagic)
+        ));
+        let mut newer = bytes.clone();
+        newer[6] = 4;
+        assert!(matches!(
+            read_snapshot_frames(newer.as_slice()),
+            Err(SnapshotFileError::UnsupportedVersion(4))
+        ));
+    }
+
+    #[test]
+    fn older_versions_load_with_missing_fields_defaulted() {
+        for version in 1..SNAPSHOT_FORMAT_VERSION {
+            let field_count = VERSION_FIELD_COUNTS[usize::from(version) - 1];
+            let mut expected = frame(10, 3);
+            let mut bytes = SNAPSHOT_MAGIC.to_vec();
+            bytes.extend_from_slice(&version.to_le_bytes());
+            bytes.extend_from_slice(&(field_count as u16).to_le_bytes());
+            for (name, ty) in &ORGANISM_FIELDS[..field_count] {
+                bytes.push(name.len() as u8);
+                bytes.extend_from_slice(name.as_bytes());
+                bytes.push(*ty as u8);
+            }
+            let mut writer = SnapshotWriter::appending(Vec::new());
+            writer.write_frame(&expected).unwrap();
+            let frame_bytes = writer.into_inner();
+            bytes.extend_from_slice(&frame_bytes[..16]);
+            for record in frame_bytes[16..].chunks(ORGANISM_RECORD_SIZE).take(3) {
+                bytes.extend_from_slice(&record[..record_size(field_count)]);
+            }
+            bytes.extend_from_slice(&frame_bytes[16 + 3 * ORGANISM_RECORD_SIZE..]);
+
+            for org in &mut expected.organisms {
+                org.species_id = None;
+                if version < 2 {
+                    org.external_uptake = 0.0;
+                    org.internal_conversion = 0.0;
+                    org.waste_produced = 0.0;
+                }
+            }
+            assert_eq!(
+                read_snapshot_frames(bytes.as_slice()).unwrap(),
+                vec![expected],
+                "version {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn binary_frames_are_much_smaller_than_json() {
+        let frames: Vec<_> = (1..=20).map(|s| frame(s * 100, 200)).collect();
+        let mut writer = SnapshotWriter::new(Vec::new()).unwrap();
+        for f in &frames {
+            writer.write_frame(f).unwrap();
+        }
+        let binary = writer.into_inner().len();
+        let json = serde_json::to_vec(&frames).unwrap().len();
+        assert!(
+            binary * 3 < json,
+            "binary {binary} bytes vs json {json} bytes"
+        );
+    }
+}
@@ -1,11 +1,12 @@
 use crate::agent::Agent;
-use rstar::{RTree, RTreeObject, AABB};
+use rstar::{PointDistance, RTree, RTreeObject, AABB};
 use std::collections::HashSet;
 
 /// Lightweight position-only struct for spatial indexing to avoid cloning full agents.
 #[derive(Clone, Debug)]
 pub struct AgentLocation {
     pub id: u32,
+    pub organism_id: u16,
     pub position: [f64; 2],
 }
 
@@ -17,12 +18,21 @@ impl RTreeObject for AgentLocation {
     }
 }
 
+impl PointDistance for AgentLocation {
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        let dx = self.position[0] - point[0];
+        let dy = self.position[1] - point[1];
+        dx * dx + dy * dy
+    }
+}
+
 /// Build an R*-tree from agent positions via bulk_load (O(n log n)).
 pub fn build_index(agents: &[Agent]) -> RTree<AgentLocation> {
     let locations: Vec<AgentLocation> = agents
         .iter()
         .map(|a| AgentLocation {
             id: a.id,
+            organism_id: a.organism_id,
             position: a.position,
         })
         .collect();
@@ -41,6 +51,7 @@ pub fn build_index_active(agents: &[Agent], organism_alive: &[bool]) -> RTree<Ag
         })
         .map(|a| AgentLocation {
             id: a.id,
+            organism_id: a.organism_id,
             position: a.position,
         })
         .collect();
@@ -57,12 +68,34 @@ pub fn count_neighbors(
     world_size: f64,
 ) -> usize {
     let mut count = 0usize;
-    for_each_unique_neighbor(tree, center, radius, self_id, world_size, |_| {
+    for_each_neighbor(tree, center, radius, self_id, world_size, |_| {
         count += 1;
     });
     count
 }
 
+/// Count neighbors within `radius` of `center` (excludes `self_id`), split into
+/// `(same, other)` by whether they belong to `self_org`, in one tree traversal.
+/// Allocation-free.
+pub fn count_neighbors_split(
+    tree: &RTree<AgentLocation>,
+    center: [f64; 2],
+    radius: f64,
+    self_id: u32,
+    self_org: u16,
+    world_size: f64,
+) -> (usize, usize) {
+    let (mut same, mut other) = (0usize, 0usize);
+    for_each_neighbor(tree, center, radius, self_id, world_size, |loc| {
+        if loc.organism_id == self_org {
+            same += 1;
+        } else {
+            other += 1;
+        }
+    });
+    (same, other)
+}
+
 /// Query neighbors within `radius` of `center`, returning their agent IDs.
 /// Uses AABB envelope query then filters by Euclidean distance.
 /// Excludes the agent with `self_id`.
@@ -74,75 +107,84 @@ pub fn query_neighbors(
     world_size: f64,
 ) -> Vec<u32> {
     let mut result = Vec::new();
-    for_each_unique_neighbor(tree, center, radius, self_id, world_size, |id| {
-        result.push(id);
+    for_each_neighbor(tree, center, radius, self_id, world_size, |loc| {
+        result.push(loc.id);
     });
     result.sort_unstable();
     result
 }
 
-fn for_each_unique_neighbor(
+/// Query windows covering the disc of `radius` around `center` on a torus of side
+/// `world_size`: the disc itself, plus its images shifted by `world_size` across each
+/// seam it reaches. Each item pairs the (possibly shifted) center with its square
+/// envelope, so a point inside an envelope is within planar reach of that center.
+pub fn toroidal_envelopes(
+    center: [f64; 2],
+    radius: f64,
+    world_size: f64,
+) -> impl Iterator<Item = ([f64; 2], AABB<[f64; 2]>)> {
+    let (x_offsets, x_len) = wrap_offsets(center[0], radius, world_size);
+    let (y_offsets, y_len) = wrap_offsets(center[1], radius, world_size);
+    x_offsets.into_iter().take(x_len).flat_map(move |xoff| {
+        y_offsets.into_iter().take(y_len).map(move |yoff| {
+            let translated = [center[0] + xoff, center[1] + yoff];
+            let envelope = AABB::from_corners(
+                [translated[0] - radius, translated[1] - radius],
+                [translated[0] + radius, translated[1] + radius],
+            );
+            (translated, envelope)
+        })
+    })
+}
+
+/// Squared distance between `a` and `b` on a torus of side `world_size`.
+pub fn toroidal_distance_sq(a: [f64; 2], b: [f64; 2], world_size: f64) -> f64 {
+    let half = world_size * 0.5;
+    let mut dx = (a[0] - b[0]).abs();
+    if dx > half {
+        dx = world_size - dx;
+    }
+    let mut dy = (a[1] - b[1]).abs();
+    if dy > half {
+        dy = world_size - dy;
+    }
+    dx * dx + dy * dy
+}
+
+/// Visit each neighbor within `radius` of `center` exactly once (excludes `self_id`).
+/// The visitor receives the full location so callers can inspect `organism_id`.
+pub fn for_each_neighbor(
     tree: &RTree<AgentLocation>,
     center: [f64; 2],
     radius: f64,
     self_id: u32,
     world_size: f64,
-    mut visitor: impl FnMut(u32),
+    mut visitor: impl FnMut(&AgentLocation),
 ) {
     assert!(
         world_size.is_finite() && world_size > 0.0,
         "world_size must be positive and finite"
     );
-    let (x_offsets, x_len) = wrap_offsets(center[0], radius, world_size);
-    let (y_offsets, y_len) = wrap_offsets(center[1], radius, world_size);
     let r_sq = radius * radius;
 
-    // Fast path: no boundary wrapping means no duplicate candidates across envelopes.
-    if x_len == 1 && y_len == 1 {
-        let envelope = AABB::from_corners(
-            [center[0] - radius, center[1] - radius],
-            [center[0] + radius, center[1] + radius],
-        );
-        for loc in tree.locate_in_envelope(&envelope) {
-            if loc.id == self_id {
-                continue;
-            }
-            let dx = loc.position[0] - center[0];
-            let dy = loc.position[1] - center[1];
-            if dx * dx + dy * dy <= r_sq {
-                visitor(loc.id);
-            }
-        }
-        return;
-    }
-
-    // Fast path 2: small radius guarantees disjoint envelopes.
-    // When radius * 2 < world_size, the toroidal query windows (shifted by world_size)
-    // are mutually disjoint in the [0, world_size) coordinate space.
-    // This allows skipping the HashSet deduplication.
+    // Fast path: when radius * 2 < world_size, the toroidal query windows (shifted by
+    // world_size) are mutually disjoint in the [0, world_size) coordinate space, so no
+    // candidate is seen twice and the HashSet deduplication can be skipped.
     if radius * 2.0 < world_size {
-        for &xoff in &x_offsets[..x_len] {
-            for &yoff in &y_offsets[..y_len] {
-                let translated = [center[0] + xoff, center[1] + yoff];
-                let envelope = AABB::from_corners(
-                    [translated[0] - radius, translated[1] - radius],
-                    [translated[0] + radius, translated[1] + radius],
-                );
-
-                for loc in tree.locate_in_envelope(&envelope) {
-                    if loc.id == self_id {
-                        continue;
-                    }
-                    // Direct distance calculation works because loc is guaranteed to be
-                    // within the query envelope, so |loc - translated| <= radius.
-                    // translated = center + offset.
-                    // dx = loc - translated = loc - center - offset.
-                    let dx = loc.position[0] - translated[0];
-                    let dy = loc.position[1] - translated[1];
-
-                    if dx * dx + dy * dy <= r_sq {
-                        visitor(loc.id);
-                    }
+        for (translated, envelope) in toroidal_envelopes(center, radius, world_size) {
+            for loc in tree.locate_in_envelope(&envelope) {
+                if loc.id == self_id {
+                    continue;
+                }
+                // Direct distance calculation works because loc is guaranteed to be
+                // within the query envelope, so |loc - translated| <= radius.
+                // translated = center + offset.
+                // dx = loc - translated = loc - center - offset.
+                let dx = loc.position[0] - translated[0];
+                let dy = loc.position[1] - translated[1];
+
+                if dx * dx + dy * dy <= r_sq {
+                    visitor(loc);
                 }
             }
         }
@@ -150,29 +192,80 @@ fn for_each_unique_neighbor(
     }
 
     let mut seen = HashSet::new();
-
-    for &xoff in &x_offsets[..x_len] {
-        for &yoff in &y_offsets[..y_len] {
-            let translated = [center[0] + xoff, center[1] + yoff];
-            let envelope = AABB::from_corners(
-                [translated[0] - radius, translated[1] - radius],
-                [translated[0] + radius, translated[1] + radius],
-            );
-
-            for loc in tree.locate_in_envelope(&envelope) {
-                if loc.id == self_id {
-                    continue;
-                }
-                let dx = wrapped_delta(loc.position[0] - center[0], world_size);
-                let dy = wrapped_delta(loc.position[1] - center[1], world_size);
-                if dx * dx + dy * dy <= r_sq && seen.insert(loc.id) {
-                    visitor(loc.id);
-                }
+    for (_, envelope) in toroidal_envelopes(center, radius, world_size) {
+        for loc in tree.locate_in_envelope(&envelope) {
+            if loc.id == self_id {
+                continue;
+            }
+            if toroidal_distance_sq(loc.position, center, world_size) <= r_sq && seen.insert(loc.id)
+            {
+                visitor(loc);
             }
         }
     }
 }
 
+/// Neighbors within `radius` of `center` (excluding `self_id`) as `(agent id,
+/// toroidal distance)` pairs in ascending distance, ties broken by id, keeping at
+/// most `max_results`. `out` is cleared first so one buffer can serve many queries.
+pub fn query_neighbors_with_distances(
+    tree: &RTree<AgentLocation>,
+    center: [f64; 2],
+    radius: f64,
+    self_id: u32,
+    world_size: f64,
+    max_results: Option<usize>,
+    out: &mut Vec<(u32, f64)>,
+) {
+    out.clear();
+    for_each_neighbor(tree, center, radius, self_id, world_size, |loc| {
+        let distance = toroidal_distance_sq(loc.position, center, world_size).sqrt();
+        out.push((loc.id, distance));
+    });
+    out.sort_unstable_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    if let Some(max) = max_results {
+        out.truncate(max);
+    }
+}
+
+/// The `k` agents nearest to `center` by toroidal distance (excluding `exclude_id`),
+/// as `(agent id, distance)` pairs in ascending distance, ties broken by id. Yields
+/// fewer than `k` items when the tree holds fewer other agents.
+///
+/// The tree's metric is planar, so its k-th nearest neighbor only bounds the toroidal
+/// k-th distance from above (wrapping never lengthens a distance). All agents within
+/// that bound are then gathered across the wrapped images of `center` and re-ranked.
+pub fn k_nearest(
+    tree: &RTree<AgentLocation>,
+    center: [f64; 2],
+    k: usize,
+    exclude_id: u32,
+    world_size: f64,
+) -> impl Iterator<Item = (u32, f64)> {
+    let mut found = Vec::new();
+    if k > 0 {
+        let planar_kth = tree
+            .nearest_neighbor_iter_with_distance_2(&center)
+            .filter(|(loc, _)| loc.id != exclude_id)
+            .nth(k - 1)
+            .map(|(_, d2)| d2.sqrt());
+        // Without k planar candidates every agent is needed; any toroidal distance
+        // is below `world_size`. The small margin keeps the k-th candidate inside the
+        // bound despite the square root round trip.
+        let bound = planar_kth.map_or(world_size, |d| d * (1.0 + 1e-9) + f64::EPSILON);
+        query_neighbors_with_distances(
+            tree,
+            center,
+            bound,
+            exclude_id,
+            world_size,
+            Some(k),
+            &mut found,
+        );
+    }
+    found.into_iter()
+}
+
 fn wrap_offsets(coord: f64, radius: f64, world_size: f64) -> ([f64; 3], usize) {
     let mut offsets = [0.0; 3];
     let mut len = 1usize;
@@ -187,13 +280,10 @@ fn wrap_offsets(coord: f64, radius: f64, world_size: f64) -> ([f64; 3], usize) {
     (offsets, len)
 }
 
-fn wrapped_delta(delta: f64, world_size: f64) -> f64 {
-    (delta + world_size / 2.0).rem_euclid(world_size) - world_size / 2.0
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     fn make_agent(id: u32, x: f64, y: f64) -> Agent {
         Agent::new(id, 0, [x, y])
@@ -211,6 +301,60 @@ mod tests {
         assert_eq!(result, vec![0, 1]);
     }
 
+    #[test]
+    fn for_each_neighbor_reports_organism_ids_across_wrap() {
+        let agents = vec![
+            Agent::new(0, 3, [0.5, 50.0]),
+            Agent::new(1, 7, [99.5, 50.0]), // wrapped distance 1.0
+            Agent::new(2, 9, [50.0, 50.0]),
+        ];
+        let tree = build_index(&agents);
+        let mut seen = Vec::new();
+        for_each_neighbor(&tree, [0.5, 50.0], 1.5, 0, 100.0, |loc| {
+            seen.push((loc.id, loc.organism_id));
+        });
+        assert_eq!(seen, vec![(1, 7)]);
+    }
+
+    #[test]
+    fn count_neighbors_split_separates_same_and_other() {
+        let agents = vec![
+            Agent::new(0, 1, [0.5, 50.0]),
+            Agent::new(1, 1, [99.5, 50.0]), // same organism, across the wrap
+            Agent::new(2, 2, [1.0, 50.0]),
+            Agent::new(3, 2, [30.0, 50.0]), // out of range
+        ];
+        let tree = build_index(&agents);
+        assert_eq!(
+            count_neighbors_split(&tree, [0.5, 50.0], 2.0, 0, 1, 100.0),
+            (1, 1)
+        );
+    }
+
+    #[test]
+    fn count_neighbors_split_counts_exactly_across_corner_seam() {
+        let agents = vec![
+            Agent::new(0, 3, [99.5, 99.5]),
+            Agent::new(1, 3, [0.5, 0.5]),   // diagonal across both seams
+            Agent::new(2, 3, [99.0, 0.2]),  // across the y seam
+            Agent::new(3, 4, [0.2, 99.0]),  // across the x seam
+            Agent::new(4, 4, [98.5, 98.5]), // inside, no wrap
+            Agent::new(5, 4, [2.0, 2.0]),   // just outside the radius
+            Agent::new(6, 5, [50.0, 50.0]),
+        ];
+        let tree = build_index(&agents);
+        assert_eq!(
+            count_neighbors_split(&tree, [99.5, 99.5], 2.0, 0, 3, 100.0),
+            (2, 2)
+        );
+        let (same, other) = count_neighbors_split(&tree, [99.5, 99.5], 2.0, 0, 4, 100.0);
+        assert_eq!((same, other), (2, 2));
+        assert_eq!(
+            same + other,
+            count_neighbors(&tree, [99.5, 99.5], 2.0, 0, 100.0)
+        );
+    }
+
     #[test]
     fn query_excludes_self() {
         let agents = vec![make_agent(0, 5.0, 5.0), make_agent(1, 6.0, 5.0)];
@@ -282,6 +426,185 @@ mod tests {
         assert_eq!(result, vec![0]);
     }
 
+    fn brute_force_k_nearest(
+        agents: &[Agent],
+        center: [f64; 2],
+        k: usize,
+        exclude_id: u32,
+        world_size: f64,
+    ) -> Vec<(u32, f64)> {
+        let mut all: Vec<(u32, f64)> = agents
+            .iter()
+            .filter(|a| a.id != exclude_id)
+            .map(|a| {
+                let distance = toroidal_distance_sq(a.position, center, world_size).sqrt();
+                (a.id, distance)
+            })
+            .collect();
+        all.sort_unstable_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        all.truncate(k);
+        all
+    }
+
+    #[test]
+    fn k_nearest_matches_brute_force_on_random_points() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha12Rng::seed_from_u64(17);
+        let world_size = 20.0;
+        for n in [1usize, 5, 40, 300] {
+            let agents: Vec<Agent> = (0..n)
+                .map(|i| {
+                    make_agent(
+                        i as u32,
+                        rng.random_range(0.0..world_size),
+                        rng.random_range(0.0..world_size),
+                    )
+                })
+                .collect();
+            let tree = build_index(&agents);
+            for _ in 0..50 {
+                let probe = rng.random_range(0..n);
+                let k = rng.random_range(1..=8);
+                let center = agents[probe].position;
+                let expected = brute_force_k_nearest(&agents, center, k, probe as u32, world_size);
+                let actual: Vec<(u32, f64)> =
+                    k_nearest(&tree, center, k, probe as u32, world_size).collect();
+                assert_eq!(actual, expected, "n={n} k={k} center={center:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn k_nearest_prefers_wrapped_neighbor_at_edge() {
+        let agents = vec![
+            make_agent(0, 0.5, 50.0),
+            make_agent(1, 99.5, 50.0), // wrapped distance 1.0
+            make_agent(2, 3.0, 50.0),  // planar distance 2.5
+        ];
+        let tree = build_index(&agents);
+        let nearest: Vec<(u32, f64)> = k_nearest(&tree, [0.5, 50.0], 1, 0, 100.0).collect();
+        assert_eq!(nearest.len(), 1);
+        assert_eq!(nearest[0].0, 1);
+        assert!((nearest[0].1 - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn k_nearest_returns_all_when_fewer_than_k() {
+        let agents = vec![make_agent(0, 1.0, 1.0), make_agent(1, 90.0, 90.0)];
+        let tree = build_index(&agents);
+        let ids: Vec<u32> = k_nearest(&tree, [1.0, 1.0], 5, 0, 100.0)
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec![1]);
+        assert_eq!(k_nearest(&tree, [1.0, 1.0], 0, 0, 100.0).count(), 0);
+    }
+
+    #[test]
+    fn toroidal_envelopes_split_at_each_seam_reached() {
+        assert_eq!(toroidal_envelopes([50.0, 50.0], 2.0, 100.0).count(), 1);
+        assert_eq!(toroidal_envelopes([1.0, 50.0], 2.0, 100.0).count(), 2);
+        let corner: Vec<[f64; 2]> = toroidal_envelopes([99.0, 0.5], 2.0, 100.0)
+            .map(|(translated, _)| translated)
+            .collect();
+        assert_eq!(
+            corner,
+            vec![[99.0, 0.5], [99.0, 100.5], [-1.0, 0.5], [-1.0, 100.5]]
+        );
+    }
+
+    #[test]
+    fn query_neighbors_with_distances_reuses_buffer_and_caps() {
+        let agents = vec![
+            make_agent(0, 0.5, 50.0),
+            make_agent(1, 99.0, 50.0), // wrapped distance 1.5
+            make_agent(2, 1.5, 50.0),  // distance 1.0
+            make_agent(3, 0.5, 52.0),  // distance 2.0
+        ];
+        let tree = build_index(&agents);
+        let mut out = vec![(99, 99.0)];
+        query_neighbors_with_distances(&tree, [0.5, 50.0], 3.0, 0, 100.0, None, &mut out);
+        let ids: Vec<u32> = out.iter().map(|&(id, _)| id).collect();
+        assert_eq!(ids, vec![2, 1, 3]);
+        assert!((out[1].1 - 1.5).abs() < 1e-9);
+
+        query_neighbors_with_distances(&tree, [0.5, 50.0], 3.0, 0, 100.0, Some(1), &mut out);
+        assert_eq!(out, vec![(2, 1.0)]);
+    }
+
+    /// Toroidal distance as the nearest of the nine periodic images of `b`.
+    fn image_distance(a: [f64; 2], b: [f64; 2], world_size: f64) -> f64 {
+        let mut best = f64::INFINITY;
+        for ox in [-world_size, 0.0, world_size] {
+            for oy in [-world_size, 0.0, world_size] {
+                let dx = a[0] - (b[0] + ox);
+                let dy = a[1] - (b[1] + oy);
+                best = best.min((dx * dx + dy * dy).sqrt());
+            }
+        }
+        best
+    }
+
+    proptest! {
+        #[test]
+        fn proptest_toroidal_distance_matches_nearest_image(
+            world_size in 1.0f64..200.0,
+            a in (0.0f64..1.0, 0.0f64..1.0),
+            b in (0.0f64..1.0, 0.0f64..1.0),
+        ) {
+            let a = [a.0 * world_size, a.1 * world_size];
+            let b = [b.0 * world_size, b.1 * world_size];
+            let distance = toroidal_distance_sq(a, b, world_size).sqrt();
+            prop_assert!((distance - image_distance(a, b, world_size)).abs() < 1e-9);
+            prop_assert_eq!(
+                toroidal_distance_sq(a, b, world_size),
+                toroidal_distance_sq(b, a, world_size)
+            );
+        }
+
+        #[test]
+        fn proptest_query_neighbors_with_distances_matches_brute_force(
+            world_size in 5.0f64..100.0,
+            points in proptest::collection::vec((0.0f64..1.0, 0.0f64..1.0), 0..120),
+            center in (0.0f64..1.0, 0.0f64..1.0),
+            radius_fraction in 0.0f64..0.8,
+            self_id in 0u32..130,
+            max_results in proptest::option::of(0usize..20),
+        ) {
+            let agents: Vec<Agent> = points
+                .iter()
+                .enumerate()
+                .map(|(i, &(x, y))| make_agent(i as u32, x * world_size, y * world_size))
+                .collect();
+            let center = [center.0 * world_size, center.1 * world_size];
+            let radius = radius_fraction * world_size;
+            let tree = build_index(&agents);
+
+            let mut expected: Vec<(u32, f64)> = agents
+                .iter()
+                .filter(|a| a.id != self_id)
+                .map(|a| (a.id, image_distance(a.position, center, world_size)))
+                .filter(|&(_, d)| d <= radius)
+                .collect();
+            expected.sort_unstable_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+            if let Some(max) = max_results {
+                expected.truncate(max);
+            }
+
+            let mut actual = Vec::new();
+            query_neighbors_with_distances(
+                &tree, center, radius, self_id, world_size, max_results, &mut actual,
+            );
+            prop_assert_eq!(actual.len(), expected.len());
+            for (&(id, d), &(expected_id, expected_d)) in actual.iter().zip(&expected) {
+                prop_assert!((d - expected_d).abs() < 1e-9);
+                // Near-equal distances may order differently after rounding.
+                if (d - expected_d).abs() < 1e-12 {
+                    prop_assert_eq!(id, expected_id);
+                }
+            }
+        }
+    }
+
     #[test]
     fn bench_count_neighbors_near_boundary() {
         use std::time::Instant;
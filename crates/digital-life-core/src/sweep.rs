@@ -0,0 +1,434 @@
+//! Deterministic parameter sweeps over `SimConfig` fields.
+//!
+//! A [`SweepSpec`] varies named config fields over value lists and repeats every
+//! combination for each seed. Fields are addressed by their serialized names, so any
+//! `SimConfig` field can be swept, and every cell config is built and validated
+//! before any simulation runs. As in [`crate::ablation`], each cell builds its own
+//! world from its seed, so results do not depend on how cells are scheduled.
+
+use crate::ablation::CellSummary;
+use crate::config::{SimConfig, SimConfigErrors};
+use crate::metrics::RunSummary;
+use crate::world::{ExperimentError, World, WorldInitError};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{error::Error, fmt};
+
+/// One swept field and the values it takes, in order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SweepAxis {
+    pub field: String,
+    pub values: Vec<Value>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SweepSpec {
+    pub baseline: SimConfig,
+    #[serde(default)]
+    pub axes: Vec<SweepAxis>,
+    /// Each seed overrides `baseline.seed` for one cell per parameter combination.
+    pub seeds: Vec<u64>,
+}
+
+/// Field values assigned to one sweep cell, keyed by field name.
+pub type SweepParams = Map<String, Value>;
+
+impl SweepSpec {
+    pub fn new(baseline: SimConfig) -> Self {
+        Self {
+            baseline,
+            axes: Vec::new(),
+            seeds: Vec::new(),
+        }
+    }
+
+    /// Add an axis sweeping `field` over `values`.
+    pub fn vary<T: Clone + Into<Value>>(mut self, field: &str, values: &[T]) -> Self {
+        self.axes.push(SweepAxis {
+            field: field.to_string(),
+            values: values.iter().cloned().map(Into::into).collect(),
+        });
+        self
+    }
+
+    pub fn seeds(mut self, seeds: &[u64]) -> Self {
+        self.seeds = seeds.to_vec();
+        self
+    }
+
+    /// Every cell's parameters and validated config, ordered by axis values (first
+    /// axis slowest), then by seed.
+    pub fn configs(&self) -> Result<Vec<(SweepParams, SimConfig)>, SweepError> {
+        if self.seeds.is_empty() {
+            return Err(SweepError::NoSeeds);
+        }
+        let baseline = serde_json::to_value(&self.baseline)
+            .ok()
+            .and_then(|value| match value {
+                Value::Object(fields) => Some(fields),
+                _ => None,
+            })
+            .expect("SimConfig serializes to an object");
+        for (i, axis) in self.axes.iter().enumerate() {
+            if axis.field == "seed" {
+                return Err(SweepError::SeedAxis);
+            }
+            if !baseline.contains_key(&axis.field) {
+                return Err(SweepError::UnknownField(axis.field.clone()));
+            }
+            if self.axes[..i].iter().any(|a| a.field == axis.field) {
+                return Err(SweepError::DuplicateField(axis.field.clone()));
+            }
+            if axis.values.is_empty() {
+                return Err(SweepError::EmptyAxis(axis.field.clone()));
+            }
+            // Check each value alone so a type error names its field.
+            for value in &axis.values {
+                let mut fields = baseline.clone();
+                fields.insert(axis.field.clone(), value.clone());
+                serde_json::from_value::<SimConfig>(Value::Object(fields)).map_err(|e| {
+                    SweepError::InvalidValue {
+                        field: axis.field.clone(),
+                        message: e.to_string(),
+                    }
+                })?;
+            }
+        }
+
+        let mut combinations = vec![SweepParams::new()];
+        for axis in &self.axes {
+            combinations = combinations
+                .into_iter()
+                .flat_map(|params| {
+                    axis.values.iter().map(move |value| {
+                        let mut params = params.clone();
+                        params.insert(axis.field.clone(), value.clone());
+                        params
+                    })
+                })
+                .collect();
+        }
+
+        let mut configs = Vec::with_capacity(combinations.len() * self.seeds.len());
+        for params in combinations {
+            let mut fields = baseline.clone();
+            fields.extend(params.clone());
+            let config: SimConfig = serde_json::from_value(Value::Object(fields))
+                .expect("each value was checked against the baseline");
+            config
+                .validate()
+                .map_err(|source| SweepError::InvalidConfig {
+                    params: params.clone(),
+                    source,
+                })?;
+            for &seed in &self.seeds {
+                configs.push((
+                    params.clone(),
+                    SimConfig {
+                        seed,
+                        ..config.clone()
+                    },
+                ));
+            }
+        }
+        Ok(configs)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SweepCell {
+    pub params: SweepParams,
+    pub seed: u64,
+    pub summary: CellSummary,
+    pub run: RunSummary,
+}
+
+/// Cells in [`SweepSpec::configs`] order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SweepResults {
+    pub spec: SweepSpec,
+    pub steps: usize,
+    pub sample_every: usize,
+    pub cells: Vec<SweepCell>,
+}
+
+impl SweepResults {
+    /// Cells whose parameters include every entry of `params`, in seed order.
+    pub fn matching<'a>(&'a self, params: &'a SweepParams) -> impl Iterator<Item = &'a SweepCell> {
+        self.cells
+            .iter()
+            .filter(move |c| params.iter().all(|(k, v)| c.params.get(k) == Some(v)))
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepError {
+    NoSeeds,
+    /// Seeds are set with [`SweepSpec::seeds`], not as an axis.
+    SeedAxis,
+    UnknownField(String),
+    DuplicateField(String),
+    EmptyAxis(String),
+    InvalidValue {
+        field: String,
+        message: String,
+    },
+    InvalidConfig {
+        params: SweepParams,
+        source: SimConfigErrors,
+    },
+    World {
+        params: SweepParams,
+        seed: u64,
+        source: WorldInitError,
+    },
+    Experiment {
+        params: SweepParams,
+        seed: u64,
+        source: ExperimentError,
+    },
+}
+
+fn params_label(params: &SweepParams) -> String {
+    if params.is_empty() {
+        return "baseline".to_string();
+    }
+    params
+        .iter()
+        .map(|(field, value)| format!("{field}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for SweepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SweepError::NoSeeds => write!(f, "sweep spec must list at least one seed"),
+            SweepError::SeedAxis => {
+                write!(f, "`seed` cannot be swept as a field; list seeds instead")
+            }
+            SweepError::UnknownField(field) => write!(f, "unknown config field `{field}`"),
+            SweepError::DuplicateField(field) => write!(f, "field `{field}` is swept twice"),
+            SweepError::EmptyAxis(field) => write!(f, "field `{field}` has no sweep values"),
+            SweepError::InvalidValue { field, message } => {
+                write!(f, "invalid value for `{field}`: {message}")
+            }
+            SweepError::InvalidConfig { params, source } => {
+                write!(f, "{}: {source}", params_label(params))
+            }
+            SweepError::World {
+                params,
+                seed,
+                source,
+            } => write!(
+                f,
+                "{} (seed {seed}): invalid world configuration: {source}",
+                params_label(params)
+            ),
+            SweepError::Experiment {
+                params,
+                seed,
+                source,
+            } => write!(f, "{} (seed {seed}): {source}", params_label(params)),
+        }
+    }
+}
+
+impl Error for SweepError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SweepError::InvalidConfig { source, .. } => Some(source),
+            SweepError::World { source, .. } => Some(source),
+            SweepError::Experiment { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn run_cell(
+    params: SweepParams,
+    config: SimConfig,
+    steps: usize,
+    sample_every: usize,
+) -> Result<SweepCell, SweepError> {
+    let seed = config.seed;
+    let mut world = match World::from_config(config) {
+        Ok(world) => world,
+        Err(source) => {
+            return Err(SweepError::World {
+                params,
+                seed,
+                source,
+            })
+        }
+    };
+    match world.try_run_experiment(steps, sample_every) {
+        Ok(run) => Ok(SweepCell {
+            params,
+            seed,
+            summary: CellSummary::from_run(&run),
+            run,
+        }),
+        Err(source) => Err(SweepError::Experiment {
+            params,
+            seed,
+            source,
+        }),
+    }
+}
+
+/// Build and validate every cell of `spec`, then run them in parallel.
+pub fn run_sweep(
+    spec: SweepSpec,
+    steps: usize,
+    sample_every: usize,
+) -> Result<SweepResults, SweepError> {
+    run_sweep_with_progress(spec, steps, sample_every, |_| {})
+}
+
+/// Run a sweep like [`run_sweep`], calling `on_cell` as each cell finishes.
+///
+/// Cells run in parallel, so `on_cell` is called from worker threads in completion
+/// order; the returned cells keep the spec's order.
+pub fn run_sweep_with_progress<F>(
+    spec: SweepSpec,
+    steps: usize,
+    sample_every: usize,
+    on_cell: F,
+) -> Result<SweepResults, SweepError>
+where
+    F: Fn(&SweepCell) + Sync,
+{
+    let configs = spec.configs()?;
+    let cells = configs
+        .into_par_iter()
+        .map(|(params, config)| {
+            let cell = run_cell(params, config, steps, sample_every)?;
+            on_cell(&cell);
+            Ok(cell)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(SweepResults {
+        spec,
+        steps,
+        sample_every,
+        cells,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn small_baseline() -> SimConfig {
+        SimConfig {
+            num_organisms: 3,
+            agents_per_organism: 4,
+            world_size: 30.0,
+            ..SimConfig::default()
+        }
+    }
+
+    #[test]
+    fn configs_form_cartesian_product_with_seeds_innermost() {
+        let spec = SweepSpec::new(small_baseline())
+            .vary("boundary_repair_rate", &[0.01, 0.02, 0.05])
+            .vary("resource_regeneration_rate", &[0.1, 0.5])
+            .seeds(&[1, 2]);
+        let configs = spec.configs().unwrap();
+        assert_eq!(configs.len(), 12);
+        let (params, config) = &configs[3];
+        assert_eq!(params["boundary_repair_rate"], json!(0.01));
+        assert_eq!(params["resource_regeneration_rate"], json!(0.5));
+        assert_eq!(config.seed, 2);
+        assert_eq!(config.boundary_repair_rate, 0.01);
+        assert_eq!(config.resource_regeneration_rate, 0.5);
+        assert_eq!(configs[4].1.boundary_repair_rate, 0.02);
+        assert_eq!(configs[4].1.seed, 1);
+    }
+
+    #[test]
+    fn unknown_and_malformed_fields_fail_before_running() {
+        let spec = |field: &str| {
+            SweepSpec::new(small_baseline())
+                .vary(field, &[0.5])
+                .seeds(&[1])
+        };
+        assert_eq!(
+            run_sweep(spec("boundary_repiar_rate"), 10, 5).unwrap_err(),
+            SweepError::UnknownField("boundary_repiar_rate".to_string())
+        );
+        assert_eq!(spec("seed").configs().unwrap_err(), SweepError::SeedAxis);
+        let err = SweepSpec::new(small_baseline())
+            .vary("enable_growth", &["sometimes"])
+            .seeds(&[1])
+            .configs()
+            .unwrap_err();
+        assert!(
+            matches!(err, SweepError::InvalidValue { ref field, .. } if field == "enable_growth")
+        );
+        let err = spec("boundary_repair_rate")
+            .vary("resource_regeneration_rate", &[0.1, -1.0])
+            .configs();
+        assert!(matches!(err, Err(SweepError::InvalidConfig { .. })));
+        assert_eq!(
+            SweepSpec::new(small_baseline()).configs().unwrap_err(),
+            SweepError::NoSeeds
+        );
+    }
+
+    #[test]
+    fn sweep_is_reproducible_and_tags_cells() {
+        let spec = SweepSpec::new(small_baseline())
+            .vary("enable_reproduction", &[true, false])
+            .seeds(&[4, 9]);
+        let a = run_sweep(spec.clone(), 20, 10).unwrap();
+        let b = run_sweep(spec, 20, 10).unwrap();
+        assert_eq!(a.to_json().unwrap(), b.to_json().unwrap());
+        assert_eq!(a.cells.len(), 4);
+        let off: SweepParams = [("enable_reproduction".to_string(), json!(false))]
+            .into_iter()
+            .collect();
+        let seeds: Vec<u64> = a.matching(&off).map(|c| c.seed).collect();
+        assert_eq!(seeds, vec![4, 9]);
+        assert!(a
+            .matching(&off)
+            .all(|c| c.run.total_reproduction_events == 0));
+        // Each cell matches a standalone run of its config.
+        let mut world = World::from_config(SimConfig {
+            seed: 9,
+            enable_reproduction: false,
+            ..small_baseline()
+        })
+        .unwrap();
+        let run = world.try_run_experiment(20, 10).unwrap();
+        assert_eq!(a.cells[3].run.final_state_hash, run.final_state_hash);
+    }
+
+    #[test]
+    fn progress_callback_sees_every_cell_once() {
+        let spec = SweepSpec::new(small_baseline())
+            .vary("enable_reproduction", &[true, false])
+            .seeds(&[4, 9]);
+        let seen = std::sync::Mutex::new(Vec::new());
+        let results = run_sweep_with_progress(spec, 20, 10, |cell| {
+            seen.lock().unwrap().push(cell.run.final_state_hash);
+        })
+        .unwrap();
+        let mut seen = seen.into_inner().unwrap();
+        let mut expected: Vec<_> = results
+            .cells
+            .iter()
+            .map(|c| c.run.final_state_hash)
+            .collect();
+        seen.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(seen, expected);
+    }
+}
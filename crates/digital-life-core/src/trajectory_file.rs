@@ -0,0 +1,239 @@
+//! Compact binary storage for agent trajectories, for animating runs at agent level.
+//!
+//! A file is a header followed by any number of frames:
+//!
+//! - header: the magic `DLTRAJ` and a `u16` format version.
+//! - frame: `u64` step, `u32` agent count, then one record per agent: `u32` agent
+//!   id, `u64` organism stable id, and the position as `f32` x and y.
+//!
+//! All integers and floats are little-endian.
+
+use crate::snapshot_file::read_full;
+use std::io::{self, Read, Write};
+use std::{error::Error, fmt};
+
+pub const TRAJECTORY_MAGIC: &[u8; 6] = b"DLTRAJ";
+pub const TRAJECTORY_FORMAT_VERSION: u16 = 1;
+
+/// Bytes per agent record.
+pub const TRAJECTORY_RECORD_SIZE: usize = 20;
+
+const FRAME_HEAD_SIZE: usize = 12;
+
+/// One agent's position in a trajectory frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrajectoryPoint {
+    pub id: u32,
+    /// Stable id of the agent's organism.
+    pub organism_id: u64,
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrajectoryFrame {
+    pub step: usize,
+    pub points: Vec<TrajectoryPoint>,
+}
+
+#[derive(Debug)]
+pub enum TrajectoryFileError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u16),
+    /// The stream stops partway through a frame; `complete_frames` precede it.
+    TruncatedFrame {
+        complete_frames: usize,
+    },
+}
+
+impl fmt::Display for TrajectoryFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrajectoryFileError::Io(err) => write!(f, "trajectory file I/O failed: {err}"),
+            TrajectoryFileError::BadMagic => write!(f, "not a trajectory file (bad magic)"),
+            TrajectoryFileError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported trajectory format version {version} (expected {TRAJECTORY_FORMAT_VERSION})"
+            ),
+            TrajectoryFileError::TruncatedFrame { complete_frames } => write!(
+                f,
+                "trajectory data ends mid-frame after {complete_frames} complete frames"
+            ),
+        }
+    }
+}
+
+impl Error for TrajectoryFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TrajectoryFileError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TrajectoryFileError {
+    fn from(err: io::Error) -> Self {
+        TrajectoryFileError::Io(err)
+    }
+}
+
+/// Streams frames to `inner` in the binary trajectory format. Frames are encoded
+/// into one reused buffer, so steady-state writing does not allocate.
+pub struct TrajectoryWriter<W: Write> {
+    inner: W,
+    record: Vec<u8>,
+}
+
+impl<W: Write> TrajectoryWriter<W> {
+    /// Start a new file: writes the header immediately.
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        inner.write_all(TRAJECTORY_MAGIC)?;
+        inner.write_all(&TRAJECTORY_FORMAT_VERSION.to_le_bytes())?;
+        Ok(Self::appending(inner))
+    }
+
+    /// Continue a file whose header was already written.
+    pub fn appending(inner: W) -> Self {
+        Self {
+            inner,
+            record: Vec::new(),
+        }
+    }
+
+    /// Write one frame holding `points`, whose length must be exactly `count`.
+    pub fn write_frame(
+        &mut self,
+        step: usize,
+        count: usize,
+        points: impl IntoIterator<Item = TrajectoryPoint>,
+    ) -> io::Result<()> {
+        let buf = &mut self.record;
+        buf.clear();
+        buf.extend_from_slice(&(step as u64).to_le_bytes());
+        buf.extend_from_slice(&(count as u32).to_le_bytes());
+        for point in points {
+            buf.extend_from_slice(&point.id.to_le_bytes());
+            buf.extend_from_slice(&point.organism_id.to_le_bytes());
+            buf.extend_from_slice(&point.x.to_le_bytes());
+            buf.extend_from_slice(&point.y.to_le_bytes());
+        }
+        debug_assert_eq!(buf.len(), FRAME_HEAD_SIZE + count * TRAJECTORY_RECORD_SIZE);
+        self.inner.write_all(buf)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Decode a whole trajectory stream, checking the magic and version first.
+pub fn read_trajectory_frames(
+    mut reader: impl Read,
+) -> Result<Vec<TrajectoryFrame>, TrajectoryFileError> {
+    let mut header = [0u8; 8];
+    if read_full(&mut reader, &mut header)? < header.len() || &header[..6] != TRAJECTORY_MAGIC {
+        return Err(TrajectoryFileError::BadMagic);
+    }
+    let version = u16::from_le_bytes([header[6], header[7]]);
+    if version != TRAJECTORY_FORMAT_VERSION {
+        return Err(TrajectoryFileError::UnsupportedVersion(version));
+    }
+    let mut frames = Vec::new();
+    let mut head = [0u8; FRAME_HEAD_SIZE];
+    loop {
+        match read_full(&mut reader, &mut head)? {
+            0 => return Ok(frames),
+            n if n < head.len() => {
+                return Err(TrajectoryFileError::TruncatedFrame {
+                    complete_frames: frames.len(),
+                });
+            }
+            _ => {}
+        }
+        let step = u64::from_le_bytes(head[0..8].try_into().expect("8 bytes")) as usize;
+        let count = u32::from_le_bytes(head[8..12].try_into().expect("4 bytes")) as usize;
+        let mut body = vec![0u8; count * TRAJECTORY_RECORD_SIZE];
+        if read_full(&mut reader, &mut body)? < body.len() {
+            return Err(TrajectoryFileError::TruncatedFrame {
+                complete_frames: frames.len(),
+            });
+        }
+        frames.push(TrajectoryFrame {
+            step,
+            points: body
+                .chunks_exact(TRAJECTORY_RECORD_SIZE)
+                .map(|r| TrajectoryPoint {
+                    id: u32::from_le_bytes(r[0..4].try_into().expect("4 bytes")),
+                    organism_id: u64::from_le_bytes(r[4..12].try_into().expect("8 bytes")),
+                    x: f32::from_le_bytes(r[12..16].try_into().expect("4 bytes")),
+                    y: f32::from_le_bytes(r[16..20].try_into().expect("4 bytes")),
+                })
+                .collect(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(step: usize, agents: usize) -> TrajectoryFrame {
+        TrajectoryFrame {
+            step,
+            points: (0..agents)
+                .map(|i| TrajectoryPoint {
+                    id: i as u32 * 3,
+                    organism_id: 1_000 + i as u64 / 2,
+                    x: 0.5 * i as f32,
+                    y: 99.75 - i as f32,
+                })
+                .collect(),
+        }
+    }
+
+    fn encode(frames: &[TrajectoryFrame]) -> Vec<u8> {
+        let mut writer = TrajectoryWriter::new(Vec::new()).unwrap();
+        for f in frames {
+            writer
+                .write_frame(f.step, f.points.len(), f.points.iter().copied())
+                .unwrap();
+        }
+        writer.into_inner()
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let frames = vec![frame(10, 4), frame(20, 0), frame(30, 7)];
+        let bytes = encode(&frames);
+        assert_eq!(
+            bytes.len(),
+            8 + 3 * FRAME_HEAD_SIZE + 11 * TRAJECTORY_RECORD_SIZE
+        );
+        assert_eq!(read_trajectory_frames(bytes.as_slice()).unwrap(), frames);
+    }
+
+    #[test]
+    fn truncated_and_foreign_data_are_rejected() {
+        let bytes = encode(&[frame(1, 3), frame(2, 3)]);
+        assert!(matches!(
+            read_trajectory_frames(&bytes[..bytes.len() - 5]),
+            Err(TrajectoryFileError::TruncatedFrame { complete_frames: 1 })
+        ));
+        assert!(matches!(
+            read_trajectory_frames(&b"DLSNAP\x03\x00"[..]),
+            Err(TrajectoryFileError::BadMagic)
+        ));
+        let mut newer = bytes.clone();
+        newer[6] = 2;
+        assert!(matches!(
+            read_trajectory_frames(newer.as_slice()),
+            Err(TrajectoryFileError::UnsupportedVersion(2))
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+use super::{ExperimentError, World};
+use crate::constants::MAX_ACTIVATION_RECORDS;
+use crate::nn::HIDDEN_SIZE;
+use std::collections::HashSet;
+
+/// Hidden-layer activations of one agent at one step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivationRecord {
+    pub step: usize,
+    pub agent_id: u32,
+    pub hidden: [f32; HIDDEN_SIZE],
+}
+
+/// Organisms whose agents' hidden activations are recorded, and the records kept
+/// so far. Bounded by [`MAX_ACTIVATION_RECORDS`]; records past it are dropped and
+/// flagged.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ActivationRecorder {
+    tracked: HashSet<u64>,
+    every: usize,
+    records: Vec<ActivationRecord>,
+    truncated: bool,
+}
+
+impl ActivationRecorder {
+    /// Whether any agent is recorded at `step`; false whenever nothing is tracked.
+    pub(super) fn is_due(&self, step: usize) -> bool {
+        !self.tracked.is_empty() && step.is_multiple_of(self.every)
+    }
+
+    pub(super) fn tracks(&self, stable_id: u64) -> bool {
+        self.tracked.contains(&stable_id)
+    }
+
+    pub(super) fn push(&mut self, record: ActivationRecord) {
+        if self.records.len() < MAX_ACTIVATION_RECORDS {
+            self.records.push(record);
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+impl World {
+    /// Record the hidden-layer activations of every agent of the organisms in
+    /// `stable_ids` on steps that are multiples of `every_n_steps`. Replaces any
+    /// previous tracking set; an empty set stops recording. Records already kept
+    /// stay until taken with [`World::take_activation_log`].
+    pub fn record_activations_for(
+        &mut self,
+        stable_ids: impl IntoIterator<Item = u64>,
+        every_n_steps: usize,
+    ) -> Result<(), ExperimentError> {
+        if every_n_steps == 0 {
+            return Err(ExperimentError::InvalidActivationEvery);
+        }
+        let recorder = &mut self.activation_recorder;
+        recorder.tracked = stable_ids.into_iter().collect();
+        recorder.every = every_n_steps;
+        Ok(())
+    }
+
+    /// Drain the recorded activations in step order, and whether records were
+    /// dropped at the bound since the last call. Tracking continues.
+    pub fn take_activation_log(&mut self) -> (Vec<ActivationRecord>, bool) {
+        let recorder = &mut self.activation_recorder;
+        let truncated = std::mem::replace(&mut recorder.truncated, false);
+        (std::mem::take(&mut recorder.records), truncated)
+    }
+}
@@ -0,0 +1,39 @@
+//! Entry points for the criterion suite in `benches/`, compiled only with the
+//! `bench-internals` feature. Not a stable API.
+
+use super::World;
+use crate::spatial::{self, AgentLocation};
+use rstar::RTree;
+
+impl World {
+    /// Spatial index over live agents, as built at the start of each step.
+    pub fn bench_spatial_index(&self) -> RTree<AgentLocation> {
+        spatial::build_index_active(&self.agents, &self.live_flags())
+    }
+
+    /// Run only the NN query phase against `tree`. Agents do not move, so repeated
+    /// calls do the same work.
+    pub fn bench_nn_query_phase(&mut self, tree: &RTree<AgentLocation>) {
+        self.step_nn_query_phase(tree);
+    }
+
+    /// Run only the boundary and metabolism phases, serially or in parallel per
+    /// `parallel_organism_phases`. Both change organism state, so bench on clones.
+    pub fn bench_organism_phases(&mut self) {
+        let threshold = self.terminal_boundary_threshold();
+        self.step_boundary_phase(threshold);
+        self.step_metabolism_phase(threshold);
+    }
+
+    /// Spatial cohesion of the current population under the configured estimator.
+    pub fn bench_spatial_cohesion(&self) -> f32 {
+        crate::metrics::compute_spatial_cohesion(
+            &self.agents,
+            &self.organisms,
+            self.config.world_size,
+            self.config.cohesion_metric_mode,
+            self.config.max_cohesion_pairs,
+            self.step_index,
+        )
+    }
+}
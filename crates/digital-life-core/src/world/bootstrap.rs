@@ -0,0 +1,214 @@
+use super::{World, WorldInitError};
+use crate::agent::Agent;
+use crate::config::{PlacementStrategy, SimConfig};
+use crate::nn::NeuralNet;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use std::f64::consts::TAU;
+
+impl World {
+    /// Seeded initial population for `config`, laid out by `config.placement`.
+    pub fn bootstrap_entities(
+        config: &SimConfig,
+    ) -> Result<(Vec<Agent>, Vec<NeuralNet>), WorldInitError> {
+        Self::bootstrap_entities_with_placement(config, config.placement)
+    }
+
+    /// Seeded initial population for `config`: agents laid out by `placement`,
+    /// followed by one controller per organism with weights drawn from `[-1, 1)`.
+    /// Positions and then weights come from one stream seeded with `config.seed`.
+    /// Only `num_organisms`, `agents_per_organism`, `max_total_agents`, `world_size`,
+    /// `seed`, `sensing_radius` and `enable_resource_gradient_sensing` are read;
+    /// `placement` overrides `config.placement`.
+    pub fn bootstrap_entities_with_placement(
+        config: &SimConfig,
+        placement: PlacementStrategy,
+    ) -> Result<(Vec<Agent>, Vec<NeuralNet>), WorldInitError> {
+        let num_organisms = config.num_organisms;
+        let agents_per_organism = config.agents_per_organism;
+        let world_size = config.world_size;
+        if num_organisms > u16::MAX as usize {
+            return Err(WorldInitError::TooManyOrganisms {
+                max: u16::MAX as usize,
+                actual: num_organisms,
+            });
+        }
+        let total_agents = num_organisms
+            .checked_mul(agents_per_organism)
+            .ok_or(WorldInitError::AgentCountOverflow)?;
+        if total_agents > config.max_total_agents {
+            return Err(WorldInitError::TooManyAgents {
+                max: config.max_total_agents,
+                actual: total_agents,
+            });
+        }
+
+        let mut rng = ChaCha12Rng::seed_from_u64(config.seed);
+        let agents = match placement {
+            PlacementStrategy::UniformGlobal => (0..total_agents)
+                .map(|id| {
+                    let x = rng.random_range(0.0..world_size);
+                    let y = rng.random_range(0.0..world_size);
+                    Agent::new(id as u32, (id / agents_per_organism) as u16, [x, y])
+                })
+                .collect(),
+            PlacementStrategy::ClusteredPerOrganism { radius: None } => {
+                Self::clustered_agents(config, &mut rng, total_agents)
+            }
+            PlacementStrategy::ClusteredPerOrganism {
+                radius: Some(radius),
+            } => {
+                let centers: Vec<[f64; 2]> = (0..num_organisms)
+                    .map(|_| {
+                        [
+                            rng.random_range(0.0..world_size),
+                            rng.random_range(0.0..world_size),
+                        ]
+                    })
+                    .collect();
+                Self::disc_clusters(config, &mut rng, &centers, radius)
+            }
+            PlacementStrategy::GridOfClusters { spacing, radius } => {
+                let side = (num_organisms as f64).sqrt().ceil().max(1.0) as usize;
+                let centers: Vec<[f64; 2]> = (0..num_organisms)
+                    .map(|org| {
+                        [
+                            ((org % side) as f64 + 0.5) * spacing,
+                            ((org / side) as f64 + 0.5) * spacing,
+                        ]
+                    })
+                    .collect();
+                Self::disc_clusters(config, &mut rng, &centers, radius)
+            }
+            PlacementStrategy::Grid => {
+                let side = (total_agents as f64).sqrt().ceil().max(1.0) as usize;
+                let spacing = world_size / side as f64;
+                (0..total_agents)
+                    .map(|id| {
+                        let x = ((id % side) as f64 + 0.5) * spacing;
+                        let y = ((id / side) as f64 + 0.5) * spacing;
+                        Agent::new(id as u32, (id / agents_per_organism) as u16, [x, y])
+                    })
+                    .collect()
+            }
+        };
+
+        let nns = (0..num_organisms)
+            .map(|_| {
+                if config.enable_resource_gradient_sensing {
+                    NeuralNet::from_weights_with_sensors(
+                        (0..NeuralNet::EXTENDED_WEIGHT_COUNT)
+                            .map(|_| rng.random_range(-1.0f32..1.0)),
+                    )
+                } else {
+                    NeuralNet::from_weights(
+                        (0..NeuralNet::WEIGHT_COUNT).map(|_| rng.random_range(-1.0f32..1.0)),
+                    )
+                }
+            })
+            .collect();
+        Ok((agents, nns))
+    }
+
+    /// [`PlacementStrategy::ClusteredPerOrganism`] layout without a radius, drawn from `rng`.
+    fn clustered_agents(
+        config: &SimConfig,
+        rng: &mut ChaCha12Rng,
+        total_agents: usize,
+    ) -> Vec<Agent> {
+        let world_size = config.world_size;
+        let agents_per_organism = config.agents_per_organism;
+        let cluster_radius = config.sensing_radius.min(world_size / 4.0);
+        let mut agents = Vec::with_capacity(total_agents);
+        for org in 0..config.num_organisms {
+            let cx: f64 = rng.random_range(0.0..world_size);
+            let cy: f64 = rng.random_range(0.0..world_size);
+            for a in 0..agents_per_organism {
+                let global_id = org * agents_per_organism + a;
+                let (dx, dy) = if cluster_radius > f64::EPSILON {
+                    (
+                        rng.random_range(-cluster_radius..cluster_radius),
+                        rng.random_range(-cluster_radius..cluster_radius),
+                    )
+                } else {
+                    (0.0, 0.0)
+                };
+                let px = (cx + dx).rem_euclid(world_size);
+                let py = (cy + dy).rem_euclid(world_size);
+                agents.push(Agent::new(global_id as u32, org as u16, [px, py]));
+            }
+        }
+        agents
+    }
+
+    /// Each organism's agents uniformly within `radius` of its entry in `centers`,
+    /// wrapped into the world.
+    fn disc_clusters(
+        config: &SimConfig,
+        rng: &mut ChaCha12Rng,
+        centers: &[[f64; 2]],
+        radius: f64,
+    ) -> Vec<Agent> {
+        let world_size = config.world_size;
+        let agents_per_organism = config.agents_per_organism;
+        let mut agents = Vec::with_capacity(centers.len() * agents_per_organism);
+        for (org, center) in centers.iter().enumerate() {
+            for a in 0..agents_per_organism {
+                let distance = radius * rng.random::<f64>().sqrt();
+                let (sin, cos) = rng.random_range(0.0..TAU).sin_cos();
+                let px = (center[0] + distance * cos).rem_euclid(world_size);
+                let py = (center[1] + distance * sin).rem_euclid(world_size);
+                let id = org * agents_per_organism + a;
+                agents.push(Agent::new(id as u32, org as u16, [px, py]));
+            }
+        }
+        agents
+    }
+
+    /// Validate `config` and build a founder population from exported controllers,
+    /// e.g. [`World::organism_nn_weights`] from an earlier run. Organism `i` gets
+    /// `weights[i]`; agents are laid out as by [`World::bootstrap_entities`].
+    /// `config.num_organisms` must equal `weights.len()`.
+    pub fn seed_from_weights(
+        weights: Vec<Vec<f32>>,
+        config: SimConfig,
+    ) -> Result<Self, WorldInitError> {
+        config.validate()?;
+        if weights.len() != config.num_organisms {
+            return Err(WorldInitError::NumOrganismsMismatch {
+                expected: config.num_organisms,
+                actual: weights.len(),
+            });
+        }
+        let nns = weights
+            .iter()
+            .enumerate()
+            .map(|(organism, w)| {
+                NeuralNet::from_weight_vec(w).ok_or(WorldInitError::ControllerWeightCount {
+                    organism,
+                    actual: w.len(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (agents, _) = Self::bootstrap_entities(&config)?;
+        Self::new(agents, nns, config)
+    }
+
+    /// Validate `config` and build a world populated by [`World::bootstrap_entities`].
+    pub fn from_config(config: SimConfig) -> Result<Self, WorldInitError> {
+        let placement = config.placement;
+        Self::from_config_with_placement(config, placement)
+    }
+
+    /// [`World::from_config`] with agents laid out by `placement` instead of
+    /// `config.placement`.
+    pub fn from_config_with_placement(
+        config: SimConfig,
+        placement: PlacementStrategy,
+    ) -> Result<Self, WorldInitError> {
+        config.validate()?;
+        let (agents, nns) = Self::bootstrap_entities_with_placement(&config, placement)?;
+        Self::new(agents, nns, config)
+    }
+}
@@ -0,0 +1,152 @@
+use super::World;
+use crate::metabolism::MetabolicState;
+use serde::{Deserialize, Serialize};
+
+/// Cumulative mass flows recorded under `audit_conservation`.
+///
+/// The audited stock is the resource field, the waste field, queued corpse
+/// deposits and every alive organism's energy, internal resource, waste and graph
+/// intermediates. Each step the stock must change by exactly the inputs minus the
+/// sinks below; whatever does not is added to `residual`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConservationLedger {
+    /// Input: resource added by regeneration and noise.
+    pub regeneration_input: f64,
+    /// Input: waste copied into the waste field; engines also keep it internally.
+    pub excreted_waste: f64,
+    /// Input: internal resource and waste a newborn starts with beyond the energy
+    /// its parent pays.
+    pub birth_endowment: f64,
+    /// Sink: [`MetabolismFlux::dissipated`](crate::metabolism::MetabolismFlux::dissipated)
+    /// plus waste-feeding and growth-efficiency losses.
+    pub metabolic_dissipation: f64,
+    /// Sink: `movement_energy_cost`.
+    pub movement_cost: f64,
+    /// Sink: crowding, genetic sensing and toxin resistance energy costs.
+    pub maintenance_cost: f64,
+    /// Sink: energy a parent pays beyond what its child receives.
+    pub reproduction_overhead: f64,
+    /// Sink: stock of dying organisms that never reaches the resource field.
+    pub death_loss: f64,
+    /// Sink: waste field decay.
+    pub waste_decay: f64,
+    /// Stock change minus inputs plus sinks, summed over audited steps.
+    pub residual: f64,
+}
+
+impl ConservationLedger {
+    fn inputs(&self) -> f64 {
+        self.regeneration_input + self.excreted_waste + self.birth_endowment
+    }
+
+    fn sinks(&self) -> f64 {
+        self.metabolic_dissipation
+            + self.movement_cost
+            + self.maintenance_cost
+            + self.reproduction_overhead
+            + self.death_loss
+            + self.waste_decay
+    }
+
+    fn accumulate(&mut self, other: &ConservationLedger) {
+        self.regeneration_input += other.regeneration_input;
+        self.excreted_waste += other.excreted_waste;
+        self.birth_endowment += other.birth_endowment;
+        self.metabolic_dissipation += other.metabolic_dissipation;
+        self.movement_cost += other.movement_cost;
+        self.maintenance_cost += other.maintenance_cost;
+        self.reproduction_overhead += other.reproduction_overhead;
+        self.death_loss += other.death_loss;
+        self.waste_decay += other.waste_decay;
+        self.residual += other.residual;
+    }
+}
+
+/// Running audit state; present while `audit_conservation` is on.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ConservationAudit {
+    ledger: ConservationLedger,
+    /// Flows recorded during the current step.
+    step: ConservationLedger,
+    stock_at_step_start: f64,
+}
+
+/// Relative per-step residual above which debug builds panic; float rounding in
+/// the f32 state stays orders of magnitude below it.
+const STEP_RESIDUAL_TOLERANCE: f64 = 1e-5;
+
+/// Audited stock held by one organism.
+pub(super) fn organism_stock(state: &MetabolicState) -> f64 {
+    state.energy as f64
+        + state.resource as f64
+        + state.waste as f64
+        + state.graph_pool.iter().map(|&v| v as f64).sum::<f64>()
+}
+
+impl World {
+    /// Total audited stock: see [`ConservationLedger`].
+    pub fn conserved_stock(&self) -> f64 {
+        let organisms: f64 = self
+            .organisms
+            .iter()
+            .filter(|o| o.alive)
+            .map(|o| organism_stock(&o.metabolic_state))
+            .sum();
+        let corpses: f64 = self
+            .pending_corpse_deposits
+            .iter()
+            .map(|&(_, amount)| amount as f64)
+            .sum();
+        self.resource_field.total()
+            + self.waste_field.as_ref().map_or(0.0, |field| field.total())
+            + organisms
+            + corpses
+    }
+
+    /// Flows recorded since `audit_conservation` was switched on, or `None` while it
+    /// is off.
+    pub fn conservation_ledger(&self) -> Option<&ConservationLedger> {
+        self.conservation.as_ref().map(|audit| &audit.ledger)
+    }
+
+    /// Record a flow for the current step; a no-op while auditing is off.
+    pub(super) fn audit_flow(&mut self, record: impl FnOnce(&mut ConservationLedger)) {
+        if let Some(audit) = &mut self.conservation {
+            record(&mut audit.step);
+        }
+    }
+
+    /// Start or stop auditing to follow the config and snapshot the stock. Changes
+    /// made between steps, such as interventions, are outside the audit.
+    pub(super) fn begin_conservation_audit(&mut self) {
+        if !self.config.audit_conservation {
+            self.conservation = None;
+            return;
+        }
+        let stock = self.conserved_stock();
+        let audit = self.conservation.get_or_insert_with(Default::default);
+        audit.step = ConservationLedger::default();
+        audit.stock_at_step_start = stock;
+    }
+
+    /// Close the step's balance and fold its flows into the ledger.
+    pub(super) fn finish_conservation_audit(&mut self) {
+        if self.conservation.is_none() {
+            return;
+        }
+        let stock = self.conserved_stock();
+        let Some(audit) = &mut self.conservation else {
+            return;
+        };
+        let step = &mut audit.step;
+        step.residual = stock - audit.stock_at_step_start - step.inputs() + step.sinks();
+        debug_assert!(
+            step.residual.abs() <= STEP_RESIDUAL_TOLERANCE * audit.stock_at_step_start.max(1.0),
+            "conservation residual {} at step {} exceeds tolerance: {step:?}",
+            step.residual,
+            self.step_index,
+        );
+        audit.ledger.accumulate(step);
+    }
+}
@@ -0,0 +1,84 @@
+use super::World;
+use crate::metrics::ShockRecovery;
+
+impl World {
+    /// Apply the shock `homeostasis_shock_schedule` lists for the current step, if any.
+    pub(super) fn apply_scheduled_shock_if_due(&mut self) {
+        let schedule = &self.config.homeostasis_shock_schedule;
+        if let Ok(i) = schedule.binary_search_by_key(&self.step_index, |&(step, _)| step) {
+            let amount = schedule[i].1;
+            self.shock_internal_state(amount);
+        }
+    }
+
+    /// Displace internal-state channels 0 and 1 of every alive agent by `amount`
+    /// and start tracking recovery to their pre-shock means. A shock still
+    /// awaiting recovery is closed as unrecovered.
+    pub(super) fn shock_internal_state(&mut self, amount: f32) {
+        let baseline = self.regulated_state_means();
+        if let Some((shock, _)) = self.pending_shock.take() {
+            self.shock_recoveries.push(shock);
+        }
+        for agent in &mut self.agents {
+            if !self.organisms[agent.organism_id as usize].alive {
+                continue;
+            }
+            for v in &mut agent.internal_state[..2] {
+                *v = (*v + amount).clamp(0.0, 1.0);
+            }
+        }
+        self.last_shock_step = Some(self.step_index);
+        if let Some(baseline) = baseline {
+            let shock = ShockRecovery {
+                step: self.step_index,
+                amount,
+                recovery_steps: None,
+            };
+            self.pending_shock = Some((shock, baseline));
+        }
+    }
+
+    /// Close the pending shock once both regulated channels are back within
+    /// `homeostasis_recovery_epsilon` of their pre-shock means.
+    pub(super) fn track_shock_recovery(&mut self) {
+        let Some((_, baseline)) = self.pending_shock else {
+            return;
+        };
+        let Some(means) = self.regulated_state_means() else {
+            return;
+        };
+        let epsilon = self.config.homeostasis_recovery_epsilon;
+        if means
+            .iter()
+            .zip(&baseline)
+            .all(|(m, b)| (m - b).abs() <= epsilon)
+        {
+            let (mut shock, _) = self.pending_shock.take().expect("checked above");
+            shock.recovery_steps = Some(self.step_index - shock.step);
+            self.shock_recoveries.push(shock);
+        }
+    }
+
+    /// This run's resolved shocks followed by the pending one, which stays pending.
+    pub(super) fn take_shock_recoveries(&mut self) -> Vec<ShockRecovery> {
+        let mut recoveries = std::mem::take(&mut self.shock_recoveries);
+        recoveries.extend(self.pending_shock.map(|(shock, _)| shock));
+        recoveries
+    }
+
+    /// Mean of internal-state channels 0 and 1 over alive agents; `None` when no
+    /// agent is alive.
+    fn regulated_state_means(&self) -> Option<[f32; 2]> {
+        let mut sums = [0.0f64; 2];
+        let mut count = 0usize;
+        for agent in &self.agents {
+            if !self.organisms[agent.organism_id as usize].alive {
+                continue;
+            }
+            sums[0] += f64::from(agent.internal_state[0]);
+            sums[1] += f64::from(agent.internal_state[1]);
+            count += 1;
+        }
+        (count > 0).then(|| sums.map(|s| (s / count as f64) as f32))
+    }
+}
@@ -0,0 +1,122 @@
+use super::{ExperimentError, ExperimentObserver, World};
+use crate::image_export::{write_grid_png, Colormap, ImageExportError};
+use crate::metrics::RunSummary;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+/// What [`World::try_run_experiment_with_png_frames`] renders at each frame step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PngFrameOptions {
+    /// Steps between frames.
+    pub every: usize,
+    pub colormap: Colormap,
+    /// Fixed resource range for every frame; `None` normalizes each frame alone.
+    pub resource_range: Option<(f32, f32)>,
+    /// Cells per side of the agent density grid.
+    pub density_cells: usize,
+    /// Fixed agent-count range for every density frame.
+    pub density_range: Option<(f32, f32)>,
+}
+
+impl Default for PngFrameOptions {
+    fn default() -> Self {
+        Self {
+            every: 100,
+            colormap: Colormap::Viridis,
+            resource_range: None,
+            density_cells: 64,
+            density_range: None,
+        }
+    }
+}
+
+struct PngFrameObserver<'a> {
+    dir: &'a Path,
+    options: PngFrameOptions,
+    error: Option<ImageExportError>,
+}
+
+impl ExperimentObserver for PngFrameObserver<'_> {
+    fn on_step(&mut self, step: usize, world: &mut World) -> ControlFlow<()> {
+        if !step.is_multiple_of(self.options.every) {
+            return ControlFlow::Continue(());
+        }
+        let frame = |kind: &str| -> PathBuf { self.dir.join(format!("{kind}_{step:07}.png")) };
+        let written = world
+            .resource_field
+            .to_png(
+                frame("resource"),
+                self.options.colormap,
+                self.options.resource_range,
+            )
+            .and_then(|()| {
+                world.agent_density_png(
+                    frame("density"),
+                    self.options.density_cells,
+                    self.options.colormap,
+                    self.options.density_range,
+                )
+            });
+        match written {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(err) => {
+                self.error = Some(err);
+                ControlFlow::Break(())
+            }
+        }
+    }
+}
+
+impl World {
+    /// Write a `grid_cells` × `grid_cells` PNG of how many agents of alive
+    /// organisms fall in each cell of the world.
+    pub fn agent_density_png(
+        &self,
+        path: impl AsRef<Path>,
+        grid_cells: usize,
+        colormap: Colormap,
+        range: Option<(f32, f32)>,
+    ) -> Result<(), ImageExportError> {
+        if grid_cells == 0 {
+            return Err(ImageExportError::InvalidGridCells);
+        }
+        let cell_size = self.config.world_size / grid_cells as f64;
+        let cell = |c: f64| ((c / cell_size) as usize).min(grid_cells - 1);
+        let mut counts = vec![0.0f32; grid_cells * grid_cells];
+        for agent in &self.agents {
+            if self.organisms[agent.organism_id as usize].alive {
+                counts[cell(agent.position[1]) * grid_cells + cell(agent.position[0])] += 1.0;
+            }
+        }
+        write_grid_png(path, &counts, grid_cells, grid_cells, colormap, range)
+    }
+
+    /// Run an experiment like `try_run_experiment`, writing `resource_<step>.png`
+    /// and `density_<step>.png` into `dir` every `options.every` steps.
+    ///
+    /// A failed write stops the run and is returned as
+    /// [`ExperimentError::SnapshotWrite`]; frames written before it stay in `dir`.
+    pub fn try_run_experiment_with_png_frames(
+        &mut self,
+        steps: usize,
+        sample_every: usize,
+        dir: impl AsRef<Path>,
+        options: PngFrameOptions,
+    ) -> Result<RunSummary, ExperimentError> {
+        if options.every == 0 {
+            return Err(ExperimentError::InvalidSnapshotEvery);
+        }
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir).map_err(|e| ExperimentError::SnapshotWrite(e.to_string()))?;
+        let mut observer = PngFrameObserver {
+            dir,
+            options,
+            error: None,
+        };
+        let summary = self.try_run_experiment_with_observer(steps, sample_every, &mut observer)?;
+        match observer.error {
+            Some(err) => Err(ExperimentError::SnapshotWrite(err.to_string())),
+            None => Ok(summary),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Free-function entry points for building a seeded world from a config, so
+//! consumers share one agent and controller factory.
+
+use super::{World, WorldInitError};
+use crate::config::{PlacementStrategy, SimConfig};
+
+/// Validate `config` and build a world laid out by `config.placement`. Agents and
+/// controller weights come from [`World::bootstrap_entities`], seeded with
+/// `config.seed`, so equal configs give equal worlds.
+pub fn spawn_world(config: &SimConfig) -> Result<World, WorldInitError> {
+    World::from_config(config.clone())
+}
+
+/// [`spawn_world`] with agents laid out by `placement` instead of
+/// `config.placement`.
+pub fn spawn_world_with_positions(
+    config: &SimConfig,
+    placement: PlacementStrategy,
+) -> Result<World, WorldInitError> {
+    World::from_config_with_placement(config.clone(), placement)
+}
@@ -0,0 +1,249 @@
+use super::{AddOrganismError, InterventionError, World};
+use crate::genome::Genome;
+use crate::metabolism::{MetabolicState, MetabolismFlux};
+use crate::metrics::{DeathCause, LineageEvent, WorldEvent};
+use crate::nn::NnParams;
+use crate::organism::{DevelopmentalProgram, HomeostasisProgram, OrganismRuntime, SensoryProgram};
+
+/// Perturbations for experiments that intervene mid-run, typically from an
+/// [`super::ExperimentObserver`]. Each goes through the same bookkeeping as the
+/// step phases, so deaths, lifespans and the event log stay consistent.
+impl World {
+    /// Multiply every resource cell on every channel by `factor`.
+    pub fn scale_resources(&mut self, factor: f32) -> Result<(), InterventionError> {
+        if !factor.is_finite() || factor < 0.0 {
+            return Err(InterventionError::InvalidResourceFactor(factor));
+        }
+        self.resource_field.scale(factor);
+        Ok(())
+    }
+
+    /// Set the current regeneration rate. An environment schedule, shift or cycle
+    /// overrides it again at its next breakpoint.
+    pub fn set_resource_rate(&mut self, rate: f32) -> Result<(), InterventionError> {
+        if !rate.is_finite() || rate < 0.0 {
+            return Err(InterventionError::InvalidResourceRate(rate));
+        }
+        self.current_resource_rate = rate;
+        Ok(())
+    }
+
+    /// Displace internal-state channels 0 and 1 of every alive agent by `amount`,
+    /// clamped to [0,1], as a scheduled `homeostasis_shock_schedule` entry would.
+    /// Recovery is reported in the next run summary's `shock_recoveries`.
+    pub fn apply_homeostasis_shock(&mut self, amount: f32) -> Result<(), InterventionError> {
+        if !amount.is_finite() {
+            return Err(InterventionError::InvalidShockAmount(amount));
+        }
+        self.shock_internal_state(amount);
+        Ok(())
+    }
+
+    /// Add a cone-shaped pulse of resource to channel 0, peaking at `amount` at
+    /// `center` and falling to zero at `radius`, as a controlled stimulus. Cells may
+    /// exceed their regeneration ceiling until consumption draws them down.
+    pub fn deposit_resource_pulse(
+        &mut self,
+        center: [f64; 2],
+        radius: f64,
+        amount: f32,
+    ) -> Result<(), InterventionError> {
+        if !center.iter().all(|c| c.is_finite()) {
+            return Err(InterventionError::InvalidPulseCenter(center));
+        }
+        if !radius.is_finite() || radius <= 0.0 {
+            return Err(InterventionError::InvalidPulseRadius(radius));
+        }
+        if !amount.is_finite() || amount < 0.0 {
+            return Err(InterventionError::InvalidPulseAmount(amount));
+        }
+        self.resource_field.add_cone(center, radius, amount);
+        Ok(())
+    }
+
+    /// Kill the alive organism with `stable_id`, recording
+    /// [`DeathCause::Intervention`].
+    pub fn kill_organism(&mut self, stable_id: u64) -> Result<(), InterventionError> {
+        let org_idx = self.intervention_target(stable_id)?;
+        self.mark_dead(org_idx, DeathCause::Intervention);
+        Ok(())
+    }
+
+    /// Add `amount` to the energy of the alive organism with `stable_id`.
+    pub fn inject_energy(&mut self, stable_id: u64, amount: f32) -> Result<(), InterventionError> {
+        if !amount.is_finite() || amount < 0.0 {
+            return Err(InterventionError::InvalidEnergyAmount(amount));
+        }
+        let org_idx = self.intervention_target(stable_id)?;
+        self.organisms[org_idx].metabolic_state.energy += amount;
+        Ok(())
+    }
+
+    /// Set the energy of the alive organism with `stable_id`. Energy at or below
+    /// `death_energy_threshold` kills it in the next metabolism phase, as usual.
+    pub fn set_organism_energy(
+        &mut self,
+        stable_id: u64,
+        value: f32,
+    ) -> Result<(), InterventionError> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(InterventionError::InvalidEnergyValue(value));
+        }
+        let org_idx = self.intervention_target(stable_id)?;
+        self.organisms[org_idx].metabolic_state.energy = value;
+        Ok(())
+    }
+
+    /// Reduce the boundary integrity of the alive organism with `stable_id` by
+    /// `amount`, flooring at zero. The boundary phase then repairs or collapses
+    /// it as it would after natural decay.
+    pub fn damage_boundary(
+        &mut self,
+        stable_id: u64,
+        amount: f32,
+    ) -> Result<(), InterventionError> {
+        if !amount.is_finite() || amount < 0.0 {
+            return Err(InterventionError::InvalidBoundaryDamage(amount));
+        }
+        let org_idx = self.intervention_target(stable_id)?;
+        let org = &mut self.organisms[org_idx];
+        org.boundary_integrity = (org.boundary_integrity - amount).max(0.0);
+        Ok(())
+    }
+
+    /// Introduce an immigrant organism decoded from `genome`, with `n_agents`
+    /// agents scattered around `center` as for a newborn. It starts mature, at the
+    /// default energy, as a generation-0 lineage root whose [`LineageEvent`] has
+    /// parent [`LineageEvent::NO_PARENT`]. Returns the new stable id.
+    pub fn add_organism(
+        &mut self,
+        genome: Genome,
+        center: [f64; 2],
+        n_agents: usize,
+    ) -> Result<u64, AddOrganismError> {
+        if n_agents == 0 {
+            return Err(AddOrganismError::NoAgents);
+        }
+        if !center.iter().all(|c| c.is_finite()) {
+            return Err(AddOrganismError::InvalidCenter(center));
+        }
+        let nn = Self::nn_from_genome(&genome).ok_or(AddOrganismError::InvalidNnWeightCount(
+            genome.nn_weights().len(),
+        ))?;
+        let layout = genome.layout();
+        if layout.metabolic_size != self.config.metabolic_genome_size {
+            return Err(AddOrganismError::MetabolicGenomeSizeMismatch {
+                expected: self.config.metabolic_genome_size,
+                actual: layout.metabolic_size,
+            });
+        }
+        if layout.agent_roles != self.config.agent_role_count {
+            return Err(AddOrganismError::AgentRoleCountMismatch {
+                expected: self.config.agent_role_count,
+                actual: layout.agent_roles,
+            });
+        }
+        if layout.maturity_gating_genes != self.config.maturity_gating_evolvable {
+            return Err(AddOrganismError::MaturityGatingGenesMismatch {
+                expected: self.config.maturity_gating_evolvable,
+                actual: layout.maturity_gating_genes,
+            });
+        }
+        let total_agents = self.live_agent_count().saturating_add(n_agents);
+        if total_agents > self.config.max_total_agents {
+            return Err(AddOrganismError::TooManyAgents {
+                max: self.config.max_total_agents,
+                actual: total_agents,
+            });
+        }
+        let remaining_ids = u64::from(self.agent_id_limit.saturating_sub(self.next_agent_id))
+            + self.free_agent_ids.len() as u64;
+        if remaining_ids < n_agents as u64 {
+            return Err(AddOrganismError::AgentIdsExhausted);
+        }
+        if self.organisms.len() >= Self::ORGANISM_ID_CAPACITY {
+            self.prune_dead_entities();
+        }
+        let id = u16::try_from(self.organisms.len())
+            .map_err(|_| AddOrganismError::OrganismIdsExhausted)?;
+
+        let world_size = self.config.world_size;
+        let center = [
+            center[0].rem_euclid(world_size),
+            center[1].rem_euclid(world_size),
+        ];
+        let developmental_program =
+            DevelopmentalProgram::decode_in_layout(genome.active_segment_data(3), &layout);
+        let agent_ids = self.with_rng(None, |world, rng| {
+            world.spawn_agents(rng, id, center, n_agents, &developmental_program)
+        });
+        let stable_id = self.next_organism_stable_id;
+        let organism = OrganismRuntime {
+            id,
+            stable_id,
+            generation: 0,
+            age_steps: 0,
+            alive: true,
+            boundary_integrity: 1.0,
+            metabolic_state: MetabolicState::default(),
+            metabolism_engine: super::decode_organism_metabolism(
+                &genome,
+                self.config.metabolism_mode,
+            ),
+            developmental_program,
+            homeostasis_program: HomeostasisProgram::decode(genome.segment_data(2)),
+            sensory_program: SensoryProgram::decode(genome.segment_data(5)),
+            ancestor_genome: genome.clone(),
+            genome,
+            nn,
+            agent_ids,
+            maturity: 1.0,
+            parent_stable_id: None,
+            hebbian_trace: self.config.enable_hebbian_plasticity.then(Box::default),
+            dormant: false,
+            last_step_flux: MetabolismFlux::default(),
+            flux_since_snapshot: MetabolismFlux::default(),
+            crowding_excess: 0.0,
+            rng_word_pos: 0,
+            nn_params: NnParams::from_config(&self.config),
+        };
+        self.next_organism_stable_id = self.next_organism_stable_id.saturating_add(1);
+        self.record_lineage(LineageEvent {
+            step: self.step_index,
+            parent_stable_id: LineageEvent::NO_PARENT,
+            child_stable_id: stable_id,
+            generation: 0,
+        });
+        self.record_event(WorldEvent::Immigration {
+            step: self.step_index,
+            stable_id,
+            center,
+        });
+        self.organism_index_by_stable_id
+            .insert(stable_id, self.organisms.len());
+        self.organisms.push(organism);
+        self.org_toroidal_sums.push([0.0, 0.0, 0.0, 0.0]);
+        self.org_counts.push(0);
+        Ok(stable_id)
+    }
+
+    /// Stable ids of the organisms currently alive, in index order.
+    pub fn alive_stable_ids(&self) -> Vec<u64> {
+        self.organisms
+            .iter()
+            .filter(|o| o.alive)
+            .map(|o| o.stable_id)
+            .collect()
+    }
+
+    /// Toroidal mean position of the agents of the alive organism with `stable_id`.
+    pub fn alive_organism_center(&self, stable_id: u64) -> Option<[f64; 2]> {
+        self.alive_organism_index(stable_id)
+            .map(|idx| self.organism_center(idx))
+    }
+
+    fn intervention_target(&self, stable_id: u64) -> Result<usize, InterventionError> {
+        self.alive_organism_index(stable_id)
+            .ok_or(InterventionError::UnknownOrganism(stable_id))
+    }
+}
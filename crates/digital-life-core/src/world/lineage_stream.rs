@@ -0,0 +1,110 @@
+use super::{ExperimentError, World};
+use crate::metrics::{count_birth, LineageEvent};
+use std::io::{self, Write};
+use std::sync::{Mutex, PoisonError};
+
+/// Lineage writer installed by [`World::set_lineage_writer`].
+struct LineageStream {
+    writer: Box<dyn Write + Send>,
+    /// First write or flush failure; later events are dropped.
+    error: Option<io::Error>,
+}
+
+/// Optional JSON Lines destination for lineage events. The mutex only makes the
+/// world `Sync`; every access goes through `&mut`. Clones (e.g. forks) start
+/// without a writer and embed their lineage.
+#[derive(Default)]
+pub(super) struct LineageSink(Option<Mutex<LineageStream>>);
+
+impl Clone for LineageSink {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl LineageSink {
+    fn stream(&mut self) -> Option<&mut LineageStream> {
+        self.0
+            .as_mut()
+            .map(|stream| stream.get_mut().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Write `event` as one JSON line; returns `false` when no writer is installed.
+    fn write(&mut self, event: &LineageEvent) -> bool {
+        let Some(stream) = self.stream() else {
+            return false;
+        };
+        if stream.error.is_none() {
+            let written = serde_json::to_writer(&mut stream.writer, event)
+                .map_err(io::Error::from)
+                .and_then(|()| stream.writer.write_all(b"\n"));
+            stream.error = written.err();
+        }
+        true
+    }
+
+    fn flush(&mut self) {
+        if let Some(stream) = self.stream() {
+            if stream.error.is_none() {
+                stream.error = stream.writer.flush().err();
+            }
+        }
+    }
+}
+
+impl World {
+    /// Stream lineage events to `writer` as JSON Lines instead of keeping them in
+    /// `RunSummary::lineage_events`, so long runs hold no lineage in memory and a
+    /// crash loses at most the events since the last flush. Wrap files in a
+    /// `BufWriter`; the stream is flushed every `lineage_flush_interval_steps` steps
+    /// and when a run ends. Replaces any previous writer without flushing it.
+    pub fn set_lineage_writer(&mut self, writer: Box<dyn Write + Send>) {
+        self.lineage_sink = LineageSink(Some(Mutex::new(LineageStream {
+            writer,
+            error: None,
+        })));
+    }
+
+    /// Remove the lineage writer, returning it unflushed; later events are embedded
+    /// in run summaries again.
+    pub fn take_lineage_writer(&mut self) -> Option<Box<dyn Write + Send>> {
+        std::mem::take(&mut self.lineage_sink).0.map(|stream| {
+            stream
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner)
+                .writer
+        })
+    }
+
+    /// Tally a birth or immigration and either stream it or keep it for the run
+    /// summary.
+    pub(super) fn record_lineage(&mut self, event: LineageEvent) {
+        count_birth(&mut self.generation_births, &event);
+        if !self.lineage_sink.write(&event) {
+            self.lineage_events.push(event);
+        }
+    }
+
+    /// Flush the lineage stream on its configured cadence.
+    pub(super) fn flush_lineage_if_due(&mut self) {
+        let interval = self.config.lineage_flush_interval_steps;
+        if interval > 0 && self.step_index.is_multiple_of(interval) {
+            self.lineage_sink.flush();
+        }
+    }
+
+    /// Flush the lineage stream at the end of a run. Returns whether lineage was
+    /// streamed, or the first write failure since the writer was installed.
+    pub(super) fn finish_lineage_stream(&mut self) -> Result<bool, ExperimentError> {
+        self.lineage_sink.flush();
+        let streaming = self.lineage_sink.is_streaming();
+        match self.lineage_sink.stream().and_then(|s| s.error.take()) {
+            Some(err) => Err(ExperimentError::LineageWrite(err.to_string())),
+            None => Ok(streaming),
+        }
+    }
+}
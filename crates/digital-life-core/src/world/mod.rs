@@ -1,20 +1,36 @@
 use crate::agent::Agent;
-use crate::config::{AblationTarget, MetabolismMode, SimConfig, SimConfigError};
-use crate::genome::{Genome, MutationRates};
-use crate::metabolism::{MetabolicState, MetabolismEngine};
-use crate::nn::NeuralNet;
-use crate::organism::{DevelopmentalProgram, OrganismRuntime};
-use crate::resource::ResourceField;
+use crate::config::{
+    CapacityPolicy, CriteriaMask, EnvironmentSchedule, MetabolismMode, MetricsSelection,
+    ResourceDynamics, ResourceInit, ResourceResetPolicy, RngMode, SenescenceMode, SimConfig,
+    SimConfigErrors, SpeciationGenomeScope,
+};
+use crate::genome::{Genome, MutationRateModifiers, MutationRates, SegmentMutationRates};
+use crate::metabolism::{CounterMetabolism, MetabolicState, MetabolismEngine, MetabolismFlux};
+use crate::nn::{NeuralNet, NnParams, SENSOR_INPUT_SIZE};
+use crate::organism::{DevelopmentalProgram, HomeostasisProgram, OrganismRuntime, SensoryProgram};
+use crate::resource::{CorrelatedNoise, ResourceField, ResourceHotspots};
+use crate::snapshot_file::SnapshotSink;
 use crate::spatial;
+use activation_log::ActivationRecorder;
+use conservation::ConservationAudit;
+use lineage_stream::LineageSink;
+use rand::seq::SliceRandom;
 use rand::Rng;
 use rand::SeedableRng;
 use rand_chacha::ChaCha12Rng;
-use std::collections::HashSet;
+use std::collections::{HashMap, VecDeque};
 use std::f64::consts::PI;
+use std::ops::ControlFlow;
+use std::sync::{Mutex, PoisonError};
 use std::time::Instant;
 use std::{error::Error, fmt};
+use trajectory_stream::TrajectorySink;
 
-use crate::metrics::{LineageEvent, OrganismSnapshot, PopulationStats, RunSummary, SnapshotFrame};
+use crate::metrics::{
+    cluster_species, DeathCause, DecodedParams, EarlyWarningWindow, EventLog, FusionEvent,
+    LifespanSummary, LineageEvent, OrganismSnapshot, PopulationStats, RunSummary, ShockRecovery,
+    SnapshotFrame, SnapshotSchedule, SpeciationMetrics, StepMetrics, TimingSummary, WorldEvent,
+};
 
 /// Decode a genome's metabolic segment into a per-organism `MetabolismEngine`.
 ///
@@ -22,7 +38,9 @@ use crate::metrics::{LineageEvent, OrganismSnapshot, PopulationStats, RunSummary
 fn decode_organism_metabolism(genome: &Genome, mode: MetabolismMode) -> Option<MetabolismEngine> {
     match mode {
         MetabolismMode::Graph => {
-            let gm = crate::metabolism::decode_graph_metabolism(genome.segment_data(1));
+            let segment =
+                genome.padded_segment_data(1, crate::metabolism::MIN_METABOLIC_SEGMENT_SIZE);
+            let gm = crate::metabolism::decode_graph_metabolism(&segment);
             Some(MetabolismEngine::Graph(gm))
         }
         MetabolismMode::Toy | MetabolismMode::Counter => None,
@@ -37,9 +55,32 @@ pub struct StepTimings {
     pub total_us: u64,
 }
 
+/// Scratch space for `&self` methods that would otherwise allocate per call.
+/// Holds no simulation state, so clones start empty.
+#[derive(Default)]
+struct Scratch<T>(Mutex<T>);
+
+impl<T> Scratch<T> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, T> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Default> Clone for Scratch<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Simulation state. `Clone` is a full deep copy including the RNG position; see
+/// [`World::fork`].
+#[derive(Clone)]
 pub struct World {
     agents: Vec<Agent>,
     organisms: Vec<OrganismRuntime>,
+    /// Index into `organisms` by stable id, kept in step with pushes and pruning.
+    /// Dead organisms stay mapped until they are pruned.
+    organism_index_by_stable_id: HashMap<u64, usize>,
     config: SimConfig,
     metabolism: MetabolismEngine,
     resource_field: ResourceField,
@@ -47,6 +88,11 @@ pub struct World {
     org_counts: Vec<usize>,
     rng: ChaCha12Rng,
     next_agent_id: u32,
+    /// Exclusive upper bound on fresh agent ids; below `u32::MAX` only in tests.
+    agent_id_limit: u32,
+    /// Ids of pruned agents awaiting reuse (oldest first) when
+    /// `recycle_agent_ids` is set; holds at most `MAX_FREE_AGENT_IDS`.
+    free_agent_ids: VecDeque<u32>,
     step_index: usize,
     original_config: Option<SimConfig>,
     scheduled_ablation_applied: bool,
@@ -54,32 +100,112 @@ pub struct World {
     deaths_last_step: usize,
     total_births: usize,
     total_deaths: usize,
+    /// Organisms absorbed by fusion this step; not counted in `deaths_last_step`.
+    fusions_last_step: usize,
+    total_fusions: usize,
     mutation_rates: MutationRates,
     next_organism_stable_id: u64,
     agent_id_exhaustions_last_step: usize,
     total_agent_id_exhaustions: usize,
+    /// Reproduction attempts halted this step because every `u16` organism id was held
+    /// by a live organism even after compaction.
+    organism_id_exhaustions_last_step: usize,
+    /// Eligible parents skipped this step because the agent cap was reached.
+    reproduction_suppressed_last_step: usize,
+    /// Agents whose NN deltas had a non-finite component zeroed this step.
+    nonfinite_outputs_last_step: usize,
+    /// Organisms whose energy or waste left the finite non-negative range this step.
+    metabolic_violations_last_step: usize,
     lifespans: Vec<usize>,
+    /// Step of the latest homeostasis shock.
+    last_shock_step: Option<usize>,
+    /// Shock awaiting recovery, with its pre-shock channel 0 and 1 means.
+    pending_shock: Option<(ShockRecovery, [f32; 2])>,
+    /// This run's shocks that recovered or were superseded.
+    shock_recoveries: Vec<ShockRecovery>,
+    /// This run's lineage events, unless they are streamed to `lineage_sink`.
     lineage_events: Vec<LineageEvent>,
+    /// This run's fusions.
+    fusion_events: Vec<FusionEvent>,
+    lineage_sink: LineageSink,
+    trajectory_sink: TrajectorySink,
+    /// This run's births per generation, tallied as events are recorded so it
+    /// survives streaming.
+    generation_births: Vec<usize>,
+    event_log: EventLog,
     /// Runtime resource regeneration rate, separate from config to avoid mutating
     /// config at runtime during environment shifts.
     current_resource_rate: f32,
+    /// `config.effective_environment_schedule()`, built once per config.
+    environment_schedule: EnvironmentSchedule<'static>,
+    /// Drifting regeneration centers; `None` under `ResourceDynamics::Static`.
+    resource_hotspots: Option<ResourceHotspots>,
+    /// Tabulated `SpatiallyCorrelated` resource noise; empty until first drawn.
+    resource_noise: CorrelatedNoise,
+    /// Hidden-layer recording requested by [`World::record_activations_for`].
+    activation_recorder: ActivationRecorder,
+    /// Excreted metabolic waste; `None` unless `enable_waste_field` is set.
+    waste_field: Option<ResourceField>,
+    /// Field waste taken up by organisms during the last step.
+    waste_uptake_last_step: f32,
+    /// Recent run samples for `StepMetrics::early_warning`; reset when a run starts.
+    early_warning: EarlyWarningWindow,
+    /// Species label per organism index from the speciation pass at step
+    /// `species_labels_step`, for snapshots taken on that step.
+    species_labels: Vec<Option<u32>>,
+    species_labels_step: Option<usize>,
 
     // Buffers for avoiding allocation in simulation steps
     deltas_buffer: Vec<[f32; 4]>,
+    /// NN inputs (and sensor readings) awaiting batched inference; `nn_pending_buffer`
+    /// holds the agent index each belongs to.
+    nn_inputs_buffer: Vec<[f32; 8]>,
+    nn_sensors_buffer: Vec<[f32; SENSOR_INPUT_SIZE]>,
+    nn_pending_buffer: Vec<usize>,
+    nn_outputs_buffer: Vec<[f32; 4]>,
     neighbor_sums_buffer: Vec<f32>,
     neighbor_counts_buffer: Vec<usize>,
     homeostasis_sums_buffer: Vec<f32>,
     homeostasis_counts_buffer: Vec<usize>,
+    movement_distance_buffer: Vec<f64>,
+    uptake_cells_buffer: Vec<(usize, usize, f32)>,
+    predation_pairs_buffer: Vec<(usize, usize)>,
+    /// Resource under each agent before this step's uptake, weighting how metabolic
+    /// gains are credited to agent energy reserves.
+    reserve_weights_buffer: Vec<f32>,
+    /// Per-organism metabolic energy change awaiting distribution over its agents.
+    reserve_deltas_buffer: Vec<f32>,
+    /// Same-organism agent index pairs within `energy_sharing_radius`.
+    energy_sharing_pairs_buffer: Vec<(usize, usize)>,
+    /// Per-cell resource gradient, valid where the matching stamp equals `step_index`.
+    resource_gradient_cache: Vec<[f32; 2]>,
+    resource_gradient_stamps: Vec<usize>,
+    /// Corpse biomass awaiting deposit: (last center, amount).
+    pending_corpse_deposits: Vec<([f64; 2], f32)>,
+    /// Mass balance bookkeeping while `audit_conservation` is enabled.
+    conservation: Option<ConservationAudit>,
+    /// Same-organism and total neighbor tallies from the last sensing pass, when
+    /// `collect_contact_metrics` is enabled.
+    contact_pairs_last_step: (usize, usize),
+    /// Scratch per-cell agent counts for the `spatial_entropy` metric.
+    occupancy_buffer: Scratch<Vec<u32>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum WorldInitError {
-    Config(SimConfigError),
+    Config(SimConfigErrors),
     AgentCountOverflow,
     TooManyAgents { max: usize, actual: usize },
     NumOrganismsMismatch { expected: usize, actual: usize },
     AgentCountMismatch { expected: usize, actual: usize },
     InvalidOrganismId,
+    TooManyOrganisms { max: usize, actual: usize },
+    MetabolicGenomeSizeMismatch { expected: usize, actual: usize },
+    AgentRoleCountMismatch { expected: usize, actual: usize },
+    MaturityGatingGenesMismatch { expected: bool, actual: bool },
+    AgentOutsideWorld { agent_id: u32, world_size: f64 },
+    ControllerWeightCount { organism: usize, actual: usize },
+    EstimatedMemoryExceedsLimit { bytes: u64, limit: u64 },
 }
 
 impl fmt::Display for WorldInitError {
@@ -103,12 +229,45 @@ impl fmt::Display for WorldInitError {
             WorldInitError::InvalidOrganismId => {
                 write!(f, "all agent organism_ids must be valid indices into nns")
             }
+            WorldInitError::TooManyOrganisms { max, actual } => write!(
+                f,
+                "num_organisms ({actual}) exceeds maximum organism count ({max})"
+            ),
+            WorldInitError::MetabolicGenomeSizeMismatch { expected, actual } => write!(
+                f,
+                "metabolic_genome_size ({actual}) cannot differ from the existing genomes ({expected})"
+            ),
+            WorldInitError::AgentRoleCountMismatch { expected, actual } => write!(
+                f,
+                "agent_role_count ({actual}) cannot differ from the existing genomes ({expected})"
+            ),
+            WorldInitError::MaturityGatingGenesMismatch { expected, actual } => write!(
+                f,
+                "maturity_gating_evolvable ({actual}) cannot differ from the existing genomes ({expected})"
+            ),
+            WorldInitError::AgentOutsideWorld {
+                agent_id,
+                world_size,
+            } => write!(
+                f,
+                "agent {agent_id} lies outside the world_size ({world_size}) torus"
+            ),
+            WorldInitError::ControllerWeightCount { organism, actual } => write!(
+                f,
+                "controller {organism} has {actual} weights; expected {} or {}",
+                NeuralNet::WEIGHT_COUNT,
+                NeuralNet::EXTENDED_WEIGHT_COUNT
+            ),
+            WorldInitError::EstimatedMemoryExceedsLimit { bytes, limit } => write!(
+                f,
+                "estimated memory ({bytes} bytes) exceeds memory_limit_bytes ({limit})"
+            ),
         }
     }
 }
 
-impl From<SimConfigError> for WorldInitError {
-    fn from(err: SimConfigError) -> Self {
+impl From<SimConfigErrors> for WorldInitError {
+    fn from(err: SimConfigErrors) -> Self {
         WorldInitError::Config(err)
     }
 }
@@ -122,12 +281,87 @@ impl Error for WorldInitError {
     }
 }
 
+/// Progress hooks for `World::try_run_experiment_with_observer`.
+pub trait ExperimentObserver {
+    /// Called with each sample as it is recorded.
+    fn on_sample(&mut self, _metrics: &StepMetrics) {}
+
+    /// Called after every step with the 1-based run step; `Break` ends the run.
+    ///
+    /// The world may be perturbed here through its intervention methods (e.g.
+    /// [`World::kill_organism`]); the step's sample is taken afterwards.
+    fn on_step(&mut self, _step: usize, _world: &mut World) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Observer that never interrupts a run.
+pub struct NoopObserver;
+
+impl ExperimentObserver for NoopObserver {}
+
+/// Writes a snapshot frame every `every` steps after forwarding each hook to `inner`;
+/// stops the run on the first failure.
+struct SnapshotStreamObserver<'a, S: SnapshotSink> {
+    writer: &'a mut S,
+    every: usize,
+    inner: &'a mut dyn ExperimentObserver,
+    error: Option<std::io::Error>,
+}
+
+impl<S: SnapshotSink> ExperimentObserver for SnapshotStreamObserver<'_, S> {
+    fn on_sample(&mut self, metrics: &StepMetrics) {
+        self.inner.on_sample(metrics);
+    }
+
+    fn on_step(&mut self, step: usize, world: &mut World) -> ControlFlow<()> {
+        let flow = self.inner.on_step(step, world);
+        if !step.is_multiple_of(self.every) {
+            return flow;
+        }
+        match self
+            .writer
+            .write_frame(&world.collect_organism_snapshots(step))
+        {
+            Ok(()) => flow,
+            Err(err) => {
+                self.error = Some(err);
+                ControlFlow::Break(())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExperimentError {
     InvalidSampleEvery,
-    TooManySteps { max: usize, actual: usize },
-    TooManySamples { max: usize, actual: usize },
-    TooManySnapshots { max: usize, actual: usize },
+    TooManySteps {
+        max: usize,
+        actual: usize,
+    },
+    TooManySamples {
+        max: usize,
+        actual: usize,
+    },
+    TooManySnapshots {
+        max: usize,
+        actual: usize,
+    },
+    InvalidSnapshotEvery,
+    /// `SnapshotSchedule::LogSpaced` needs `first >= 1` and a finite `factor > 1`.
+    InvalidSnapshotSchedule,
+    /// [`World::record_activations_for`] needs a positive recording interval.
+    InvalidActivationEvery,
+    /// Writing a streamed snapshot frame failed; the run stopped at that step.
+    SnapshotWrite(String),
+    /// Writing or flushing the lineage stream failed; events after the failure
+    /// were dropped.
+    LineageWrite(String),
+    /// [`World::set_trajectory_writer`] needs a positive cadence and agent limit.
+    InvalidTrajectoryRecorder,
+    /// Writing or flushing the trajectory recorder failed; frames after the failure
+    /// were dropped.
+    TrajectoryWrite(String),
 }
 
 impl fmt::Display for ExperimentError {
@@ -149,21 +383,176 @@ impl fmt::Display for ExperimentError {
                     "snapshot count ({actual}) exceeds supported maximum ({max})"
                 )
             }
+            ExperimentError::InvalidSnapshotEvery => write!(f, "snapshot_every must be positive"),
+            ExperimentError::InvalidSnapshotSchedule => write!(
+                f,
+                "log-spaced snapshot schedule needs first >= 1 and a finite factor > 1"
+            ),
+            ExperimentError::InvalidActivationEvery => {
+                write!(f, "activation recording interval must be positive")
+            }
+            ExperimentError::SnapshotWrite(message) => {
+                write!(f, "failed to write snapshot frame: {message}")
+            }
+            ExperimentError::LineageWrite(message) => {
+                write!(f, "failed to write lineage events: {message}")
+            }
+            ExperimentError::InvalidTrajectoryRecorder => write!(
+                f,
+                "trajectory every_n_steps and max_agents must be positive"
+            ),
+            ExperimentError::TrajectoryWrite(message) => {
+                write!(f, "failed to write trajectory frame: {message}")
+            }
         }
     }
 }
 
 impl Error for ExperimentError {}
 
+/// Rejected mid-run intervention; the world is left unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterventionError {
+    InvalidResourceFactor(f32),
+    InvalidResourceRate(f32),
+    InvalidEnergyAmount(f32),
+    InvalidEnergyValue(f32),
+    InvalidBoundaryDamage(f32),
+    InvalidShockAmount(f32),
+    InvalidPulseCenter([f64; 2]),
+    InvalidPulseRadius(f64),
+    InvalidPulseAmount(f32),
+    /// No alive organism has this stable id.
+    UnknownOrganism(u64),
+}
+
+impl fmt::Display for InterventionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterventionError::InvalidResourceFactor(factor) => write!(
+                f,
+                "resource scale factor must be finite and non-negative, got {factor}"
+            ),
+            InterventionError::InvalidResourceRate(rate) => write!(
+                f,
+                "resource rate must be finite and non-negative, got {rate}"
+            ),
+            InterventionError::InvalidEnergyAmount(amount) => write!(
+                f,
+                "injected energy must be finite and non-negative, got {amount}"
+            ),
+            InterventionError::InvalidEnergyValue(value) => write!(
+                f,
+                "organism energy must be finite and non-negative, got {value}"
+            ),
+            InterventionError::InvalidBoundaryDamage(amount) => write!(
+                f,
+                "boundary damage must be finite and non-negative, got {amount}"
+            ),
+            InterventionError::InvalidShockAmount(amount) => {
+                write!(f, "homeostasis shock must be finite, got {amount}")
+            }
+            InterventionError::InvalidPulseCenter(center) => write!(
+                f,
+                "resource pulse center must be finite, got [{}, {}]",
+                center[0], center[1]
+            ),
+            InterventionError::InvalidPulseRadius(radius) => write!(
+                f,
+                "resource pulse radius must be finite and positive, got {radius}"
+            ),
+            InterventionError::InvalidPulseAmount(amount) => write!(
+                f,
+                "resource pulse amount must be finite and non-negative, got {amount}"
+            ),
+            InterventionError::UnknownOrganism(stable_id) => {
+                write!(f, "no alive organism with stable id {stable_id}")
+            }
+        }
+    }
+}
+
+impl Error for InterventionError {}
+
+/// Rejected [`World::add_organism`] call; the world is left unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AddOrganismError {
+    NoAgents,
+    InvalidCenter([f64; 2]),
+    /// The genome's NN segment decodes to neither controller layout.
+    InvalidNnWeightCount(usize),
+    MetabolicGenomeSizeMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    AgentRoleCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    MaturityGatingGenesMismatch {
+        expected: bool,
+        actual: bool,
+    },
+    TooManyAgents {
+        max: usize,
+        actual: usize,
+    },
+    AgentIdsExhausted,
+    /// Every `u16` organism id is held by an alive organism.
+    OrganismIdsExhausted,
+}
+
+impl fmt::Display for AddOrganismError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddOrganismError::NoAgents => write!(f, "n_agents must be positive"),
+            AddOrganismError::InvalidCenter(center) => {
+                write!(f, "center must be finite, got {center:?}")
+            }
+            AddOrganismError::InvalidNnWeightCount(actual) => write!(
+                f,
+                "genome has {actual} NN weights, expected {} or {}",
+                NeuralNet::WEIGHT_COUNT,
+                NeuralNet::EXTENDED_WEIGHT_COUNT
+            ),
+            AddOrganismError::MetabolicGenomeSizeMismatch { expected, actual } => write!(
+                f,
+                "genome metabolic segment has {actual} values, but metabolic_genome_size is {expected}"
+            ),
+            AddOrganismError::AgentRoleCountMismatch { expected, actual } => write!(
+                f,
+                "genome encodes {actual} agent roles, but agent_role_count is {expected}"
+            ),
+            AddOrganismError::MaturityGatingGenesMismatch { expected, actual } => write!(
+                f,
+                "genome has maturity gating genes: {actual}, but maturity_gating_evolvable is {expected}"
+            ),
+            AddOrganismError::TooManyAgents { max, actual } => {
+                write!(f, "too many agents: {actual} > max {max}")
+            }
+            AddOrganismError::AgentIdsExhausted => write!(f, "agent ids are exhausted"),
+            AddOrganismError::OrganismIdsExhausted => write!(f, "organism ids are exhausted"),
+        }
+    }
+}
+
+impl Error for AddOrganismError {}
+
 impl World {
     pub const MAX_WORLD_SIZE: f64 = crate::constants::MAX_WORLD_SIZE;
 
-    pub const MAX_EXPERIMENT_STEPS: usize = 1_000_000;
-    pub const MAX_EXPERIMENT_SAMPLES: usize = 50_000;
+    /// Default `SimConfig::max_experiment_steps`.
+    pub const MAX_EXPERIMENT_STEPS: usize = SimConfig::MAX_EXPERIMENT_STEPS;
+    /// Default `SimConfig::max_experiment_samples`.
+    pub const MAX_EXPERIMENT_SAMPLES: usize = SimConfig::MAX_EXPERIMENT_SAMPLES;
     pub const MAX_EXPERIMENT_SNAPSHOTS: usize = 1_000;
+    /// Number of distinct `u16` organism ids, i.e. the most organisms `organisms` can hold.
+    pub const ORGANISM_ID_CAPACITY: usize = u16::MAX as usize + 1;
+    /// Most pruned agent ids kept for reuse; further ids are dropped.
+    pub const MAX_FREE_AGENT_IDS: usize = SimConfig::MAX_TOTAL_AGENTS;
 
     pub fn new(
-        agents: Vec<Agent>,
+        mut agents: Vec<Agent>,
         nns: Vec<NeuralNet>,
         config: SimConfig,
     ) -> Result<Self, WorldInitError> {
@@ -178,12 +567,18 @@ impl World {
             .num_organisms
             .checked_mul(config.agents_per_organism)
             .ok_or(WorldInitError::AgentCountOverflow)?;
-        if expected_agent_count > SimConfig::MAX_TOTAL_AGENTS {
+        if expected_agent_count > config.max_total_agents {
             return Err(WorldInitError::TooManyAgents {
-                max: SimConfig::MAX_TOTAL_AGENTS,
+                max: config.max_total_agents,
                 actual: expected_agent_count,
             });
         }
+        if let Some(limit) = config.memory_limit_bytes {
+            let bytes = config.estimated_memory_bytes();
+            if bytes > limit {
+                return Err(WorldInitError::EstimatedMemoryExceedsLimit { bytes, limit });
+            }
+        }
         if agents.len() != expected_agent_count {
             return Err(WorldInitError::AgentCountMismatch {
                 expected: expected_agent_count,
@@ -198,8 +593,18 @@ impl World {
             .into_iter()
             .enumerate()
             .map(|(id, nn)| {
-                let genome = Genome::with_nn_weights(nn.to_weight_vec());
-                let developmental_program = DevelopmentalProgram::decode(genome.segment_data(3));
+                let nn = if config.enable_resource_gradient_sensing {
+                    nn.with_sensor_inputs()
+                } else {
+                    nn
+                };
+                let genome = Genome::with_layout(nn.to_weight_vec(), config.genome_layout());
+                let developmental_program = DevelopmentalProgram::decode_in_layout(
+                    genome.active_segment_data(3),
+                    &config.genome_layout(),
+                );
+                let homeostasis_program = HomeostasisProgram::decode(genome.segment_data(2));
+                let sensory_program = SensoryProgram::decode(genome.segment_data(5));
                 OrganismRuntime {
                     id: id as u16,
                     stable_id: id as u64,
@@ -215,7 +620,16 @@ impl World {
                     maturity: 1.0,
                     metabolism_engine: None,
                     developmental_program,
+                    homeostasis_program,
+                    sensory_program,
                     parent_stable_id: None,
+                    hebbian_trace: config.enable_hebbian_plasticity.then(Box::default),
+                    dormant: false,
+                    last_step_flux: MetabolismFlux::default(),
+                    flux_since_snapshot: MetabolismFlux::default(),
+                    crowding_excess: 0.0,
+                    rng_word_pos: 0,
+                    nn_params: NnParams::from_config(&config),
                 }
             })
             .collect();
@@ -225,13 +639,22 @@ impl World {
                 .agent_ids
                 .push(agent.id);
         }
+        let mut role_cursors = vec![0usize; organisms.len()];
+        for agent in &mut agents {
+            let org_idx = agent.organism_id as usize;
+            let org = &organisms[org_idx];
+            agent.role = org
+                .developmental_program
+                .role_for_index(role_cursors[org_idx], org.agent_ids.len());
+            role_cursors[org_idx] += 1;
+        }
 
         // Graph mode: initialize each organism's metabolic genome segment with
         // small random values, then decode into per-organism metabolism engines.
         let mut init_rng = ChaCha12Rng::seed_from_u64(config.seed.wrapping_add(1));
         if config.metabolism_mode == MetabolismMode::Graph {
             for org in &mut organisms {
-                let mut seg = [0.0f32; Genome::METABOLIC_SIZE];
+                let mut seg = vec![0.0f32; config.metabolic_genome_size];
                 for v in &mut seg {
                     *v = init_rng.random_range(-0.5f32..0.5);
                 }
@@ -242,30 +665,35 @@ impl World {
         }
 
         let max_agent_id = agents.iter().map(|a| a.id).max().unwrap_or(0);
-        let metabolism = match config.metabolism_mode {
-            MetabolismMode::Toy => MetabolismEngine::default(),
-            MetabolismMode::Counter => {
-                MetabolismEngine::Counter(crate::metabolism::CounterMetabolism::default())
-            }
-            MetabolismMode::Graph => {
-                MetabolismEngine::Graph(crate::metabolism::GraphMetabolism::default())
-            }
-        };
+        // Derive everything that reads `config` first so it moves into the world
+        // without a copy.
+        let metabolism = Self::metabolism_engine_from_config(&config);
+        let resource_field = Self::resource_field_from_config(&config);
+        let rng = ChaCha12Rng::seed_from_u64(config.seed);
+        let mutation_rates = Self::mutation_rates_from_config(&config);
+        let event_log = EventLog::new(config.event_log_max_entries);
+        let environment_schedule = config.effective_environment_schedule().into_owned();
+        let current_resource_rate = environment_schedule.rate_at(0);
+        let resource_hotspots = Self::resource_hotspots_from_config(&config);
+        let waste_field = Self::waste_field_from_config(&config);
+        let early_warning = EarlyWarningWindow::new(config.early_warning_window);
 
-        let world_size = config.world_size;
         let org_count = organisms.len();
         let agent_count = agents.len();
         let next_organism_stable_id = org_count as u64;
         Ok(Self {
             agents,
             organisms,
-            config: config.clone(),
+            organism_index_by_stable_id: (0..org_count).map(|idx| (idx as u64, idx)).collect(),
+            config,
             metabolism,
-            resource_field: ResourceField::new(world_size, 1.0, 1.0),
+            resource_field,
             org_toroidal_sums: vec![[0.0, 0.0, 0.0, 0.0]; org_count],
             org_counts: vec![0; org_count],
-            rng: ChaCha12Rng::seed_from_u64(config.seed),
+            rng,
             next_agent_id: max_agent_id.saturating_add(1),
+            agent_id_limit: u32::MAX,
+            free_agent_ids: VecDeque::new(),
             step_index: 0,
             original_config: None,
             scheduled_ablation_applied: false,
@@ -273,22 +701,133 @@ impl World {
             deaths_last_step: 0,
             total_births: 0,
             total_deaths: 0,
-            mutation_rates: Self::mutation_rates_from_config(&config),
+            fusions_last_step: 0,
+            total_fusions: 0,
+            mutation_rates,
             next_organism_stable_id,
             agent_id_exhaustions_last_step: 0,
             total_agent_id_exhaustions: 0,
+            organism_id_exhaustions_last_step: 0,
+            reproduction_suppressed_last_step: 0,
+            nonfinite_outputs_last_step: 0,
+            metabolic_violations_last_step: 0,
             lifespans: Vec::new(),
+            last_shock_step: None,
+            pending_shock: None,
+            shock_recoveries: Vec::new(),
             lineage_events: Vec::new(),
-            current_resource_rate: config.resource_regeneration_rate,
+            fusion_events: Vec::new(),
+            lineage_sink: LineageSink::default(),
+            trajectory_sink: TrajectorySink::default(),
+            generation_births: Vec::new(),
+            event_log,
+            current_resource_rate,
+            environment_schedule,
+            resource_hotspots,
+            resource_noise: CorrelatedNoise::default(),
+            activation_recorder: ActivationRecorder::default(),
+            waste_field,
+            waste_uptake_last_step: 0.0,
+            early_warning,
+            species_labels: Vec::new(),
+            species_labels_step: None,
             deltas_buffer: Vec::with_capacity(agent_count),
+            nn_inputs_buffer: Vec::with_capacity(agent_count),
+            nn_sensors_buffer: Vec::new(),
+            nn_pending_buffer: Vec::with_capacity(agent_count),
+            nn_outputs_buffer: Vec::with_capacity(agent_count),
             neighbor_sums_buffer: Vec::with_capacity(org_count),
             neighbor_counts_buffer: Vec::with_capacity(org_count),
             homeostasis_sums_buffer: Vec::with_capacity(org_count),
             homeostasis_counts_buffer: Vec::with_capacity(org_count),
+            movement_distance_buffer: Vec::with_capacity(org_count),
+            uptake_cells_buffer: Vec::new(),
+            reserve_weights_buffer: Vec::new(),
+            reserve_deltas_buffer: Vec::new(),
+            energy_sharing_pairs_buffer: Vec::new(),
+            predation_pairs_buffer: Vec::new(),
+            resource_gradient_cache: Vec::new(),
+            resource_gradient_stamps: Vec::new(),
+            pending_corpse_deposits: Vec::new(),
+            conservation: None,
+            contact_pairs_last_step: (0, 0),
+            occupancy_buffer: Scratch::default(),
         })
     }
 
+    /// Fresh resource field laid out per `config.resource_init`. Random layouts draw
+    /// from their own seed-derived stream so they do not shift the world RNG.
+    fn resource_field_from_config(config: &SimConfig) -> ResourceField {
+        let mut field =
+            ResourceField::with_channels(config.world_size, 1.0, 1.0, config.resource_channels);
+        let mut rng = ChaCha12Rng::seed_from_u64(config.seed.wrapping_add(2));
+        match config.resource_init {
+            ResourceInit::Uniform => {}
+            ResourceInit::Patches {
+                count,
+                radius,
+                peak,
+                background,
+            } => {
+                field.init_patches(&mut rng, count, radius, peak, background);
+            }
+            ResourceInit::Gradient { axis, low, high } => field.init_gradient(axis, low, high),
+            ResourceInit::Noise { mean, amplitude } => field.init_noise(&mut rng, mean, amplitude),
+        }
+        field
+    }
+
+    /// Empty waste field on the resource grid, when `config.enable_waste_field` is set.
+    fn waste_field_from_config(config: &SimConfig) -> Option<ResourceField> {
+        config
+            .enable_waste_field
+            .then(|| ResourceField::new(config.world_size, 1.0, 0.0))
+    }
+
+    /// Hotspot trajectories for `config.resource_dynamics`, drawn from their own
+    /// seed-derived stream.
+    fn resource_hotspots_from_config(config: &SimConfig) -> Option<ResourceHotspots> {
+        match config.resource_dynamics {
+            ResourceDynamics::Static => None,
+            ResourceDynamics::MovingHotspots { count, speed, .. } => {
+                let mut rng = ChaCha12Rng::seed_from_u64(config.seed.wrapping_add(3));
+                Some(ResourceHotspots::new(
+                    &mut rng,
+                    count,
+                    speed,
+                    config.world_size,
+                ))
+            }
+        }
+    }
+
+    /// Shared engine for `config.metabolism_mode`. Graph mode organisms carry their
+    /// own decoded engine; the shared one is only a fallback there.
+    fn metabolism_engine_from_config(config: &SimConfig) -> MetabolismEngine {
+        match config.metabolism_mode {
+            MetabolismMode::Toy => MetabolismEngine::default(),
+            MetabolismMode::Counter => MetabolismEngine::Counter(CounterMetabolism {
+                uptake_rate: config.counter_uptake_rate,
+                flat_efficiency: config.counter_conversion_efficiency,
+                waste_fraction: config.counter_waste_fraction,
+                basal_cost: config.counter_basal_cost,
+                ..CounterMetabolism::default()
+            }),
+            MetabolismMode::Graph => {
+                MetabolismEngine::Graph(crate::metabolism::GraphMetabolism::default())
+            }
+        }
+    }
+
     fn mutation_rates_from_config(config: &SimConfig) -> MutationRates {
+        let mut per_segment = [None; 7];
+        for o in &config.mutation_segment_overrides {
+            per_segment[o.segment.index()] = Some(SegmentMutationRates {
+                point_rate: o.point_rate,
+                reset_rate: o.reset_rate,
+                scale_rate: o.scale_rate,
+            });
+        }
         MutationRates {
             point_rate: config.mutation_point_rate,
             point_scale: config.mutation_point_scale,
@@ -297,6 +836,9 @@ impl World {
             scale_min: config.mutation_scale_min,
             scale_max: config.mutation_scale_max,
             value_limit: config.mutation_value_limit,
+            per_segment,
+            duplication_rate: config.mutation_duplication_rate,
+            deletion_rate: config.mutation_deletion_rate,
         }
     }
 
@@ -304,9 +846,41 @@ impl World {
         &self.config
     }
 
+    /// Apply `f` to a copy of the current config and install it through
+    /// [`World::set_config`], so one flag can change without the caller cloning
+    /// and rebuilding the config. On error the world keeps its current config.
+    pub fn config_mut_with(
+        &mut self,
+        f: impl FnOnce(&mut SimConfig),
+    ) -> Result<(), WorldInitError> {
+        let mut config = self.config.clone();
+        f(&mut config);
+        self.set_config(config)
+    }
+
     pub fn set_config(&mut self, config: SimConfig) -> Result<(), WorldInitError> {
         let mode_changed = self.config.metabolism_mode != config.metabolism_mode;
+        let plasticity_changed =
+            self.config.enable_hebbian_plasticity != config.enable_hebbian_plasticity;
         config.validate()?;
+        if config.metabolic_genome_size != self.config.metabolic_genome_size {
+            return Err(WorldInitError::MetabolicGenomeSizeMismatch {
+                expected: self.config.metabolic_genome_size,
+                actual: config.metabolic_genome_size,
+            });
+        }
+        if config.agent_role_count != self.config.agent_role_count {
+            return Err(WorldInitError::AgentRoleCountMismatch {
+                expected: self.config.agent_role_count,
+                actual: config.agent_role_count,
+            });
+        }
+        if config.maturity_gating_evolvable != self.config.maturity_gating_evolvable {
+            return Err(WorldInitError::MaturityGatingGenesMismatch {
+                expected: self.config.maturity_gating_evolvable,
+                actual: config.maturity_gating_evolvable,
+            });
+        }
         if config.num_organisms != self.organisms.len() {
             return Err(WorldInitError::NumOrganismsMismatch {
                 expected: config.num_organisms,
@@ -323,34 +897,94 @@ impl World {
                 actual: self.agents.len(),
             });
         }
-        if (self.config.world_size - config.world_size).abs() > f64::EPSILON {
-            self.resource_field = ResourceField::new(config.world_size, 1.0, 1.0);
+        let resized = (self.config.world_size - config.world_size).abs() > f64::EPSILON;
+        // Only a resize can strand agents; `World::new` does not bound positions.
+        let stranded = resized
+            .then(|| {
+                self.agents.iter().find(|a| {
+                    a.position
+                        .iter()
+                        .any(|&c| !(0.0..config.world_size).contains(&c))
+                })
+            })
+            .flatten();
+        if let Some(agent) = stranded {
+            return Err(WorldInitError::AgentOutsideWorld {
+                agent_id: agent.id,
+                world_size: config.world_size,
+            });
+        }
+        if self.config.resource_init != config.resource_init
+            || self.config.resource_channels != config.resource_channels
+            || (resized && config.resource_reset_policy == ResourceResetPolicy::Reset)
+        {
+            self.resource_field = Self::resource_field_from_config(&config);
+        } else if resized {
+            self.resource_field = self.resource_field.rescaled(config.world_size);
         }
-        self.current_resource_rate = config.resource_regeneration_rate;
+        if resized || self.config.resource_dynamics != config.resource_dynamics {
+            self.resource_hotspots = Self::resource_hotspots_from_config(&config);
+            if let Some(hotspots) = &mut self.resource_hotspots {
+                hotspots.update(self.step_index as f64 * config.dt);
+            }
+        }
+        if resized || self.config.enable_waste_field != config.enable_waste_field {
+            self.waste_field = Self::waste_field_from_config(&config);
+        }
+        self.environment_schedule = config.effective_environment_schedule().into_owned();
+        self.current_resource_rate = self.environment_schedule.rate_at(self.step_index);
+        self.mutation_rates = Self::mutation_rates_from_config(&config);
+        self.metabolism = Self::metabolism_engine_from_config(&config);
         self.config = config;
         self.original_config = None;
         self.scheduled_ablation_applied = false;
-        self.mutation_rates = Self::mutation_rates_from_config(&self.config);
         if mode_changed {
-            self.metabolism = match self.config.metabolism_mode {
-                MetabolismMode::Toy => MetabolismEngine::default(),
-                MetabolismMode::Counter => {
-                    MetabolismEngine::Counter(crate::metabolism::CounterMetabolism::default())
-                }
-                MetabolismMode::Graph => {
-                    MetabolismEngine::Graph(crate::metabolism::GraphMetabolism::default())
-                }
-            };
-            for org in &mut self.organisms {
+            for org in self.organisms.iter_mut().filter(|o| o.alive) {
                 org.metabolism_engine =
                     decode_organism_metabolism(&org.genome, self.config.metabolism_mode);
             }
         }
+        if plasticity_changed {
+            for org in self.organisms.iter_mut().filter(|o| o.alive) {
+                org.hebbian_trace = self.config.enable_hebbian_plasticity.then(Box::default);
+            }
+        }
+        if !self.config.enable_dormancy {
+            for org in &mut self.organisms {
+                org.dormant = false;
+            }
+        }
+        if !self.config.recycle_agent_ids {
+            self.free_agent_ids.clear();
+        }
         Ok(())
     }
 
-    pub fn set_metabolism_engine(&mut self, engine: MetabolismEngine) {
-        self.metabolism = engine;
+    /// Independent copy of the current state for counterfactual branches.
+    ///
+    /// Agents, organisms, the resource field, counters, logs and the exact RNG position
+    /// are all duplicated, so the original and the fork stepped identically stay
+    /// identical (equal `state_hash` and metrics). Only the `StepTimings` returned by
+    /// `step` differ, since they measure wall-clock time.
+    pub fn fork(&self) -> World {
+        self.clone()
+    }
+
+    /// Like [`World::fork`], but the copy's RNG is reseeded from `seed` (also recorded
+    /// as its `config.seed`), so its future diverges from the original's.
+    pub fn fork_with_seed(&self, seed: u64) -> World {
+        let mut fork = self.clone();
+        fork.rng = ChaCha12Rng::seed_from_u64(seed);
+        fork.config.seed = seed;
+        fork
+    }
+
+    /// Replace the shared engine used by organisms without a genome-decoded one.
+    /// Accepts a [`MetabolismEngine`] or any [`Metabolism`](crate::metabolism::Metabolism)
+    /// implementor. `set_config` rebuilds the engine from `metabolism_mode`, so
+    /// reinstall custom engines after it.
+    pub fn set_metabolism_engine(&mut self, engine: impl Into<MetabolismEngine>) {
+        self.metabolism = engine.into();
     }
 
     pub fn agents(&self) -> &[Agent] {
@@ -365,6 +999,40 @@ impl World {
         &mut self.resource_field
     }
 
+    /// Current regeneration hotspot centers; empty under `ResourceDynamics::Static`.
+    pub fn resource_hotspots(&self) -> &[[f64; 2]] {
+        self.resource_hotspots
+            .as_ref()
+            .map_or(&[], |hotspots| hotspots.centers())
+    }
+
+    /// Environmental waste field; `None` unless `enable_waste_field` is set.
+    pub fn waste_field(&self) -> Option<&ResourceField> {
+        self.waste_field.as_ref()
+    }
+
+    /// Genome of the alive organism with `stable_id`.
+    pub fn organism_genome(&self, stable_id: u64) -> Option<&Genome> {
+        self.alive_organism_index(stable_id)
+            .map(|idx| &self.organisms[idx].genome)
+    }
+
+    /// Controller weights of the alive organism with `stable_id`, in
+    /// [`NeuralNet::to_weight_vec`] order. These are the inherited weights; Hebbian
+    /// offsets are not included.
+    pub fn organism_nn_weights(&self, stable_id: u64) -> Option<Vec<f32>> {
+        self.alive_organism_index(stable_id)
+            .map(|idx| self.organisms[idx].nn.to_weight_vec())
+    }
+
+    /// Index of the alive organism with `stable_id`.
+    fn alive_organism_index(&self, stable_id: u64) -> Option<usize> {
+        self.organism_index_by_stable_id
+            .get(&stable_id)
+            .copied()
+            .filter(|&idx| self.organisms[idx].alive)
+    }
+
     pub fn metabolic_state(&self, organism_id: usize) -> Option<&MetabolicState> {
         self.organisms.get(organism_id).map(|o| &o.metabolic_state)
     }
@@ -386,6 +1054,7 @@ impl World {
             alive_count: alive,
             total_births: self.total_births,
             total_deaths: self.total_deaths,
+            total_fusions: self.total_fusions,
             mean_generation: if alive > 0 {
                 generation_sum / alive as f32
             } else {
@@ -398,7 +1067,16 @@ impl World {
         self.organisms.iter().map(|o| o.alive).collect()
     }
 
-    fn alive_count(&self) -> usize {
+    /// Number of agents belonging to alive organisms.
+    fn live_agent_count(&self) -> usize {
+        self.agents
+            .iter()
+            .filter(|a| self.organisms[a.organism_id as usize].alive)
+            .count()
+    }
+
+    /// Number of organisms currently alive.
+    pub fn alive_count(&self) -> usize {
         self.organisms.iter().filter(|o| o.alive).count()
     }
 
@@ -409,7 +1087,10 @@ impl World {
     }
 
     fn next_agent_id_checked(&mut self) -> Option<u32> {
-        if self.next_agent_id == u32::MAX {
+        if let Some(id) = self.free_agent_ids.pop_front() {
+            return Some(id);
+        }
+        if self.next_agent_id >= self.agent_id_limit {
             return None;
         }
         let id = self.next_agent_id;
@@ -484,10 +1165,20 @@ impl World {
             {
                 agent.organism_id = new_org_id;
                 new_agents.push(agent);
+            } else if self.config.recycle_agent_ids
+                && self.free_agent_ids.len() < Self::MAX_FREE_AGENT_IDS
+            {
+                self.free_agent_ids.push_back(agent.id);
             }
         }
 
         self.organisms = new_organisms;
+        self.organism_index_by_stable_id = self
+            .organisms
+            .iter()
+            .enumerate()
+            .map(|(idx, org)| (org.stable_id, idx))
+            .collect();
         self.agents = new_agents;
         for agent in &self.agents {
             self.organisms[agent.organism_id as usize]
@@ -509,17 +1200,165 @@ impl World {
         (angle.rem_euclid(2.0 * PI) / (2.0 * PI)) * world_size
     }
 
-    /// Effective sensing radius for an organism, accounting for developmental stage.
+    /// Effective sensing radius for an organism, accounting for developmental stage
+    /// and, under `enable_genetic_sensing`, its sensory radius multiplier.
     fn effective_sensing_radius(&self, org_idx: usize) -> f64 {
+        let org = &self.organisms[org_idx];
         let dev_sensing = if self.config.enable_growth {
-            self.organisms[org_idx]
-                .developmental_program
-                .stage_factors(self.organisms[org_idx].maturity)
-                .1
+            org.developmental_program
+                .stage_factors(org.maturity)
+                .sensing
         } else {
             1.0
         };
-        self.config.sensing_radius * dev_sensing as f64
+        self.config.sensing_radius
+            * (dev_sensing * Self::genetic_radius_multiplier(&self.config, org)) as f64
+    }
+
+    /// Genome-decoded sensing-radius multiplier; 1.0 without `enable_genetic_sensing`.
+    fn genetic_radius_multiplier(config: &SimConfig, org: &OrganismRuntime) -> f32 {
+        if config.enable_genetic_sensing {
+            org.sensory_program.radius_multiplier
+        } else {
+            1.0
+        }
+    }
+
+    /// Metabolic efficiency multiplier for an organism's developmental stage.
+    fn metabolic_stage_factor(config: &SimConfig, org: &OrganismRuntime) -> f32 {
+        if config.enable_growth {
+            org.developmental_program
+                .stage_factors(org.maturity)
+                .metabolic
+        } else {
+            config.growth_immature_metabolic_efficiency
+                + org.maturity * (1.0 - config.growth_immature_metabolic_efficiency)
+        }
+    }
+
+    /// Multiplier on boundary repair and metabolic gains from senescence: 1.0 before
+    /// onset, declining with age afterwards per `senescence_mode`.
+    fn senescence_factor(config: &SimConfig, org: &OrganismRuntime) -> f32 {
+        if config.senescence_mode == SenescenceMode::Off {
+            return 1.0;
+        }
+        let (onset, rate) = if config.senescence_evolvable {
+            let pace = org.developmental_program.aging_rate_modifier;
+            (
+                config.senescence_onset_steps as f32 / pace,
+                config.senescence_rate * pace,
+            )
+        } else {
+            (config.senescence_onset_steps as f32, config.senescence_rate)
+        };
+        let past_onset = org.age_steps as f32 - onset;
+        if past_onset <= 0.0 {
+            return 1.0;
+        }
+        match config.senescence_mode {
+            SenescenceMode::Off => 1.0,
+            SenescenceMode::Linear => (1.0 - rate * past_onset).max(0.0),
+            SenescenceMode::Exponential => (-rate * past_onset).exp(),
+        }
+    }
+
+    /// Sample population metrics for the current world state, labelled with `step`.
+    pub fn collect_step_metrics(&self, step: usize) -> StepMetrics {
+        crate::metrics::collect_step_metrics(
+            step,
+            self.step_index,
+            self.config.seed,
+            self.config.metrics_selection,
+            &self.config.genome_diversity,
+            self.config.world_size,
+            self.config.cohesion_metric_mode,
+            self.config.max_cohesion_pairs,
+            match self.config.spatial_entropy_grid {
+                0 => self.resource_field.width(),
+                grid => grid,
+            },
+            &mut self.occupancy_buffer.lock(),
+            self.resource_field.channel_totals(),
+            self.resource_field.variance(),
+            self.current_resource_rate,
+            self.config
+                .collect_contact_metrics
+                .then(|| match self.contact_pairs_last_step {
+                    (_, 0) => 0.0,
+                    (same, total) => same as f32 / total as f32,
+                }),
+            self.config.enable_dormancy,
+            self.config.agent_role_count,
+            self.last_shock_step
+                .map(|shock| self.step_index.saturating_sub(shock)),
+            self.waste_field
+                .as_ref()
+                .map(|field| (field.total(), self.waste_uptake_last_step)),
+            self.births_last_step,
+            self.deaths_last_step,
+            self.fusions_last_step,
+            self.agent_id_exhaustions_last_step,
+            self.organism_id_exhaustions_last_step,
+            self.reproduction_suppressed_last_step,
+            self.nonfinite_outputs_last_step,
+            self.metabolic_violations_last_step,
+            self.conservation_ledger().map(|ledger| ledger.residual),
+            &self.organisms,
+            &self.agents,
+        )
+    }
+
+    /// Sample metrics for a run's `sample_index`-th sample, advancing the early-warning
+    /// window and attaching its indicators on the configured cadence.
+    fn collect_run_sample(&mut self, step: usize, sample_index: usize) -> StepMetrics {
+        let mut metrics = self.collect_step_metrics(step);
+        if self.config.collect_early_warning
+            && self
+                .config
+                .metrics_selection
+                .contains(MetricsSelection::EARLY_WARNING)
+        {
+            self.early_warning.push(&metrics);
+            if (sample_index + 1).is_multiple_of(self.config.early_warning_every) {
+                metrics.early_warning = self.early_warning.metrics();
+            }
+        }
+        let every = self.config.speciation_metric_every;
+        if every > 0 && (sample_index + 1).is_multiple_of(every) {
+            metrics.speciation = Some(self.run_speciation_pass());
+        }
+        metrics
+    }
+
+    /// Cluster alive genomes into species, keeping each organism's label for
+    /// snapshots taken this step.
+    fn run_speciation_pass(&mut self) -> SpeciationMetrics {
+        let (alive, genomes): (Vec<usize>, Vec<&[f32]>) = self
+            .organisms
+            .iter()
+            .enumerate()
+            .filter(|(_, org)| org.alive)
+            .map(|(idx, org)| {
+                let genome = match self.config.speciation_genome_scope {
+                    SpeciationGenomeScope::NnWeights => org.genome.nn_weights(),
+                    SpeciationGenomeScope::FullGenome => org.genome.data(),
+                };
+                (idx, genome)
+            })
+            .unzip();
+        let (metrics, labels) = cluster_species(
+            &genomes,
+            self.config.speciation_distance_threshold,
+            self.config.seed,
+            self.step_index,
+        );
+        self.species_labels.clear();
+        self.species_labels.resize(self.organisms.len(), None);
+        for (idx, label) in alive.into_iter().zip(labels) {
+            self.species_labels[idx] = label;
+        }
+        self.species_labels_step = Some(self.step_index);
+        metrics
     }
 
     pub fn run_experiment(&mut self, steps: usize, sample_every: usize) -> RunSummary {
@@ -531,13 +1370,27 @@ impl World {
         &mut self,
         steps: usize,
         sample_every: usize,
+    ) -> Result<RunSummary, ExperimentError> {
+        self.try_run_experiment_with_observer(steps, sample_every, &mut NoopObserver)
+    }
+
+    /// Run an experiment like `try_run_experiment`, reporting progress to `observer`.
+    ///
+    /// If the observer returns `Break` from `on_step`, the run stops after that step:
+    /// a final sample is recorded if one was not already taken, and the summary has
+    /// `cancelled = true` with `steps_executed` set to the steps actually run.
+    pub fn try_run_experiment_with_observer(
+        &mut self,
+        steps: usize,
+        sample_every: usize,
+        observer: &mut dyn ExperimentObserver,
     ) -> Result<RunSummary, ExperimentError> {
         if sample_every == 0 {
             return Err(ExperimentError::InvalidSampleEvery);
         }
-        if steps > Self::MAX_EXPERIMENT_STEPS {
+        if steps > self.config.max_experiment_steps {
             return Err(ExperimentError::TooManySteps {
-                max: Self::MAX_EXPERIMENT_STEPS,
+                max: self.config.max_experiment_steps,
                 actual: steps,
             });
         }
@@ -546,59 +1399,97 @@ impl World {
         } else {
             ((steps - 1) / sample_every) + 1
         };
-        if estimated_samples > Self::MAX_EXPERIMENT_SAMPLES {
+        if estimated_samples > self.config.max_experiment_samples {
             return Err(ExperimentError::TooManySamples {
-                max: Self::MAX_EXPERIMENT_SAMPLES,
+                max: self.config.max_experiment_samples,
                 actual: estimated_samples,
             });
         }
 
         self.lifespans.clear();
+        self.shock_recoveries.clear();
         self.lineage_events.clear();
+        self.fusion_events.clear();
+        self.generation_births.clear();
+        self.early_warning = EarlyWarningWindow::new(self.config.early_warning_window);
+        let criteria_mask = self.config.criteria_mask();
         let births_before = self.total_births;
         let mut samples = Vec::with_capacity(estimated_samples);
+        let mut timings = self.config.collect_timings.then(TimingSummary::default);
+        let mut steps_executed = 0;
+        let mut cancelled = false;
         for step in 1..=steps {
-            self.step();
-            if step % sample_every == 0 || step == steps {
-                samples.push(crate::metrics::collect_step_metrics(
-                    step,
-                    self.step_index,
-                    self.config.world_size,
-                    self.resource_field.total(),
-                    self.births_last_step,
-                    self.deaths_last_step,
-                    self.agent_id_exhaustions_last_step,
-                    &self.organisms,
-                    &self.agents,
-                ));
+            let step_timings = self.step();
+            if let Some(timings) = timings.as_mut() {
+                timings.record(step, &step_timings, self.agents.len(), self.organisms.len());
+            }
+            steps_executed = step;
+            let stop = observer.on_step(step, self).is_break();
+            if step % sample_every == 0 || step == steps || stop {
+                let metrics = self.collect_run_sample(step, samples.len());
+                observer.on_sample(&metrics);
+                samples.push(metrics);
+            }
+            if stop {
+                cancelled = step < steps;
+                break;
             }
         }
+        let lineage_streamed = self.finish_lineage_stream()?;
+        self.finish_trajectory_stream()?;
+        let (events, events_truncated) = self.event_log.take();
+        let lifespans = std::mem::take(&mut self.lifespans);
+        let lineage_events = std::mem::take(&mut self.lineage_events);
         Ok(RunSummary {
-            schema_version: 1,
+            schema_version: RunSummary::SCHEMA_VERSION,
             steps,
             sample_every,
             final_alive_count: self.alive_count(),
             samples,
-            lifespans: std::mem::take(&mut self.lifespans),
+            lifespan_summary: LifespanSummary::from_lifespans(&lifespans),
+            lifespans,
+            shock_recoveries: self.take_shock_recoveries(),
             total_reproduction_events: self.total_births - births_before,
-            lineage_events: std::mem::take(&mut self.lineage_events),
+            births_per_generation: std::mem::take(&mut self.generation_births),
+            lineage_events,
+            lineage_streamed,
+            fusion_events: std::mem::take(&mut self.fusion_events),
             organism_snapshots: Vec::new(),
+            final_state_hash: self.state_hash(),
+            events,
+            events_truncated,
+            steps_executed,
+            cancelled,
+            metabolism_mode: self.config.metabolism_mode,
+            criteria_mask: Some(criteria_mask),
+            metrics_selection: self.config.metrics_selection,
+            timings,
+            migrated_from: None,
+            paired_seed: None,
+            snapshot_schedule: None,
         })
     }
 
-    /// Collect a snapshot of all alive organisms at the current step.
+    /// Collect a snapshot of all alive organisms at the current step and restart
+    /// their flux totals.
     ///
     /// Computes centers and agent counts directly so snapshot correctness does
     /// not depend on whether metabolism is enabled this step.
-    fn collect_organism_snapshots(&self, step: usize) -> SnapshotFrame {
+    fn collect_organism_snapshots(&mut self, step: usize) -> SnapshotFrame {
         let (centers, counts) = self.compute_organism_centers_with_counts();
         let organisms: Vec<OrganismSnapshot> = self
             .organisms
-            .iter()
+            .iter_mut()
             .enumerate()
             .filter(|(_, org)| org.alive)
             .map(|(idx, org)| {
                 let center = centers.get(idx).and_then(|c| *c).unwrap_or([0.0, 0.0]);
+                let flux = std::mem::take(&mut org.flux_since_snapshot);
+                let species_id = if self.species_labels_step == Some(self.step_index) {
+                    self.species_labels.get(idx).copied().flatten()
+                } else {
+                    None
+                };
                 OrganismSnapshot {
                     stable_id: org.stable_id,
                     generation: org.generation,
@@ -610,10 +1501,23 @@ impl World {
                     center_x: center[0],
                     center_y: center[1],
                     n_agents: counts[idx],
+                    dormant: org.dormant,
+                    external_uptake: flux.consumed_external,
+                    internal_conversion: flux.consumed_total,
+                    waste_produced: flux.produced_waste,
+                    species_id,
+                    decoded_params: self
+                        .config
+                        .snapshot_decoded_params
+                        .then(|| DecodedParams::from_organism(org)),
                 }
             })
             .collect();
-        SnapshotFrame { step, organisms }
+        SnapshotFrame {
+            step,
+            organisms,
+            resource_hotspots: self.resource_hotspots().to_vec(),
+        }
     }
 
     /// Run an experiment like `try_run_experiment`, but also collect per-organism
@@ -623,117 +1527,362 @@ impl World {
         steps: usize,
         sample_every: usize,
         snapshot_steps: &[usize],
+    ) -> Result<RunSummary, ExperimentError> {
+        if snapshot_steps.len() > Self::MAX_EXPERIMENT_SNAPSHOTS {
+            return Err(ExperimentError::TooManySnapshots {
+                max: Self::MAX_EXPERIMENT_SNAPSHOTS,
+                actual: snapshot_steps.len(),
+            });
+        }
+        self.try_run_experiment_with_snapshot_schedule(
+            steps,
+            sample_every,
+            &SnapshotSchedule::Steps(snapshot_steps.to_vec()),
+        )
+    }
+
+    /// Run an experiment like `try_run_experiment`, but also collect per-organism
+    /// snapshots on `schedule`, which is recorded in the summary. At most
+    /// [`World::MAX_EXPERIMENT_SNAPSHOTS`] frames may fall within `steps`.
+    pub fn try_run_experiment_with_snapshot_schedule(
+        &mut self,
+        steps: usize,
+        sample_every: usize,
+        schedule: &SnapshotSchedule,
     ) -> Result<RunSummary, ExperimentError> {
         if sample_every == 0 {
             return Err(ExperimentError::InvalidSampleEvery);
         }
-        if steps > Self::MAX_EXPERIMENT_STEPS {
+        if steps > self.config.max_experiment_steps {
             return Err(ExperimentError::TooManySteps {
-                max: Self::MAX_EXPERIMENT_STEPS,
+                max: self.config.max_experiment_steps,
                 actual: steps,
             });
         }
-        if snapshot_steps.len() > Self::MAX_EXPERIMENT_SNAPSHOTS {
-            return Err(ExperimentError::TooManySnapshots {
-                max: Self::MAX_EXPERIMENT_SNAPSHOTS,
-                actual: snapshot_steps.len(),
-            });
-        }
+        let snapshot_steps = Self::resolve_snapshot_schedule(schedule, steps)?;
         let estimated_samples = if steps == 0 {
             0
         } else {
             ((steps - 1) / sample_every) + 1
         };
-        if estimated_samples > Self::MAX_EXPERIMENT_SAMPLES {
+        if estimated_samples > self.config.max_experiment_samples {
             return Err(ExperimentError::TooManySamples {
-                max: Self::MAX_EXPERIMENT_SAMPLES,
+                max: self.config.max_experiment_samples,
                 actual: estimated_samples,
             });
         }
 
         self.lifespans.clear();
+        self.shock_recoveries.clear();
         self.lineage_events.clear();
+        self.fusion_events.clear();
+        self.generation_births.clear();
+        self.early_warning = EarlyWarningWindow::new(self.config.early_warning_window);
+        let criteria_mask = self.config.criteria_mask();
         let births_before = self.total_births;
         let mut samples = Vec::with_capacity(estimated_samples);
         let mut snapshots = Vec::with_capacity(snapshot_steps.len());
-        let snapshot_steps_set: HashSet<usize> = snapshot_steps.iter().copied().collect();
+        let mut next_snapshot = snapshot_steps.iter().copied().peekable();
 
+        let mut timings = self.config.collect_timings.then(TimingSummary::default);
         for step in 1..=steps {
-            self.step();
+            let step_timings = self.step();
+            if let Some(timings) = timings.as_mut() {
+                timings.record(step, &step_timings, self.agents.len(), self.organisms.len());
+            }
             if step % sample_every == 0 || step == steps {
-                samples.push(crate::metrics::collect_step_metrics(
-                    step,
-                    self.step_index,
-                    self.config.world_size,
-                    self.resource_field.total(),
-                    self.births_last_step,
-                    self.deaths_last_step,
-                    self.agent_id_exhaustions_last_step,
-                    &self.organisms,
-                    &self.agents,
-                ));
-            }
-            if snapshot_steps_set.contains(&step) {
+                samples.push(self.collect_run_sample(step, samples.len()));
+            }
+            if next_snapshot.next_if_eq(&step).is_some() {
                 snapshots.push(self.collect_organism_snapshots(step));
             }
         }
+        let lineage_streamed = self.finish_lineage_stream()?;
+        self.finish_trajectory_stream()?;
+        let (events, events_truncated) = self.event_log.take();
+        let lifespans = std::mem::take(&mut self.lifespans);
+        let lineage_events = std::mem::take(&mut self.lineage_events);
         Ok(RunSummary {
-            schema_version: 1,
+            schema_version: RunSummary::SCHEMA_VERSION,
             steps,
             sample_every,
             final_alive_count: self.alive_count(),
             samples,
-            lifespans: std::mem::take(&mut self.lifespans),
+            lifespan_summary: LifespanSummary::from_lifespans(&lifespans),
+            lifespans,
+            shock_recoveries: self.take_shock_recoveries(),
             total_reproduction_events: self.total_births - births_before,
-            lineage_events: std::mem::take(&mut self.lineage_events),
+            births_per_generation: std::mem::take(&mut self.generation_births),
+            lineage_events,
+            lineage_streamed,
+            fusion_events: std::mem::take(&mut self.fusion_events),
             organism_snapshots: snapshots,
+            final_state_hash: self.state_hash(),
+            events,
+            events_truncated,
+            steps_executed: steps,
+            cancelled: false,
+            metabolism_mode: self.config.metabolism_mode,
+            criteria_mask: Some(criteria_mask),
+            metrics_selection: self.config.metrics_selection,
+            timings,
+            migrated_from: None,
+            paired_seed: None,
+            snapshot_schedule: Some(schedule.clone()),
         })
     }
 
-    fn mark_dead(&mut self, org_idx: usize) {
-        if let Some(org) = self.organisms.get_mut(org_idx) {
-            if org.alive {
-                self.lifespans.push(org.age_steps);
-                org.alive = false;
-                org.boundary_integrity = 0.0;
-                self.deaths_last_step += 1;
-                self.total_deaths += 1;
+    /// Ascending snapshot steps of `schedule` within a run of `steps` steps.
+    fn resolve_snapshot_schedule(
+        schedule: &SnapshotSchedule,
+        steps: usize,
+    ) -> Result<Vec<usize>, ExperimentError> {
+        let too_many = |actual| ExperimentError::TooManySnapshots {
+            max: Self::MAX_EXPERIMENT_SNAPSHOTS,
+            actual,
+        };
+        match *schedule {
+            SnapshotSchedule::Every(0) => return Err(ExperimentError::InvalidSnapshotEvery),
+            // Checked before materializing, so a dense schedule fails fast.
+            SnapshotSchedule::Every(n) if steps / n > Self::MAX_EXPERIMENT_SNAPSHOTS => {
+                return Err(too_many(steps / n));
             }
+            SnapshotSchedule::LogSpaced { first, factor, .. }
+                if first == 0 || !factor.is_finite() || factor <= 1.0 =>
+            {
+                return Err(ExperimentError::InvalidSnapshotSchedule);
+            }
+            _ => {}
+        }
+        let snapshot_steps = schedule.steps_through(steps);
+        if snapshot_steps.len() > Self::MAX_EXPERIMENT_SNAPSHOTS {
+            return Err(too_many(snapshot_steps.len()));
         }
+        Ok(snapshot_steps)
     }
 
-    fn maybe_reproduce(&mut self) {
-        let child_agents =
-            (self.config.agents_per_organism / 2).max(self.config.reproduction_child_min_agents);
-        let parent_indices: Vec<usize> = self
-            .organisms
+    /// Run an experiment like `try_run_experiment`, streaming a snapshot frame to
+    /// `writer` every `snapshot_every` steps instead of keeping frames in memory.
+    /// `writer` is a [`SnapshotWriter`](crate::snapshot_file::SnapshotWriter) or, with
+    /// the `arrow-export` feature, a
+    /// [`ParquetSnapshotWriter`](crate::parquet_export::ParquetSnapshotWriter).
+    ///
+    /// A failed write stops the run and is returned as
+    /// [`ExperimentError::SnapshotWrite`]; frames written before it stay in the file.
+    pub fn try_run_experiment_with_snapshot_writer<S: SnapshotSink>(
+        &mut self,
+        steps: usize,
+        sample_every: usize,
+        snapshot_every: usize,
+        writer: &mut S,
+    ) -> Result<RunSummary, ExperimentError> {
+        self.try_run_experiment_with_snapshot_writer_and_observer(
+            steps,
+            sample_every,
+            snapshot_every,
+            writer,
+            &mut NoopObserver,
+        )
+    }
+
+    /// Run an experiment like `try_run_experiment_with_snapshot_writer`, also
+    /// reporting progress to `observer` as in `try_run_experiment_with_observer`.
+    ///
+    /// `observer` sees each step before its frame is written; a `Break` from it still
+    /// writes that step's frame when one is due.
+    pub fn try_run_experiment_with_snapshot_writer_and_observer<S: SnapshotSink>(
+        &mut self,
+        steps: usize,
+        sample_every: usize,
+        snapshot_every: usize,
+        writer: &mut S,
+        observer: &mut dyn ExperimentObserver,
+    ) -> Result<RunSummary, ExperimentError> {
+        if snapshot_every == 0 {
+            return Err(ExperimentError::InvalidSnapshotEvery);
+        }
+        let mut observer = SnapshotStreamObserver {
+            writer,
+            every: snapshot_every,
+            inner: observer,
+            error: None,
+        };
+        let mut summary =
+            self.try_run_experiment_with_observer(steps, sample_every, &mut observer)?;
+        summary.snapshot_schedule = Some(SnapshotSchedule::Every(snapshot_every));
+        let flushed = match observer.error {
+            Some(err) => Err(err),
+            None => observer.writer.flush(),
+        };
+        flushed.map_err(|e| ExperimentError::SnapshotWrite(e.to_string()))?;
+        Ok(summary)
+    }
+
+    /// Append to the event log when `enable_event_log` is set.
+    fn record_event(&mut self, event: WorldEvent) {
+        if !self.config.enable_event_log {
+            return;
+        }
+        self.event_log
+            .set_max_entries(self.config.event_log_max_entries);
+        self.event_log.push(event);
+    }
+
+    /// Energy ceiling of the engine that metabolizes organism `org_idx`.
+    fn organism_max_energy(&self, org_idx: usize) -> f32 {
+        self.organisms[org_idx]
+            .metabolism_engine
+            .as_ref()
+            .unwrap_or(&self.metabolism)
+            .max_energy()
+    }
+
+    /// Organism center from the toroidal sums cached by the agent state phase, falling
+    /// back to a full scan when the organism has no cached agents this step.
+    fn cached_organism_center(&self, org_idx: usize) -> [f64; 2] {
+        if self.org_counts.get(org_idx).copied().unwrap_or(0) == 0 {
+            return self.organism_center(org_idx);
+        }
+        let sums = self.org_toroidal_sums[org_idx];
+        [
+            Self::toroidal_mean_coord(sums[0], sums[1], self.config.world_size),
+            Self::toroidal_mean_coord(sums[2], sums[3], self.config.world_size),
+        ]
+    }
+
+    /// Toroidal mean position of an organism's agents, regardless of its alive flag.
+    fn organism_center(&self, org_idx: usize) -> [f64; 2] {
+        let tau_over_world = (2.0 * PI) / self.config.world_size;
+        let mut sums = [0.0f64; 4];
+        let mut count = 0usize;
+        for agent in self
+            .agents
+            .iter()
+            .filter(|a| a.organism_id as usize == org_idx)
+        {
+            let (sin_x, cos_x) = (agent.position[0] * tau_over_world).sin_cos();
+            let (sin_y, cos_y) = (agent.position[1] * tau_over_world).sin_cos();
+            sums[0] += sin_x;
+            sums[1] += cos_x;
+            sums[2] += sin_y;
+            sums[3] += cos_y;
+            count += 1;
+        }
+        if count == 0 {
+            return [0.0, 0.0];
+        }
+        [
+            Self::toroidal_mean_coord(sums[0], sums[1], self.config.world_size),
+            Self::toroidal_mean_coord(sums[2], sums[3], self.config.world_size),
+        ]
+    }
+
+    fn mark_dead(&mut self, org_idx: usize, cause: DeathCause) {
+        let Some(org) = self.organisms.get(org_idx).filter(|o| o.alive) else {
+            return;
+        };
+        let (stable_id, age, final_energy) =
+            (org.stable_id, org.age_steps, org.metabolic_state.energy);
+        let biomass = self.config.corpse_recycling_fraction
+            * (final_energy.max(0.0)
+                + org.metabolic_state.waste.max(0.0) * self.config.corpse_waste_recycle_scale);
+        let stock = conservation::organism_stock(&org.metabolic_state);
+        if biomass > 0.0 {
+            let center = self.cached_organism_center(org_idx);
+            self.pending_corpse_deposits.push((center, biomass));
+        }
+        self.audit_flow(|flows| flows.death_loss += stock - biomass.max(0.0) as f64);
+        if self.config.enable_event_log {
+            let center = self.organism_center(org_idx);
+            self.record_event(WorldEvent::Death {
+                step: self.step_index,
+                stable_id,
+                age,
+                cause,
+                center,
+                final_energy,
+            });
+        }
+        let org = &mut self.organisms[org_idx];
+        if cause != DeathCause::Fused {
+            self.lifespans.push(org.age_steps);
+        }
+        org.alive = false;
+        org.boundary_integrity = 0.0;
+        // Dead organisms can linger until the next compaction; free their heap data
+        // now. Nothing reads it once `alive` is false.
+        org.genome.release();
+        org.ancestor_genome.release();
+        org.metabolism_engine = None;
+        org.hebbian_trace = None;
+        if cause == DeathCause::Fused {
+            self.fusions_last_step += 1;
+            self.total_fusions += 1;
+        } else {
+            self.deaths_last_step += 1;
+            self.total_deaths += 1;
+        }
+    }
+
+    /// Indices of alive organisms that currently meet every reproduction requirement.
+    fn reproduction_parent_indices(&self) -> Vec<usize> {
+        self.organisms
             .iter()
             .enumerate()
             .filter_map(|(idx, org)| {
                 let mature_enough = org.maturity >= 1.0;
                 (org.alive
+                    && !org.dormant
                     && org.metabolic_state.energy >= self.config.reproduction_min_energy
                     && org.boundary_integrity >= self.config.reproduction_min_boundary
                     && mature_enough)
                     .then_some(idx)
             })
-            .collect();
+            .collect()
+    }
+
+    fn maybe_reproduce(&mut self) {
+        let child_agents =
+            (self.config.agents_per_organism / 2).max(self.config.reproduction_child_min_agents);
+        let mut parent_indices = self.reproduction_parent_indices();
         if parent_indices.is_empty() {
             return;
         }
+        // Organism ids index `organisms`, so dead-but-unpruned entries consume the u16 id
+        // space. Compact early rather than stalling reproduction on a small live population.
+        if self.organisms.len() + parent_indices.len() > Self::ORGANISM_ID_CAPACITY
+            && self.organisms.iter().any(|o| !o.alive)
+        {
+            self.prune_dead_entities();
+            parent_indices = self.reproduction_parent_indices();
+        }
+        // Canonical order: which parents reach the caps first, and the randomness
+        // each consumes, must not depend on where compaction left them.
+        parent_indices.sort_by_key(|&idx| self.organisms[idx].stable_id);
+        match self.config.capacity_policy {
+            CapacityPolicy::FirstComeFirstServed => {}
+            CapacityPolicy::RandomLottery => parent_indices.shuffle(&mut self.rng),
+            CapacityPolicy::EnergyRanked => parent_indices.sort_by(|&a, &b| {
+                let energy = |idx: usize| self.organisms[idx].metabolic_state.energy;
+                energy(b).total_cmp(&energy(a))
+            }),
+        }
         let centers = self.compute_organism_centers();
 
-        for parent_idx in parent_indices {
-            if self
-                .agents
-                .len()
+        // The cap counts live agents only, so it bites at the same point however
+        // many dead agents are still awaiting compaction.
+        let mut live_agents = self.live_agent_count();
+        for (attempt, &parent_idx) in parent_indices.iter().enumerate() {
+            if live_agents
                 .checked_add(child_agents)
-                .map(|n| n > SimConfig::MAX_TOTAL_AGENTS)
+                .map(|n| n > self.config.max_total_agents)
                 .unwrap_or(true)
             {
+                self.reproduction_suppressed_last_step += parent_indices.len() - attempt;
                 break;
             }
-            let remaining_ids = u32::MAX as u64 - self.next_agent_id as u64;
+            let remaining_ids = u64::from(self.agent_id_limit.saturating_sub(self.next_agent_id))
+                + self.free_agent_ids.len() as u64;
             if remaining_ids + 1 < child_agents as u64 {
                 self.agent_id_exhaustions_last_step += 1;
                 self.total_agent_id_exhaustions += 1;
@@ -742,7 +1891,10 @@ impl World {
 
             let child_id = match u16::try_from(self.organisms.len()) {
                 Ok(id) => id,
-                Err(_) => break,
+                Err(_) => {
+                    self.organism_id_exhaustions_last_step += 1;
+                    break;
+                }
             };
 
             let center = centers
@@ -750,7 +1902,9 @@ impl World {
                 .and_then(|c| *c)
                 .unwrap_or([0.0, 0.0]);
 
+            let agents_before = self.agents.len();
             self.spawn_child(parent_idx, child_id, center, child_agents);
+            live_agents += self.agents.len() - agents_before;
         }
     }
 
@@ -775,46 +1929,53 @@ impl World {
             )
         };
 
-        if self.config.enable_evolution {
-            child_genome.mutate(&mut self.rng, &self.mutation_rates);
-        }
-        let child_weights = if child_genome.nn_weights().len() == NeuralNet::WEIGHT_COUNT {
-            child_genome.nn_weights().to_vec()
-        } else {
-            self.organisms[parent_idx].nn.to_weight_vec()
-        };
-        let child_nn = NeuralNet::from_weights(child_weights.into_iter());
-        let mut child_agent_ids = Vec::with_capacity(child_agents);
-
-        for _ in 0..child_agents {
-            let theta = self.rng.random::<f64>() * 2.0 * PI;
-            let radius = self.rng.random::<f64>().sqrt() * self.config.reproduction_spawn_radius;
-            let (sin_theta, cos_theta) = theta.sin_cos();
-            let pos = [
-                (center[0] + radius * cos_theta).rem_euclid(self.config.world_size),
-                (center[1] + radius * sin_theta).rem_euclid(self.config.world_size),
-            ];
-            let Some(id) = self.next_agent_id_checked() else {
-                break;
-            };
-            let mut agent = Agent::new(id, child_id, pos);
-            agent.internal_state[2] = 1.0;
-            child_agent_ids.push(id);
-            self.agents.push(agent);
-        }
+        let (child_genome, developmental_program, child_agent_ids) =
+            self.with_rng(Some(parent_idx), |world, rng| {
+                if world.config.enable_evolution {
+                    // Self-adaptive rates are decoded from the parent's segment 6 before
+                    // mutation.
+                    let rates = if world.config.enable_self_adaptive_mutation {
+                        let modifiers = MutationRateModifiers::decode(child_genome.segment_data(6));
+                        world.mutation_rates.scaled(&modifiers)
+                    } else {
+                        world.mutation_rates
+                    };
+                    child_genome.mutate(rng, &rates);
+                    if world.config.enable_structural_mutation {
+                        child_genome.mutate_structure(rng, &rates);
+                    }
+                }
+                let developmental_program = DevelopmentalProgram::decode_in_layout(
+                    child_genome.active_segment_data(3),
+                    &world.config.genome_layout(),
+                );
+                let child_agent_ids =
+                    world.spawn_agents(rng, child_id, center, child_agents, &developmental_program);
+                (child_genome, developmental_program, child_agent_ids)
+            });
         if child_agent_ids.is_empty() {
             return;
         }
+        let child_nn = Self::nn_from_genome(&child_genome)
+            .unwrap_or_else(|| self.organisms[parent_idx].nn.clone());
 
+        let parent_energy = self.organisms[parent_idx].metabolic_state.energy;
         self.organisms[parent_idx].metabolic_state.energy -= self.config.reproduction_energy_cost;
 
         let metabolic_state = MetabolicState {
             energy: self.config.reproduction_energy_cost,
             ..MetabolicState::default()
         };
+        let paid = parent_energy - self.organisms[parent_idx].metabolic_state.energy;
+        self.audit_flow(|flows| {
+            flows.reproduction_overhead += (paid - metabolic_state.energy) as f64;
+            flows.birth_endowment +=
+                conservation::organism_stock(&metabolic_state) - metabolic_state.energy as f64;
+        });
         let child_metabolism_engine =
             decode_organism_metabolism(&child_genome, self.config.metabolism_mode);
-        let developmental_program = DevelopmentalProgram::decode(child_genome.segment_data(3));
+        let homeostasis_program = HomeostasisProgram::decode(child_genome.segment_data(2));
+        let sensory_program = SensoryProgram::decode(child_genome.segment_data(5));
         let child_stable_id = self.next_organism_stable_id;
         let child_generation = parent_generation + 1;
         let child = OrganismRuntime {
@@ -832,15 +1993,32 @@ impl World {
             maturity: 0.0,
             metabolism_engine: child_metabolism_engine,
             developmental_program,
+            homeostasis_program,
+            sensory_program,
             parent_stable_id: Some(parent_stable_id),
+            hebbian_trace: self.config.enable_hebbian_plasticity.then(Box::default),
+            dormant: false,
+            last_step_flux: MetabolismFlux::default(),
+            flux_since_snapshot: MetabolismFlux::default(),
+            crowding_excess: 0.0,
+            rng_word_pos: 0,
+            nn_params: NnParams::from_config(&self.config),
         };
         self.next_organism_stable_id = self.next_organism_stable_id.saturating_add(1);
-        self.lineage_events.push(LineageEvent {
+        self.record_lineage(LineageEvent {
             step: self.step_index,
             parent_stable_id,
             child_stable_id,
             generation: child_generation,
         });
+        self.record_event(WorldEvent::Birth {
+            step: self.step_index,
+            parent_stable_id,
+            child_stable_id,
+            center,
+        });
+        self.organism_index_by_stable_id
+            .insert(child_stable_id, self.organisms.len());
         self.organisms.push(child);
         self.org_toroidal_sums.push([0.0, 0.0, 0.0, 0.0]);
         self.org_counts.push(0);
@@ -848,6 +2026,74 @@ impl World {
         self.total_births += 1;
     }
 
+    /// Controller decoded from the genome's NN segment, or `None` if its length
+    /// matches neither layout.
+    pub(crate) fn nn_from_genome(genome: &Genome) -> Option<NeuralNet> {
+        NeuralNet::from_weight_vec(genome.nn_weights())
+    }
+
+    /// Run `f` with the RNG for draws made on behalf of the organism at `owner`: its
+    /// own stream under `RngMode::PerOrganism`, whose stored position advances, and
+    /// otherwise, or for world-level events (`owner = None`), the world RNG.
+    fn with_rng<R>(
+        &mut self,
+        owner: Option<usize>,
+        f: impl FnOnce(&mut Self, &mut ChaCha12Rng) -> R,
+    ) -> R {
+        match (self.config.rng_mode, owner) {
+            (RngMode::PerOrganism, Some(idx)) => {
+                let org = &self.organisms[idx];
+                let mut rng = crate::rng::organism_stream_rng(
+                    self.config.seed,
+                    org.stable_id,
+                    org.rng_word_pos,
+                );
+                let out = f(self, &mut rng);
+                self.organisms[idx].rng_word_pos = rng.get_word_pos();
+                out
+            }
+            _ => {
+                // Placeholder while `f` holds the world RNG; never drawn from.
+                let mut rng = std::mem::replace(&mut self.rng, ChaCha12Rng::seed_from_u64(0));
+                let out = f(self, &mut rng);
+                self.rng = rng;
+                out
+            }
+        }
+    }
+
+    /// Append up to `count` agents of `organism_id`, scattered uniformly over the
+    /// disc of `reproduction_spawn_radius` around `center`, with roles assigned by
+    /// `program`, and return their ids. Stops early if agent ids run out.
+    fn spawn_agents(
+        &mut self,
+        rng: &mut ChaCha12Rng,
+        organism_id: u16,
+        center: [f64; 2],
+        count: usize,
+        program: &DevelopmentalProgram,
+    ) -> Vec<u32> {
+        let mut agent_ids = Vec::with_capacity(count);
+        for _ in 0..count {
+            let theta = rng.random::<f64>() * 2.0 * PI;
+            let radius = rng.random::<f64>().sqrt() * self.config.reproduction_spawn_radius;
+            let (sin_theta, cos_theta) = theta.sin_cos();
+            let pos = [
+                (center[0] + radius * cos_theta).rem_euclid(self.config.world_size),
+                (center[1] + radius * sin_theta).rem_euclid(self.config.world_size),
+            ];
+            let Some(id) = self.next_agent_id_checked() else {
+                break;
+            };
+            let mut agent = Agent::new(id, organism_id, pos);
+            agent.internal_state[2] = 1.0;
+            agent.role = program.role_for_index(agent_ids.len(), count);
+            agent_ids.push(id);
+            self.agents.push(agent);
+        }
+        agent_ids
+    }
+
     fn apply_scheduled_ablation_if_due(&mut self) {
         if self.scheduled_ablation_applied {
             return;
@@ -858,17 +2104,13 @@ impl World {
         if self.original_config.is_none() {
             self.original_config = Some(self.config.clone());
         }
-        for target in &self.config.ablation_targets {
-            match target {
-                AblationTarget::Metabolism => self.config.enable_metabolism = false,
-                AblationTarget::Boundary => self.config.enable_boundary_maintenance = false,
-                AblationTarget::Homeostasis => self.config.enable_homeostasis = false,
-                AblationTarget::Response => self.config.enable_response = false,
-                AblationTarget::Reproduction => self.config.enable_reproduction = false,
-                AblationTarget::Evolution => self.config.enable_evolution = false,
-                AblationTarget::Growth => self.config.enable_growth = false,
-            }
-        }
+        let ablated = self
+            .config
+            .ablation_targets
+            .iter()
+            .fold(CriteriaMask::empty(), |mask, &target| mask | target.into());
+        self.config
+            .set_criteria_mask(self.config.criteria_mask() - ablated);
         self.scheduled_ablation_applied = true;
     }
 
@@ -876,9 +2118,17 @@ impl World {
         let total_start = Instant::now();
         self.step_index = self.step_index.saturating_add(1);
         self.apply_scheduled_ablation_if_due();
+        self.apply_scheduled_shock_if_due();
         self.births_last_step = 0;
         self.deaths_last_step = 0;
+        self.fusions_last_step = 0;
         self.agent_id_exhaustions_last_step = 0;
+        self.organism_id_exhaustions_last_step = 0;
+        self.reproduction_suppressed_last_step = 0;
+        self.nonfinite_outputs_last_step = 0;
+        self.metabolic_violations_last_step = 0;
+        self.waste_uptake_last_step = 0.0;
+        self.begin_conservation_audit();
         let boundary_terminal_threshold = self.terminal_boundary_threshold();
 
         let t0 = Instant::now();
@@ -894,7 +2144,11 @@ impl World {
         self.step_agent_state_phase();
         self.step_boundary_phase(boundary_terminal_threshold);
         self.step_metabolism_phase(boundary_terminal_threshold);
+        self.step_energy_reserve_phase(&tree);
+        self.step_predation_phase(boundary_terminal_threshold);
         self.step_growth_and_crowding_phase(boundary_terminal_threshold);
+        self.step_corpse_recycling_phase();
+        self.step_fusion_phase();
 
         if self.config.enable_reproduction {
             self.maybe_reproduce();
@@ -909,8 +2163,13 @@ impl World {
         {
             self.prune_dead_entities();
         }
+        self.expose_boundary();
 
         self.step_environment_phase(&tree);
+        self.track_shock_recovery();
+        self.flush_lineage_if_due();
+        self.record_trajectory_if_due();
+        self.finish_conservation_audit();
 
         let state_update_us = t2.elapsed().as_micros() as u64;
 
@@ -923,6 +2182,25 @@ impl World {
     }
 }
 
+mod activation_log;
+#[cfg(feature = "bench-internals")]
+mod bench_hooks;
+mod bootstrap;
+mod conservation;
+mod homeostasis_shock;
+#[cfg(feature = "image-export")]
+mod image_export;
+pub mod init;
+mod intervention;
+mod lineage_stream;
 mod phases;
+mod state_hash;
 #[cfg(test)]
 mod tests;
+mod trajectory_stream;
+
+pub use crate::config::PlacementStrategy;
+pub use activation_log::ActivationRecord;
+pub use conservation::ConservationLedger;
+#[cfg(feature = "image-export")]
+pub use image_export::PngFrameOptions;
@@ -1,10 +1,24 @@
 use super::super::World;
-use crate::config::HomeostasisMode;
-use std::f64::consts::PI;
+use crate::agent::Agent;
+use crate::config::{BoundaryExposure, HomeostasisMode, Locomotion, SimConfig};
+use std::f64::consts::{PI, TAU};
 
 impl World {
+    /// Heading locomotion: turn by `turn` and accelerate along the new heading by
+    /// `thrust`, then apply drag. Sideways velocity is discarded, so the agent
+    /// always moves along its heading (backwards if the speed is negative).
+    fn steer(agent: &mut Agent, thrust: f32, turn: f32, config: &SimConfig) {
+        agent.heading = (agent.heading + turn as f64 * config.heading_max_turn_rate * config.dt)
+            .rem_euclid(TAU);
+        let (sin, cos) = agent.heading.sin_cos();
+        let speed = agent.velocity[0] * cos + agent.velocity[1] * sin + thrust as f64 * config.dt;
+        let speed = speed * (1.0 - config.heading_drag * config.dt).clamp(0.0, 1.0);
+        agent.velocity = [speed * cos, speed * sin];
+    }
+
     /// Apply movement + homeostasis updates for each alive agent and gather
-    /// aggregates consumed by boundary + metabolism phases.
+    /// aggregates consumed by boundary + metabolism phases. Movement energy is
+    /// debited here so the metabolism phase's death check sees it.
     pub(in crate::world) fn step_agent_state_phase(&mut self) {
         let org_count = self.organisms.len();
         if self.homeostasis_sums_buffer.len() != org_count {
@@ -17,7 +31,15 @@ impl World {
         self.org_toroidal_sums.fill([0.0, 0.0, 0.0, 0.0]);
         self.org_counts.fill(0);
 
+        let track_movement = self.config.movement_energy_cost > 0.0;
+        if track_movement {
+            self.movement_distance_buffer.clear();
+            self.movement_distance_buffer.resize(org_count, 0.0);
+        }
+
         let config = &self.config;
+        let lagged_boundary = config.boundary_exposure == BoundaryExposure::Lagged;
+        let maturity_gating = config.enable_growth && config.enable_maturity_gating;
         let world_size = config.world_size;
         let tau_over_world = (2.0 * PI) / world_size;
 
@@ -28,6 +50,7 @@ impl World {
         let homeostasis_counts = &mut self.homeostasis_counts_buffer;
         let org_toroidal_sums = &mut self.org_toroidal_sums;
         let org_counts = &mut self.org_counts;
+        let movement_distance = &mut self.movement_distance_buffer;
 
         for (agent, delta) in agents.iter_mut().zip(deltas.iter()) {
             let org_idx = agent.organism_id as usize;
@@ -35,21 +58,51 @@ impl World {
                 agent.velocity = [0.0, 0.0];
                 continue;
             }
-            // Expose boundary with a one-step lag to avoid an extra full pass.
-            agent.internal_state[2] = organisms[org_idx].boundary_integrity;
+            // Expose boundary with a one-step lag to avoid an extra full pass;
+            // `BoundaryExposure::Fresh` pays for that pass in `expose_boundary`.
+            if lagged_boundary {
+                agent.internal_state[2] = organisms[org_idx].boundary_integrity;
+            }
 
-            if config.enable_response {
-                agent.velocity[0] += delta[0] as f64 * config.dt;
-                agent.velocity[1] += delta[1] as f64 * config.dt;
+            if organisms[org_idx].dormant {
+                agent.velocity = [0.0, 0.0];
+            } else if config.enable_response {
+                let gain = organisms[org_idx]
+                    .developmental_program
+                    .role(agent.role)
+                    .movement_gain;
+                match config.locomotion {
+                    Locomotion::Holonomic => {
+                        agent.velocity[0] += (delta[0] * gain) as f64 * config.dt;
+                        agent.velocity[1] += (delta[1] * gain) as f64 * config.dt;
+                    }
+                    Locomotion::Heading => Self::steer(agent, delta[0] * gain, delta[1], config),
+                }
             }
 
+            let max_speed = if maturity_gating {
+                let org = &organisms[org_idx];
+                config.max_speed
+                    * org
+                        .developmental_program
+                        .stage_factors(org.maturity)
+                        .movement as f64
+            } else {
+                config.max_speed
+            };
             let speed_sq =
                 agent.velocity[0] * agent.velocity[0] + agent.velocity[1] * agent.velocity[1];
-            if speed_sq > config.max_speed * config.max_speed {
-                let scale = config.max_speed / speed_sq.sqrt();
+            if speed_sq > max_speed * max_speed {
+                let scale = max_speed / speed_sq.sqrt();
                 agent.velocity[0] *= scale;
                 agent.velocity[1] *= scale;
             }
+            if track_movement {
+                movement_distance[org_idx] += (agent.velocity[0] * agent.velocity[0]
+                    + agent.velocity[1] * agent.velocity[1])
+                    .sqrt()
+                    * config.dt;
+            }
 
             agent.position[0] =
                 (agent.position[0] + agent.velocity[0] * config.dt).rem_euclid(config.world_size);
@@ -60,7 +113,21 @@ impl World {
             agent.internal_state[0] = (agent.internal_state[0] - h_decay).max(0.0);
             agent.internal_state[1] = (agent.internal_state[1] - h_decay).max(0.0);
 
-            if config.enable_homeostasis {
+            if config.enable_genetic_homeostasis {
+                // Ablating homeostasis removes only the genetic corrective term.
+                let program = &organisms[org_idx].homeostasis_program;
+                for channel in 0..2 {
+                    let state = agent.internal_state[channel];
+                    let correction = if config.enable_homeostasis {
+                        program.correction(channel, state)
+                    } else {
+                        0.0
+                    };
+                    agent.internal_state[channel] = (state
+                        + (delta[2 + channel] + correction) * config.dt as f32)
+                        .clamp(0.0, 1.0);
+                }
+            } else if config.enable_homeostasis {
                 match config.homeostasis_mode {
                     HomeostasisMode::NnRegulator => {
                         agent.internal_state[0] =
@@ -97,5 +164,39 @@ impl World {
             org_toroidal_sums[org_idx][3] += cos_y;
             org_counts[org_idx] += 1;
         }
+
+        if track_movement {
+            let cost = self.config.movement_energy_cost;
+            let mut spent = 0.0f64;
+            for (org, &distance) in self
+                .organisms
+                .iter_mut()
+                .zip(&self.movement_distance_buffer)
+            {
+                if !org.alive || distance == 0.0 {
+                    continue;
+                }
+                let stage_factor = Self::metabolic_stage_factor(&self.config, org);
+                let before = org.metabolic_state.energy;
+                org.metabolic_state.energy =
+                    (org.metabolic_state.energy - cost * stage_factor * distance as f32).max(0.0);
+                spent += (before - org.metabolic_state.energy) as f64;
+            }
+            self.audit_flow(|flows| flows.movement_cost += spent);
+        }
+    }
+
+    /// Copy each alive organism's current boundary integrity into its agents'
+    /// `internal_state[2]` under `BoundaryExposure::Fresh`.
+    pub(in crate::world) fn expose_boundary(&mut self) {
+        if self.config.boundary_exposure != BoundaryExposure::Fresh {
+            return;
+        }
+        for agent in &mut self.agents {
+            let org = &self.organisms[agent.organism_id as usize];
+            if org.alive {
+                agent.internal_state[2] = org.boundary_integrity;
+            }
+        }
     }
 }
@@ -1,87 +1,141 @@
 use super::super::World;
 use crate::config::BoundaryMode;
+use crate::metrics::DeathCause;
+use crate::organism::OrganismRuntime;
+use rayon::prelude::*;
+
+/// Mean resultant length of an organism's agent positions on both toroidal axes, in
+/// `[0, 1]`: 1 when the agents coincide, near 0 when they are spread evenly.
+fn toroidal_cohesion(sums: [f64; 4], count: usize) -> f32 {
+    if count == 0 {
+        return 0.0;
+    }
+    let count = count as f64;
+    let x_mag = (sums[0].powi(2) + sums[1].powi(2)).sqrt() / count;
+    let y_mag = (sums[2].powi(2) + sums[3].powi(2)).sqrt() / count;
+    (0.5 * (x_mag + y_mag)).clamp(0.0, 1.0) as f32
+}
 
 impl World {
+    /// Field waste at each alive organism's center while `waste_field_toxicity` is
+    /// on; empty otherwise.
+    fn local_toxin_levels(&self) -> Vec<f32> {
+        match &self.waste_field {
+            Some(field) if self.config.waste_field_toxicity > 0.0 => self
+                .organisms
+                .iter()
+                .enumerate()
+                .map(|(org_idx, org)| {
+                    if !org.alive {
+                        return 0.0;
+                    }
+                    let [x, y] = self.cached_organism_center(org_idx);
+                    field.get(x, y)
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
     /// Update boundary integrity using homeostasis aggregates from the state phase.
     pub(in crate::world) fn step_boundary_phase(&mut self, boundary_terminal_threshold: f32) {
         if !self.config.enable_boundary_maintenance {
             return;
         }
 
-        let mut to_kill = Vec::new();
-        {
-            let config = &self.config;
-            let dt = config.dt as f32;
-            let homeostasis_sums = &self.homeostasis_sums_buffer;
-            let homeostasis_counts = &self.homeostasis_counts_buffer;
+        let local_toxin = self.local_toxin_levels();
+        let config = &self.config;
+        let dt = config.dt as f32;
+        let homeostasis_sums = &self.homeostasis_sums_buffer;
+        let homeostasis_counts = &self.homeostasis_counts_buffer;
+        let toroidal_sums = &self.org_toroidal_sums;
+        let org_counts = &self.org_counts;
 
-            for (org_idx, org) in self.organisms.iter_mut().enumerate() {
-                if !org.alive {
-                    org.boundary_integrity = 0.0;
-                    continue;
-                }
+        // Updates one organism and reports whether its boundary collapsed.
+        let update = |org_idx: usize, org: &mut OrganismRuntime| -> bool {
+            if !org.alive {
+                org.boundary_integrity = 0.0;
+                return false;
+            }
 
-                let energy_deficit =
-                    (config.metabolic_viability_floor - org.metabolic_state.energy).max(0.0);
-                let decay = config.boundary_decay_base_rate
-                    + config.boundary_decay_energy_scale
-                        * (energy_deficit
-                            + org.metabolic_state.waste * config.boundary_waste_pressure_scale);
-                let homeostasis_factor = if homeostasis_counts[org_idx] > 0 {
-                    homeostasis_sums[org_idx] / homeostasis_counts[org_idx] as f32
-                } else {
-                    0.5
-                };
-                let dev_boundary = if config.enable_growth {
-                    org.developmental_program.stage_factors(org.maturity).0
-                } else {
-                    1.0
-                };
-                let (decay_mode_scale, repair_mode_scale) = match config.boundary_mode {
-                    BoundaryMode::ScalarRepair => (1.0, 1.0),
-                    BoundaryMode::SpatialHullFeedback => {
-                        let cohesion = if self.org_counts[org_idx] > 0 {
-                            let count = self.org_counts[org_idx] as f64;
-                            let x_mag = (self.org_toroidal_sums[org_idx][0].powi(2)
-                                + self.org_toroidal_sums[org_idx][1].powi(2))
-                            .sqrt()
-                                / count;
-                            let y_mag = (self.org_toroidal_sums[org_idx][2].powi(2)
-                                + self.org_toroidal_sums[org_idx][3].powi(2))
-                            .sqrt()
-                                / count;
-                            (0.5 * (x_mag + y_mag)).clamp(0.0, 1.0) as f32
-                        } else {
-                            0.0
-                        };
-                        let repair_scale = config.spatial_hull_repair_base
-                            + config.spatial_hull_repair_cohesion_scale * cohesion;
-                        let decay_scale = (config.spatial_hull_decay_base
-                            - config.spatial_hull_decay_cohesion_scale * cohesion)
-                            .max(config.spatial_hull_decay_min);
-                        (decay_scale, repair_scale)
-                    }
-                };
-                let repair = (org.metabolic_state.energy
-                    - org.metabolic_state.waste
-                        * config.boundary_waste_pressure_scale
-                        * config.boundary_repair_waste_penalty_scale)
-                    .max(0.0)
-                    * config.boundary_repair_rate
-                    * homeostasis_factor
-                    * dev_boundary
-                    * repair_mode_scale;
-                org.boundary_integrity = (org.boundary_integrity - decay * decay_mode_scale * dt
-                    + repair * dt)
-                    .clamp(0.0, 1.0);
-                if org.boundary_integrity <= boundary_terminal_threshold {
-                    to_kill.push(org_idx);
-                }
+            let energy_deficit =
+                (config.metabolic_viability_floor - org.metabolic_state.energy).max(0.0);
+            let mut decay = config.boundary_decay_base_rate
+                + config.boundary_decay_energy_scale
+                    * (energy_deficit
+                        + org.metabolic_state.waste * config.boundary_waste_pressure_scale);
+            if config.cohesion_boundary_coupling > 0.0 {
+                let dispersion =
+                    1.0 - toroidal_cohesion(toroidal_sums[org_idx], org_counts[org_idx]);
+                decay += config.cohesion_boundary_coupling
+                    * (dispersion - config.boundary_cohesion_threshold).max(0.0);
             }
-        }
+            if let Some(&toxin) = local_toxin.get(org_idx) {
+                decay +=
+                    config.waste_field_toxicity * toxin * org.homeostasis_program.toxin_exposure();
+            }
+            let dormancy_scale = if org.dormant {
+                config.dormancy_metabolic_scale
+            } else {
+                1.0
+            };
+            let homeostasis_factor = if homeostasis_counts[org_idx] > 0 {
+                homeostasis_sums[org_idx] / homeostasis_counts[org_idx] as f32
+            } else {
+                0.5
+            };
+            let dev_boundary = if config.enable_growth {
+                org.developmental_program
+                    .stage_factors(org.maturity)
+                    .boundary_repair
+            } else {
+                1.0
+            };
+            let (decay_mode_scale, repair_mode_scale) = match config.boundary_mode {
+                BoundaryMode::ScalarRepair => (1.0, 1.0),
+                BoundaryMode::SpatialHullFeedback => {
+                    let cohesion = toroidal_cohesion(toroidal_sums[org_idx], org_counts[org_idx]);
+                    let repair_scale = config.spatial_hull_repair_base
+                        + config.spatial_hull_repair_cohesion_scale * cohesion;
+                    let decay_scale = (config.spatial_hull_decay_base
+                        - config.spatial_hull_decay_cohesion_scale * cohesion)
+                        .max(config.spatial_hull_decay_min);
+                    (decay_scale, repair_scale)
+                }
+            };
+            let repair = (org.metabolic_state.energy
+                - org.metabolic_state.waste
+                    * config.boundary_waste_pressure_scale
+                    * config.boundary_repair_waste_penalty_scale)
+                .max(0.0)
+                * config.boundary_repair_rate
+                * homeostasis_factor
+                * dev_boundary
+                * repair_mode_scale
+                * Self::senescence_factor(config, org);
+            org.boundary_integrity = (org.boundary_integrity
+                - decay * decay_mode_scale * dormancy_scale * dt
+                + repair * dt)
+                .clamp(0.0, 1.0);
+            org.boundary_integrity <= boundary_terminal_threshold
+        };
+        // Collected in index order either way, so deaths are marked in the same order.
+        let to_kill: Vec<usize> = if config.parallel_organism_phases {
+            self.organisms
+                .par_iter_mut()
+                .enumerate()
+                .filter_map(|(org_idx, org)| update(org_idx, org).then_some(org_idx))
+                .collect()
+        } else {
+            self.organisms
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(org_idx, org)| update(org_idx, org).then_some(org_idx))
+                .collect()
+        };
 
         for org_idx in to_kill {
-            self.mark_dead(org_idx);
+            self.mark_dead(org_idx, DeathCause::BoundaryCollapse);
         }
     }
 }
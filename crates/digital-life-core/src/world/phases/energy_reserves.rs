@@ -0,0 +1,164 @@
+use crate::spatial;
+use crate::spatial::AgentLocation;
+use rstar::RTree;
+
+use super::super::World;
+
+/// Index of `internal_state` holding an agent's energy reserve.
+const RESERVE: usize = 3;
+
+impl World {
+    /// Record the resource under each agent before uptake, so the metabolism phase's
+    /// gains can be credited to the agents standing on rich cells.
+    pub(in crate::world) fn collect_reserve_weights(&mut self) {
+        let channels = self.resource_field.channels();
+        let weights = &mut self.reserve_weights_buffer;
+        weights.clear();
+        for agent in &self.agents {
+            let alive = self
+                .organisms
+                .get(agent.organism_id as usize)
+                .is_some_and(|o| o.alive);
+            let weight = if alive {
+                let cell = self
+                    .resource_field
+                    .cell_index(agent.position[0], agent.position[1]);
+                (0..channels)
+                    .map(|c| self.resource_field.get_cell_channel(c, cell))
+                    .sum()
+            } else {
+                0.0
+            };
+            weights.push(weight);
+        }
+        self.reserve_deltas_buffer.clear();
+        self.reserve_deltas_buffer.resize(self.organisms.len(), 0.0);
+    }
+
+    /// Maintain per-agent energy reserves under `enable_agent_energy_reserves`.
+    ///
+    /// Organism energy stays authoritative for every other phase, so reserves are
+    /// first rescaled to the organism's energy before this step's metabolism. The
+    /// metabolic change is then split across its agents: gains by the resource under
+    /// each agent, losses in proportion to reserves. After sharing, the organism's
+    /// energy is set to the sum of its agents' reserves.
+    pub(in crate::world) fn step_energy_reserve_phase(&mut self, tree: &RTree<AgentLocation>) {
+        if !self.config.enable_agent_energy_reserves {
+            return;
+        }
+        let org_count = self.organisms.len();
+        let deltas = std::mem::take(&mut self.reserve_deltas_buffer);
+        let weights = std::mem::take(&mut self.reserve_weights_buffer);
+        let credited = deltas.len() == org_count && weights.len() == self.agents.len();
+
+        // Per organism: (reserve sum, weight sum, alive agent count).
+        let mut totals = vec![(0.0f32, 0.0f32, 0usize); org_count];
+        for (agent_idx, agent) in self.agents.iter().enumerate() {
+            let org_idx = agent.organism_id as usize;
+            if !self.organisms[org_idx].alive {
+                continue;
+            }
+            let total = &mut totals[org_idx];
+            total.0 += agent.internal_state[RESERVE].max(0.0);
+            if credited {
+                total.1 += weights[agent_idx];
+            }
+            total.2 += 1;
+        }
+
+        for (agent_idx, agent) in self.agents.iter_mut().enumerate() {
+            let org_idx = agent.organism_id as usize;
+            if !self.organisms[org_idx].alive {
+                continue;
+            }
+            let (reserve_sum, weight_sum, count) = totals[org_idx];
+            let share = if reserve_sum > 0.0 {
+                agent.internal_state[RESERVE].max(0.0) / reserve_sum
+            } else {
+                1.0 / count as f32
+            };
+            let delta = if credited { deltas[org_idx] } else { 0.0 };
+            let energy = self.organisms[org_idx].metabolic_state.energy;
+            agent.internal_state[RESERVE] = if delta > 0.0 && weight_sum > 0.0 {
+                (energy - delta) * share + delta * weights[agent_idx] / weight_sum
+            } else {
+                energy * share
+            };
+        }
+        self.reserve_deltas_buffer = deltas;
+        self.reserve_deltas_buffer.clear();
+        self.reserve_weights_buffer = weights;
+
+        let rate = (self.config.energy_sharing_rate * self.config.dt as f32).min(1.0);
+        if rate > 0.0 && self.config.energy_sharing_radius > 0.0 {
+            self.share_agent_energy(tree, rate);
+        }
+
+        for org in &mut self.organisms {
+            if org.alive {
+                org.metabolic_state.energy = 0.0;
+            }
+        }
+        for agent in &self.agents {
+            let org = &mut self.organisms[agent.organism_id as usize];
+            if org.alive {
+                org.metabolic_state.energy += agent.internal_state[RESERVE];
+            }
+        }
+    }
+
+    /// Diffuse reserves between same-organism agents within `energy_sharing_radius`.
+    ///
+    /// Each pair exchanges `rate` of its difference, divided by one plus the larger of
+    /// the two agents' pair counts. The exchange is symmetric, so an organism's total
+    /// is conserved, and no agent moves past the mean of its neighborhood.
+    fn share_agent_energy(&mut self, tree: &RTree<AgentLocation>, rate: f32) {
+        let mut ids: Vec<(u32, usize)> = self
+            .agents
+            .iter()
+            .enumerate()
+            .map(|(agent_idx, agent)| (agent.id, agent_idx))
+            .collect();
+        ids.sort_unstable();
+
+        let pairs = &mut self.energy_sharing_pairs_buffer;
+        pairs.clear();
+        for (agent_idx, agent) in self.agents.iter().enumerate() {
+            if !self.organisms[agent.organism_id as usize].alive {
+                continue;
+            }
+            spatial::for_each_neighbor(
+                tree,
+                agent.position,
+                self.config.energy_sharing_radius,
+                agent.id,
+                self.config.world_size,
+                |loc| {
+                    if loc.organism_id != agent.organism_id || loc.id < agent.id {
+                        return;
+                    }
+                    if let Ok(found) = ids.binary_search_by_key(&loc.id, |&(id, _)| id) {
+                        pairs.push((agent_idx, ids[found].1));
+                    }
+                },
+            );
+        }
+
+        let mut degrees = vec![0u32; self.agents.len()];
+        for &(a, b) in pairs.iter() {
+            degrees[a] += 1;
+            degrees[b] += 1;
+        }
+        let mut flows = vec![0.0f32; self.agents.len()];
+        for &(a, b) in pairs.iter() {
+            let weight = rate / (1 + degrees[a].max(degrees[b])) as f32;
+            let flow = weight
+                * (self.agents[b].internal_state[RESERVE] - self.agents[a].internal_state[RESERVE]);
+            flows[a] += flow;
+            flows[b] -= flow;
+        }
+        for (agent, flow) in self.agents.iter_mut().zip(flows) {
+            agent.internal_state[RESERVE] += flow;
+        }
+    }
+}
@@ -1,10 +1,24 @@
 use super::super::World;
+use crate::config::{ResourceDynamics, ResourceNoiseKind};
 use crate::spatial;
 use crate::spatial::AgentLocation;
 use rstar::RTree;
 
 impl World {
-    /// Apply optional sham work and environment updates.
+    /// Deposit biomass queued by this step's deaths at each organism's last center.
+    pub(in crate::world) fn step_corpse_recycling_phase(&mut self) {
+        let cap = self.config.corpse_deposit_cell_cap;
+        let mut overflow = 0.0f64;
+        for ([x, y], amount) in self.pending_corpse_deposits.drain(..) {
+            let deposited = self.resource_field.deposit(x, y, amount, cap);
+            overflow += (amount - deposited) as f64;
+        }
+        self.audit_flow(|flows| flows.death_loss += overflow);
+    }
+
+    /// Apply optional sham work and environment updates. The regeneration rate is
+    /// only reset when the step reaches an environment schedule breakpoint, so a rate
+    /// set through [`World::set_resource_rate`] holds until the next one.
     pub(in crate::world) fn step_environment_phase(&mut self, tree: &RTree<AgentLocation>) {
         if self.config.enable_sham_process {
             let mut _sham_sum: f64 = 0.0;
@@ -25,24 +39,65 @@ impl World {
             }
         }
 
-        if self.config.environment_shift_step > 0
-            && self.step_index == self.config.environment_shift_step
-        {
-            self.current_resource_rate = self.config.environment_shift_resource_rate;
+        if let Some(rate) = self.environment_schedule.rate_change_at(self.step_index) {
+            self.current_resource_rate = rate;
         }
 
-        if self.config.environment_cycle_period > 0 {
-            let phase = (self.step_index / self.config.environment_cycle_period) % 2;
-            self.current_resource_rate = if phase == 0 {
-                self.config.resource_regeneration_rate
-            } else {
-                self.config.environment_cycle_low_rate
-            };
+        if let Some(field) = &mut self.waste_field {
+            let retained = 1.0 - self.config.waste_field_decay_rate * self.config.dt as f32;
+            let before = field.total();
+            field.scale(retained.clamp(0.0, 1.0));
+            let decayed = before - field.total();
+            self.audit_flow(|flows| flows.waste_decay += decayed);
+        }
+
+        let resource_before = self.resource_field.total();
+
+        let rate = self.current_resource_rate * self.config.dt as f32;
+        match (self.config.resource_dynamics, &mut self.resource_hotspots) {
+            (ResourceDynamics::MovingHotspots { radius, peak, .. }, Some(hotspots)) => {
+                hotspots.update(self.step_index as f64 * self.config.dt);
+                if rate > 0.0 {
+                    self.resource_field
+                        .regenerate_hotspots(hotspots.centers(), radius, rate, peak);
+                }
+            }
+            _ => {
+                if rate > 0.0 {
+                    self.resource_field.regenerate_with(
+                        self.config.regeneration_model,
+                        rate,
+                        self.config.resource_cell_capacity,
+                    );
+                }
+            }
         }
 
-        if self.current_resource_rate > 0.0 {
-            self.resource_field
-                .regenerate(self.current_resource_rate * self.config.dt as f32);
+        let noise = self.config.resource_noise_amplitude * self.config.dt as f32;
+        if noise > 0.0 {
+            match self.config.resource_noise_kind {
+                ResourceNoiseKind::White => {
+                    self.resource_field.perturb_white(&mut self.rng, noise);
+                }
+                ResourceNoiseKind::SpatiallyCorrelated { length_scale } => {
+                    if !self.resource_noise.is_drawn_for(&self.resource_field)
+                        || self
+                            .step_index
+                            .is_multiple_of(self.config.resource_noise_refresh_steps)
+                    {
+                        self.resource_noise.redraw(
+                            &mut self.rng,
+                            &self.resource_field,
+                            length_scale,
+                        );
+                    }
+                    self.resource_field
+                        .perturb_pattern(self.resource_noise.pattern(), noise);
+                }
+            }
         }
+
+        let regenerated = self.resource_field.total() - resource_before;
+        self.audit_flow(|flows| flows.regeneration_input += regenerated);
     }
 }
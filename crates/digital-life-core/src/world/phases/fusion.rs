@@ -0,0 +1,117 @@
+use super::super::World;
+use crate::metabolism::MetabolicState;
+use crate::metrics::{l2_distance, DeathCause, FusionEvent};
+use crate::spatial;
+
+impl World {
+    /// Pairs of alive organisms close and similar enough to fuse, as (absorber,
+    /// absorbed). Each organism takes part in at most one fusion per step; pairs are
+    /// claimed in index order so the result is deterministic.
+    fn collect_fusion_pairs(&self) -> Vec<(usize, usize)> {
+        let radius_sq = self.config.fusion_radius * self.config.fusion_radius;
+        let threshold = self.config.fusion_compatibility_threshold;
+        let candidates: Vec<(usize, [f64; 2])> = self
+            .organisms
+            .iter()
+            .enumerate()
+            .filter(|(_, org)| org.alive && !org.agent_ids.is_empty())
+            .map(|(idx, _)| (idx, self.cached_organism_center(idx)))
+            .collect();
+        let mut claimed = vec![false; self.organisms.len()];
+        let mut pairs = Vec::new();
+        for (i, &(a, center_a)) in candidates.iter().enumerate() {
+            if claimed[a] {
+                continue;
+            }
+            for &(b, center_b) in &candidates[i + 1..] {
+                if claimed[b]
+                    || spatial::toroidal_distance_sq(center_a, center_b, self.config.world_size)
+                        > radius_sq
+                {
+                    continue;
+                }
+                let distance = l2_distance(
+                    self.organisms[a].genome.nn_weights(),
+                    self.organisms[b].genome.nn_weights(),
+                );
+                if distance > threshold {
+                    continue;
+                }
+                claimed[a] = true;
+                claimed[b] = true;
+                // The larger organism absorbs; ties go to the lower index.
+                if self.organisms[b].agent_ids.len() > self.organisms[a].agent_ids.len() {
+                    pairs.push((b, a));
+                } else {
+                    pairs.push((a, b));
+                }
+                break;
+            }
+        }
+        pairs
+    }
+
+    /// Merge overlapping compatible organisms: the absorber takes over the other's
+    /// agents and sums its energy, internal resource and waste, and boundary
+    /// integrity becomes the agent-weighted mean of the two.
+    pub(in crate::world) fn step_fusion_phase(&mut self) {
+        if !self.config.enable_fusion {
+            return;
+        }
+        for (absorber, absorbed) in self.collect_fusion_pairs() {
+            let absorber_id = self.organisms[absorber].id;
+            let moved = std::mem::take(&mut self.organisms[absorbed].agent_ids);
+            for agent in &mut self.agents {
+                if agent.organism_id as usize == absorbed {
+                    agent.organism_id = absorber_id;
+                }
+            }
+
+            let absorbed_org = &mut self.organisms[absorbed];
+            let absorbed_state = std::mem::replace(
+                &mut absorbed_org.metabolic_state,
+                MetabolicState {
+                    energy: 0.0,
+                    resource: 0.0,
+                    waste: 0.0,
+                    graph_pool: Vec::new(),
+                    graph_scratch: Vec::new(),
+                },
+            );
+            let absorbed_boundary = absorbed_org.boundary_integrity;
+            let absorbed_stable_id = absorbed_org.stable_id;
+            let absorber_org = &mut self.organisms[absorber];
+            let (kept, gained) = (absorber_org.agent_ids.len() as f32, moved.len() as f32);
+            absorber_org.boundary_integrity = (absorber_org.boundary_integrity * kept
+                + absorbed_boundary * gained)
+                / (kept + gained);
+            absorber_org.agent_ids.extend(moved);
+            let state = &mut absorber_org.metabolic_state;
+            state.energy += absorbed_state.energy;
+            state.resource += absorbed_state.resource;
+            state.waste += absorbed_state.waste;
+            if state.graph_pool.len() == absorbed_state.graph_pool.len() {
+                for (pool, extra) in state.graph_pool.iter_mut().zip(&absorbed_state.graph_pool) {
+                    *pool += extra;
+                }
+            } else {
+                // Incompatible graph layouts: the intermediates die with the organism.
+                self.organisms[absorbed].metabolic_state.graph_pool = absorbed_state.graph_pool;
+            }
+            let absorber_stable_id = self.organisms[absorber].stable_id;
+
+            let sums = std::mem::take(&mut self.org_toroidal_sums[absorbed]);
+            for (total, part) in self.org_toroidal_sums[absorber].iter_mut().zip(sums) {
+                *total += part;
+            }
+            self.org_counts[absorber] += std::mem::take(&mut self.org_counts[absorbed]);
+
+            self.mark_dead(absorbed, DeathCause::Fused);
+            self.fusion_events.push(FusionEvent {
+                step: self.step_index,
+                absorber_stable_id,
+                absorbed_stable_id,
+            });
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use super::super::World;
+use crate::metrics::DeathCause;
 
 impl World {
-    /// Update age, growth stage, and crowding effects, then mark deaths.
+    /// Update age, growth stage, crowding effects and sensing costs, then mark deaths.
     pub(in crate::world) fn step_growth_and_crowding_phase(
         &mut self,
         boundary_terminal_threshold: f32,
     ) {
         let mut to_kill = Vec::new();
+        let mut maintenance = 0.0f64;
         {
             let config = &self.config;
             let neighbor_sums = &self.neighbor_sums_buffer;
@@ -18,11 +20,11 @@ impl World {
                 }
                 org.age_steps = org.age_steps.saturating_add(1);
                 if org.age_steps > config.max_organism_age_steps {
-                    to_kill.push(org_idx);
+                    to_kill.push((org_idx, DeathCause::Age));
                     continue;
                 }
 
-                if config.enable_growth && org.maturity < 1.0 {
+                if config.enable_growth && org.maturity < 1.0 && !org.dormant {
                     let base_rate = 1.0 / config.growth_maturation_steps as f32;
                     let rate = base_rate * org.developmental_program.maturation_rate_modifier;
                     org.maturity = (org.maturity + rate).min(1.0);
@@ -33,20 +35,42 @@ impl World {
                 } else {
                     0.0
                 };
-                if avg_neighbors > config.crowding_neighbor_threshold {
-                    let excess = avg_neighbors - config.crowding_neighbor_threshold;
+                org.crowding_excess = (avg_neighbors - config.crowding_neighbor_threshold).max(0.0);
+                let mut starved = false;
+                let energy_before_costs = org.metabolic_state.energy;
+                if org.crowding_excess > 0.0 {
+                    let excess = org.crowding_excess;
                     org.boundary_integrity = (org.boundary_integrity
                         - excess * config.crowding_boundary_decay * config.dt as f32)
                         .clamp(0.0, 1.0);
+                    if config.crowding_energy_cost > 0.0 {
+                        org.metabolic_state.energy = (org.metabolic_state.energy
+                            - excess * config.crowding_energy_cost * config.dt as f32)
+                            .max(0.0);
+                        starved = org.metabolic_state.energy <= config.death_energy_threshold;
+                    }
                 }
-                if org.boundary_integrity <= boundary_terminal_threshold {
-                    to_kill.push(org_idx);
+                if config.enable_genetic_sensing && config.sensing_cost_per_radius > 0.0 {
+                    // Wider genetic sensing costs energy in proportion to its radius.
+                    let radius =
+                        config.sensing_radius as f32 * Self::genetic_radius_multiplier(config, org);
+                    org.metabolic_state.energy = (org.metabolic_state.energy
+                        - radius * config.sensing_cost_per_radius * config.dt as f32)
+                        .max(0.0);
+                    starved |= org.metabolic_state.energy <= config.death_energy_threshold;
+                }
+                maintenance += (energy_before_costs - org.metabolic_state.energy) as f64;
+                if starved {
+                    to_kill.push((org_idx, DeathCause::EnergyDepletion));
+                } else if org.boundary_integrity <= boundary_terminal_threshold {
+                    to_kill.push((org_idx, DeathCause::BoundaryCollapse));
                 }
             }
         }
+        self.audit_flow(|flows| flows.maintenance_cost += maintenance);
 
-        for org_idx in to_kill {
-            self.mark_dead(org_idx);
+        for (org_idx, cause) in to_kill {
+            self.mark_dead(org_idx, cause);
         }
     }
 }
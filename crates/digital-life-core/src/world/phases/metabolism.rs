@@ -1,63 +1,541 @@
 use super::super::World;
+use crate::config::{ResourceSamplingMode, ResourceUptakeMode, SimConfig};
+use crate::metabolism::{MetabolismEngine, MetabolismFlux};
+use crate::metrics::DeathCause;
+use crate::organism::OrganismRuntime;
+use crate::resource::{ResourceField, MAX_RESOURCE_CHANNELS};
+use rayon::prelude::*;
+use std::ops::Range;
 
 impl World {
+    /// Per-channel uptake weights: decoded from the genome for graph engines, uniform
+    /// otherwise.
+    fn uptake_affinities(org: &OrganismRuntime, channels: usize) -> [f32; MAX_RESOURCE_CHANNELS] {
+        match &org.metabolism_engine {
+            Some(MetabolismEngine::Graph(gm)) => gm.uptake_affinities(channels),
+            _ => {
+                let mut uniform = [0.0; MAX_RESOURCE_CHANNELS];
+                uniform[..channels].fill(1.0);
+                uniform
+            }
+        }
+    }
+
+    /// Collect the distinct (organism, resource cell, uptake share) triples occupied
+    /// by alive agents, sorted by organism so each organism's cells form a contiguous
+    /// run. The share comes from the agent's role and is 1 without roles.
+    ///
+    /// Deduplicating per cell ensures a cell shared by several agents of the same
+    /// organism is read and depleted once per step, at the largest of their shares.
+    fn collect_uptake_cells(&mut self) {
+        let cells = &mut self.uptake_cells_buffer;
+        cells.clear();
+        for agent in &self.agents {
+            let org_idx = agent.organism_id as usize;
+            let Some(org) = self.organisms.get(org_idx).filter(|o| o.alive) else {
+                continue;
+            };
+            let cell = self
+                .resource_field
+                .cell_index(agent.position[0], agent.position[1]);
+            let share = org.developmental_program.role(agent.role).uptake_share;
+            cells.push((org_idx, cell, share));
+        }
+        cells.sort_unstable_by_key(|&(org_idx, cell, _)| (org_idx, cell));
+        cells.dedup_by(|next, kept| {
+            let same_cell = (next.0, next.1) == (kept.0, kept.1);
+            if same_cell {
+                kept.2 = kept.2.max(next.2);
+            }
+            same_cell
+        });
+    }
+
     /// Update per-organism metabolism and consume resource field.
+    ///
+    /// Under `parallel_organism_phases` organisms are metabolized in waves on the
+    /// rayon pool; see [`World::step_metabolism_waves`].
     pub(in crate::world) fn step_metabolism_phase(&mut self, boundary_terminal_threshold: f32) {
         if !self.config.enable_metabolism {
+            for org in &mut self.organisms {
+                org.last_step_flux = MetabolismFlux::default();
+            }
+            return;
+        }
+        if self.config.resource_uptake_mode == ResourceUptakeMode::PerAgent {
+            self.collect_uptake_cells();
+        }
+        if self.config.enable_agent_energy_reserves {
+            self.collect_reserve_weights();
+        }
+        if self.config.parallel_organism_phases {
+            self.step_metabolism_waves(boundary_terminal_threshold);
             return;
         }
-        let world_size = self.config.world_size;
 
         let mut to_kill = Vec::new();
-        for (org_idx, org) in self.organisms.iter_mut().enumerate() {
-            if !org.alive {
+        let mut cell_cursor = 0usize;
+        for org_idx in 0..self.organisms.len() {
+            if !self.organisms[org_idx].alive {
+                continue;
+            }
+            let center = self.metabolism_center(org_idx);
+            let cells = self.uptake_cell_range(org_idx, &mut cell_cursor);
+            let inputs = MetabolismInputs::new(
+                &self.config,
+                &self.metabolism,
+                &self.resource_field,
+                self.waste_field.as_ref(),
+                &self.uptake_cells_buffer,
+                boundary_terminal_threshold,
+            );
+            let outcome = inputs.metabolize(&mut self.organisms[org_idx], center, cells);
+            outcome.apply(
+                &mut self.resource_field,
+                self.waste_field.as_mut(),
+                &self.uptake_cells_buffer,
+            );
+            self.record_metabolism_outcome(org_idx, &outcome, &mut to_kill);
+        }
+        for (org_idx, cause) in to_kill {
+            self.mark_dead(org_idx, cause);
+        }
+    }
+
+    /// Metabolize organisms in waves, each wave in parallel.
+    ///
+    /// An organism joins the wave after the latest one holding an earlier organism
+    /// that reads or depletes any of the same resource or waste cells. Organisms in
+    /// a wave therefore touch disjoint cells, and organisms sharing a cell still run
+    /// in index order, so fields, organisms and counters end up exactly as in the
+    /// serial pass. Only the fields' running totals are summed in wave order and can
+    /// differ from it in the last bits.
+    fn step_metabolism_waves(&mut self, boundary_terminal_threshold: f32) {
+        let resource_cells = self.resource_field.width() * self.resource_field.height();
+        let waste_cells = self
+            .waste_field
+            .as_ref()
+            .map_or(0, |field| field.width() * field.height());
+        // Wave after the last one to touch each cell; resource cells come first.
+        let mut next_wave = vec![0u32; resource_cells + waste_cells];
+        let mut plan = Vec::new();
+        let mut cell_cursor = 0usize;
+        let mut touched = Vec::new();
+        for org_idx in 0..self.organisms.len() {
+            if !self.organisms[org_idx].alive {
                 continue;
             }
-            let center = if self.org_counts[org_idx] > 0 {
-                [
-                    Self::toroidal_mean_coord(
-                        self.org_toroidal_sums[org_idx][0],
-                        self.org_toroidal_sums[org_idx][1],
-                        world_size,
-                    ),
-                    Self::toroidal_mean_coord(
-                        self.org_toroidal_sums[org_idx][2],
-                        self.org_toroidal_sums[org_idx][3],
-                        world_size,
-                    ),
-                ]
+            let center = self.metabolism_center(org_idx);
+            let cells = self.uptake_cell_range(org_idx, &mut cell_cursor);
+            touched.clear();
+            touched.push(self.resource_field.cell_index(center[0], center[1]));
+            touched.extend(
+                self.uptake_cells_buffer[cells.clone()]
+                    .iter()
+                    .map(|&(_, cell, _)| cell),
+            );
+            if self.config.resource_sampling == ResourceSamplingMode::Bilinear {
+                touched.extend(
+                    self.resource_field
+                        .bilinear_cells(center[0], center[1])
+                        .map(|(cell, _)| cell),
+                );
+            }
+            if let Some(field) = &self.waste_field {
+                touched.push(resource_cells + field.cell_index(center[0], center[1]));
+            }
+            let wave = touched
+                .iter()
+                .map(|&slot| next_wave[slot])
+                .max()
+                .unwrap_or(0);
+            for &slot in &touched {
+                next_wave[slot] = wave + 1;
+            }
+            plan.push((wave, org_idx, center, cells));
+        }
+        plan.sort_by_key(|&(wave, org_idx, ..)| (wave, org_idx));
+
+        let mut outcomes: Vec<Option<MetabolismOutcome>> = Vec::new();
+        outcomes.resize_with(self.organisms.len(), || None);
+        {
+            let mut slots: Vec<Option<&mut OrganismRuntime>> =
+                self.organisms.iter_mut().map(Some).collect();
+            let mut jobs: Vec<_> = plan
+                .into_iter()
+                .map(|(wave, org_idx, center, cells)| {
+                    let org = slots[org_idx]
+                        .take()
+                        .expect("each organism is planned once");
+                    (wave, org_idx, org, center, cells)
+                })
+                .collect();
+            for wave in jobs.chunk_by_mut(|a, b| a.0 == b.0) {
+                let inputs = MetabolismInputs::new(
+                    &self.config,
+                    &self.metabolism,
+                    &self.resource_field,
+                    self.waste_field.as_ref(),
+                    &self.uptake_cells_buffer,
+                    boundary_terminal_threshold,
+                );
+                let results: Vec<MetabolismOutcome> = wave
+                    .par_iter_mut()
+                    .map(|(_, _, org, center, cells)| {
+                        inputs.metabolize(org, *center, cells.clone())
+                    })
+                    .collect();
+                for ((_, org_idx, ..), outcome) in wave.iter().zip(results) {
+                    outcome.apply(
+                        &mut self.resource_field,
+                        self.waste_field.as_mut(),
+                        &self.uptake_cells_buffer,
+                    );
+                    outcomes[*org_idx] = Some(outcome);
+                }
+            }
+        }
+
+        let mut to_kill = Vec::new();
+        for (org_idx, outcome) in outcomes.into_iter().enumerate() {
+            if let Some(outcome) = outcome {
+                self.record_metabolism_outcome(org_idx, &outcome, &mut to_kill);
+            }
+        }
+        for (org_idx, cause) in to_kill {
+            self.mark_dead(org_idx, cause);
+        }
+    }
+
+    /// Toroidal center of an organism's agents, or the origin when it has none.
+    fn metabolism_center(&self, org_idx: usize) -> [f64; 2] {
+        if self.org_counts[org_idx] == 0 {
+            return [0.0, 0.0];
+        }
+        let sums = self.org_toroidal_sums[org_idx];
+        let world_size = self.config.world_size;
+        [
+            Self::toroidal_mean_coord(sums[0], sums[1], world_size),
+            Self::toroidal_mean_coord(sums[2], sums[3], world_size),
+        ]
+    }
+
+    /// Range of `uptake_cells_buffer` holding `org_idx`'s cells, advancing `cursor`
+    /// past them. Empty outside per-agent uptake; organisms must be visited in
+    /// index order.
+    fn uptake_cell_range(&self, org_idx: usize, cursor: &mut usize) -> Range<usize> {
+        if self.config.resource_uptake_mode != ResourceUptakeMode::PerAgent {
+            return 0..0;
+        }
+        let cells = &self.uptake_cells_buffer;
+        while *cursor < cells.len() && cells[*cursor].0 < org_idx {
+            *cursor += 1;
+        }
+        let start = *cursor;
+        while *cursor < cells.len() && cells[*cursor].0 == org_idx {
+            *cursor += 1;
+        }
+        start..*cursor
+    }
+
+    fn record_metabolism_outcome(
+        &mut self,
+        org_idx: usize,
+        outcome: &MetabolismOutcome,
+        to_kill: &mut Vec<(usize, DeathCause)>,
+    ) {
+        if self.config.enable_agent_energy_reserves {
+            self.reserve_deltas_buffer[org_idx] = outcome.energy_change;
+        }
+        if self.waste_field.is_some() {
+            self.waste_uptake_last_step += outcome.waste_taken.unwrap_or(0.0);
+        }
+        if outcome.state_violation {
+            self.metabolic_violations_last_step += 1;
+        }
+        let excreted = if self.waste_field.is_some() {
+            outcome.produced_waste
+        } else {
+            0.0
+        };
+        self.audit_flow(|flows| {
+            flows.metabolic_dissipation += outcome.dissipated as f64;
+            flows.maintenance_cost += outcome.resistance_upkeep as f64;
+            flows.excreted_waste += excreted as f64;
+        });
+        if let Some(cause) = outcome.death {
+            to_kill.push((org_idx, cause));
+        }
+    }
+}
+
+/// What an organism's metabolism step reads and never writes.
+struct MetabolismInputs<'a> {
+    config: &'a SimConfig,
+    shared_engine: &'a MetabolismEngine,
+    field: &'a ResourceField,
+    waste_field: Option<&'a ResourceField>,
+    uptake_cells: &'a [(usize, usize, f32)],
+    per_agent: bool,
+    bilinear: bool,
+    channels: usize,
+    boundary_terminal_threshold: f32,
+}
+
+/// How an organism's external uptake is withdrawn from the resource field.
+enum Depletion {
+    None,
+    /// Split across the organism's occupied cells in proportion to concentration.
+    PerAgent {
+        fraction: f32,
+    },
+    /// Drawn from each channel of the center cell by its weighted contribution.
+    Channels {
+        cell: usize,
+        fraction: f32,
+        affinities: [f32; MAX_RESOURCE_CHANNELS],
+    },
+    Bilinear(f32),
+    Center(f32),
+}
+
+/// Field writes and world bookkeeping left over from one organism's metabolism.
+struct MetabolismOutcome {
+    center: [f64; 2],
+    cells: Range<usize>,
+    depletion: Depletion,
+    /// Taken from the waste field by a graph engine feeding on it.
+    waste_taken: Option<f32>,
+    produced_waste: f32,
+    /// Engine dissipation plus the waste-feeding and growth-efficiency losses.
+    dissipated: f32,
+    /// Energy spent maintaining toxin resistance.
+    resistance_upkeep: f32,
+    energy_change: f32,
+    state_violation: bool,
+    death: Option<DeathCause>,
+}
+
+impl<'a> MetabolismInputs<'a> {
+    fn new(
+        config: &'a SimConfig,
+        shared_engine: &'a MetabolismEngine,
+        field: &'a ResourceField,
+        waste_field: Option<&'a ResourceField>,
+        uptake_cells: &'a [(usize, usize, f32)],
+        boundary_terminal_threshold: f32,
+    ) -> Self {
+        Self {
+            config,
+            shared_engine,
+            field,
+            waste_field,
+            uptake_cells,
+            per_agent: config.resource_uptake_mode == ResourceUptakeMode::PerAgent,
+            bilinear: config.resource_sampling == ResourceSamplingMode::Bilinear,
+            // Validation restricts multi-channel fields to center/nearest uptake.
+            channels: field.channels(),
+            boundary_terminal_threshold,
+        }
+    }
+
+    /// Step `org`'s metabolism against the fields as they stand, leaving the field
+    /// writes to [`MetabolismOutcome::apply`].
+    fn metabolize(
+        &self,
+        org: &mut OrganismRuntime,
+        center: [f64; 2],
+        cells: Range<usize>,
+    ) -> MetabolismOutcome {
+        let config = self.config;
+        let channels = self.channels;
+        let org_cells = &self.uptake_cells[cells.clone()];
+        let affinities = World::uptake_affinities(org, channels);
+        // Dormant organisms do not feed, so the resource under them can recover.
+        let external = if org.dormant {
+            0.0
+        } else if self.per_agent {
+            org_cells
+                .iter()
+                .map(|&(_, cell, share)| self.field.get_cell(cell) * share)
+                .sum()
+        } else if channels > 1 {
+            let cell = self.field.cell_index(center[0], center[1]);
+            (0..channels)
+                .map(|c| affinities[c] * self.field.get_cell_channel(c, cell))
+                .sum()
+        } else if self.bilinear {
+            self.field.get_bilinear(center[0], center[1])
+        } else {
+            self.field.get(center[0], center[1])
+        };
+        // Wake-up is judged on the resource present before this step's uptake.
+        let local_resource = if org.dormant {
+            let cell = self.field.cell_index(center[0], center[1]);
+            (0..channels)
+                .map(|c| self.field.get_cell_channel(c, cell))
+                .sum()
+        } else {
+            0.0
+        };
+        let basal_scale = if org.dormant {
+            config.dormancy_metabolic_scale
+        } else {
+            1.0
+        };
+        let pre_energy = org.metabolic_state.energy;
+        let engine = org.metabolism_engine.as_ref().unwrap_or(self.shared_engine);
+        let flux = engine.step_with_basal_scale(
+            &mut org.metabolic_state,
+            external,
+            config.dt as f32,
+            basal_scale,
+        );
+        // Only graph engines decode a waste affinity; dormant organisms do not feed.
+        let waste_feeder = match &org.metabolism_engine {
+            Some(MetabolismEngine::Graph(gm)) if !org.dormant => Some(gm),
+            _ => None,
+        };
+        let energy_before_feeding = org.metabolic_state.energy;
+        let waste_taken = match (self.waste_field, waste_feeder) {
+            (Some(field), Some(gm)) => Some(gm.consume_waste(
+                &mut org.metabolic_state,
+                field.get_cell(field.cell_index(center[0], center[1])),
+                config.waste_uptake_efficiency,
+                config.dt as f32,
+            )),
+            _ => None,
+        };
+        let feeding_loss = waste_taken.map_or(0.0, |taken| {
+            taken - (org.metabolic_state.energy - energy_before_feeding)
+        });
+        let state = &mut org.metabolic_state;
+        let valid = |v: f32| v.is_finite() && v >= 0.0;
+        let state_violation = !valid(state.energy) || !valid(state.waste);
+        if state_violation {
+            // Custom engines are not bound by the built-in clamps.
+            for v in [&mut state.energy, &mut state.waste] {
+                if !valid(*v) {
+                    *v = 0.0;
+                }
+            }
+        }
+        let energy_delta = org.metabolic_state.energy - pre_energy;
+        let mut scaling_loss = 0.0;
+        if energy_delta > 0.0 {
+            let growth_factor =
+                World::metabolic_stage_factor(config, org) * World::senescence_factor(config, org);
+            let unscaled = org.metabolic_state.energy;
+            org.metabolic_state.energy =
+                pre_energy + energy_delta * growth_factor * config.metabolism_efficiency_multiplier;
+            scaling_loss = unscaled - org.metabolic_state.energy;
+        }
+        let resistance_upkeep = if self.waste_field.is_some() {
+            (org.homeostasis_program
+                .resistance_upkeep(config.toxin_resistance_cost)
+                * basal_scale
+                * config.dt as f32)
+                .min(org.metabolic_state.energy.max(0.0))
+        } else {
+            0.0
+        };
+        org.metabolic_state.energy -= resistance_upkeep;
+        let depletion = if flux.consumed_external <= 0.0 {
+            Depletion::None
+        } else if self.per_agent {
+            Depletion::PerAgent {
+                fraction: flux.consumed_external / external,
+            }
+        } else if channels > 1 {
+            Depletion::Channels {
+                cell: self.field.cell_index(center[0], center[1]),
+                fraction: flux.consumed_external / external,
+                affinities,
+            }
+        } else if self.bilinear {
+            Depletion::Bilinear(flux.consumed_external)
+        } else {
+            Depletion::Center(flux.consumed_external)
+        };
+
+        org.flux_since_snapshot.accumulate(&flux);
+        org.last_step_flux = flux;
+
+        if config.enable_dormancy {
+            org.dormant = if org.dormant {
+                local_resource <= config.dormancy_exit_resource
             } else {
-                [0.0, 0.0]
+                org.metabolic_state.energy < config.dormancy_entry_threshold
             };
-            let external = self.resource_field.get(center[0], center[1]);
-            let pre_energy = org.metabolic_state.energy;
-            let engine = org.metabolism_engine.as_ref().unwrap_or(&self.metabolism);
-            let flux = engine.step(&mut org.metabolic_state, external, self.config.dt as f32);
-            let energy_delta = org.metabolic_state.energy - pre_energy;
-            if energy_delta > 0.0 {
-                let growth_factor = if self.config.enable_growth {
-                    org.developmental_program.stage_factors(org.maturity).2
-                } else {
-                    self.config.growth_immature_metabolic_efficiency
-                        + org.maturity * (1.0 - self.config.growth_immature_metabolic_efficiency)
-                };
-                org.metabolic_state.energy = pre_energy
-                    + energy_delta * growth_factor * self.config.metabolism_efficiency_multiplier;
-            }
-            if flux.consumed_external > 0.0 {
-                let _ = self
-                    .resource_field
-                    .take(center[0], center[1], flux.consumed_external);
-            }
-
-            if org.metabolic_state.energy <= self.config.death_energy_threshold
-                || org.boundary_integrity <= boundary_terminal_threshold
-            {
-                to_kill.push(org_idx);
-            }
-        }
-        for org_idx in to_kill {
-            self.mark_dead(org_idx);
+        }
+
+        debug_assert!(
+            org.metabolic_state.energy.is_finite(),
+            "energy must stay finite after metabolism"
+        );
+        let death = if org.metabolic_state.energy <= config.death_energy_threshold {
+            Some(DeathCause::EnergyDepletion)
+        } else if org.boundary_integrity <= self.boundary_terminal_threshold {
+            Some(DeathCause::BoundaryCollapse)
+        } else {
+            None
+        };
+        MetabolismOutcome {
+            center,
+            cells,
+            depletion,
+            waste_taken,
+            produced_waste: flux.produced_waste,
+            dissipated: flux.dissipated + feeding_loss + scaling_loss,
+            resistance_upkeep,
+            energy_change: org.metabolic_state.energy - pre_energy,
+            state_violation,
+            death,
+        }
+    }
+}
+
+impl MetabolismOutcome {
+    /// Withdraw the organism's uptake and waste feeding, then excrete its waste, so
+    /// an organism's own waste reaches it next step.
+    fn apply(
+        &self,
+        field: &mut ResourceField,
+        waste_field: Option<&mut ResourceField>,
+        uptake_cells: &[(usize, usize, f32)],
+    ) {
+        let [x, y] = self.center;
+        match self.depletion {
+            Depletion::None => {}
+            Depletion::PerAgent { fraction } => {
+                for &(_, cell, share) in &uptake_cells[self.cells.clone()] {
+                    let amount = field.get_cell(cell) * share * fraction;
+                    let _ = field.take_cell(cell, amount);
+                }
+            }
+            Depletion::Channels {
+                cell,
+                fraction,
+                ref affinities,
+            } => {
+                for (c, &affinity) in affinities.iter().enumerate().take(field.channels()) {
+                    let amount = affinity * field.get_cell_channel(c, cell) * fraction;
+                    let _ = field.take_cell_channel(c, cell, amount);
+                }
+            }
+            Depletion::Bilinear(amount) => {
+                let _ = field.take_bilinear(x, y, amount);
+            }
+            Depletion::Center(amount) => {
+                let _ = field.take(x, y, amount);
+            }
+        }
+        if let Some(waste) = waste_field {
+            if let Some(taken) = self.waste_taken {
+                let cell = waste.cell_index(x, y);
+                let _ = waste.take_cell(cell, taken);
+            }
+            let _ = waste.deposit(x, y, self.produced_waste, f32::INFINITY);
         }
     }
 }
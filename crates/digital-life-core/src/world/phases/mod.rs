@@ -1,6 +1,9 @@
 mod agent_state;
 mod boundary;
+mod energy_reserves;
 mod environment;
+mod fusion;
 mod growth;
 mod metabolism;
 mod nn_query;
+mod predation;
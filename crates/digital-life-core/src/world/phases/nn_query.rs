@@ -1,21 +1,53 @@
+use crate::config::SensingMode;
+use crate::nn::HebbianRule;
+use crate::organism::{SensoryProgram, StageFactors};
 use crate::spatial;
 use crate::spatial::AgentLocation;
 use rstar::RTree;
+use std::borrow::Cow;
 
-use super::super::World;
+use super::super::{ActivationRecord, World};
 
 impl World {
     /// Compute neighbor-informed neural deltas for all agents.
+    ///
+    /// Inputs are gathered per agent, then evaluated with one batched NN call per run of
+    /// same-organism agents. Plastic organisms are evaluated per agent in place, since
+    /// each pass updates the trace the next one reads.
     pub(in crate::world) fn step_nn_query_phase(&mut self, tree: &RTree<AgentLocation>) {
         let deltas = &mut self.deltas_buffer;
+        let nn_inputs = &mut self.nn_inputs_buffer;
+        let nn_sensors = &mut self.nn_sensors_buffer;
+        let nn_pending = &mut self.nn_pending_buffer;
+        let nn_outputs = &mut self.nn_outputs_buffer;
         let neighbor_sums = &mut self.neighbor_sums_buffer;
         let neighbor_counts = &mut self.neighbor_counts_buffer;
         let agents = &self.agents;
-        let organisms = &self.organisms;
+        let organisms = &mut self.organisms;
         let config = &self.config;
+        let field = &self.resource_field;
+        let gradient_cache = &mut self.resource_gradient_cache;
+        let gradient_stamps = &mut self.resource_gradient_stamps;
+        let step_index = self.step_index;
+        let activations = &mut self.activation_recorder;
+        let recording = activations.is_due(step_index);
+        let extended_sensors = config.enable_resource_gradient_sensing;
+        let hebbian_rule = HebbianRule {
+            learning_rate: config.hebbian_learning_rate,
+            decay: config.hebbian_decay,
+            max_delta: config.hebbian_max_delta,
+        };
+        if extended_sensors && gradient_cache.len() != field.data().len() {
+            gradient_cache.resize(field.data().len(), [0.0; 2]);
+            gradient_stamps.clear();
+            gradient_stamps.resize(field.data().len(), usize::MAX);
+        }
 
         deltas.clear();
         deltas.reserve(agents.len());
+        nn_inputs.clear();
+        nn_sensors.clear();
+        nn_pending.clear();
 
         let org_count = organisms.len();
         if neighbor_sums.len() != org_count {
@@ -24,8 +56,10 @@ impl World {
         }
         neighbor_sums.fill(0.0);
         neighbor_counts.fill(0);
+        let collect_contacts = config.collect_contact_metrics;
+        let (mut same_pairs, mut total_pairs) = (0usize, 0usize);
 
-        for agent in agents {
+        for (agent_idx, agent) in agents.iter().enumerate() {
             let org_idx = agent.organism_id as usize;
             // Manual lookup to avoid borrowing self methods
             if !organisms.get(org_idx).map(|o| o.alive).unwrap_or(false) {
@@ -34,26 +68,76 @@ impl World {
             }
 
             // Inline effective_sensing_radius logic to avoid borrow conflicts
-            let dev_sensing = if config.enable_growth {
-                organisms[org_idx]
-                    .developmental_program
-                    .stage_factors(organisms[org_idx].maturity)
-                    .1
+            let program = &organisms[org_idx].developmental_program;
+            let stage = if config.enable_growth {
+                program.stage_factors(organisms[org_idx].maturity)
+            } else {
+                StageFactors::ADULT
+            };
+            let role_sensing = program.role(agent.role).sensing_multiplier;
+            let sensory = if config.enable_genetic_sensing {
+                organisms[org_idx].sensory_program
             } else {
-                1.0
+                SensoryProgram::default()
             };
-            let effective_radius = config.sensing_radius * dev_sensing as f64;
+            let effective_radius = config.sensing_radius
+                * (stage.sensing * role_sensing * sensory.radius_multiplier) as f64;
+            let neighbor_norm = config.neighbor_norm * sensory.neighbor_norm_multiplier as f64;
 
-            let neighbor_count = spatial::count_neighbors(
-                tree,
-                agent.position,
-                effective_radius,
-                agent.id,
-                config.world_size,
-            );
+            let (neighbor_count, same_count) = if collect_contacts || extended_sensors {
+                let (same, other) = spatial::count_neighbors_split(
+                    tree,
+                    agent.position,
+                    effective_radius,
+                    agent.id,
+                    agent.organism_id,
+                    config.world_size,
+                );
+                if collect_contacts {
+                    same_pairs += same;
+                    total_pairs += same + other;
+                }
+                (same + other, same)
+            } else {
+                let count = spatial::count_neighbors(
+                    tree,
+                    agent.position,
+                    effective_radius,
+                    agent.id,
+                    config.world_size,
+                );
+                (count, count)
+            };
 
             neighbor_sums[org_idx] += neighbor_count as f32;
             neighbor_counts[org_idx] += 1;
+            // Dormant organisms still count toward crowding but do not act.
+            if organisms[org_idx].dormant {
+                deltas.push([0.0; 4]);
+                continue;
+            }
+
+            let neighbor_input = match config.sensing_mode {
+                // The extended suite reads other organisms through a separate sensor.
+                SensingMode::Radius if extended_sensors => same_count as f32 / neighbor_norm as f32,
+                SensingMode::Radius => neighbor_count as f32 / neighbor_norm as f32,
+                SensingMode::KNearest { k } => {
+                    let (sum, n) =
+                        spatial::k_nearest(tree, agent.position, k, agent.id, config.world_size)
+                            .fold((0.0, 0usize), |(sum, n), (_, d)| (sum + d, n + 1));
+                    if n == 0 || effective_radius <= 0.0 {
+                        0.0
+                    } else {
+                        (1.0 - sum / n as f64 / effective_radius).clamp(0.0, 1.0) as f32
+                    }
+                }
+            };
+
+            let neighbor_input = if config.enable_maturity_gating {
+                neighbor_input * stage.neighbor_sensing
+            } else {
+                neighbor_input
+            };
 
             let input: [f32; 8] = [
                 (agent.position[0] / config.world_size) as f32,
@@ -63,10 +147,92 @@ impl World {
                 agent.internal_state[0],
                 agent.internal_state[1],
                 agent.internal_state[2],
-                neighbor_count as f32 / config.neighbor_norm as f32,
+                neighbor_input,
             ];
-            let nn = &organisms[org_idx].nn;
-            deltas.push(nn.forward(&input));
+            let sensors = if extended_sensors {
+                // Gradients are computed once per occupied cell per step.
+                let cell = field.cell_index(agent.position[0], agent.position[1]);
+                if gradient_stamps[cell] != step_index {
+                    gradient_cache[cell] = field.gradient_at_cell(cell);
+                    gradient_stamps[cell] = step_index;
+                }
+                let [gx, gy] = gradient_cache[cell];
+                let norm = config.resource_gradient_norm / sensory.gradient_gain;
+                let other_count = (neighbor_count - same_count) as f32;
+                Some([
+                    gx / norm,
+                    gy / norm,
+                    sensory.contact_gain * other_count / neighbor_norm as f32,
+                    organisms[org_idx]
+                        .developmental_program
+                        .role_signal(agent.role),
+                ])
+            } else {
+                None
+            };
+            let org = &mut organisms[org_idx];
+            if recording && activations.tracks(org.stable_id) {
+                let nn = match org.hebbian_trace.as_deref() {
+                    Some(trace) => Cow::Owned(org.nn.with_trace(trace)),
+                    None => Cow::Borrowed(&org.nn),
+                };
+                activations.push(ActivationRecord {
+                    step: step_index,
+                    agent_id: agent.id,
+                    hidden: nn.hidden_activations(&input, sensors.as_ref(), &org.nn_params),
+                });
+            }
+            if let Some(trace) = org.hebbian_trace.as_deref_mut() {
+                deltas.push(org.nn.forward_plastic(
+                    &input,
+                    sensors.as_ref(),
+                    trace,
+                    &hebbian_rule,
+                    &org.nn_params,
+                ));
+                continue;
+            }
+            // Filled in by the batched pass below.
+            deltas.push([0.0; 4]);
+            nn_pending.push(agent_idx);
+            nn_inputs.push(input);
+            if let Some(sensors) = sensors {
+                nn_sensors.push(sensors);
+            }
+        }
+        self.contact_pairs_last_step = (same_pairs, total_pairs);
+
+        nn_outputs.clear();
+        let mut offset = 0;
+        for run in nn_pending.chunk_by(|&a, &b| agents[a].organism_id == agents[b].organism_id) {
+            let org = &organisms[agents[run[0]].organism_id as usize];
+            let range = offset..offset + run.len();
+            if extended_sensors {
+                org.nn.forward_batch_with_sensors(
+                    &nn_inputs[range.clone()],
+                    &nn_sensors[range],
+                    &org.nn_params,
+                    nn_outputs,
+                );
+            } else {
+                org.nn
+                    .forward_batch(&nn_inputs[range], &org.nn_params, nn_outputs);
+            }
+            offset += run.len();
+        }
+        for (&agent_idx, &delta) in nn_pending.iter().zip(nn_outputs.iter()) {
+            deltas[agent_idx] = delta;
+        }
+
+        // A NaN delta would reach velocity, position and every toroidal sum after it.
+        for delta in deltas.iter_mut() {
+            if delta.iter().all(|d| d.is_finite()) {
+                continue;
+            }
+            for d in delta.iter_mut().filter(|d| !d.is_finite()) {
+                *d = 0.0;
+            }
+            self.nonfinite_outputs_last_step += 1;
         }
     }
 }
@@ -0,0 +1,85 @@
+use crate::metrics::DeathCause;
+use crate::spatial;
+use crate::spatial::AgentLocation;
+use rstar::RTree;
+
+use super::super::World;
+
+impl World {
+    /// Collect distinct (predator, prey) organism pairs with at least one agent contact
+    /// within `predation_radius` and a sufficient energy lead, sorted for determinism.
+    fn collect_predation_pairs(&mut self, tree: &RTree<AgentLocation>) {
+        let pairs = &mut self.predation_pairs_buffer;
+        pairs.clear();
+        let organisms = &self.organisms;
+        let config = &self.config;
+        for agent in &self.agents {
+            let predator = agent.organism_id as usize;
+            let Some(predator_org) = organisms.get(predator).filter(|o| o.alive) else {
+                continue;
+            };
+            let predator_energy = predator_org.metabolic_state.energy;
+            spatial::for_each_neighbor(
+                tree,
+                agent.position,
+                config.predation_radius,
+                agent.id,
+                config.world_size,
+                |loc| {
+                    let prey = loc.organism_id as usize;
+                    if prey == predator {
+                        return;
+                    }
+                    if let Some(prey_org) = organisms.get(prey).filter(|o| o.alive) {
+                        if predator_energy - prey_org.metabolic_state.energy
+                            >= config.predation_energy_advantage
+                        {
+                            pairs.push((predator, prey));
+                        }
+                    }
+                },
+            );
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+    }
+
+    /// Transfer energy from weaker to stronger organisms in contact and damage prey boundaries.
+    ///
+    /// Contacts are found at the agents' positions after this step's movement.
+    /// Eligibility is decided from energies at the start of the phase; pairs are then
+    /// applied in sorted order, skipping any whose predator or prey was killed by an
+    /// earlier pair. Predators gain at most up to their engine's `max_energy`, and
+    /// prey lose only what the predator could take.
+    pub(in crate::world) fn step_predation_phase(&mut self, boundary_terminal_threshold: f32) {
+        if !self.config.enable_predation {
+            return;
+        }
+        let tree = spatial::build_index_active(&self.agents, &self.live_flags());
+        self.collect_predation_pairs(&tree);
+
+        let transfer = self.config.predation_transfer;
+        let damage = self.config.predation_boundary_damage;
+        let pairs = std::mem::take(&mut self.predation_pairs_buffer);
+        for &(predator, prey) in &pairs {
+            if !self.organisms[predator].alive || !self.organisms[prey].alive {
+                continue;
+            }
+            let headroom = (self.organism_max_energy(predator)
+                - self.organisms[predator].metabolic_state.energy)
+                .max(0.0);
+            let prey_org = &mut self.organisms[prey];
+            let taken = transfer
+                .min(prey_org.metabolic_state.energy.max(0.0))
+                .min(headroom);
+            prey_org.metabolic_state.energy -= taken;
+            prey_org.boundary_integrity = (prey_org.boundary_integrity - damage).clamp(0.0, 1.0);
+            let killed = prey_org.boundary_integrity <= boundary_terminal_threshold;
+            self.organisms[predator].metabolic_state.energy += taken;
+            if killed {
+                self.mark_dead(prey, DeathCause::Predation);
+            }
+        }
+        self.predation_pairs_buffer = pairs;
+    }
+}
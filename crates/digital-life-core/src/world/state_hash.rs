@@ -0,0 +1,156 @@
+use super::World;
+use crate::config::{Locomotion, RngMode};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a over canonical little-endian encodings.
+///
+/// Hand-rolled rather than `DefaultHasher` because the std hasher's output is
+/// not guaranteed to be stable across Rust releases.
+struct StateHasher(u64);
+
+impl StateHasher {
+    fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_bytes(&[u8::from(value)]);
+    }
+
+    /// Floats are hashed by bit pattern with `-0.0` folded into `0.0` and all
+    /// NaNs folded into one canonical NaN.
+    fn write_f32(&mut self, value: f32) {
+        let canonical = if value == 0.0 {
+            0.0
+        } else if value.is_nan() {
+            f32::NAN
+        } else {
+            value
+        };
+        self.write_bytes(&canonical.to_bits().to_le_bytes());
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        let canonical = if value == 0.0 {
+            0.0
+        } else if value.is_nan() {
+            f64::NAN
+        } else {
+            value
+        };
+        self.write_bytes(&canonical.to_bits().to_le_bytes());
+    }
+
+    fn write_f32_slice(&mut self, values: &[f32]) {
+        self.write_usize(values.len());
+        for &v in values {
+            self.write_f32(v);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl World {
+    /// Deterministic fingerprint of the simulation state.
+    ///
+    /// Covers step/counter bookkeeping, RNG position, every agent's kinematics and
+    /// internal state, every organism's physiology and genome, and the resource
+    /// field, hashed in storage order. Intended for replay and regression checks:
+    /// two runs with the same config and seed must agree, and the value is
+    /// expected to change whenever simulation behavior is intentionally changed.
+    pub fn state_hash(&self) -> u64 {
+        let mut h = StateHasher::new();
+        h.write_usize(self.step_index);
+        h.write_u64(u64::from(self.next_agent_id));
+        h.write_u64(self.next_organism_stable_id);
+        h.write_usize(self.total_births);
+        h.write_usize(self.total_deaths);
+        h.write_usize(self.total_agent_id_exhaustions);
+        // Skipped when empty so hashes of runs without id recycling are unchanged.
+        if !self.free_agent_ids.is_empty() {
+            h.write_usize(self.free_agent_ids.len());
+            for &id in &self.free_agent_ids {
+                h.write_u64(u64::from(id));
+            }
+        }
+        h.write_f32(self.current_resource_rate);
+        h.write_bytes(&self.rng.get_word_pos().to_le_bytes());
+
+        h.write_usize(self.agents.len());
+        for agent in &self.agents {
+            h.write_u64(u64::from(agent.id));
+            h.write_u64(u64::from(agent.organism_id));
+            for &v in agent.position.iter().chain(&agent.velocity) {
+                h.write_f64(v);
+            }
+            for &v in &agent.internal_state {
+                h.write_f32(v);
+            }
+            if self.config.locomotion == Locomotion::Heading {
+                h.write_f64(agent.heading);
+            }
+            if self.config.agent_role_count > 0 {
+                h.write_u64(u64::from(agent.role));
+            }
+        }
+
+        h.write_usize(self.organisms.len());
+        for org in &self.organisms {
+            h.write_u64(org.stable_id);
+            h.write_u64(u64::from(org.generation));
+            h.write_usize(org.age_steps);
+            h.write_bool(org.alive);
+            h.write_f32(org.boundary_integrity);
+            h.write_f32(org.maturity);
+            h.write_f32(org.metabolic_state.energy);
+            h.write_f32(org.metabolic_state.resource);
+            h.write_f32(org.metabolic_state.waste);
+            h.write_f32_slice(&org.metabolic_state.graph_pool);
+            h.write_f32_slice(org.genome.data());
+            // Always 0 under `RngMode::Global`, so those hashes are unchanged.
+            if self.config.rng_mode == RngMode::PerOrganism {
+                h.write_bytes(&org.rng_word_pos.to_le_bytes());
+            }
+            if self.config.enable_dormancy {
+                h.write_bool(org.dormant);
+            }
+            if self.config.enable_hebbian_plasticity {
+                if let Some(trace) = &org.hebbian_trace {
+                    for row in &trace.d_ih {
+                        h.write_f32_slice(row);
+                    }
+                    for row in &trace.d_ho {
+                        h.write_f32_slice(row);
+                    }
+                }
+            }
+        }
+
+        h.write_f32_slice(self.resource_field.data());
+        // Skipped when absent so hashes of runs without a waste field are unchanged.
+        if let Some(field) = &self.waste_field {
+            h.write_f32_slice(field.data());
+        }
+        h.finish()
+    }
+}
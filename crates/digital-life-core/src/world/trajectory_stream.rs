@@ -0,0 +1,138 @@
+use super::{ExperimentError, World};
+use crate::trajectory_file::{TrajectoryPoint, TrajectoryWriter};
+use std::io::{self, Write};
+use std::sync::{Mutex, PoisonError};
+
+/// Trajectory recorder installed by [`World::set_trajectory_writer`].
+struct TrajectoryStream {
+    writer: TrajectoryWriter<Box<dyn Write + Send>>,
+    every_n_steps: usize,
+    max_agents: usize,
+    /// Reused (subsample key, agent index) buffer for choosing recorded agents.
+    selection: Vec<(u64, usize)>,
+    /// First write or flush failure; later frames are dropped.
+    error: Option<io::Error>,
+}
+
+/// Optional binary destination for agent positions. As with the lineage sink, the
+/// mutex only makes the world `Sync`, and clones start without a recorder.
+#[derive(Default)]
+pub(super) struct TrajectorySink(Option<Mutex<TrajectoryStream>>);
+
+impl Clone for TrajectorySink {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl TrajectorySink {
+    fn stream(&mut self) -> Option<&mut TrajectoryStream> {
+        self.0
+            .as_mut()
+            .map(|stream| stream.get_mut().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// Pseudo-random rank of an agent id for subsampling (SplitMix64 finalizer), so the
+/// same agents are kept from frame to frame while they live.
+fn subsample_key(id: u32) -> u64 {
+    let mut z = u64::from(id).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl World {
+    /// Record the positions of alive organisms' agents to `writer` every
+    /// `every_n_steps` steps in the [`trajectory_file`](crate::trajectory_file)
+    /// format, for agent-level animation. When more than `max_agents` agents are
+    /// alive, the `max_agents` with the lowest hash of their id are recorded. The
+    /// header is written immediately; frames are flushed when a run ends. Replaces
+    /// any previous recorder without flushing it.
+    pub fn set_trajectory_writer(
+        &mut self,
+        writer: Box<dyn Write + Send>,
+        every_n_steps: usize,
+        max_agents: usize,
+    ) -> Result<(), ExperimentError> {
+        if every_n_steps == 0 || max_agents == 0 {
+            return Err(ExperimentError::InvalidTrajectoryRecorder);
+        }
+        let writer = TrajectoryWriter::new(writer)
+            .map_err(|err| ExperimentError::TrajectoryWrite(err.to_string()))?;
+        self.trajectory_sink = TrajectorySink(Some(Mutex::new(TrajectoryStream {
+            writer,
+            every_n_steps,
+            max_agents,
+            selection: Vec::new(),
+            error: None,
+        })));
+        Ok(())
+    }
+
+    /// Remove the trajectory recorder, returning its writer unflushed.
+    pub fn take_trajectory_writer(&mut self) -> Option<Box<dyn Write + Send>> {
+        std::mem::take(&mut self.trajectory_sink).0.map(|stream| {
+            stream
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner)
+                .writer
+                .into_inner()
+        })
+    }
+
+    /// Write a trajectory frame when the recorder's cadence is due.
+    pub(super) fn record_trajectory_if_due(&mut self) {
+        let step = self.step_index;
+        let Some(stream) = self.trajectory_sink.stream() else {
+            return;
+        };
+        if stream.error.is_some() || !step.is_multiple_of(stream.every_n_steps) {
+            return;
+        }
+        let organisms = &self.organisms;
+        let selection = &mut stream.selection;
+        selection.clear();
+        selection.extend(
+            self.agents
+                .iter()
+                .enumerate()
+                .filter(|(_, agent)| organisms[agent.organism_id as usize].alive)
+                .map(|(idx, agent)| (subsample_key(agent.id), idx)),
+        );
+        if selection.len() > stream.max_agents {
+            selection.select_nth_unstable(stream.max_agents);
+            selection.truncate(stream.max_agents);
+            selection.sort_unstable_by_key(|&(_, idx)| idx);
+        }
+        let agents = &self.agents;
+        let points = selection.iter().map(|&(_, idx)| {
+            let agent = &agents[idx];
+            TrajectoryPoint {
+                id: agent.id,
+                organism_id: organisms[agent.organism_id as usize].stable_id,
+                x: agent.position[0] as f32,
+                y: agent.position[1] as f32,
+            }
+        });
+        stream.error = stream
+            .writer
+            .write_frame(step, selection.len(), points)
+            .err();
+    }
+
+    /// Flush the trajectory recorder at the end of a run, reporting the first write
+    /// failure since it was installed.
+    pub(super) fn finish_trajectory_stream(&mut self) -> Result<(), ExperimentError> {
+        let Some(stream) = self.trajectory_sink.stream() else {
+            return Ok(());
+        };
+        if stream.error.is_none() {
+            stream.error = stream.writer.flush().err();
+        }
+        match stream.error.take() {
+            Some(err) => Err(ExperimentError::TrajectoryWrite(err.to_string())),
+            None => Ok(()),
+        }
+    }
+}
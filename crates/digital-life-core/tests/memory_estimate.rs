@@ -0,0 +1,60 @@
+//! Checks `SimConfig::estimated_memory_bytes` against the heap a world actually
+//! allocates. Lives in its own test binary so the counting allocator only sees
+//! this test.
+
+use digital_life_core::config::SimConfig;
+use digital_life_core::world::World;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size(), Ordering::SeqCst);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::SeqCst);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn estimate_is_within_twenty_percent_of_mid_size_world_allocation() {
+    let configs = [
+        SimConfig {
+            num_organisms: 500,
+            agents_per_organism: 20,
+            world_size: 200.0,
+            ..SimConfig::default()
+        },
+        SimConfig {
+            num_organisms: 2_000,
+            agents_per_organism: 5,
+            world_size: 120.0,
+            enable_resource_gradient_sensing: true,
+            enable_waste_field: true,
+            ..SimConfig::default()
+        },
+    ];
+    for config in configs {
+        let estimate = config.estimated_memory_bytes() as f64;
+        let before = LIVE_BYTES.load(Ordering::SeqCst);
+        let mut world = World::from_config(config).unwrap();
+        world.step();
+        let actual = (LIVE_BYTES.load(Ordering::SeqCst) - before) as f64;
+        drop(world);
+        let ratio = estimate / actual;
+        assert!(
+            (0.8..=1.2).contains(&ratio),
+            "estimate {estimate} vs allocated {actual} (ratio {ratio:.3})"
+        );
+    }
+}
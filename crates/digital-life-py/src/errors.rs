@@ -0,0 +1,420 @@
+//! Python exception hierarchy for failures reported by the bindings.
+//!
+//! Every exception derives from `DigitalLifeError`, itself a `ValueError`, so
+//! callers catching `ValueError` keep working. Core errors reach Python through
+//! [`CoreError`]: its `From` impls match every variant of every core error the
+//! bindings surface, so a new variant fails to compile until it is mapped.
+
+use digital_life_core::ablation::AblationError;
+use digital_life_core::config::SimConfigErrors;
+use digital_life_core::lineage::LineageCycleError;
+use digital_life_core::metrics::SchemaError;
+use digital_life_core::resource::ResourceGridError;
+use digital_life_core::sweep::SweepError;
+use digital_life_core::world::{
+    AddOrganismError, ExperimentError as CoreExperimentError, InterventionError,
+    WorldInitError as CoreWorldInitError,
+};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyKeyboardInterrupt, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::fmt;
+
+create_exception!(
+    _core,
+    DigitalLifeError,
+    PyValueError,
+    "Base class for errors raised by digital_life."
+);
+create_exception!(
+    _core,
+    ConfigError,
+    DigitalLifeError,
+    "Invalid config values or experiment spec. Config validation failures carry \
+     `violations`, a list of dicts with `field`, `value` and `message`."
+);
+create_exception!(
+    _core,
+    WorldInitError,
+    DigitalLifeError,
+    "A world could not be built from an otherwise valid config."
+);
+create_exception!(
+    _core,
+    ExperimentError,
+    DigitalLifeError,
+    "Rejected run parameters, intervention, or resource edit."
+);
+create_exception!(
+    _core,
+    CheckpointError,
+    DigitalLifeError,
+    "Writing a snapshot file, lineage stream, or trajectory file failed."
+);
+
+/// Python exception class a [`CoreError`] is raised as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ErrorKind {
+    Base,
+    Config,
+    WorldInit,
+    Experiment,
+    Checkpoint,
+}
+
+/// Structured field attached to the raised exception.
+#[derive(Clone, Debug, PartialEq)]
+enum Attr {
+    Int(u64),
+    Float(f64),
+    /// `(field, value, message)` per invalid config value.
+    Violations(Vec<(Option<String>, Option<String>, String)>),
+}
+
+/// Failure carried out of GIL-free code and raised once the GIL is held again.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CoreError {
+    kind: ErrorKind,
+    message: String,
+    attrs: Vec<(&'static str, Attr)>,
+}
+
+impl CoreError {
+    pub(crate) fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            attrs: Vec::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Prefix the message with the operation that failed.
+    pub(crate) fn context(mut self, prefix: &str) -> Self {
+        self.message = format!("{prefix}: {}", self.message);
+        self
+    }
+
+    fn int(mut self, name: &'static str, value: impl TryInto<u64>) -> Self {
+        let value = value.try_into().unwrap_or(u64::MAX);
+        self.attrs.push((name, Attr::Int(value)));
+        self
+    }
+
+    fn float(mut self, name: &'static str, value: f64) -> Self {
+        self.attrs.push((name, Attr::Float(value)));
+        self
+    }
+
+    /// `expected` and `actual` counts of a mismatch.
+    fn counts(self, expected: usize, actual: usize) -> Self {
+        self.int("expected", expected).int("actual", actual)
+    }
+
+    /// `max` and `actual` of an exceeded limit.
+    pub(crate) fn limit(self, max: usize, actual: usize) -> Self {
+        self.int("max", max).int("actual", actual)
+    }
+
+    /// Keep the mapping of a wrapped error under the wrapper's message.
+    fn reworded(self, message: String) -> Self {
+        Self { message, ..self }
+    }
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<CoreError> for PyErr {
+    fn from(err: CoreError) -> PyErr {
+        let pyerr = match err.kind {
+            ErrorKind::Base => DigitalLifeError::new_err(err.message),
+            ErrorKind::Config => ConfigError::new_err(err.message),
+            ErrorKind::WorldInit => WorldInitError::new_err(err.message),
+            ErrorKind::Experiment => ExperimentError::new_err(err.message),
+            ErrorKind::Checkpoint => CheckpointError::new_err(err.message),
+        };
+        if err.attrs.is_empty() {
+            return pyerr;
+        }
+        Python::attach(|py| {
+            let value = pyerr.value(py);
+            for (name, attr) in err.attrs {
+                let set = match attr {
+                    Attr::Int(v) => value.setattr(name, v),
+                    Attr::Float(v) => value.setattr(name, v),
+                    Attr::Violations(violations) => violations
+                        .into_iter()
+                        .map(|(field, value, message)| {
+                            let entry = PyDict::new(py);
+                            entry.set_item("field", field)?;
+                            entry.set_item("value", value)?;
+                            entry.set_item("message", message)?;
+                            Ok(entry)
+                        })
+                        .collect::<PyResult<Vec<_>>>()
+                        .and_then(|entries| value.setattr(name, entries)),
+                };
+                if let Err(e) = set {
+                    return e;
+                }
+            }
+            pyerr
+        })
+    }
+}
+
+/// Error to raise for a run that its observer stopped with `err`. A
+/// KeyboardInterrupt is re-raised with the partial summary JSON attached as
+/// `partial_summary`, or `None` if the run produced none.
+pub(crate) fn observed_run_error(
+    py: Python<'_>,
+    err: PyErr,
+    partial_summary: Option<String>,
+) -> PyErr {
+    if !err.is_instance_of::<PyKeyboardInterrupt>(py) {
+        return err;
+    }
+    match err.value(py).setattr("partial_summary", partial_summary) {
+        Ok(()) => err,
+        Err(e) => e,
+    }
+}
+
+impl From<SimConfigErrors> for CoreError {
+    fn from(err: SimConfigErrors) -> Self {
+        let violations = err
+            .violations()
+            .iter()
+            .map(|v| (v.field.clone(), v.value.clone(), v.error.to_string()))
+            .collect();
+        let mut mapped = CoreError::new(ErrorKind::Config, err.to_string());
+        mapped
+            .attrs
+            .push(("violations", Attr::Violations(violations)));
+        mapped
+    }
+}
+
+impl From<CoreWorldInitError> for CoreError {
+    fn from(err: CoreWorldInitError) -> Self {
+        let base = CoreError::new(ErrorKind::WorldInit, err.to_string());
+        match err {
+            CoreWorldInitError::Config(errors) => errors.into(),
+            CoreWorldInitError::AgentCountOverflow | CoreWorldInitError::InvalidOrganismId => base,
+            CoreWorldInitError::TooManyAgents { max, actual }
+            | CoreWorldInitError::TooManyOrganisms { max, actual } => base.limit(max, actual),
+            CoreWorldInitError::NumOrganismsMismatch { expected, actual }
+            | CoreWorldInitError::AgentCountMismatch { expected, actual }
+            | CoreWorldInitError::MetabolicGenomeSizeMismatch { expected, actual }
+            | CoreWorldInitError::AgentRoleCountMismatch { expected, actual } => {
+                base.counts(expected, actual)
+            }
+            CoreWorldInitError::MaturityGatingGenesMismatch { .. } => base,
+            CoreWorldInitError::AgentOutsideWorld {
+                agent_id,
+                world_size,
+            } => base
+                .int("agent_id", agent_id)
+                .float("world_size", world_size),
+            CoreWorldInitError::ControllerWeightCount { organism, actual } => {
+                base.int("organism", organism).int("actual", actual)
+            }
+            CoreWorldInitError::EstimatedMemoryExceedsLimit { bytes, limit } => {
+                base.int("bytes", bytes).int("limit", limit)
+            }
+        }
+    }
+}
+
+impl From<CoreExperimentError> for CoreError {
+    fn from(err: CoreExperimentError) -> Self {
+        let message = err.to_string();
+        let base = CoreError::new(ErrorKind::Experiment, message.clone());
+        match err {
+            CoreExperimentError::InvalidSampleEvery
+            | CoreExperimentError::InvalidSnapshotEvery
+            | CoreExperimentError::InvalidSnapshotSchedule
+            | CoreExperimentError::InvalidActivationEvery
+            | CoreExperimentError::InvalidTrajectoryRecorder => base,
+            CoreExperimentError::TooManySteps { max, actual }
+            | CoreExperimentError::TooManySamples { max, actual }
+            | CoreExperimentError::TooManySnapshots { max, actual } => base.limit(max, actual),
+            CoreExperimentError::SnapshotWrite(_)
+            | CoreExperimentError::LineageWrite(_)
+            | CoreExperimentError::TrajectoryWrite(_) => {
+                CoreError::new(ErrorKind::Checkpoint, message)
+            }
+        }
+    }
+}
+
+impl From<InterventionError> for CoreError {
+    fn from(err: InterventionError) -> Self {
+        let base = CoreError::new(ErrorKind::Experiment, err.to_string());
+        match err {
+            InterventionError::InvalidResourceFactor(value)
+            | InterventionError::InvalidResourceRate(value)
+            | InterventionError::InvalidEnergyAmount(value)
+            | InterventionError::InvalidEnergyValue(value)
+            | InterventionError::InvalidBoundaryDamage(value)
+            | InterventionError::InvalidShockAmount(value)
+            | InterventionError::InvalidPulseAmount(value) => base.float("value", value as f64),
+            InterventionError::InvalidPulseRadius(value) => base.float("value", value),
+            InterventionError::InvalidPulseCenter(_) => base,
+            InterventionError::UnknownOrganism(stable_id) => base.int("stable_id", stable_id),
+        }
+    }
+}
+
+impl From<AddOrganismError> for CoreError {
+    fn from(err: AddOrganismError) -> Self {
+        let base = CoreError::new(ErrorKind::Experiment, err.to_string());
+        match err {
+            AddOrganismError::NoAgents
+            | AddOrganismError::InvalidCenter(_)
+            | AddOrganismError::AgentIdsExhausted
+            | AddOrganismError::OrganismIdsExhausted => base,
+            AddOrganismError::InvalidNnWeightCount(actual) => base.int("actual", actual),
+            AddOrganismError::MetabolicGenomeSizeMismatch { expected, actual }
+            | AddOrganismError::AgentRoleCountMismatch { expected, actual } => {
+                base.counts(expected, actual)
+            }
+            AddOrganismError::MaturityGatingGenesMismatch { .. } => base,
+            AddOrganismError::TooManyAgents { max, actual } => base.limit(max, actual),
+        }
+    }
+}
+
+impl From<ResourceGridError> for CoreError {
+    fn from(err: ResourceGridError) -> Self {
+        let base = CoreError::new(ErrorKind::Experiment, err.to_string());
+        match err {
+            ResourceGridError::LengthMismatch { expected, actual } => base.counts(expected, actual),
+            ResourceGridError::InvalidValue { row, col, value } => base
+                .int("row", row)
+                .int("col", col)
+                .float("value", value as f64),
+        }
+    }
+}
+
+impl From<SweepError> for CoreError {
+    fn from(err: SweepError) -> Self {
+        let message = err.to_string();
+        match err {
+            SweepError::NoSeeds
+            | SweepError::SeedAxis
+            | SweepError::UnknownField(_)
+            | SweepError::DuplicateField(_)
+            | SweepError::EmptyAxis(_)
+            | SweepError::InvalidValue { .. } => CoreError::new(ErrorKind::Config, message),
+            SweepError::InvalidConfig { source, .. } => CoreError::from(source).reworded(message),
+            SweepError::World { source, .. } => CoreError::from(source).reworded(message),
+            SweepError::Experiment { source, .. } => CoreError::from(source).reworded(message),
+        }
+    }
+}
+
+impl From<AblationError> for CoreError {
+    fn from(err: AblationError) -> Self {
+        let message = err.to_string();
+        match err {
+            AblationError::NoSeeds => CoreError::new(ErrorKind::Config, message),
+            AblationError::World { source, .. } => CoreError::from(source).reworded(message),
+            AblationError::Experiment { source, .. } => CoreError::from(source).reworded(message),
+        }
+    }
+}
+
+impl From<LineageCycleError> for CoreError {
+    fn from(err: LineageCycleError) -> Self {
+        CoreError::new(ErrorKind::Base, err.to_string()).int("stable_id", err.stable_id)
+    }
+}
+
+impl From<SchemaError> for CoreError {
+    fn from(err: SchemaError) -> Self {
+        match err {
+            SchemaError::NotAnObject
+            | SchemaError::InvalidVersion(_)
+            | SchemaError::UnsupportedVersion { .. }
+            | SchemaError::Deserialize { .. } => CoreError::new(ErrorKind::Base, err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use digital_life_core::config::SimConfig;
+
+    #[test]
+    fn config_violations_map_to_config_error_with_fields() {
+        let config = SimConfig {
+            world_size: -1.0,
+            ..SimConfig::default()
+        };
+        let err = CoreError::from(config.validate().unwrap_err());
+        assert_eq!(err.kind(), ErrorKind::Config);
+        assert!(err.to_string().starts_with("world_size"), "{err}");
+        let [(name, Attr::Violations(violations))] = err.attrs.as_slice() else {
+            panic!("expected violations, got {:?}", err.attrs);
+        };
+        assert_eq!(*name, "violations");
+        assert_eq!(violations[0].0.as_deref(), Some("world_size"));
+
+        let wrapped = CoreError::from(CoreWorldInitError::Config(config.validate().unwrap_err()));
+        assert_eq!(wrapped.kind(), ErrorKind::Config);
+    }
+
+    #[test]
+    fn count_mismatches_keep_expected_and_actual() {
+        let err = CoreError::from(CoreWorldInitError::AgentCountMismatch {
+            expected: 10,
+            actual: 7,
+        });
+        assert_eq!(err.kind(), ErrorKind::WorldInit);
+        assert_eq!(
+            err.attrs,
+            vec![("expected", Attr::Int(10)), ("actual", Attr::Int(7))]
+        );
+        assert_eq!(
+            err.to_string(),
+            CoreWorldInitError::AgentCountMismatch {
+                expected: 10,
+                actual: 7
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn stream_failures_are_checkpoint_errors() {
+        let err = CoreError::from(CoreExperimentError::SnapshotWrite("disk full".into()));
+        assert_eq!(err.kind(), ErrorKind::Checkpoint);
+        let err = CoreError::from(CoreExperimentError::InvalidSampleEvery);
+        assert_eq!(err.kind(), ErrorKind::Experiment);
+        let err = CoreError::from(InterventionError::UnknownOrganism(4));
+        assert_eq!(err.attrs, vec![("stable_id", Attr::Int(4))]);
+    }
+
+    #[test]
+    fn wrapped_sweep_errors_keep_source_class_and_outer_message() {
+        let err = CoreError::from(SweepError::World {
+            params: Default::default(),
+            seed: 3,
+            source: CoreWorldInitError::AgentCountOverflow,
+        });
+        assert_eq!(err.kind(), ErrorKind::WorldInit);
+        assert!(err.to_string().contains("seed 3"), "{err}");
+    }
+}
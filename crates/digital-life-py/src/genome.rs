@@ -0,0 +1,279 @@
+//! `Genome` and `MutationRates` exposed to Python for studying mutation and
+//! crossover operators outside the simulation.
+//!
+//! Mutation and crossover always take an explicit seed, so the same genomes, seed
+//! and rates give the same result in Python as in Rust.
+
+use digital_life_core::config::SimConfig;
+use digital_life_core::genome::{CrossoverError, Genome, GenomeLayout, MutationRates};
+use digital_life_core::rng::create_rng;
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Mutation operator settings. Omitted keyword arguments take the simulation
+/// defaults.
+#[pyclass(name = "MutationRates", module = "digital_life._core", from_py_object)]
+#[derive(Clone)]
+pub struct PyMutationRates {
+    inner: MutationRates,
+}
+
+fn validate_rates(rates: &MutationRates) -> Result<(), String> {
+    let probabilities = [
+        ("point_rate", rates.point_rate),
+        ("reset_rate", rates.reset_rate),
+        ("scale_rate", rates.scale_rate),
+    ];
+    for (name, rate) in probabilities {
+        if !(rate.is_finite() && (0.0..=1.0).contains(&rate)) {
+            return Err(format!("{name} must be finite and within [0,1]"));
+        }
+    }
+    if rates.point_rate + rates.reset_rate + rates.scale_rate > 1.0 {
+        return Err("point_rate + reset_rate + scale_rate must be <= 1".to_string());
+    }
+    if !(rates.point_scale.is_finite() && rates.point_scale >= 0.0) {
+        return Err("point_scale must be finite and non-negative".to_string());
+    }
+    if !(rates.scale_min.is_finite()
+        && rates.scale_max.is_finite()
+        && rates.scale_min <= rates.scale_max)
+    {
+        return Err(
+            "scale_min and scale_max must be finite with scale_min <= scale_max".to_string(),
+        );
+    }
+    if !(rates.value_limit.is_finite() && rates.value_limit > 0.0) {
+        return Err("value_limit must be finite and positive".to_string());
+    }
+    Ok(())
+}
+
+#[pymethods]
+impl PyMutationRates {
+    #[new]
+    #[pyo3(signature = (
+        *,
+        point_rate=None,
+        point_scale=None,
+        reset_rate=None,
+        scale_rate=None,
+        scale_min=None,
+        scale_max=None,
+        value_limit=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        point_rate: Option<f32>,
+        point_scale: Option<f32>,
+        reset_rate: Option<f32>,
+        scale_rate: Option<f32>,
+        scale_min: Option<f32>,
+        scale_max: Option<f32>,
+        value_limit: Option<f32>,
+    ) -> PyResult<Self> {
+        let defaults = MutationRates::default();
+        let inner = MutationRates {
+            point_rate: point_rate.unwrap_or(defaults.point_rate),
+            point_scale: point_scale.unwrap_or(defaults.point_scale),
+            reset_rate: reset_rate.unwrap_or(defaults.reset_rate),
+            scale_rate: scale_rate.unwrap_or(defaults.scale_rate),
+            scale_min: scale_min.unwrap_or(defaults.scale_min),
+            scale_max: scale_max.unwrap_or(defaults.scale_max),
+            value_limit: value_limit.unwrap_or(defaults.value_limit),
+            ..defaults
+        };
+        validate_rates(&inner).map_err(PyValueError::new_err)?;
+        Ok(Self { inner })
+    }
+
+    #[getter]
+    fn point_rate(&self) -> f32 {
+        self.inner.point_rate
+    }
+
+    #[getter]
+    fn point_scale(&self) -> f32 {
+        self.inner.point_scale
+    }
+
+    #[getter]
+    fn reset_rate(&self) -> f32 {
+        self.inner.reset_rate
+    }
+
+    #[getter]
+    fn scale_rate(&self) -> f32 {
+        self.inner.scale_rate
+    }
+
+    #[getter]
+    fn scale_min(&self) -> f32 {
+        self.inner.scale_min
+    }
+
+    #[getter]
+    fn scale_max(&self) -> f32 {
+        self.inner.scale_max
+    }
+
+    #[getter]
+    fn value_limit(&self) -> f32 {
+        self.inner.value_limit
+    }
+
+    fn __repr__(&self) -> String {
+        let r = &self.inner;
+        format!(
+            "MutationRates(point_rate={}, point_scale={}, reset_rate={}, scale_rate={}, \
+             scale_min={}, scale_max={}, value_limit={})",
+            r.point_rate,
+            r.point_scale,
+            r.reset_rate,
+            r.scale_rate,
+            r.scale_min,
+            r.scale_max,
+            r.value_limit
+        )
+    }
+}
+
+/// `rates` may be a `MutationRates`, a dict of its keyword arguments, or `None`
+/// for the defaults.
+fn rates_from_py(rates: Option<&Bound<'_, PyAny>>) -> PyResult<MutationRates> {
+    let Some(rates) = rates else {
+        return Ok(MutationRates::default());
+    };
+    if let Ok(rates) = rates.extract::<PyMutationRates>() {
+        return Ok(rates.inner);
+    }
+    let kwargs = rates
+        .cast::<PyDict>()
+        .map_err(|_| PyValueError::new_err("rates must be a MutationRates or a dict"))?;
+    let rates = rates
+        .py()
+        .get_type::<PyMutationRates>()
+        .call((), Some(kwargs))?;
+    Ok(rates.extract::<PyMutationRates>()?.inner)
+}
+
+/// Layout with the given metabolic segment size, within the range `SimConfig`
+/// accepts for `metabolic_genome_size`.
+fn layout_from_py(metabolic_size: usize) -> PyResult<GenomeLayout> {
+    let range = SimConfig::MIN_METABOLIC_GENOME_SIZE..=SimConfig::MAX_METABOLIC_GENOME_SIZE;
+    if !range.contains(&metabolic_size) {
+        return Err(PyValueError::new_err(format!(
+            "metabolic_size ({metabolic_size}) must be between {} and {}",
+            range.start(),
+            range.end()
+        )));
+    }
+    Ok(GenomeLayout {
+        metabolic_size,
+        ..GenomeLayout::default()
+    })
+}
+
+/// Wrap a crossover child; parents with different layouts raise `ValueError`.
+fn crossed(child: Result<Genome, CrossoverError>) -> PyResult<PyGenome> {
+    child
+        .map(|inner| PyGenome { inner })
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Genome of seven segments: NN weights followed by the regulatory segments,
+/// indexed 0..=6 as in the simulation. The metabolic segment (1) has
+/// `metabolic_size` genes, matching `SimConfig.metabolic_genome_size`.
+#[pyclass(name = "Genome", module = "digital_life._core")]
+pub struct PyGenome {
+    inner: Genome,
+}
+
+#[pymethods]
+impl PyGenome {
+    /// Genome with the given NN weights and zeroed regulatory segments.
+    #[new]
+    #[pyo3(signature = (nn_weights, metabolic_size=Genome::METABOLIC_SIZE))]
+    fn new(nn_weights: Vec<f32>, metabolic_size: usize) -> PyResult<Self> {
+        Ok(Self {
+            inner: Genome::with_layout(nn_weights, layout_from_py(metabolic_size)?),
+        })
+    }
+
+    /// Rebuild a genome from the full flat data returned by `data()` or
+    /// `World.organism_genome`.
+    #[staticmethod]
+    #[pyo3(signature = (data, metabolic_size=Genome::METABOLIC_SIZE))]
+    fn from_data(data: Vec<f32>, metabolic_size: usize) -> PyResult<Self> {
+        Genome::from_data_with_layout(data, layout_from_py(metabolic_size)?)
+            .map(|inner| Self { inner })
+            .ok_or_else(|| PyValueError::new_err("genome data is shorter than its fixed segments"))
+    }
+
+    #[getter]
+    fn metabolic_size(&self) -> usize {
+        self.inner.layout().metabolic_size
+    }
+
+    /// Every gene, segments concatenated in order.
+    fn data(&self) -> Vec<f32> {
+        self.inner.data().to_vec()
+    }
+
+    /// Genes of segment `index` (0 = NN weights, ..., 6 = evolution).
+    fn segment(&self, index: usize) -> PyResult<Vec<f32>> {
+        if index >= self.inner.segments().len() {
+            return Err(PyIndexError::new_err(format!(
+                "segment index {index} out of range 0..=6"
+            )));
+        }
+        Ok(self.inner.segment_data(index).to_vec())
+    }
+
+    /// Apply one round of mutation in place, drawing from a stream seeded by `seed`.
+    #[pyo3(signature = (seed, rates=None))]
+    fn mutate(&mut self, seed: u64, rates: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
+        let rates = rates_from_py(rates)?;
+        self.inner.mutate(&mut create_rng(seed), &rates);
+        Ok(())
+    }
+
+    /// Child taking each gene from this genome or `other` with equal probability,
+    /// drawing from a stream seeded by `seed`.
+    fn crossover_uniform(&self, other: PyRef<'_, Self>, seed: u64) -> PyResult<Self> {
+        crossed(
+            self.inner
+                .crossover_uniform(&other.inner, &mut create_rng(seed)),
+        )
+    }
+
+    /// Child taking the genes before a random cut point from this genome and the
+    /// rest from `other`.
+    fn crossover_one_point(&self, other: PyRef<'_, Self>, seed: u64) -> PyResult<Self> {
+        crossed(
+            self.inner
+                .crossover_one_point(&other.inner, &mut create_rng(seed)),
+        )
+    }
+
+    /// Child taking each whole segment from this genome or `other`.
+    fn crossover_segmentwise(&self, other: PyRef<'_, Self>, seed: u64) -> PyResult<Self> {
+        crossed(
+            self.inner
+                .crossover_segmentwise(&other.inner, &mut create_rng(seed)),
+        )
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.data().len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Genome(len={}, nn_weights={})",
+            self.inner.data().len(),
+            self.inner.nn_weights().len()
+        )
+    }
+}
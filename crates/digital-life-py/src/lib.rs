@@ -1,13 +1,23 @@
+use digital_life_core::ablation::{run_ablation, AblationSpec};
 use digital_life_core::agent::Agent;
+use digital_life_core::analysis;
 use digital_life_core::config::SimConfig;
+use digital_life_core::lineage::LineageTree;
+use digital_life_core::metrics::{LineageEvent, RunSummary};
 use digital_life_core::nn::NeuralNet;
-use digital_life_core::world::World;
+use digital_life_core::sweep::{run_sweep, SweepSpec};
+use digital_life_core::world::{ExperimentObserver, World};
+use errors::{CoreError, ErrorKind};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use rand::Rng;
-use rand::SeedableRng;
-use rand_chacha::ChaCha12Rng;
+use pyo3::types::PyBool;
 use serde_json::json;
+use std::ops::ControlFlow;
+
+mod errors;
+mod genome;
+mod sim_config;
+mod world;
 
 /// Minimal PyO3 module exposing digital-life-core to Python.
 #[pyfunction]
@@ -25,20 +35,33 @@ fn default_config_json() -> PyResult<String> {
 fn validate_config_json(config_json: &str) -> PyResult<bool> {
     world_from_config_json(config_json)
         .map(|_| true)
-        .map_err(PyValueError::new_err)
+        .map_err(PyErr::from)
 }
 
 #[pyfunction]
 fn step_once(
+    py: Python<'_>,
     num_organisms: usize,
     agents_per_organism: usize,
     world_size: f64,
 ) -> PyResult<(usize, u64)> {
+    py.detach(|| step_once_impl(num_organisms, agents_per_organism, world_size))
+        .map_err(PyErr::from)
+}
+
+fn step_once_impl(
+    num_organisms: usize,
+    agents_per_organism: usize,
+    world_size: f64,
+) -> Result<(usize, u64), CoreError> {
     if world_size > World::MAX_WORLD_SIZE {
-        return Err(PyValueError::new_err(format!(
-            "world_size ({world_size}) exceeds supported maximum ({})",
-            World::MAX_WORLD_SIZE
-        )));
+        return Err(CoreError::new(
+            ErrorKind::Config,
+            format!(
+                "world_size ({world_size}) exceeds supported maximum ({})",
+                World::MAX_WORLD_SIZE
+            ),
+        ));
     }
 
     let config = SimConfig {
@@ -53,57 +76,117 @@ fn step_once(
         world_size,
         config.seed,
         config.sensing_radius,
-    )
-    .map_err(PyValueError::new_err)?;
+    )?;
     let mut world = World::new(agents, nns, config)
-        .map_err(|e| PyValueError::new_err(format!("invalid world configuration: {e}")))?;
+        .map_err(|e| CoreError::from(e).context("invalid world configuration"))?;
     let timings = world.step();
     Ok((world.agents().len(), timings.total_us))
 }
 
+/// The simulation runs with the GIL released; only Rust-owned values cross the
+/// boundary, and the JSON result is handed back after the GIL is reacquired.
+///
+/// Every `callback_every` steps the GIL is briefly reacquired to check for
+/// KeyboardInterrupt and to call `callback(step, alive_count)` if given. A
+/// callback returning `False` stops the run early and returns the partial summary
+/// with `cancelled` set. An interrupt or an exception raised by the callback stops
+/// the run and propagates; a KeyboardInterrupt carries the partial summary JSON as
+/// its `partial_summary` attribute.
 #[pyfunction]
-fn run_experiment_json(config_json: &str, steps: usize, sample_every: usize) -> PyResult<String> {
-    run_experiment_json_impl(config_json, steps, sample_every).map_err(PyValueError::new_err)
+#[pyo3(signature = (config_json, steps, sample_every, callback=None, callback_every=1000))]
+fn run_experiment_json(
+    py: Python<'_>,
+    config_json: &str,
+    steps: usize,
+    sample_every: usize,
+    callback: Option<Py<PyAny>>,
+    callback_every: usize,
+) -> PyResult<String> {
+    if callback_every == 0 {
+        return Err(errors::ExperimentError::new_err(
+            "callback_every must be positive",
+        ));
+    }
+    let mut observer = PyProgressObserver {
+        callback,
+        every: callback_every,
+        error: None,
+    };
+    let result =
+        py.detach(|| run_experiment_json_impl(config_json, steps, sample_every, &mut observer));
+    if let Some(err) = observer.error {
+        return Err(errors::observed_run_error(py, err, result.ok()));
+    }
+    result.map_err(PyErr::from)
+}
+
+/// Bridges `ExperimentObserver` to an optional Python progress callback.
+struct PyProgressObserver {
+    callback: Option<Py<PyAny>>,
+    every: usize,
+    error: Option<PyErr>,
+}
+
+impl ExperimentObserver for PyProgressObserver {
+    fn on_step(&mut self, step: usize, world: &mut World) -> ControlFlow<()> {
+        if !step.is_multiple_of(self.every) {
+            return ControlFlow::Continue(());
+        }
+        Python::attach(|py| {
+            let outcome = py.check_signals().and_then(|()| match &self.callback {
+                Some(callback) => callback
+                    .call1(py, (step, world.alive_count()))
+                    .map(|ret| ret.bind(py).is(PyBool::new(py, false))),
+                None => Ok(false),
+            });
+            match outcome {
+                Ok(false) => ControlFlow::Continue(()),
+                Ok(true) => ControlFlow::Break(()),
+                Err(err) => {
+                    self.error = Some(err);
+                    ControlFlow::Break(())
+                }
+            }
+        })
+    }
 }
 
 #[pyfunction]
 fn run_evolution_experiment_json(
+    py: Python<'_>,
     config_json: &str,
     steps: usize,
     sample_every: usize,
 ) -> PyResult<String> {
-    run_evolution_experiment_json_impl(config_json, steps, sample_every)
-        .map_err(PyValueError::new_err)
+    py.detach(|| run_evolution_experiment_json_impl(config_json, steps, sample_every))
+        .map_err(PyErr::from)
 }
 
 fn run_experiment_json_impl(
     config_json: &str,
     steps: usize,
     sample_every: usize,
-) -> Result<String, String> {
-    if steps > World::MAX_EXPERIMENT_STEPS {
-        return Err(format!(
-            "steps ({steps}) exceeds supported maximum ({})",
-            World::MAX_EXPERIMENT_STEPS
-        ));
-    }
+    observer: &mut dyn ExperimentObserver,
+) -> Result<String, CoreError> {
     let mut world = world_from_config_json(config_json)?;
     let summary = world
-        .try_run_experiment(steps, sample_every)
-        .map_err(|e| format!("invalid experiment parameters: {e}"))?;
-    serde_json::to_string(&summary)
-        .map_err(|e| format!("failed to serialize experiment summary: {e}"))
+        .try_run_experiment_with_observer(steps, sample_every, observer)
+        .map_err(experiment_error)?;
+    serde_json::to_string(&summary).map_err(|e| serialize_error("experiment summary", e))
 }
 
 #[pyfunction]
 fn run_niche_experiment_json(
+    py: Python<'_>,
     config_json: &str,
     steps: usize,
     sample_every: usize,
     snapshot_steps_json: &str,
 ) -> PyResult<String> {
-    run_niche_experiment_json_impl(config_json, steps, sample_every, snapshot_steps_json)
-        .map_err(PyValueError::new_err)
+    py.detach(|| {
+        run_niche_experiment_json_impl(config_json, steps, sample_every, snapshot_steps_json)
+    })
+    .map_err(PyErr::from)
 }
 
 fn run_niche_experiment_json_impl(
@@ -111,45 +194,204 @@ fn run_niche_experiment_json_impl(
     steps: usize,
     sample_every: usize,
     snapshot_steps_json: &str,
-) -> Result<String, String> {
+) -> Result<String, CoreError> {
     // Pre-check for maximum items to avoid allocating a massive vector
     // A valid JSON array of N items has at least N-1 commas.
     // If commas >= MAX, we definitely have >= MAX+1 items (or invalid JSON).
     if snapshot_steps_json.bytes().filter(|&b| b == b',').count() >= World::MAX_EXPERIMENT_SNAPSHOTS
     {
-        return Err(format!(
-            "snapshot_steps json complexity exceeds supported maximum ({})",
-            World::MAX_EXPERIMENT_SNAPSHOTS
+        return Err(CoreError::new(
+            ErrorKind::Experiment,
+            format!(
+                "snapshot_steps json complexity exceeds supported maximum ({})",
+                World::MAX_EXPERIMENT_SNAPSHOTS
+            ),
         ));
     }
 
-    let snapshot_steps: Vec<usize> = serde_json::from_str(snapshot_steps_json)
-        .map_err(|e| format!("invalid snapshot_steps json: {e}"))?;
+    let snapshot_steps: Vec<usize> =
+        serde_json::from_str(snapshot_steps_json).map_err(|e| invalid_json("snapshot_steps", e))?;
     // Post-check for exact count
     if snapshot_steps.len() > World::MAX_EXPERIMENT_SNAPSHOTS {
-        return Err(format!(
-            "snapshot_steps count ({}) exceeds supported maximum ({})",
-            snapshot_steps.len(),
-            World::MAX_EXPERIMENT_SNAPSHOTS
-        ));
+        return Err(CoreError::new(
+            ErrorKind::Experiment,
+            format!(
+                "snapshot_steps count ({}) exceeds supported maximum ({})",
+                snapshot_steps.len(),
+                World::MAX_EXPERIMENT_SNAPSHOTS
+            ),
+        )
+        .limit(World::MAX_EXPERIMENT_SNAPSHOTS, snapshot_steps.len()));
     }
     let mut world = world_from_config_json(config_json)?;
     let summary = world
         .try_run_experiment_with_snapshots(steps, sample_every, &snapshot_steps)
-        .map_err(|e| format!("invalid experiment parameters: {e}"))?;
-    serde_json::to_string(&summary)
-        .map_err(|e| format!("failed to serialize experiment summary: {e}"))
+        .map_err(experiment_error)?;
+    serde_json::to_string(&summary).map_err(|e| serialize_error("experiment summary", e))
+}
+
+/// Runs the baseline plus one knockout per listed criterion for every seed; see
+/// `digital_life_core::ablation::AblationSpec` for the spec layout.
+#[pyfunction]
+fn run_ablation_json(py: Python<'_>, spec_json: &str) -> PyResult<String> {
+    py.detach(|| run_ablation_json_impl(spec_json))
+        .map_err(PyErr::from)
+}
+
+fn run_ablation_json_impl(spec_json: &str) -> Result<String, CoreError> {
+    let spec: AblationSpec = serde_json::from_str(spec_json).map_err(|e| {
+        CoreError::new(
+            ErrorKind::Config,
+            format!("invalid ablation spec json: {e}"),
+        )
+    })?;
+    let results = run_ablation(spec)?;
+    results
+        .to_json()
+        .map_err(|e| serialize_error("ablation results", e))
+}
+
+/// Runs every combination of the spec's swept field values for every seed; see
+/// `digital_life_core::sweep::SweepSpec` for the spec layout. Unknown or mistyped
+/// fields are rejected before any cell runs.
+#[pyfunction]
+fn run_sweep_json(
+    py: Python<'_>,
+    spec_json: &str,
+    steps: usize,
+    sample_every: usize,
+) -> PyResult<String> {
+    py.detach(|| run_sweep_json_impl(spec_json, steps, sample_every))
+        .map_err(PyErr::from)
+}
+
+fn run_sweep_json_impl(
+    spec_json: &str,
+    steps: usize,
+    sample_every: usize,
+) -> Result<String, CoreError> {
+    let spec: SweepSpec = serde_json::from_str(spec_json)
+        .map_err(|e| CoreError::new(ErrorKind::Config, format!("invalid sweep spec json: {e}")))?;
+    let results = run_sweep(spec, steps, sample_every)?;
+    results
+        .to_json()
+        .map_err(|e| serialize_error("sweep results", e))
+}
+
+#[pyfunction]
+fn lineage_to_newick(lineage_events_json: &str, founder_ids_json: &str) -> PyResult<String> {
+    lineage_tree_from_json(lineage_events_json, founder_ids_json)
+        .and_then(|tree| tree.to_newick().map_err(CoreError::from))
+        .map_err(PyErr::from)
+}
+
+#[pyfunction]
+fn lineage_to_dot(lineage_events_json: &str, founder_ids_json: &str) -> PyResult<String> {
+    lineage_tree_from_json(lineage_events_json, founder_ids_json)
+        .map(|tree| tree.to_dot())
+        .map_err(PyErr::from)
+}
+
+fn lineage_tree_from_json(
+    lineage_events_json: &str,
+    founder_ids_json: &str,
+) -> Result<LineageTree, CoreError> {
+    let events: Vec<LineageEvent> =
+        serde_json::from_str(lineage_events_json).map_err(|e| invalid_json("lineage_events", e))?;
+    let founder_ids: Vec<u64> =
+        serde_json::from_str(founder_ids_json).map_err(|e| invalid_json("founder_ids", e))?;
+    Ok(LineageTree::from_events(&events, &founder_ids))
+}
+
+/// Founder stable ids that the organisms in `final_alive_ids_json` descend from,
+/// traced through the summary's lineage events.
+#[pyfunction]
+fn surviving_founder_lineages(
+    summary_json: &str,
+    final_alive_ids_json: &str,
+) -> PyResult<Vec<u64>> {
+    let summary = summary_from_json(summary_json)?;
+    let alive_ids: Vec<u64> = serde_json::from_str(final_alive_ids_json)
+        .map_err(|e| invalid_json("final_alive_ids", e))?;
+    Ok(analysis::surviving_founder_lineages(&summary, &alive_ids))
+}
+
+/// Running effective population size at each of the summary's samples.
+#[pyfunction]
+fn effective_population_size_series(summary_json: &str) -> PyResult<Vec<f64>> {
+    summary_from_json(summary_json)
+        .map(|summary| analysis::effective_population_size_series(&summary))
+        .map_err(PyErr::from)
+}
+
+/// `(alive_count, step)` of the smallest sampled population, or `None`.
+#[pyfunction]
+fn min_population_and_step(summary_json: &str) -> PyResult<Option<(usize, usize)>> {
+    summary_from_json(summary_json)
+        .map(|summary| analysis::min_population_and_step(&summary))
+        .map_err(PyErr::from)
+}
+
+/// Compares two run summaries (`b - a`) metric by metric over their shared steps;
+/// see `digital_life_core::analysis::ComparisonReport` for the report layout.
+#[pyfunction]
+fn compare_runs_json(a_json: &str, b_json: &str, tail_fraction: f64) -> PyResult<String> {
+    let (a, b) = (summary_from_json(a_json)?, summary_from_json(b_json)?);
+    serde_json::to_string(&analysis::compare(&a, &b, tail_fraction))
+        .map_err(|e| serialize_error("comparison report", e).into())
+}
+
+/// Compares two groups of replicate run summaries, adding Cohen's d per metric
+/// and a Mann-Whitney U test over final alive counts.
+#[pyfunction]
+fn compare_replicates_json(
+    a_jsons: Vec<String>,
+    b_jsons: Vec<String>,
+    tail_fraction: f64,
+) -> PyResult<String> {
+    let parse = |jsons: &[String]| {
+        jsons
+            .iter()
+            .map(|json| summary_from_json(json))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let (a, b) = (parse(&a_jsons)?, parse(&b_jsons)?);
+    serde_json::to_string(&analysis::compare_replicates(&a, &b, tail_fraction))
+        .map_err(|e| serialize_error("comparison report", e).into())
+}
+
+fn summary_from_json(summary_json: &str) -> Result<RunSummary, CoreError> {
+    let value: serde_json::Value =
+        serde_json::from_str(summary_json).map_err(|e| invalid_json("summary", e))?;
+    RunSummary::from_json_value(value)
+        .map_err(|e| CoreError::from(e).context("invalid summary json"))
+}
+
+/// Malformed JSON argument `name`.
+fn invalid_json(name: &str, err: serde_json::Error) -> CoreError {
+    CoreError::new(ErrorKind::Base, format!("invalid {name} json: {err}"))
+}
+
+fn serialize_error(what: &str, err: serde_json::Error) -> CoreError {
+    CoreError::new(
+        ErrorKind::Base,
+        format!("failed to serialize {what}: {err}"),
+    )
+}
+
+fn experiment_error(err: digital_life_core::world::ExperimentError) -> CoreError {
+    CoreError::from(err).context("invalid experiment parameters")
 }
 
 fn run_evolution_experiment_json_impl(
     config_json: &str,
     steps: usize,
     sample_every: usize,
-) -> Result<String, String> {
+) -> Result<String, CoreError> {
     let mut world = world_from_config_json(config_json)?;
     let summary = world
         .try_run_experiment(steps, sample_every)
-        .map_err(|e| format!("invalid experiment parameters: {e}"))?;
+        .map_err(experiment_error)?;
     let stats = world.population_stats();
     let config = world.config();
     let payload = json!({
@@ -175,22 +417,14 @@ fn run_evolution_experiment_json_impl(
             "mutation_value_limit": config.mutation_value_limit,
         }
     });
-    serde_json::to_string(&payload)
-        .map_err(|e| format!("failed to serialize evolution experiment summary: {e}"))
+    serde_json::to_string(&payload).map_err(|e| serialize_error("evolution experiment summary", e))
 }
 
-fn world_from_config_json(config_json: &str) -> Result<World, String> {
-    let config: SimConfig =
-        serde_json::from_str(config_json).map_err(|e| format!("invalid config json: {e}"))?;
-    let (agents, nns) = bootstrap_entities(
-        config.num_organisms,
-        config.agents_per_organism,
-        config.world_size,
-        config.seed,
-        config.sensing_radius,
-    )
-    .map_err(|e| format!("invalid world configuration: {e}"))?;
-    World::new(agents, nns, config).map_err(|e| format!("invalid world configuration: {e}"))
+fn world_from_config_json(config_json: &str) -> Result<World, CoreError> {
+    let config: SimConfig = serde_json::from_str(config_json)
+        .map_err(|e| CoreError::new(ErrorKind::Config, format!("invalid config json: {e}")))?;
+    World::from_config(config)
+        .map_err(|e| CoreError::from(e).context("invalid world configuration"))
 }
 
 fn bootstrap_entities(
@@ -199,62 +433,71 @@ fn bootstrap_entities(
     world_size: f64,
     seed: u64,
     sensing_radius: f64,
-) -> Result<(Vec<Agent>, Vec<NeuralNet>), String> {
-    let total_agents = checked_total_agents(num_organisms, agents_per_organism)?;
-    let mut rng = ChaCha12Rng::seed_from_u64(seed);
-    let cluster_radius = sensing_radius.min(world_size / 4.0);
-
-    let mut agents = Vec::with_capacity(total_agents);
-    for org in 0..num_organisms {
-        let cx: f64 = rng.random_range(0.0..world_size);
-        let cy: f64 = rng.random_range(0.0..world_size);
-        for a in 0..agents_per_organism {
-            let global_id = org * agents_per_organism + a;
-            let (dx, dy) = if cluster_radius > f64::EPSILON {
-                (
-                    rng.random_range(-cluster_radius..cluster_radius),
-                    rng.random_range(-cluster_radius..cluster_radius),
-                )
-            } else {
-                (0.0, 0.0)
-            };
-            let px = (cx + dx).rem_euclid(world_size);
-            let py = (cy + dy).rem_euclid(world_size);
-            agents.push(Agent::new(global_id as u32, org as u16, [px, py]));
-        }
-    }
-
-    let nns = (0..num_organisms)
-        .map(|_| {
-            NeuralNet::from_weights(
-                (0..NeuralNet::WEIGHT_COUNT).map(|_| rng.random_range(-1.0f32..1.0)),
-            )
-        })
-        .collect();
-    Ok((agents, nns))
+) -> Result<(Vec<Agent>, Vec<NeuralNet>), CoreError> {
+    checked_total_agents(num_organisms, agents_per_organism)?;
+    let config = SimConfig {
+        num_organisms,
+        agents_per_organism,
+        world_size,
+        seed,
+        sensing_radius,
+        ..SimConfig::default()
+    };
+    Ok(World::bootstrap_entities(&config)?)
 }
 
-fn checked_total_agents(num_organisms: usize, agents_per_organism: usize) -> Result<usize, String> {
+fn checked_total_agents(
+    num_organisms: usize,
+    agents_per_organism: usize,
+) -> Result<usize, CoreError> {
     if num_organisms > u16::MAX as usize {
-        return Err(format!(
-            "num_organisms ({num_organisms}) exceeds maximum organism count ({})",
-            u16::MAX
-        ));
+        return Err(CoreError::new(
+            ErrorKind::Config,
+            format!(
+                "num_organisms ({num_organisms}) exceeds maximum organism count ({})",
+                u16::MAX
+            ),
+        )
+        .limit(u16::MAX as usize, num_organisms));
     }
     let total_agents = num_organisms
         .checked_mul(agents_per_organism)
-        .ok_or_else(|| "num_organisms * agents_per_organism overflows usize".to_string())?;
+        .ok_or_else(|| {
+            CoreError::new(
+                ErrorKind::Config,
+                "num_organisms * agents_per_organism overflows usize",
+            )
+        })?;
     if total_agents > SimConfig::MAX_TOTAL_AGENTS {
-        return Err(format!(
-            "total agents ({total_agents}) exceeds supported maximum ({})",
-            SimConfig::MAX_TOTAL_AGENTS
-        ));
+        return Err(CoreError::new(
+            ErrorKind::Config,
+            format!(
+                "total agents ({total_agents}) exceeds supported maximum ({})",
+                SimConfig::MAX_TOTAL_AGENTS
+            ),
+        )
+        .limit(SimConfig::MAX_TOTAL_AGENTS, total_agents));
     }
     Ok(total_agents)
 }
 
 #[pymodule]
 fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<sim_config::PySimConfig>()?;
+    m.add_class::<genome::PyGenome>()?;
+    m.add_class::<genome::PyMutationRates>()?;
+    m.add_class::<world::PyWorld>()?;
+    m.add_class::<world::PyWorldView>()?;
+    m.add_class::<world::StepIterator>()?;
+    let py = m.py();
+    m.add(
+        "DigitalLifeError",
+        py.get_type::<errors::DigitalLifeError>(),
+    )?;
+    m.add("ConfigError", py.get_type::<errors::ConfigError>())?;
+    m.add("WorldInitError", py.get_type::<errors::WorldInitError>())?;
+    m.add("ExperimentError", py.get_type::<errors::ExperimentError>())?;
+    m.add("CheckpointError", py.get_type::<errors::CheckpointError>())?;
     m.add_function(wrap_pyfunction!(version, m)?)?;
     m.add_function(wrap_pyfunction!(default_config_json, m)?)?;
     m.add_function(wrap_pyfunction!(validate_config_json, m)?)?;
@@ -262,12 +505,22 @@ fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(run_experiment_json, m)?)?;
     m.add_function(wrap_pyfunction!(run_evolution_experiment_json, m)?)?;
     m.add_function(wrap_pyfunction!(run_niche_experiment_json, m)?)?;
+    m.add_function(wrap_pyfunction!(run_ablation_json, m)?)?;
+    m.add_function(wrap_pyfunction!(run_sweep_json, m)?)?;
+    m.add_function(wrap_pyfunction!(lineage_to_newick, m)?)?;
+    m.add_function(wrap_pyfunction!(lineage_to_dot, m)?)?;
+    m.add_function(wrap_pyfunction!(surviving_founder_lineages, m)?)?;
+    m.add_function(wrap_pyfunction!(effective_population_size_series, m)?)?;
+    m.add_function(wrap_pyfunction!(min_population_and_step, m)?)?;
+    m.add_function(wrap_pyfunction!(compare_runs_json, m)?)?;
+    m.add_function(wrap_pyfunction!(compare_replicates_json, m)?)?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use digital_life_core::world::NoopObserver;
 
     #[test]
     fn checked_total_agents_rejects_overflow() {
@@ -318,7 +571,8 @@ mod tests {
             ..SimConfig::default()
         })
         .expect("config should serialize");
-        let output = run_experiment_json_impl(&config_json, 10, 5).expect("experiment should run");
+        let output = run_experiment_json_impl(&config_json, 10, 5, &mut NoopObserver)
+            .expect("experiment should run");
         let payload: serde_json::Value =
             serde_json::from_str(&output).expect("output should be valid json");
         assert_eq!(payload["steps"].as_u64(), Some(10));
@@ -329,7 +583,7 @@ mod tests {
     fn run_experiment_json_impl_rejects_zero_sampling_interval() {
         let config_json =
             serde_json::to_string(&SimConfig::default()).expect("config should serialize");
-        let result = run_experiment_json_impl(&config_json, 10, 0);
+        let result = run_experiment_json_impl(&config_json, 10, 0, &mut NoopObserver);
         assert!(result.is_err());
     }
 
@@ -341,7 +595,8 @@ mod tests {
             ..SimConfig::default()
         })
         .expect("config should serialize");
-        let output = run_experiment_json_impl(&config_json, 5, 1).expect("experiment should run");
+        let output = run_experiment_json_impl(&config_json, 5, 1, &mut NoopObserver)
+            .expect("experiment should run");
         let payload: serde_json::Value =
             serde_json::from_str(&output).expect("output should be valid json");
         let sample = &payload["samples"][0];
@@ -458,7 +713,7 @@ mod tests {
         let config_json = serde_json::to_string(&config).unwrap();
 
         // This should not panic
-        let result = run_experiment_json_impl(&config_json, 1, 1);
+        let result = run_experiment_json_impl(&config_json, 1, 1, &mut NoopObserver);
         assert!(result.is_ok());
     }
 
@@ -477,4 +732,93 @@ mod tests {
         let result = run_niche_experiment_json_impl(&config_json, 10, 5, &snapshot_steps_json);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn run_ablation_json_impl_returns_cells_per_condition_and_seed() {
+        let spec = json!({
+            "baseline": {"num_organisms": 2, "agents_per_organism": 3, "world_size": 30.0},
+            "criteria": ["metabolism", "boundary_maintenance"],
+            "seeds": [1, 2],
+            "steps": 5,
+            "sample_every": 5,
+        });
+        let output = run_ablation_json_impl(&spec.to_string()).expect("ablation should run");
+        let value: serde_json::Value = serde_json::from_str(&output).expect("valid json");
+        let cells = value["cells"].as_array().expect("cells array");
+        assert_eq!(cells.len(), 6);
+        assert!(cells[0]["criterion"].is_null());
+        assert_eq!(cells[2]["criterion"], "metabolism");
+        assert!(cells[0]["summary"]["final_alive_count"].is_u64());
+    }
+
+    #[test]
+    fn run_ablation_json_impl_rejects_unknown_criterion() {
+        let spec = r#"{"baseline": {}, "criteria": ["telepathy"], "seeds": [1], "steps": 1, "sample_every": 1}"#;
+        let err = run_ablation_json_impl(spec).unwrap_err();
+        assert!(
+            err.to_string().starts_with("invalid ablation spec json"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn run_sweep_json_impl_tags_cells_with_params() {
+        let spec = json!({
+            "baseline": {"num_organisms": 2, "agents_per_organism": 3, "world_size": 30.0},
+            "axes": [{"field": "boundary_repair_rate", "values": [0.01, 0.05]}],
+            "seeds": [1, 2],
+        });
+        let output = run_sweep_json_impl(&spec.to_string(), 5, 5).expect("sweep should run");
+        let value: serde_json::Value = serde_json::from_str(&output).expect("valid json");
+        let cells = value["cells"].as_array().expect("cells array");
+        assert_eq!(cells.len(), 4);
+        assert_eq!(cells[2]["params"]["boundary_repair_rate"], 0.05);
+        assert_eq!(cells[3]["seed"], 2);
+    }
+
+    #[test]
+    fn run_sweep_json_impl_names_unknown_field() {
+        let spec =
+            r#"{"baseline": {}, "axes": [{"field": "telepathy", "values": [1]}], "seeds": [1]}"#;
+        let err = run_sweep_json_impl(spec, 1, 1).unwrap_err();
+        assert!(err.to_string().contains("`telepathy`"), "{err}");
+    }
+
+    #[test]
+    fn lineage_tree_from_json_builds_forest() {
+        let events = r#"[{"step":5,"parent_stable_id":0,"child_stable_id":2,"generation":1}]"#;
+        let tree = lineage_tree_from_json(events, "[0, 1]").expect("lineage json should parse");
+        assert_eq!(tree.to_newick().unwrap(), "(2:5)0;\n1;\n");
+    }
+
+    #[test]
+    fn lineage_tree_from_json_rejects_invalid_payload() {
+        assert!(lineage_tree_from_json("not-json", "[]").is_err());
+        assert!(lineage_tree_from_json("[]", "{}").is_err());
+    }
+
+    #[test]
+    fn summary_from_json_migrates_and_rejects_garbage() {
+        let summary = summary_from_json(
+            r#"{"steps": 2, "sample_every": 1, "final_alive_count": 1, "samples": []}"#,
+        )
+        .expect("v1 summary should load");
+        assert_eq!(summary.migrated_from, Some(1));
+        assert!(summary_from_json("[]").is_err());
+        assert!(summary_from_json("not-json").is_err());
+    }
+
+    #[test]
+    fn step_once_impl_rejects_excessive_world_size() {
+        let result = step_once_impl(1, 1, World::MAX_WORLD_SIZE + 1.0);
+        let err = result.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Config);
+        assert!(err.to_string().contains("exceeds supported maximum"));
+    }
+
+    #[test]
+    fn step_once_impl_steps_bootstrapped_world() {
+        let (agent_count, _) = step_once_impl(2, 3, 20.0).expect("small world should step");
+        assert_eq!(agent_count, 6);
+    }
 }
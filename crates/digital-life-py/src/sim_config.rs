@@ -0,0 +1,214 @@
+//! `SimConfig` exposed to Python as a validated, keyword-constructible class.
+//!
+//! Fields are bridged through the config's serde representation, so every
+//! `SimConfig` field is reachable by name without a hand-written accessor and new
+//! fields appear automatically. Enum fields take their snake_case names
+//! (e.g. `metabolism_mode="graph"`).
+
+use crate::errors::{CoreError, ErrorKind};
+use digital_life_core::config::SimConfig;
+use pyo3::exceptions::{PyAttributeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde_json::{Map, Value};
+
+/// Field map of `config`. Goes through JSON text rather than `to_value` so `f32`
+/// fields keep their shortest decimal form (`0.01`, not `0.009999999776482582`).
+fn config_fields(config: &SimConfig) -> Result<Map<String, Value>, String> {
+    let text =
+        serde_json::to_string(config).map_err(|e| format!("failed to serialize config: {e}"))?;
+    match serde_json::from_str(&text) {
+        Ok(Value::Object(fields)) => Ok(fields),
+        Ok(_) => Err("config did not serialize to an object".to_string()),
+        Err(e) => Err(format!("failed to serialize config: {e}")),
+    }
+}
+
+/// Apply `updates` on top of `base` one field at a time, so type errors name the
+/// offending field, then run full validation.
+fn apply_fields(base: &SimConfig, updates: Map<String, Value>) -> Result<SimConfig, CoreError> {
+    let config_error = |message: String| CoreError::new(ErrorKind::Config, message);
+    let mut fields = config_fields(base).map_err(|e| CoreError::new(ErrorKind::Base, e))?;
+    let mut config = base.clone();
+    for (key, value) in updates {
+        if !fields.contains_key(&key) {
+            return Err(config_error(format!("unknown config field `{key}`")));
+        }
+        fields.insert(key.clone(), value);
+        config = serde_json::from_value(Value::Object(fields.clone()))
+            .map_err(|e| config_error(format!("invalid value for `{key}`: {e}")))?;
+    }
+    config.validate()?;
+    Ok(config)
+}
+
+fn to_json_value(value: &Bound<'_, PyAny>) -> PyResult<Value> {
+    let json = value.py().import("json")?;
+    let text: String = json.call_method1("dumps", (value,))?.extract()?;
+    serde_json::from_str(&text).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn to_python<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    py.import("json")?
+        .call_method1("loads", (value.to_string(),))
+}
+
+fn dict_to_fields(dict: &Bound<'_, PyDict>) -> PyResult<Map<String, Value>> {
+    match to_json_value(dict.as_any())? {
+        Value::Object(fields) => Ok(fields),
+        _ => Err(PyValueError::new_err("config must be a dict")),
+    }
+}
+
+/// Simulation configuration. Construct with keyword arguments; omitted fields take
+/// their defaults. Every assignment is re-validated and rejected values leave the
+/// config unchanged.
+#[pyclass(name = "SimConfig", module = "digital_life._core", eq, from_py_object)]
+#[derive(Clone, PartialEq)]
+pub struct PySimConfig {
+    pub(crate) inner: SimConfig,
+}
+
+#[pymethods]
+impl PySimConfig {
+    #[new]
+    #[pyo3(signature = (**kwargs))]
+    fn new(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let updates = match kwargs {
+            Some(kwargs) => dict_to_fields(kwargs)?,
+            None => Map::new(),
+        };
+        let inner = apply_fields(&SimConfig::default(), updates)?;
+        Ok(Self { inner })
+    }
+
+    #[staticmethod]
+    fn from_dict(fields: &Bound<'_, PyDict>) -> PyResult<Self> {
+        Self::new(Some(fields))
+    }
+
+    #[staticmethod]
+    fn from_json(config_json: &str) -> PyResult<Self> {
+        let updates: Map<String, Value> = serde_json::from_str(config_json)
+            .map_err(|e| CoreError::new(ErrorKind::Config, format!("invalid config json: {e}")))?;
+        let inner = apply_fields(&SimConfig::default(), updates)?;
+        Ok(Self { inner })
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let fields = config_fields(&self.inner).map_err(PyValueError::new_err)?;
+        to_python(py, &Value::Object(fields))
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner)
+            .map_err(|e| PyValueError::new_err(format!("failed to serialize config: {e}")))
+    }
+
+    /// Raise `ConfigError` listing every invalid field with its provided value.
+    fn validate(&self) -> PyResult<()> {
+        Ok(self.inner.validate().map_err(CoreError::from)?)
+    }
+
+    fn __getattr__<'py>(&self, py: Python<'py>, name: &str) -> PyResult<Bound<'py, PyAny>> {
+        let fields = config_fields(&self.inner).map_err(PyValueError::new_err)?;
+        match fields.get(name) {
+            Some(value) => to_python(py, value),
+            None => Err(PyAttributeError::new_err(format!(
+                "SimConfig has no field `{name}`"
+            ))),
+        }
+    }
+
+    fn __setattr__(&mut self, name: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let mut updates = Map::new();
+        updates.insert(name.to_string(), to_json_value(value)?);
+        self.inner = apply_fields(&self.inner, updates).map_err(|e| {
+            if e.to_string().starts_with("unknown config field") {
+                PyAttributeError::new_err(e.to_string())
+            } else {
+                PyErr::from(e)
+            }
+        })?;
+        Ok(())
+    }
+
+    fn __dir__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let fields = config_fields(&self.inner).map_err(PyValueError::new_err)?;
+        let methods = ["from_dict", "from_json", "to_dict", "to_json", "validate"];
+        let names: Vec<&str> = fields
+            .keys()
+            .map(String::as_str)
+            .chain(methods.iter().copied())
+            .collect();
+        PyList::new(py, names)
+    }
+
+    fn __repr__(&self) -> String {
+        let defaults = config_fields(&SimConfig::default()).unwrap_or_default();
+        let changed: Vec<String> = config_fields(&self.inner)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(k, v)| defaults.get(k) != Some(v))
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect();
+        format!("SimConfig({})", changed.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn updates(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(fields) => fields,
+            _ => panic!("expected object"),
+        }
+    }
+
+    #[test]
+    fn apply_fields_sets_values_and_enum_strings() {
+        let config = apply_fields(
+            &SimConfig::default(),
+            updates(json!({"seed": 9, "metabolism_mode": "graph", "world_size": 50.0})),
+        )
+        .expect("valid updates should apply");
+        assert_eq!(config.seed, 9);
+        assert_eq!(
+            config.metabolism_mode,
+            digital_life_core::config::MetabolismMode::Graph
+        );
+        assert!((config.world_size - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn apply_fields_rejects_unknown_field() {
+        let err =
+            apply_fields(&SimConfig::default(), updates(json!({"world_sise": 1.0}))).unwrap_err();
+        assert_eq!(err.to_string(), "unknown config field `world_sise`");
+    }
+
+    #[test]
+    fn apply_fields_names_field_with_wrong_type() {
+        let err = apply_fields(
+            &SimConfig::default(),
+            updates(json!({"metabolism_mode": "photosynthesis"})),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid value for `metabolism_mode`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn apply_fields_reports_validation_error_by_field() {
+        let err =
+            apply_fields(&SimConfig::default(), updates(json!({"world_size": -1.0}))).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Config);
+        assert!(err.to_string().starts_with("world_size"), "{err}");
+    }
+}
@@ -0,0 +1,610 @@
+//! `World` exposed to Python for experiments that intervene mid-run.
+//!
+//! The simulation runs with the GIL released; it is reacquired only on callback
+//! steps, where the callback receives a `WorldView`. Interventions requested on
+//! the view are queued and applied to the world, in call order, once the callback
+//! returns, so Python never holds a reference into the running world.
+
+use crate::errors::{self, CoreError};
+use crate::sim_config::PySimConfig;
+use digital_life_core::genome::Genome;
+use digital_life_core::nn::NeuralNet;
+use digital_life_core::snapshot_file::SnapshotWriter;
+use digital_life_core::world::{ExperimentError, ExperimentObserver, World};
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::ops::ControlFlow;
+
+#[derive(Clone, Debug)]
+enum Intervention {
+    ScaleResources(f32),
+    SetResourceRate(f32),
+    KillOrganism(u64),
+    InjectEnergy(u64, f32),
+    SetOrganismEnergy(u64, f32),
+    DamageBoundary(u64, f32),
+    HomeostasisShock(f32),
+    AddOrganism {
+        weights: Vec<f32>,
+        center: [f64; 2],
+        n_agents: usize,
+    },
+}
+
+impl Intervention {
+    fn apply(self, world: &mut World) -> PyResult<()> {
+        let result = match self {
+            Intervention::ScaleResources(factor) => world.scale_resources(factor),
+            Intervention::SetResourceRate(rate) => world.set_resource_rate(rate),
+            Intervention::KillOrganism(stable_id) => world.kill_organism(stable_id),
+            Intervention::InjectEnergy(stable_id, amount) => world.inject_energy(stable_id, amount),
+            Intervention::SetOrganismEnergy(stable_id, value) => {
+                world.set_organism_energy(stable_id, value)
+            }
+            Intervention::DamageBoundary(stable_id, amount) => {
+                world.damage_boundary(stable_id, amount)
+            }
+            Intervention::HomeostasisShock(amount) => world.apply_homeostasis_shock(amount),
+            Intervention::AddOrganism {
+                weights,
+                center,
+                n_agents,
+            } => return add_organism(world, weights, center, n_agents).map(drop),
+        };
+        Ok(result.map_err(CoreError::from)?)
+    }
+}
+
+/// Add an immigrant built from `weights`: either bare NN weights, with the other
+/// segments zeroed as for a founder, or full genome data from `organism_genome`.
+fn add_organism(
+    world: &mut World,
+    weights: Vec<f32>,
+    center: [f64; 2],
+    n_agents: usize,
+) -> PyResult<u64> {
+    let layout = world.config().genome_layout();
+    let genome = match weights.len() {
+        NeuralNet::WEIGHT_COUNT | NeuralNet::EXTENDED_WEIGHT_COUNT => {
+            Genome::with_layout(weights, layout)
+        }
+        len => Genome::from_data_with_layout(weights, layout).ok_or_else(|| {
+            errors::ExperimentError::new_err(format!(
+                "weights must be {} or {} NN weights or full genome data, got {len} values",
+                NeuralNet::WEIGHT_COUNT,
+                NeuralNet::EXTENDED_WEIGHT_COUNT
+            ))
+        })?,
+    };
+    Ok(world
+        .add_organism(genome, center, n_agents)
+        .map_err(CoreError::from)?)
+}
+
+/// State of the world at a callback step. Only valid during the callback.
+#[pyclass(name = "WorldView", module = "digital_life._core")]
+pub struct PyWorldView {
+    #[pyo3(get)]
+    step: usize,
+    #[pyo3(get)]
+    alive_count: usize,
+    #[pyo3(get)]
+    alive_stable_ids: Vec<u64>,
+    pending: Vec<Intervention>,
+    open: bool,
+}
+
+impl PyWorldView {
+    fn queue(&mut self, intervention: Intervention) -> PyResult<()> {
+        if !self.open {
+            return Err(PyRuntimeError::new_err(
+                "WorldView can only be used inside its callback",
+            ));
+        }
+        self.pending.push(intervention);
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl PyWorldView {
+    /// Multiply every resource cell by `factor`.
+    fn scale_resources(&mut self, factor: f32) -> PyResult<()> {
+        self.queue(Intervention::ScaleResources(factor))
+    }
+
+    /// Set the resource regeneration rate until the next schedule, shift or cycle
+    /// breakpoint.
+    fn set_resource_rate(&mut self, rate: f32) -> PyResult<()> {
+        self.queue(Intervention::SetResourceRate(rate))
+    }
+
+    /// Kill the alive organism with `stable_id`.
+    fn kill_organism(&mut self, stable_id: u64) -> PyResult<()> {
+        self.queue(Intervention::KillOrganism(stable_id))
+    }
+
+    /// Add `amount` to the energy of the alive organism with `stable_id`.
+    fn inject_energy(&mut self, stable_id: u64, amount: f32) -> PyResult<()> {
+        self.queue(Intervention::InjectEnergy(stable_id, amount))
+    }
+
+    /// Set the energy of the alive organism with `stable_id`.
+    fn set_organism_energy(&mut self, stable_id: u64, value: f32) -> PyResult<()> {
+        self.queue(Intervention::SetOrganismEnergy(stable_id, value))
+    }
+
+    /// Reduce the boundary integrity of the alive organism with `stable_id`.
+    fn damage_boundary(&mut self, stable_id: u64, amount: f32) -> PyResult<()> {
+        self.queue(Intervention::DamageBoundary(stable_id, amount))
+    }
+
+    /// Displace the regulated internal state of every alive agent by `amount` and
+    /// track its recovery in `shock_recoveries`.
+    fn apply_homeostasis_shock(&mut self, amount: f32) -> PyResult<()> {
+        self.queue(Intervention::HomeostasisShock(amount))
+    }
+
+    /// Queue an immigrant organism with NN `weights` and `n_agents` agents around
+    /// `(x, y)`; see `World.add_organism`.
+    fn add_organism(&mut self, weights: Vec<f32>, x: f64, y: f64, n_agents: usize) -> PyResult<()> {
+        self.queue(Intervention::AddOrganism {
+            weights,
+            center: [x, y],
+            n_agents,
+        })
+    }
+}
+
+/// Simulation world built from a `SimConfig`.
+#[pyclass(name = "World", module = "digital_life._core")]
+pub struct PyWorld {
+    world: World,
+}
+
+#[pymethods]
+impl PyWorld {
+    #[new]
+    fn new(config: &PySimConfig) -> PyResult<Self> {
+        let world = World::from_config(config.inner.clone())
+            .map_err(|e| CoreError::from(e).context("invalid world configuration"))?;
+        Ok(Self { world })
+    }
+
+    /// World whose founders carry the controllers in `weights`, one list of NN
+    /// weights per organism as returned by `organism_nn_weights`. The config's
+    /// `num_organisms` must match `len(weights)`.
+    #[staticmethod]
+    fn seed_from_weights(weights: Vec<Vec<f32>>, config: &PySimConfig) -> PyResult<Self> {
+        let world = World::seed_from_weights(weights, config.inner.clone())
+            .map_err(|e| CoreError::from(e).context("invalid founder controllers"))?;
+        Ok(Self { world })
+    }
+
+    #[getter]
+    fn alive_count(&self) -> usize {
+        self.world.alive_count()
+    }
+
+    /// Full genome data of the alive organism with `stable_id`, as a list of floats.
+    fn organism_genome(&self, stable_id: u64) -> PyResult<Vec<f32>> {
+        self.world
+            .organism_genome(stable_id)
+            .map(|genome| genome.data().to_vec())
+            .ok_or_else(|| {
+                errors::ExperimentError::new_err(format!(
+                    "no alive organism with stable id {stable_id}"
+                ))
+            })
+    }
+
+    /// NN weights of the alive organism with `stable_id`, in the order
+    /// `seed_from_weights` and `add_organism` accept.
+    fn organism_nn_weights(&self, stable_id: u64) -> PyResult<Vec<f32>> {
+        self.world.organism_nn_weights(stable_id).ok_or_else(|| {
+            errors::ExperimentError::new_err(format!(
+                "no alive organism with stable id {stable_id}"
+            ))
+        })
+    }
+
+    /// Stable ids of the organisms currently alive.
+    fn alive_stable_ids(&self) -> Vec<u64> {
+        self.world.alive_stable_ids()
+    }
+
+    /// Add an immigrant organism with `n_agents` agents scattered around `(x, y)` and
+    /// return its stable id. `weights` is either the NN weights alone or full genome
+    /// data as returned by `organism_genome`. The immigrant starts mature at the
+    /// default energy as a new lineage root. Raises `ValueError` for a bad weight
+    /// count or center, or when the world is full.
+    fn add_organism(
+        &mut self,
+        weights: Vec<f32>,
+        x: f64,
+        y: f64,
+        n_agents: usize,
+    ) -> PyResult<u64> {
+        add_organism(&mut self.world, weights, [x, y], n_agents)
+    }
+
+    /// Record the hidden-layer activations of every agent of the organisms in
+    /// `stable_ids` on steps that are multiples of `every_n_steps`, replacing any
+    /// previous tracking set. An empty list stops recording.
+    #[pyo3(signature = (stable_ids, every_n_steps=1))]
+    fn record_activations_for(
+        &mut self,
+        stable_ids: Vec<u64>,
+        every_n_steps: usize,
+    ) -> PyResult<()> {
+        self.world
+            .record_activations_for(stable_ids, every_n_steps)
+            .map_err(|e| CoreError::from(e).into())
+    }
+
+    /// Drain the recorded activations as `(steps, agent_ids, hidden, truncated)`.
+    /// `hidden` holds little-endian float32 rows of 16 values, one per record;
+    /// `digital_life.activations.take_activation_log` wraps it as a numpy array.
+    fn take_activation_log<'py>(
+        &mut self,
+        py: Python<'py>,
+    ) -> (Vec<usize>, Vec<u32>, Bound<'py, PyBytes>, bool) {
+        let (records, truncated) = self.world.take_activation_log();
+        let hidden: Vec<u8> = records
+            .iter()
+            .flat_map(|r| r.hidden)
+            .flat_map(f32::to_le_bytes)
+            .collect();
+        (
+            records.iter().map(|r| r.step).collect(),
+            records.iter().map(|r| r.agent_id).collect(),
+            PyBytes::new(py, &hidden),
+            truncated,
+        )
+    }
+
+    /// Record agent positions to a binary trajectory file at `path` every
+    /// `every_n_steps` steps, at most `max_agents` per frame (chosen by a hash of the
+    /// agent id), replacing any previous recorder. Frames are flushed when each run
+    /// ends; load the file with `digital_life.trajectories.load_trajectory_file`.
+    #[pyo3(signature = (path, every_n_steps, max_agents=usize::MAX))]
+    fn set_trajectory_file(
+        &mut self,
+        path: std::path::PathBuf,
+        every_n_steps: usize,
+        max_agents: usize,
+    ) -> PyResult<()> {
+        let file = File::create(&path)
+            .map_err(|e| PyIOError::new_err(format!("{}: {e}", path.display())))?;
+        match self.world.set_trajectory_writer(
+            Box::new(BufWriter::new(file)),
+            every_n_steps,
+            max_agents,
+        ) {
+            Err(err @ ExperimentError::TrajectoryWrite(_)) => Err(CoreError::from(err)
+                .context(&path.display().to_string())
+                .into()),
+            result => result.map_err(|e| CoreError::from(e).into()),
+        }
+    }
+
+    /// Stop recording trajectories and flush the file.
+    fn close_trajectory_file(&mut self) -> PyResult<()> {
+        if let Some(mut writer) = self.world.take_trajectory_writer() {
+            writer
+                .flush()
+                .map_err(|e| PyIOError::new_err(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Resource grid shape as `(height, width, cell_size)`.
+    fn resource_dimensions(&self) -> (usize, usize, f64) {
+        let field = self.world.resource_field();
+        (field.height(), field.width(), field.cell_size())
+    }
+
+    /// Replace the resource field with an `(height, width)` grid (a 2-D array or
+    /// nested sequences of floats). The values also become each cell's regeneration
+    /// ceiling and apply to every channel. Raises `ValueError` on a shape mismatch or
+    /// at the first NaN, infinite or negative value.
+    fn set_resource_grid(&mut self, grid: &Bound<'_, PyAny>) -> PyResult<()> {
+        let (height, width, _) = self.resource_dimensions();
+        let mut data = Vec::with_capacity(height * width);
+        let mut rows = 0;
+        for row in grid.try_iter()? {
+            let start = data.len();
+            for value in row?.try_iter()? {
+                data.push(value?.extract::<f32>()?);
+            }
+            if data.len() - start != width {
+                return Err(errors::ExperimentError::new_err(format!(
+                    "resource grid row {rows} must have {width} values, got {}",
+                    data.len() - start
+                )));
+            }
+            rows += 1;
+        }
+        if rows != height {
+            return Err(errors::ExperimentError::new_err(format!(
+                "resource grid must have {height} rows, got {rows}"
+            )));
+        }
+        self.world
+            .resource_field_mut()
+            .set_grid(&data)
+            .map_err(|e| CoreError::from(e).into())
+    }
+
+    /// Set the resource cell containing `(x, y)` to `value` on every channel, also
+    /// making it the cell's regeneration ceiling.
+    fn set_resource_cell(&mut self, x: f64, y: f64, value: f32) -> PyResult<()> {
+        self.world
+            .resource_field_mut()
+            .set_cell_value(x, y, value)
+            .map_err(|e| CoreError::from(e).into())
+    }
+
+    /// Multiply every resource cell by `factor`.
+    fn scale_resources(&mut self, factor: f32) -> PyResult<()> {
+        self.world
+            .scale_resources(factor)
+            .map_err(|e| CoreError::from(e).into())
+    }
+
+    /// Kill the alive organism with `stable_id` now, outside any run. Kills that
+    /// should show up in a run's samples go through the callback view instead.
+    fn kill_organism(&mut self, stable_id: u64) -> PyResult<()> {
+        Intervention::KillOrganism(stable_id).apply(&mut self.world)
+    }
+
+    /// Set the energy of the alive organism with `stable_id`.
+    fn set_organism_energy(&mut self, stable_id: u64, value: f32) -> PyResult<()> {
+        Intervention::SetOrganismEnergy(stable_id, value).apply(&mut self.world)
+    }
+
+    /// Reduce the boundary integrity of the alive organism with `stable_id` by
+    /// `amount`, flooring at zero.
+    fn damage_boundary(&mut self, stable_id: u64, amount: f32) -> PyResult<()> {
+        Intervention::DamageBoundary(stable_id, amount).apply(&mut self.world)
+    }
+
+    /// Displace internal-state channels 0 and 1 of every alive agent by `amount`,
+    /// clamped to [0, 1]. The next run reports how long the population took to
+    /// return to its pre-shock means in `shock_recoveries`.
+    fn apply_homeostasis_shock(&mut self, amount: f32) -> PyResult<()> {
+        Intervention::HomeostasisShock(amount).apply(&mut self.world)
+    }
+
+    /// Iterate over `steps` steps without blocking: each iteration advances
+    /// `sample_every` steps (fewer for the last one) with the GIL released and yields
+    /// that step's metrics as a dict, whose `step` counts from the iterator's start.
+    ///
+    /// Leaving the loop early leaves the world ready to continue. Counters such as
+    /// stable ids and births carry on into later iterators and runs; like any steps
+    /// taken outside a run, lineage events and lifespans recorded while iterating
+    /// are not included in a later run's summary.
+    #[pyo3(signature = (steps, sample_every=1))]
+    fn iter_steps(slf: Py<Self>, steps: usize, sample_every: usize) -> PyResult<StepIterator> {
+        if sample_every == 0 {
+            return Err(errors::ExperimentError::new_err(
+                "sample_every must be positive",
+            ));
+        }
+        Ok(StepIterator {
+            world: slf,
+            steps,
+            sample_every,
+            done: 0,
+        })
+    }
+
+    /// Run `steps` steps and return the summary JSON.
+    fn run_experiment(
+        &mut self,
+        py: Python<'_>,
+        steps: usize,
+        sample_every: usize,
+    ) -> PyResult<String> {
+        let world = &mut self.world;
+        summary_json(py.detach(|| world.try_run_experiment(steps, sample_every)))
+    }
+
+    /// Run `steps` steps and return the summary JSON, calling `callback(view)`
+    /// every `callback_every` steps.
+    ///
+    /// Interventions queued on the view are applied before that step is sampled;
+    /// an invalid one raises `ValueError`. As with `run_experiment_json`, a callback
+    /// returning `False` stops the run early with `cancelled` set, while a
+    /// KeyboardInterrupt (carrying `partial_summary`) or an exception raised by the
+    /// callback propagates.
+    #[pyo3(signature = (steps, sample_every, callback, callback_every=1000))]
+    fn run_with_callback(
+        &mut self,
+        py: Python<'_>,
+        steps: usize,
+        sample_every: usize,
+        callback: Py<PyAny>,
+        callback_every: usize,
+    ) -> PyResult<String> {
+        if callback_every == 0 {
+            return Err(errors::ExperimentError::new_err(
+                "callback_every must be positive",
+            ));
+        }
+        let mut observer = PyInterventionObserver {
+            callback,
+            every: callback_every,
+            error: None,
+        };
+        let world = &mut self.world;
+        let result = py
+            .detach(|| world.try_run_experiment_with_observer(steps, sample_every, &mut observer));
+        if let Some(err) = observer.error {
+            return Err(errors::observed_run_error(
+                py,
+                err,
+                summary_json(result).ok(),
+            ));
+        }
+        summary_json(result)
+    }
+
+    /// Run `steps` steps and return the summary JSON, streaming a binary snapshot
+    /// frame to `path` every `snapshot_every` steps. With `append=True` frames are
+    /// added to an existing snapshot file instead of starting a new one. Load the file
+    /// with `digital_life.snapshots.load_snapshot_file`.
+    #[pyo3(signature = (steps, sample_every, snapshot_every, path, append=false))]
+    fn run_with_snapshot_file(
+        &mut self,
+        py: Python<'_>,
+        steps: usize,
+        sample_every: usize,
+        snapshot_every: usize,
+        path: std::path::PathBuf,
+        append: bool,
+    ) -> PyResult<String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&path)
+            .map_err(|e| PyIOError::new_err(format!("{}: {e}", path.display())))?;
+        let empty = file
+            .metadata()
+            .map_err(|e| PyIOError::new_err(format!("{}: {e}", path.display())))?
+            .len()
+            == 0;
+        let file = BufWriter::new(file);
+        let mut writer = if append && !empty {
+            SnapshotWriter::appending(file)
+        } else {
+            SnapshotWriter::new(file).map_err(|e| PyIOError::new_err(e.to_string()))?
+        };
+        let world = &mut self.world;
+        let result = py.detach(|| {
+            world.try_run_experiment_with_snapshot_writer(
+                steps,
+                sample_every,
+                snapshot_every,
+                &mut writer,
+            )
+        });
+        if let Err(err @ ExperimentError::SnapshotWrite(_)) = result {
+            return Err(CoreError::from(err)
+                .context(&path.display().to_string())
+                .into());
+        }
+        summary_json(result)
+    }
+}
+
+/// Iterator returned by `World.iter_steps`.
+#[pyclass(name = "StepIterator", module = "digital_life._core")]
+pub struct StepIterator {
+    world: Py<PyWorld>,
+    steps: usize,
+    sample_every: usize,
+    done: usize,
+}
+
+#[pymethods]
+impl StepIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__<'py>(&mut self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        if self.done >= self.steps {
+            return Ok(None);
+        }
+        py.check_signals()?;
+        let advance = self.sample_every.min(self.steps - self.done);
+        let step = self.done + advance;
+        let mut world = self.world.borrow_mut(py);
+        let world = &mut world.world;
+        let metrics = py.detach(|| {
+            for _ in 0..advance {
+                world.step();
+            }
+            world.collect_step_metrics(step)
+        });
+        self.done = step;
+        // Through text rather than `serde_json::Value`, so f32 fields read back as
+        // they do in summary JSON.
+        let json = serde_json::to_string(&metrics)
+            .map_err(|e| PyValueError::new_err(format!("failed to serialize metrics: {e}")))?;
+        py.import("json")?.call_method1("loads", (json,)).map(Some)
+    }
+}
+
+fn summary_json(
+    result: Result<digital_life_core::RunSummary, ExperimentError>,
+) -> PyResult<String> {
+    let summary =
+        result.map_err(|e| CoreError::from(e).context("invalid experiment parameters"))?;
+    serde_json::to_string(&summary)
+        .map_err(|e| PyValueError::new_err(format!("failed to serialize experiment summary: {e}")))
+}
+
+struct PyInterventionObserver {
+    callback: Py<PyAny>,
+    every: usize,
+    error: Option<PyErr>,
+}
+
+impl PyInterventionObserver {
+    /// Call the callback with a fresh view; returns whether it asked to stop and
+    /// the interventions it queued.
+    fn call(
+        &self,
+        py: Python<'_>,
+        step: usize,
+        world: &World,
+    ) -> PyResult<(bool, Vec<Intervention>)> {
+        py.check_signals()?;
+        let view = Bound::new(
+            py,
+            PyWorldView {
+                step,
+                alive_count: world.alive_count(),
+                alive_stable_ids: world.alive_stable_ids(),
+                pending: Vec::new(),
+                open: true,
+            },
+        )?;
+        let ret = self.callback.call1(py, (view.clone(),));
+        let mut view = view.borrow_mut();
+        view.open = false;
+        let stop = ret?.bind(py).is(PyBool::new(py, false));
+        Ok((stop, std::mem::take(&mut view.pending)))
+    }
+}
+
+impl ExperimentObserver for PyInterventionObserver {
+    fn on_step(&mut self, step: usize, world: &mut World) -> ControlFlow<()> {
+        if !step.is_multiple_of(self.every) {
+            return ControlFlow::Continue(());
+        }
+        Python::attach(|py| {
+            let outcome = self.call(py, step, world).and_then(|(stop, pending)| {
+                for intervention in pending {
+                    intervention.apply(world)?;
+                }
+                Ok(stop)
+            });
+            match outcome {
+                Ok(false) => ControlFlow::Continue(()),
+                Ok(true) => ControlFlow::Break(()),
+                Err(err) => {
+                    self.error = Some(err);
+                    ControlFlow::Break(())
+                }
+            }
+        })
+    }
+}
@@ -1,15 +1,18 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use digital_life_core::agent::Agent;
 use digital_life_core::config::{MetabolismMode, SimConfig};
-use digital_life_core::nn::NeuralNet;
-use digital_life_core::world::World;
-use rand::Rng;
-use rand::SeedableRng;
-use rand_chacha::ChaCha12Rng;
+use digital_life_core::metrics::RunSummary;
+use digital_life_core::snapshot_file::SnapshotWriter;
+use digital_life_core::sweep::{run_sweep_with_progress, SweepCell, SweepSpec};
+use digital_life_core::world::init::{spawn_world, spawn_world_with_positions};
+use digital_life_core::world::{ExperimentObserver, PlacementStrategy, World};
+use serde::Deserialize;
 use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io::{BufReader, BufWriter};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
 
 const WORLD_SIZE: f64 = 100.0;
 const WARMUP_STEPS: usize = 10;
@@ -17,7 +20,7 @@ const BENCHMARK_STEPS: usize = 200;
 const TARGET_SPS: f64 = 100.0;
 
 #[derive(Parser)]
-#[command(name = "digital-life")]
+#[command(name = "digital-life-cli")]
 #[command(about = "Digital Life Simulation CLI")]
 struct Cli {
     #[command(subcommand)]
@@ -28,17 +31,65 @@ struct Cli {
 enum Commands {
     /// Run a single simulation from a config file
     Run {
-        /// Path to config file (JSON)
+        /// Path to config file (TOML, or JSON with a .json extension)
         #[arg(long)]
         config: PathBuf,
 
-        /// Output directory for results (optional)
+        /// Number of simulation steps to run
+        #[arg(long, default_value_t = 10000)]
+        steps: usize,
+
+        /// Steps between metric samples
+        #[arg(long, default_value_t = 100)]
+        sample_every: usize,
+
+        /// Path for the run summary JSON (optional)
         #[arg(long)]
         out: Option<PathBuf>,
 
-        /// Number of simulation steps to run (default: 10000)
+        /// Stream snapshot frames, e.g. `every=1000`
+        #[arg(long, value_parser = parse_snapshots, requires = "snapshot_out")]
+        snapshots: Option<usize>,
+
+        /// Path for the binary snapshot file
+        #[arg(long, requires = "snapshots")]
+        snapshot_out: Option<PathBuf>,
+
+        /// Steps between progress lines; 0 disables progress
+        #[arg(long, default_value_t = 1000)]
+        progress_every: usize,
+    },
+    /// Run a parameter sweep described by a TOML spec
+    Sweep {
+        /// Path to the sweep spec (TOML)
+        #[arg(long)]
+        spec: PathBuf,
+
+        /// Directory for `sweep.json`
+        #[arg(long)]
+        out_dir: PathBuf,
+    },
+    /// Run one config over a range of seeds
+    Replicates {
+        /// Path to config file (TOML, or JSON with a .json extension)
+        #[arg(long)]
+        config: PathBuf,
+
+        /// Inclusive seed range, e.g. `1..30` for 30 seeds
+        #[arg(long, value_parser = parse_seeds)]
+        seeds: SeedRange,
+
+        /// Number of simulation steps per seed
         #[arg(long, default_value_t = 10000)]
         steps: usize,
+
+        /// Steps between metric samples
+        #[arg(long, default_value_t = 100)]
+        sample_every: usize,
+
+        /// Directory for one `seed_<n>.json` summary per seed
+        #[arg(long)]
+        out_dir: PathBuf,
     },
     /// Run the performance benchmark suite
     Benchmark,
@@ -46,32 +97,126 @@ enum Commands {
     DumpDefaultConfig,
 }
 
-fn create_agents(config: &SimConfig) -> Result<Vec<Agent>> {
-    let total_agents = config.num_organisms * config.agents_per_organism;
-    let mut rng = ChaCha12Rng::seed_from_u64(config.seed);
-    let mut agents = Vec::with_capacity(total_agents);
-    for org in 0..config.num_organisms {
-        for i in 0..config.agents_per_organism {
-            let id = (org * config.agents_per_organism + i) as u32;
-            let organism_id: u16 = org.try_into().context("Organism ID overflow (max 65535)")?;
-            let pos = [
-                rng.random::<f64>() * config.world_size,
-                rng.random::<f64>() * config.world_size,
-            ];
-            agents.push(Agent::new(id, organism_id, pos));
+#[derive(Clone, Copy, Debug)]
+struct SeedRange {
+    first: u64,
+    last: u64,
+}
+
+fn parse_seeds(value: &str) -> Result<SeedRange, String> {
+    let (first, last) = value
+        .split_once("..")
+        .ok_or_else(|| format!("expected a seed range like `1..30`, got `{value}`"))?;
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<u64>()
+            .map_err(|e| format!("invalid seed `{s}`: {e}"))
+    };
+    let range = SeedRange {
+        first: parse(first)?,
+        last: parse(last)?,
+    };
+    if range.first > range.last {
+        return Err(format!("seed range `{value}` is empty"));
+    }
+    Ok(range)
+}
+
+fn parse_snapshots(value: &str) -> Result<usize, String> {
+    let every = value
+        .strip_prefix("every=")
+        .ok_or_else(|| format!("expected `every=<steps>`, got `{value}`"))?;
+    match every.parse::<usize>() {
+        Ok(0) => Err("snapshot interval must be positive".to_string()),
+        Ok(every) => Ok(every),
+        Err(e) => Err(format!("invalid snapshot interval `{every}`: {e}")),
+    }
+}
+
+/// A sweep spec file: run length plus the [`SweepSpec`] fields (`baseline`, `axes`,
+/// `seeds`).
+#[derive(Deserialize)]
+struct SweepFile {
+    #[serde(default = "default_sweep_steps")]
+    steps: usize,
+    #[serde(default = "default_sample_every")]
+    sample_every: usize,
+    #[serde(flatten)]
+    spec: SweepSpec,
+}
+
+fn default_sweep_steps() -> usize {
+    10000
+}
+
+fn default_sample_every() -> usize {
+    100
+}
+
+/// Prints step and alive organism count every `every` steps.
+struct ProgressObserver {
+    every: usize,
+    steps: usize,
+}
+
+impl ExperimentObserver for ProgressObserver {
+    fn on_step(&mut self, step: usize, world: &mut World) -> ControlFlow<()> {
+        if self.every > 0 && step.is_multiple_of(self.every) {
+            println!("step {step}/{} alive {}", self.steps, world.alive_count());
         }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Prints a completion line as each sweep cell finishes.
+struct CellProgress {
+    total: usize,
+    done: AtomicUsize,
+}
+
+impl CellProgress {
+    fn new(total: usize) -> Self {
+        Self {
+            total,
+            done: AtomicUsize::new(0),
+        }
+    }
+
+    fn report(&self, cell: &SweepCell) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        println!(
+            "cell {done}/{} seed {} final alive {}",
+            self.total, cell.seed, cell.run.final_alive_count
+        );
     }
-    Ok(agents)
 }
 
-fn create_nns(config: &SimConfig) -> Vec<NeuralNet> {
-    let mut rng = ChaCha12Rng::seed_from_u64(config.seed);
-    (0..config.num_organisms)
-        .map(|_| {
-            let weights = (0..NeuralNet::WEIGHT_COUNT).map(|_| rng.random::<f32>() * 2.0 - 1.0);
-            NeuralNet::from_weights(weights)
-        })
-        .collect()
+/// Load a TOML config, or a JSON one when the extension is `.json`. Both are
+/// validated.
+fn load_config(path: &Path) -> Result<SimConfig> {
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let file = File::open(path).with_context(|| format!("failed to open {path:?}"))?;
+        let config: SimConfig = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("failed to parse {path:?}"))?;
+        config
+            .validate()
+            .with_context(|| format!("invalid config {path:?}"))?;
+        Ok(config)
+    } else {
+        SimConfig::from_toml_file(path).with_context(|| format!("invalid config {path:?}"))
+    }
+}
+
+/// Write `summary` as pretty JSON with the config that produced it under `config`.
+fn write_summary(path: &Path, summary: &RunSummary, config: &SimConfig) -> Result<()> {
+    let mut value = serde_json::to_value(summary)?;
+    value
+        .as_object_mut()
+        .expect("RunSummary serializes to an object")
+        .insert("config".to_string(), serde_json::to_value(config)?);
+    let file = File::create(path).with_context(|| format!("failed to create {path:?}"))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), &value)
+        .with_context(|| format!("failed to write {path:?}"))
 }
 
 fn run_benchmark(
@@ -93,10 +238,8 @@ fn run_benchmark(
         .validate()
         .context("Benchmark config validation error")?;
 
-    let agents = create_agents(&config)?;
-    let nns = create_nns(&config);
-    let mut world =
-        World::new(agents, nns, config.clone()).context("Failed to initialize benchmark world")?;
+    let mut world = spawn_world_with_positions(&config, PlacementStrategy::UniformGlobal)
+        .context("Failed to initialize benchmark world")?;
 
     // Warmup
     for _ in 0..WARMUP_STEPS {
@@ -117,6 +260,13 @@ fn run_benchmark(
         total_time += timings.total_us;
     }
 
+    // Metrics sampling cost, measured separately so it does not skew step timings.
+    let metrics_start = Instant::now();
+    for step in 0..BENCHMARK_STEPS {
+        std::hint::black_box(world.collect_step_metrics(step));
+    }
+    let avg_metrics_us = metrics_start.elapsed().as_micros() as f64 / BENCHMARK_STEPS as f64;
+
     let avg_step_us = total_time as f64 / BENCHMARK_STEPS as f64;
     let steps_per_sec = 1_000_000.0 / avg_step_us;
 
@@ -132,6 +282,8 @@ fn run_benchmark(
         total_state as f64 / BENCHMARK_STEPS as f64,
     );
 
+    println!("  Metrics:       {avg_metrics_us:.0} us per sampled step");
+
     let verdict = if steps_per_sec >= TARGET_SPS {
         "GO"
     } else {
@@ -182,34 +334,93 @@ fn main() -> Result<()> {
                 }
             }
         }
-        Commands::Run { config, out, steps } => {
-            let file = File::open(&config).context("failed to open config file")?;
-            let reader = BufReader::new(file);
-            let sim_config: SimConfig =
-                serde_json::from_reader(reader).context("failed to parse config")?;
-
-            // Validate config
-            sim_config.validate().context("Config validation error")?;
-
-            println!("Loaded config from {:?}", config);
-            println!("Simulating for {} steps...", steps);
-
-            let agents = create_agents(&sim_config)?;
-            let nns = create_nns(&sim_config);
-            let mut world = World::new(agents, nns, sim_config.clone())
-                .context("Failed to initialize world")?;
-
-            let summary = world.run_experiment(steps, 100);
-
-            if let Some(out_dir) = out {
-                std::fs::create_dir_all(&out_dir).context("failed to create output directory")?;
-                let summary_path = out_dir.join("summary.json");
-                let file = File::create(summary_path).context("failed to create summary file")?;
-                serde_json::to_writer_pretty(file, &summary).context("failed to write summary")?;
-                println!("Run complete. Results saved to {:?}", out_dir);
-            } else {
-                println!("Run complete. Final alive: {}", summary.final_alive_count);
+        Commands::Run {
+            config,
+            steps,
+            sample_every,
+            out,
+            snapshots,
+            snapshot_out,
+            progress_every,
+        } => {
+            let sim_config = load_config(&config)?;
+            println!("Loaded config from {config:?}");
+            println!("Simulating for {steps} steps...");
+
+            let mut world = spawn_world(&sim_config).context("failed to initialize world")?;
+            let mut progress = ProgressObserver {
+                every: progress_every,
+                steps,
+            };
+            let summary = match (snapshots, snapshot_out) {
+                (Some(every), Some(path)) => {
+                    let file = File::create(&path)
+                        .with_context(|| format!("failed to create {path:?}"))?;
+                    let mut writer = SnapshotWriter::new(BufWriter::new(file))
+                        .with_context(|| format!("failed to write {path:?}"))?;
+                    world.try_run_experiment_with_snapshot_writer_and_observer(
+                        steps,
+                        sample_every,
+                        every,
+                        &mut writer,
+                        &mut progress,
+                    )?
+                }
+                _ => world.try_run_experiment_with_observer(steps, sample_every, &mut progress)?,
+            };
+
+            println!("Run complete. Final alive: {}", summary.final_alive_count);
+            if let Some(path) = out {
+                write_summary(&path, &summary, &sim_config)?;
+                println!("Summary saved to {path:?}");
+            }
+        }
+        Commands::Sweep { spec, out_dir } => {
+            let input = std::fs::read_to_string(&spec)
+                .with_context(|| format!("failed to read {spec:?}"))?;
+            let file: SweepFile =
+                toml::from_str(&input).with_context(|| format!("failed to parse {spec:?}"))?;
+            let cells = file.spec.configs()?.len();
+            println!("Running {cells} sweep cells for {} steps...", file.steps);
+            let progress = CellProgress::new(cells);
+            let results =
+                run_sweep_with_progress(file.spec, file.steps, file.sample_every, |cell| {
+                    progress.report(cell)
+                })?;
+
+            std::fs::create_dir_all(&out_dir)
+                .with_context(|| format!("failed to create {out_dir:?}"))?;
+            let path = out_dir.join("sweep.json");
+            std::fs::write(&path, results.to_json()?)
+                .with_context(|| format!("failed to write {path:?}"))?;
+            println!("Sweep complete. Results saved to {path:?}");
+        }
+        Commands::Replicates {
+            config,
+            seeds,
+            steps,
+            sample_every,
+            out_dir,
+        } => {
+            let sim_config = load_config(&config)?;
+            let seeds: Vec<u64> = (seeds.first..=seeds.last).collect();
+            println!("Running {} replicates for {steps} steps...", seeds.len());
+            let spec = SweepSpec::new(sim_config.clone()).seeds(&seeds);
+            let progress = CellProgress::new(seeds.len());
+            let results =
+                run_sweep_with_progress(spec, steps, sample_every, |cell| progress.report(cell))?;
+
+            std::fs::create_dir_all(&out_dir)
+                .with_context(|| format!("failed to create {out_dir:?}"))?;
+            for cell in &results.cells {
+                let config = SimConfig {
+                    seed: cell.seed,
+                    ..sim_config.clone()
+                };
+                let path = out_dir.join(format!("seed_{}.json", cell.seed));
+                write_summary(&path, &cell.run, &config)?;
             }
+            println!("Replicates complete. Results saved to {out_dir:?}");
         }
     }
     Ok(())
@@ -0,0 +1,163 @@
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+
+const TINY_CONFIG: &str = "\
+seed = 7
+world_size = 30.0
+num_organisms = 3
+agents_per_organism = 4
+";
+
+fn cli() -> Command {
+    Command::cargo_bin("digital-life-cli").unwrap()
+}
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn run_writes_summary_with_embedded_config_and_prints_progress() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("cfg.toml");
+    fs::write(&config, TINY_CONFIG).unwrap();
+    let out = dir.path().join("summary.json");
+
+    let output = cli()
+        .args(["run", "--steps", "20", "--sample-every", "5"])
+        .args(["--progress-every", "10", "--config"])
+        .arg(&config)
+        .arg("--out")
+        .arg(&out)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    assert!(stdout.contains("step 10/20 alive"), "{stdout}");
+    assert!(stdout.contains("step 20/20 alive"), "{stdout}");
+
+    let summary = read_json(&out);
+    assert_eq!(summary["steps"], 20);
+    assert_eq!(summary["samples"].as_array().unwrap().len(), 4);
+    assert_eq!(summary["config"]["seed"], 7);
+    assert_eq!(summary["config"]["num_organisms"], 3);
+}
+
+#[test]
+fn run_streams_snapshots_to_file_and_prints_progress() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("cfg.toml");
+    fs::write(&config, TINY_CONFIG).unwrap();
+    let frames = dir.path().join("frames.bin");
+
+    let output = cli()
+        .args(["run", "--steps", "10", "--snapshots", "every=5"])
+        .args(["--progress-every", "5", "--config"])
+        .arg(&config)
+        .arg("--snapshot-out")
+        .arg(&frames)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    assert!(stdout.contains("step 5/10 alive"), "{stdout}");
+    assert!(stdout.contains("step 10/10 alive"), "{stdout}");
+
+    let file = fs::File::open(&frames).unwrap();
+    let read = digital_life_core::snapshot_file::read_snapshot_frames(file).unwrap();
+    assert_eq!(read.len(), 2);
+}
+
+#[test]
+fn invalid_config_exits_nonzero_with_core_message() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("cfg.toml");
+    fs::write(&config, "num_organisms = 0\n").unwrap();
+
+    let output = cli()
+        .args(["run", "--steps", "10", "--config"])
+        .arg(&config)
+        .assert()
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8(output).unwrap();
+    assert!(stderr.contains("num_organisms"), "{stderr}");
+}
+
+#[test]
+fn replicates_write_one_summary_per_seed() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("cfg.toml");
+    fs::write(&config, TINY_CONFIG).unwrap();
+    let out_dir = dir.path().join("results");
+
+    let output = cli()
+        .args(["replicates", "--seeds", "1..3", "--steps", "10", "--config"])
+        .arg(&config)
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    for done in 1..=3 {
+        assert!(stdout.contains(&format!("cell {done}/3 seed")), "{stdout}");
+    }
+
+    for seed in 1..=3 {
+        let summary = read_json(&out_dir.join(format!("seed_{seed}.json")));
+        assert_eq!(summary["config"]["seed"], seed);
+        assert_eq!(summary["steps"], 10);
+    }
+}
+
+#[test]
+fn sweep_writes_results_for_every_cell() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = dir.path().join("sweep.toml");
+    fs::write(
+        &spec,
+        "\
+steps = 10
+sample_every = 5
+seeds = [1, 2]
+
+[baseline]
+world_size = 30.0
+num_organisms = 3
+agents_per_organism = 4
+
+[[axes]]
+field = \"boundary_repair_rate\"
+values = [0.01, 0.05]
+",
+    )
+    .unwrap();
+    let out_dir = dir.path().join("results");
+
+    let output = cli()
+        .args(["sweep", "--spec"])
+        .arg(&spec)
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output).unwrap();
+    assert!(stdout.contains("cell 4/4 seed"), "{stdout}");
+
+    let results = read_json(&out_dir.join("sweep.json"));
+    assert_eq!(results["cells"].as_array().unwrap().len(), 4);
+    assert_eq!(results["steps"], 10);
+}
@@ -1,21 +1,61 @@
 """Digital Life: Artificial life simulation framework."""
 
 from ._core import (
+    CheckpointError,
+    ConfigError,
+    DigitalLifeError,
+    ExperimentError,
+    Genome,
+    MutationRates,
+    SimConfig,
+    StepIterator,
+    World,
+    WorldInitError,
+    WorldView,
+    compare_replicates_json,
+    compare_runs_json,
     default_config_json,
+    effective_population_size_series,
+    lineage_to_dot,
+    lineage_to_newick,
+    min_population_and_step,
+    run_ablation_json,
     run_evolution_experiment_json,
     run_experiment_json,
     run_niche_experiment_json,
+    run_sweep_json,
     step_once,
+    surviving_founder_lineages,
     validate_config_json,
     version,
 )
 
 __all__ = [
     "version",
+    "SimConfig",
+    "Genome",
+    "MutationRates",
+    "World",
+    "WorldView",
+    "StepIterator",
+    "DigitalLifeError",
+    "ConfigError",
+    "WorldInitError",
+    "ExperimentError",
+    "CheckpointError",
     "default_config_json",
     "validate_config_json",
     "step_once",
     "run_experiment_json",
     "run_evolution_experiment_json",
     "run_niche_experiment_json",
+    "run_ablation_json",
+    "run_sweep_json",
+    "lineage_to_newick",
+    "lineage_to_dot",
+    "surviving_founder_lineages",
+    "effective_population_size_series",
+    "min_population_and_step",
+    "compare_runs_json",
+    "compare_replicates_json",
 ]
@@ -0,0 +1,35 @@
+"""Hidden-layer activations recorded by `World.record_activations_for`."""
+
+from __future__ import annotations
+
+from typing import NamedTuple
+
+import numpy as np
+
+from ._core import World
+
+HIDDEN_SIZE = 16
+
+
+class ActivationLog(NamedTuple):
+    """Recorded activations: `hidden[i]` belongs to agent `agent_id[i]` at `step[i]`."""
+
+    step: np.ndarray
+    agent_id: np.ndarray
+    hidden: np.ndarray
+    truncated: bool
+
+
+def take_activation_log(world: World) -> ActivationLog:
+    """Drain `world`'s recorded activations, with `hidden` shaped `(records, 16)`.
+
+    `truncated` is true when records past the core's bound were dropped since the
+    last call.
+    """
+    steps, agent_ids, hidden, truncated = world.take_activation_log()
+    return ActivationLog(
+        step=np.asarray(steps, dtype=np.uint64),
+        agent_id=np.asarray(agent_ids, dtype=np.uint32),
+        hidden=np.frombuffer(hidden, dtype="<f4").reshape(-1, HIDDEN_SIZE),
+        truncated=truncated,
+    )
@@ -0,0 +1,76 @@
+"""Loader for binary snapshot files written by `World.run_with_snapshot_file`.
+
+The format is described in `crates/digital-life-core/src/snapshot_file.rs`: a header
+naming each per-organism field and its type, then appended frames of fixed-width
+little-endian records.
+"""
+
+from __future__ import annotations
+
+import struct
+from pathlib import Path
+
+import numpy as np
+
+MAGIC = b"DLSNAP"
+FORMAT_VERSION = 3
+# Older versions wrote a prefix of the current fields; the header-driven dtype
+# handles every one of them.
+_SUPPORTED_VERSIONS = {1, 2, FORMAT_VERSION}
+# `species_id` value for organisms without a species label.
+NO_SPECIES = 2**32 - 1
+_TYPE_CODES = {0: "u1", 1: "<u4", 2: "<u8", 3: "<f4", 4: "<f8"}
+_FRAME_HEAD = struct.Struct("<QII")
+
+
+def _read_header(data: bytes) -> tuple[np.dtype, int]:
+    if data[:6] != MAGIC:
+        raise ValueError("not a snapshot file (bad magic)")
+    version, n_fields = struct.unpack_from("<HH", data, 6)
+    if version not in _SUPPORTED_VERSIONS:
+        raise ValueError(f"unsupported snapshot format version {version}")
+    offset = 10
+    fields = []
+    for _ in range(n_fields):
+        length = data[offset]
+        name = data[offset + 1 : offset + 1 + length].decode()
+        fields.append((name, _TYPE_CODES[data[offset + 1 + length]]))
+        offset += length + 2
+    return np.dtype(fields), offset
+
+
+def load_snapshot_file(path: str | Path) -> dict[str, np.ndarray]:
+    """Load every frame as flat per-organism columns.
+
+    Returns one array per header field (e.g. `energy`, `center_x`) plus `step`, the
+    frame step of each row. `frame_steps` and `frame_offsets` index the frames: rows
+    `frame_offsets[i]:frame_offsets[i + 1]` belong to `frame_steps[i]`.
+    `resource_hotspots` holds `(step, x, y)` rows for frames that recorded hotspots.
+    """
+    data = Path(path).read_bytes()
+    record, offset = _read_header(data)
+    chunks, steps, counts, hotspots = [], [], [], []
+    while offset < len(data):
+        if offset + _FRAME_HEAD.size > len(data):
+            raise ValueError(f"snapshot data ends mid-frame after {len(steps)} frames")
+        step, n_orgs, n_hotspots = _FRAME_HEAD.unpack_from(data, offset)
+        offset += _FRAME_HEAD.size
+        end = offset + n_orgs * record.itemsize + n_hotspots * 16
+        if end > len(data):
+            raise ValueError(f"snapshot data ends mid-frame after {len(steps)} frames")
+        chunks.append(np.frombuffer(data, dtype=record, count=n_orgs, offset=offset))
+        offset += n_orgs * record.itemsize
+        centers = np.frombuffer(data, dtype="<f8", count=2 * n_hotspots, offset=offset)
+        hotspots.extend((step, x, y) for x, y in centers.reshape(-1, 2))
+        offset = end
+        steps.append(step)
+        counts.append(n_orgs)
+
+    rows = np.concatenate(chunks) if chunks else np.zeros(0, dtype=record)
+    columns = {name: rows[name].copy() for name in record.names}
+    columns["dormant"] = columns["dormant"].astype(bool)
+    columns["step"] = np.repeat(np.asarray(steps, dtype=np.uint64), counts)
+    columns["frame_steps"] = np.asarray(steps, dtype=np.uint64)
+    columns["frame_offsets"] = np.concatenate([[0], np.cumsum(counts, dtype=np.int64)])
+    columns["resource_hotspots"] = np.asarray(hotspots, dtype=np.float64).reshape(-1, 3)
+    return columns
@@ -0,0 +1,52 @@
+"""Loader for binary trajectory files written by `World.set_trajectory_file`.
+
+The format is described in `crates/digital-life-core/src/trajectory_file.rs`: a short
+header, then frames of fixed-width little-endian per-agent records.
+"""
+
+from __future__ import annotations
+
+import struct
+from pathlib import Path
+
+import numpy as np
+
+MAGIC = b"DLTRAJ"
+FORMAT_VERSION = 1
+RECORD = np.dtype([("id", "<u4"), ("organism_id", "<u8"), ("x", "<f4"), ("y", "<f4")])
+_HEADER = struct.Struct("<6sH")
+_FRAME_HEAD = struct.Struct("<QI")
+
+
+def load_trajectory_file(path: str | Path) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """Load every frame as flat per-agent rows `(steps, ids, xy)`.
+
+    `steps` is the frame step of each row, `ids` a structured array with the agent
+    `id` and its organism's stable `organism_id`, and `xy` a `(rows, 2)` float32
+    array of positions. Rows of one frame are contiguous.
+    """
+    data = Path(path).read_bytes()
+    if len(data) < _HEADER.size or data[:6] != MAGIC:
+        raise ValueError("not a trajectory file (bad magic)")
+    _, version = _HEADER.unpack_from(data)
+    if version != FORMAT_VERSION:
+        raise ValueError(f"unsupported trajectory format version {version}")
+    offset = _HEADER.size
+    chunks, steps, counts = [], [], []
+    while offset < len(data):
+        if offset + _FRAME_HEAD.size > len(data):
+            raise ValueError(f"trajectory data ends mid-frame after {len(steps)} frames")
+        step, count = _FRAME_HEAD.unpack_from(data, offset)
+        offset += _FRAME_HEAD.size
+        if offset + count * RECORD.itemsize > len(data):
+            raise ValueError(f"trajectory data ends mid-frame after {len(steps)} frames")
+        chunks.append(np.frombuffer(data, dtype=RECORD, count=count, offset=offset))
+        offset += count * RECORD.itemsize
+        steps.append(step)
+        counts.append(count)
+
+    rows = np.concatenate(chunks) if chunks else np.zeros(0, dtype=RECORD)
+    step_column = np.repeat(np.asarray(steps, dtype=np.uint64), counts)
+    ids = rows[["id", "organism_id"]].copy()
+    xy = np.stack([rows["x"], rows["y"]], axis=1)
+    return step_column, ids, xy
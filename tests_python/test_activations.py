@@ -0,0 +1,56 @@
+"""World binding: recording hidden-layer activations of tracked organisms."""
+
+from __future__ import annotations
+
+import json
+
+import pytest
+
+from digital_life import ExperimentError, SimConfig, World
+
+
+def _world() -> World:
+    return World(
+        SimConfig(
+            num_organisms=4,
+            agents_per_organism=5,
+            world_size=20.0,
+            seed=3,
+            enable_reproduction=False,
+        )
+    )
+
+
+def test_tracked_organism_records_at_cadence():
+    world = _world()
+    world.record_activations_for([1], every_n_steps=5)
+    world.run_experiment(20, 20)
+    steps, agent_ids, hidden, truncated = world.take_activation_log()
+    assert sorted(set(steps)) == [5, 10, 15, 20]
+    assert len(hidden) == 4 * len(steps) * 16
+    assert not truncated
+    assert world.take_activation_log()[0] == []
+
+
+def test_untracked_world_records_nothing_and_keeps_trajectory():
+    tracked, untracked = _world(), _world()
+    tracked.record_activations_for([0, 2])
+    a = json.loads(tracked.run_experiment(10, 10))
+    b = json.loads(untracked.run_experiment(10, 10))
+    assert a["final_state_hash"] == b["final_state_hash"]
+    assert untracked.take_activation_log()[0] == []
+    with pytest.raises(ExperimentError, match="interval"):
+        tracked.record_activations_for([0], every_n_steps=0)
+
+
+def test_activation_log_loads_as_numpy_array():
+    np = pytest.importorskip("numpy")
+    from digital_life.activations import take_activation_log
+
+    world = _world()
+    world.record_activations_for([0], every_n_steps=2)
+    world.run_experiment(6, 6)
+    log = take_activation_log(world)
+    assert log.hidden.shape == (len(log.step), 16)
+    assert set(log.step.tolist()) == {2, 4, 6}
+    assert np.all(np.abs(log.hidden) <= 1.0)
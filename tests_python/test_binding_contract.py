@@ -56,9 +56,9 @@ def test_default_config_is_valid_json():
 
 
 def test_run_experiment_json_schema_version():
-    """RunSummary must carry schema_version == 1."""
+    """RunSummary must carry schema_version == 2."""
     result = json.loads(digital_life.run_experiment_json(_make_config(), 10, 5))
-    assert result["schema_version"] == 1
+    assert result["schema_version"] == 2
 
 
 def test_run_experiment_json_required_top_level_fields():
@@ -67,16 +67,30 @@ def test_run_experiment_json_required_top_level_fields():
     required = {
         "schema_version",
         "steps",
+        "steps_executed",
+        "cancelled",
+        "metabolism_mode",
+        "criteria_mask",
         "sample_every",
         "final_alive_count",
         "samples",
         "lifespans",
         "total_reproduction_events",
         "lineage_events",
+        "final_state_hash",
     }
     assert required.issubset(result.keys())
 
 
+def test_run_experiment_json_criteria_mask_names_enabled_criteria():
+    """criteria_mask lists the enabled criteria by name."""
+    config = _make_config(enable_growth=False)
+    result = json.loads(digital_life.run_experiment_json(config, 10, 5))
+    enabled = set(result["criteria_mask"].split(" | "))
+    assert "GROWTH" not in enabled
+    assert {"METABOLISM", "REPRODUCTION", "EVOLUTION"} <= enabled
+
+
 def test_run_experiment_json_types():
     """Top-level field types must match expected Python types."""
     result = json.loads(digital_life.run_experiment_json(_make_config(), 10, 5))
@@ -101,12 +115,14 @@ def test_run_experiment_json_sample_fields():
         "boundary_mean",
         "alive_count",
         "resource_total",
+        "resource_variance",
         "birth_count",
         "death_count",
         "population_size",
         "mean_generation",
         "mean_genome_drift",
         "agent_id_exhaustion_events",
+        "organism_id_exhaustion_events",
         "energy_std",
         "waste_std",
         "boundary_std",
@@ -135,6 +151,64 @@ def test_run_experiment_json_steps_field_matches_argument():
     result = json.loads(digital_life.run_experiment_json(_make_config(), 15, 5))
     assert result["steps"] == 15
     assert result["sample_every"] == 5
+    assert result["steps_executed"] == 15
+    assert result["cancelled"] is False
+
+
+# ---------------------------------------------------------------------------
+# run_experiment_json progress callback
+# ---------------------------------------------------------------------------
+
+
+def test_run_experiment_json_callback_receives_step_and_alive_count():
+    calls = []
+    digital_life.run_experiment_json(
+        _make_config(),
+        20,
+        5,
+        callback=lambda step, alive: calls.append((step, alive)),
+        callback_every=5,
+    )
+    assert [step for step, _ in calls] == [5, 10, 15, 20]
+    assert all(isinstance(alive, int) for _, alive in calls)
+
+
+def test_run_experiment_json_callback_returning_false_cancels():
+    result = json.loads(
+        digital_life.run_experiment_json(
+            _make_config(), 100, 5, callback=lambda step, alive: step < 10, callback_every=5
+        )
+    )
+    assert result["cancelled"] is True
+    assert result["steps_executed"] == 10
+    assert result["steps"] == 100
+
+
+def test_run_experiment_json_callback_exception_propagates():
+    def fail(step, alive):
+        raise RuntimeError("stop")
+
+    with pytest.raises(RuntimeError, match="stop"):
+        digital_life.run_experiment_json(_make_config(), 10, 5, callback=fail, callback_every=5)
+
+
+def test_run_experiment_json_keyboard_interrupt_propagates_with_partial_summary():
+    def interrupt(step, alive):
+        if step >= 10:
+            raise KeyboardInterrupt
+
+    with pytest.raises(KeyboardInterrupt) as excinfo:
+        digital_life.run_experiment_json(
+            _make_config(), 100, 5, callback=interrupt, callback_every=5
+        )
+    partial = json.loads(excinfo.value.partial_summary)
+    assert partial["cancelled"] is True
+    assert partial["steps_executed"] == 10
+
+
+def test_run_experiment_json_rejects_zero_callback_every():
+    with pytest.raises(ValueError, match="callback_every"):
+        digital_life.run_experiment_json(_make_config(), 10, 5, callback_every=0)
 
 
 def test_validate_config_json_rejects_oversized_world():
@@ -143,3 +217,68 @@ def test_validate_config_json_rejects_oversized_world():
     cfg["world_size"] = 99_999.0  # exceeds MAX_WORLD_SIZE — caught at config validation layer
     with pytest.raises(Exception, match="world_size"):
         digital_life.validate_config_json(json.dumps(cfg))
+
+
+def test_errors_raise_matching_exception_classes():
+    with pytest.raises(digital_life.ConfigError, match="world_size"):
+        digital_life.validate_config_json(_make_config(world_size=-1.0))
+    with pytest.raises(digital_life.ExperimentError, match="sample_every") as excinfo:
+        digital_life.run_experiment_json(_make_config(), 10, 0)
+    assert not isinstance(excinfo.value, digital_life.ConfigError)
+    with pytest.raises(digital_life.ExperimentError) as excinfo:
+        digital_life.run_experiment_json(_make_config(), 10**9, 5)
+    assert (excinfo.value.max, excinfo.value.actual) == (1_000_000, 10**9)
+
+
+# ---------------------------------------------------------------------------
+# Population analysis over a run summary
+# ---------------------------------------------------------------------------
+
+
+def test_population_analysis_reads_run_summary():
+    summary = json.loads(digital_life.run_experiment_json(_make_config(), 20, 5))
+    summary["lineage_events"] = [
+        {"step": 3, "parent_stable_id": 0, "child_stable_id": 7, "generation": 1},
+    ]
+    summary_json = json.dumps(summary)
+
+    founders = digital_life.surviving_founder_lineages(summary_json, json.dumps([7, 1]))
+    assert founders == [0, 1]
+
+    series = digital_life.effective_population_size_series(summary_json)
+    assert len(series) == len(summary["samples"])
+    assert all(value >= 0.0 for value in series)
+
+    alive, step = digital_life.min_population_and_step(summary_json)
+    assert alive == min(s["alive_count"] for s in summary["samples"])
+    assert step in [s["step"] for s in summary["samples"]]
+
+
+def test_compare_runs_reports_differences_and_rank_test():
+    config = _make_config()
+    control = digital_life.run_experiment_json(config, 20, 5)
+    treatment = json.loads(control)
+    for sample in treatment["samples"]:
+        sample["energy_mean"] += 1.0
+    treatment_json = json.dumps(treatment)
+
+    report = json.loads(digital_life.compare_runs_json(control, treatment_json, 0.5))
+    energy = next(m for m in report["metrics"] if m["metric"] == "energy_mean")
+    assert energy["final_difference"] == pytest.approx(1.0)
+    assert energy["tail_mean_difference"] == pytest.approx(1.0)
+    assert energy["cohens_d"] is None
+    assert report["final_alive_count"]["exact"] is True
+
+    replicates = json.loads(
+        digital_life.compare_replicates_json([control, control], [treatment_json], 0.5)
+    )
+    assert (replicates["a_runs"], replicates["b_runs"]) == (2, 1)
+    assert replicates["final_alive_count"]["p_value"] == pytest.approx(1.0)
+
+    with pytest.raises(ValueError, match="summary json"):
+        digital_life.compare_runs_json("[]", control, 0.5)
+
+
+def test_population_analysis_rejects_invalid_json():
+    with pytest.raises(ValueError, match="summary json"):
+        digital_life.min_population_and_step("[]")
@@ -0,0 +1,127 @@
+"""Genome and MutationRates bindings for offline mutation studies."""
+
+from __future__ import annotations
+
+import pytest
+
+from digital_life import Genome, MutationRates, SimConfig, World
+
+
+def test_mutation_is_deterministic_for_fixed_seed():
+    a = Genome([0.5] * 16)
+    b = Genome([0.5] * 16)
+    a.mutate(123)
+    b.mutate(123)
+    assert a.data() == b.data()
+    assert a.data() != Genome([0.5] * 16).data()
+
+
+def test_different_seeds_diverge():
+    a = Genome([0.5] * 16)
+    b = Genome([0.5] * 16)
+    rates = MutationRates(point_rate=0.5)
+    a.mutate(1, rates)
+    b.mutate(2, rates)
+    assert a.data() != b.data()
+
+
+def test_rates_dict_matches_rates_object():
+    a = Genome([0.5] * 16)
+    b = Genome([0.5] * 16)
+    a.mutate(7, {"point_rate": 0.3, "point_scale": 0.5})
+    b.mutate(7, MutationRates(point_rate=0.3, point_scale=0.5))
+    assert a.data() == b.data()
+
+
+def test_mutation_respects_value_limit():
+    genome = Genome([1.5] * 32)
+    rates = MutationRates(point_rate=0.9, point_scale=1.0, value_limit=1.0)
+    for seed in range(20):
+        genome.mutate(seed, rates)
+    assert all(-1.0 <= v <= 1.0 for v in genome.data())
+
+
+def test_segments_follow_simulation_layout():
+    genome = Genome([1.0] * 10)
+    assert genome.segment(0) == [1.0] * 10
+    assert genome.segment(1) == [0.0] * 16
+    assert len(genome.segment(6)) == 4
+    assert len(genome) == 10 + 16 + 8 + 8 + 4 + 4 + 4
+    with pytest.raises(IndexError):
+        genome.segment(7)
+
+
+def test_mutation_rates_defaults_and_validation():
+    rates = MutationRates()
+    assert rates.point_rate == pytest.approx(0.02)
+    assert rates.value_limit == 2.0
+    with pytest.raises(ValueError, match="point_rate"):
+        MutationRates(point_rate=1.5)
+    with pytest.raises(ValueError, match="<= 1"):
+        MutationRates(point_rate=0.6, reset_rate=0.6)
+
+
+def test_world_organism_genome_round_trips():
+    world = World(SimConfig(num_organisms=3, agents_per_organism=4, world_size=20.0, seed=5))
+    stable_id = world.alive_stable_ids()[0]
+    data = world.organism_genome(stable_id)
+    genome = Genome.from_data(data)
+    assert genome.data() == pytest.approx(data)
+    assert len(genome.segment(0)) == len(data) - 44
+    with pytest.raises(ValueError, match="no alive organism"):
+        world.organism_genome(10**9)
+
+
+def test_metabolic_size_matches_world_layout():
+    config = SimConfig(
+        num_organisms=2,
+        agents_per_organism=4,
+        world_size=20.0,
+        seed=5,
+        metabolism_mode="graph",
+        metabolic_genome_size=32,
+    )
+    world = World(config)
+    data = world.organism_genome(world.alive_stable_ids()[0])
+    genome = Genome.from_data(data, metabolic_size=32)
+    assert genome.metabolic_size == 32
+    assert len(genome.segment(1)) == 32
+    assert genome.segment(0) == Genome.from_data(data).segment(0)[:-16]
+    assert Genome([0.0] * 4).metabolic_size == 16
+    with pytest.raises(ValueError, match="metabolic_size"):
+        Genome([0.0] * 4, metabolic_size=8)
+
+
+def test_exported_controllers_reseed_a_new_world():
+    config = SimConfig(num_organisms=3, agents_per_organism=4, world_size=20.0, seed=5)
+    world = World(config)
+    exported = [world.organism_nn_weights(i) for i in world.alive_stable_ids()]
+    assert exported[0] == world.organism_genome(0)[: len(exported[0])]
+    reseeded = World.seed_from_weights(exported, config)
+    assert [reseeded.organism_nn_weights(i) for i in reseeded.alive_stable_ids()] == exported
+    with pytest.raises(ValueError, match="no alive organism"):
+        world.organism_nn_weights(10**9)
+    with pytest.raises(ValueError, match="num_organisms"):
+        World.seed_from_weights(exported[:2], config)
+
+
+def test_crossover_is_deterministic_and_mixes_parents():
+    a = Genome([1.0] * 16)
+    b = Genome([-1.0] * 16)
+    for name in ("crossover_uniform", "crossover_one_point", "crossover_segmentwise"):
+        child = getattr(a, name)(b, 11)
+        assert child.data() == getattr(a, name)(b, 11).data()
+        assert all(v in (1.0, -1.0, 0.0) for v in child.data())
+        assert any(getattr(a, name)(b, seed).data() != a.data() for seed in range(10))
+
+
+def test_segmentwise_crossover_keeps_segments_whole():
+    a = Genome([1.0] * 16)
+    b = Genome([-1.0] * 16)
+    child = a.crossover_segmentwise(b, 4)
+    assert child.segment(0) in ([1.0] * 16, [-1.0] * 16)
+
+
+def test_crossover_rejects_mismatched_layouts():
+    with pytest.raises(ValueError, match="different layouts"):
+        Genome([0.0] * 16).crossover_uniform(Genome([0.0] * 12), 1)
@@ -0,0 +1,59 @@
+"""GIL release tests: long-running bindings must not block other Python threads."""
+
+from __future__ import annotations
+
+import json
+import threading
+import time
+
+import digital_life
+
+_WATCH_INTERVAL_S = 0.005
+
+
+def _make_config() -> str:
+    base = json.loads(digital_life.default_config_json())
+    base.update(
+        {
+            "num_organisms": 2,
+            "agents_per_organism": 5,
+            "world_size": 20.0,
+            "seed": 42,
+            # Keep both organisms alive so every step does real work.
+            "enable_boundary_maintenance": False,
+            "enable_reproduction": False,
+            "max_organism_age_steps": 1_000_000,
+        }
+    )
+    return json.dumps(base)
+
+
+def test_watcher_thread_advances_during_long_experiment():
+    """A watcher thread keeps ticking while a 50k-step experiment runs."""
+    ticks: list[float] = []
+    stop = threading.Event()
+
+    def watch() -> None:
+        while not stop.is_set():
+            ticks.append(time.perf_counter())
+            time.sleep(_WATCH_INTERVAL_S)
+
+    watcher = threading.Thread(target=watch, daemon=True)
+    watcher.start()
+    try:
+        start = time.perf_counter()
+        result = json.loads(digital_life.run_experiment_json(_make_config(), 50_000, 5_000))
+        end = time.perf_counter()
+    finally:
+        stop.set()
+        watcher.join(timeout=5.0)
+
+    assert result["steps"] == 50_000
+    assert result["final_alive_count"] == 2
+    during = [t for t in ticks if start < t < end]
+    # With the GIL held for the whole call the watcher could not run at all;
+    # require it to have ticked for a meaningful share of the run.
+    expected = (end - start) / _WATCH_INTERVAL_S
+    assert len(during) >= max(3, int(expected * 0.1)), (
+        f"watcher ticked {len(during)} times during a {end - start:.3f}s run"
+    )
@@ -0,0 +1,91 @@
+"""SimConfig binding: keyword construction, validation, and dict/JSON round-trips."""
+
+from __future__ import annotations
+
+import json
+
+import pytest
+
+import digital_life
+from digital_life import SimConfig
+
+
+def test_defaults_match_default_config_json():
+    assert SimConfig().to_dict() == json.loads(digital_life.default_config_json())
+
+
+def test_keyword_construction_sets_fields():
+    config = SimConfig(seed=9, world_size=50.0, metabolism_mode="graph")
+    assert config.seed == 9
+    assert config.world_size == 50.0
+    assert config.metabolism_mode == "graph"
+
+
+@pytest.mark.parametrize("mode", ["toy", "counter", "graph"])
+def test_metabolism_mode_accepts_known_strings(mode):
+    assert SimConfig(metabolism_mode=mode).metabolism_mode == mode
+
+
+def test_negative_world_size_error_names_field():
+    with pytest.raises(ValueError, match="world_size"):
+        SimConfig(world_size=-1.0)
+
+
+def test_unknown_keyword_is_rejected():
+    with pytest.raises(ValueError, match="world_sise"):
+        SimConfig(world_sise=10.0)
+
+
+def test_invalid_enum_value_names_field():
+    with pytest.raises(ValueError, match="metabolism_mode"):
+        SimConfig(metabolism_mode="photosynthesis")
+
+
+def test_assignment_revalidates_and_keeps_previous_value():
+    config = SimConfig(dt=0.1)
+    with pytest.raises(ValueError, match="dt"):
+        config.dt = -0.5
+    assert config.dt == 0.1
+    config.dt = 0.2
+    assert config.dt == 0.2
+
+
+def test_assigning_unknown_attribute_raises_attribute_error():
+    config = SimConfig()
+    with pytest.raises(AttributeError):
+        config.not_a_field = 1
+
+
+def test_dict_and_json_round_trip():
+    config = SimConfig(seed=3, enable_growth=False, environment_schedule=[[10, 0.002]])
+    assert SimConfig.from_dict(config.to_dict()) == config
+    assert SimConfig.from_json(config.to_json()) == config
+
+
+def test_to_json_is_accepted_by_experiment_bindings():
+    config = SimConfig(num_organisms=2, agents_per_organism=3, world_size=20.0)
+    result = json.loads(digital_life.run_experiment_json(config.to_json(), 5, 5))
+    assert result["steps"] == 5
+
+
+def test_dir_lists_config_fields():
+    assert "world_size" in dir(SimConfig())
+
+
+def test_validation_error_lists_every_invalid_field():
+    with pytest.raises(ValueError) as excinfo:
+        SimConfig(world_size=-1.0, dt=0.0, max_speed=-2.0)
+    message = str(excinfo.value)
+    assert message.startswith("3 invalid config values:")
+    for fragment in ("world_size", "(got -1.0)", "dt must", "max_speed must", "(got -2.0)"):
+        assert fragment in message
+
+
+def test_invalid_config_raises_config_error_with_violations():
+    with pytest.raises(digital_life.ConfigError) as excinfo:
+        SimConfig(world_size=-1.0, dt=0.0)
+    err = excinfo.value
+    assert isinstance(err, digital_life.DigitalLifeError)
+    assert isinstance(err, ValueError)
+    assert [v["field"] for v in err.violations] == ["world_size", "dt"]
+    assert err.violations[0]["value"] == "-1.0"
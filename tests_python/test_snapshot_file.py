@@ -0,0 +1,51 @@
+"""Binary snapshot files streamed from `World.run_with_snapshot_file`."""
+
+from __future__ import annotations
+
+import json
+
+import pytest
+
+from digital_life import SimConfig, World
+
+np = pytest.importorskip("numpy")
+from digital_life.snapshots import load_snapshot_file  # noqa: E402
+
+
+def _world() -> World:
+    return World(SimConfig(num_organisms=6, agents_per_organism=4, world_size=30.0, seed=8))
+
+
+def test_streamed_frames_load_as_columns(tmp_path):
+    path = tmp_path / "frames.dlsnap"
+    summary = json.loads(_world().run_with_snapshot_file(40, 10, 10, str(path)))
+    columns = load_snapshot_file(path)
+    assert list(columns["frame_steps"]) == [10, 20, 30, 40]
+    offsets = columns["frame_offsets"]
+    assert offsets[-1] == len(columns["energy"]) == len(columns["step"])
+    assert offsets[-1] - offsets[-2] == summary["final_alive_count"]
+    assert columns["dormant"].dtype == bool
+    assert np.all(columns["n_agents"] > 0)
+
+
+def test_append_continues_an_existing_file(tmp_path):
+    path = tmp_path / "frames.dlsnap"
+    world = _world()
+    world.run_with_snapshot_file(20, 10, 10, str(path))
+    world.run_with_snapshot_file(20, 10, 10, str(path), append=True)
+    assert list(load_snapshot_file(path)["frame_steps"]) == [10, 20, 10, 20]
+
+
+def test_binary_file_is_smaller_than_json_snapshots(tmp_path):
+    path = tmp_path / "frames.dlsnap"
+    _world().run_with_snapshot_file(40, 10, 10, str(path))
+    columns = load_snapshot_file(path)
+    as_json = json.dumps({k: v.tolist() for k, v in columns.items()})
+    assert path.stat().st_size < len(as_json)
+
+
+def test_rejects_non_snapshot_file(tmp_path):
+    path = tmp_path / "bogus.dlsnap"
+    path.write_bytes(b"not a snapshot")
+    with pytest.raises(ValueError, match="bad magic"):
+        load_snapshot_file(path)
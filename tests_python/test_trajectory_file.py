@@ -0,0 +1,49 @@
+"""Binary agent trajectories recorded with `World.set_trajectory_file`."""
+
+from __future__ import annotations
+
+import pytest
+
+from digital_life import ExperimentError, SimConfig, World
+
+np = pytest.importorskip("numpy")
+from digital_life.trajectories import load_trajectory_file  # noqa: E402
+
+
+def _world() -> World:
+    return World(SimConfig(num_organisms=6, agents_per_organism=4, world_size=30.0, seed=8))
+
+
+def test_recorded_frames_load_as_rows(tmp_path):
+    path = tmp_path / "agents.dltraj"
+    world = _world()
+    world.set_trajectory_file(str(path), 10)
+    world.run_experiment(40, 10)
+    steps, ids, xy = load_trajectory_file(path)
+    assert sorted(set(steps.tolist())) == [10, 20, 30, 40]
+    assert len(steps) == len(ids) == len(xy)
+    assert xy.shape[1] == 2 and xy.dtype == np.float32
+    assert np.all((xy >= 0.0) & (xy <= 30.0))
+    assert set(ids.dtype.names) == {"id", "organism_id"}
+
+
+def test_max_agents_caps_each_frame(tmp_path):
+    path = tmp_path / "agents.dltraj"
+    world = _world()
+    world.set_trajectory_file(str(path), 5, max_agents=7)
+    world.run_experiment(20, 10)
+    world.close_trajectory_file()
+    steps, _, _ = load_trajectory_file(path)
+    assert np.bincount(steps.astype(np.int64))[5::5].tolist() == [7, 7, 7, 7]
+
+
+def test_rejects_zero_cadence(tmp_path):
+    with pytest.raises(ExperimentError):
+        _world().set_trajectory_file(str(tmp_path / "agents.dltraj"), 0)
+
+
+def test_rejects_non_trajectory_file(tmp_path):
+    path = tmp_path / "bogus.dltraj"
+    path.write_bytes(b"not a trajectory")
+    with pytest.raises(ValueError, match="bad magic"):
+        load_trajectory_file(path)
@@ -0,0 +1,140 @@
+"""World binding: mid-run interventions from a Python callback."""
+
+from __future__ import annotations
+
+import json
+
+import pytest
+
+from digital_life import SimConfig, World
+
+NO_PARENT = 2**64 - 1
+
+
+def _world() -> World:
+    return World(SimConfig(num_organisms=4, agents_per_organism=5, world_size=30.0, seed=3))
+
+
+def test_killing_population_at_step_100_leaves_no_survivors():
+    seen = []
+
+    def callback(view):
+        seen.append(view.step)
+        if view.step == 100:
+            assert view.alive_count == len(view.alive_stable_ids)
+            for stable_id in view.alive_stable_ids:
+                view.kill_organism(stable_id)
+
+    world = _world()
+    summary = json.loads(world.run_with_callback(300, 50, callback, callback_every=50))
+    assert seen == [50, 100, 150, 200, 250, 300]
+    assert summary["final_alive_count"] == 0
+    assert world.alive_count == 0
+    assert summary["samples"][1]["alive_count"] == 0
+
+
+def test_resource_and_energy_interventions_apply():
+    def callback(view):
+        if view.step == 10:
+            view.set_resource_rate(0.0)
+            view.scale_resources(0.0)
+            view.inject_energy(view.alive_stable_ids[0], 0.5)
+
+    summary = json.loads(_world().run_with_callback(20, 10, callback, callback_every=10))
+    totals = [s["resource_total"] for s in summary["samples"]]
+    assert totals[0] == 0.0
+    assert totals[1] <= 1e-6
+
+
+def test_invalid_intervention_raises_value_error():
+    def callback(view):
+        view.kill_organism(10**9)
+
+    with pytest.raises(ValueError, match="no alive organism"):
+        _world().run_with_callback(10, 10, callback, callback_every=5)
+
+
+def test_view_is_closed_after_callback():
+    views = []
+    _world().run_with_callback(10, 10, views.append, callback_every=10)
+    with pytest.raises(RuntimeError, match="inside its callback"):
+        views[0].scale_resources(0.5)
+
+
+def test_returning_false_cancels_run():
+    summary = json.loads(_world().run_with_callback(100, 10, lambda view: False, callback_every=20))
+    assert summary["cancelled"] is True
+    assert summary["steps_executed"] == 20
+
+
+def test_immigrant_added_mid_run_founds_a_lineage():
+    world = _world()
+    genome = world.organism_genome(world.alive_stable_ids()[0])
+    alive_after = []
+
+    def callback(view):
+        if view.step == 500:
+            view.add_organism(genome, 15.0, 15.0, 5)
+        elif view.step == 510:
+            alive_after.extend(view.alive_stable_ids)
+
+    summary = json.loads(world.run_with_callback(1000, 100, callback, callback_every=10))
+    lineage = summary["lineage_events"]
+    immigrant = next(e for e in lineage if e["parent_stable_id"] == NO_PARENT)
+    assert (immigrant["step"], immigrant["generation"]) == (500, 0)
+    assert immigrant["child_stable_id"] in alive_after
+    assert any(e["parent_stable_id"] == immigrant["child_stable_id"] for e in lineage)
+
+
+def test_add_organism_returns_stable_id_and_validates():
+    world = _world()
+    genome = world.organism_genome(world.alive_stable_ids()[0])
+    stable_id = world.add_organism(genome, 1.0, 2.0, 3)
+    assert stable_id in world.alive_stable_ids()
+    assert world.organism_genome(stable_id) == genome
+    with pytest.raises(ValueError, match="n_agents must be positive"):
+        world.add_organism(genome, 1.0, 2.0, 0)
+    with pytest.raises(ValueError, match="full genome data"):
+        world.add_organism([0.0], 1.0, 2.0, 3)
+
+
+def test_targeted_perturbations_by_stable_id():
+    world = _world()
+    target, other = world.alive_stable_ids()[:2]
+    world.kill_organism(target)
+    assert target not in world.alive_stable_ids()
+    world.set_organism_energy(other, 0.0)
+    world.damage_boundary(other, 0.5)
+    with pytest.raises(ValueError, match="no alive organism"):
+        world.set_organism_energy(target, 0.5)
+    with pytest.raises(ValueError, match="boundary damage"):
+        world.damage_boundary(other, -1.0)
+
+
+def test_view_perturbations_apply_at_callback_step():
+    def callback(view):
+        if view.step == 10:
+            view.set_organism_energy(view.alive_stable_ids[0], 2.0)
+            view.damage_boundary(view.alive_stable_ids[1], 0.2)
+
+    perturbed = json.loads(_world().run_with_callback(10, 10, callback, callback_every=10))
+    baseline = json.loads(_world().run_with_callback(10, 10, lambda view: None, callback_every=10))
+    assert perturbed["samples"][0]["energy_mean"] > baseline["samples"][0]["energy_mean"]
+    assert perturbed["samples"][0]["boundary_mean"] < baseline["samples"][0]["boundary_mean"]
+
+
+def test_homeostasis_shock_is_reported_in_summary():
+    world = _world()
+    world.apply_homeostasis_shock(-0.2)
+    with pytest.raises(ValueError, match="homeostasis shock must be finite"):
+        world.apply_homeostasis_shock(float("nan"))
+
+    def callback(view):
+        if view.step == 20:
+            view.apply_homeostasis_shock(0.3)
+
+    summary = json.loads(world.run_with_callback(40, 10, callback, callback_every=10))
+    shock = summary["shock_recoveries"][-1]
+    assert shock["step"] == 20
+    assert shock["amount"] == pytest.approx(0.3)
+    assert summary["samples"][-1]["steps_since_last_shock"] == 20
@@ -0,0 +1,45 @@
+"""World binding: incremental stepping through `World.iter_steps`."""
+
+from __future__ import annotations
+
+import json
+
+import pytest
+
+from digital_life import SimConfig, World
+
+
+def _world() -> World:
+    return World(SimConfig(num_organisms=4, agents_per_organism=5, world_size=30.0, seed=3))
+
+
+def test_iter_steps_yields_metrics_every_sample_and_stops_at_budget():
+    samples = list(_world().iter_steps(25, sample_every=10))
+    assert [s["step"] for s in samples] == [10, 20, 25]
+    assert all(isinstance(s, dict) and "alive_count" in s for s in samples)
+
+
+def test_iteration_matches_blocking_run():
+    iterated = [s["energy_mean"] for s in _world().iter_steps(30, sample_every=10)]
+    summary = json.loads(_world().run_experiment(30, 10))
+    assert iterated == [s["energy_mean"] for s in summary["samples"]]
+
+
+def test_partial_iteration_then_run_experiment_continues_the_world():
+    world = _world()
+    for metrics in world.iter_steps(1000, sample_every=10):
+        if metrics["step"] == 40:
+            break
+    continued = json.loads(world.run_experiment(60, 20))
+
+    reference = _world()
+    json.loads(reference.run_experiment(40, 40))
+    expected = json.loads(reference.run_experiment(60, 20))
+    assert continued["final_state_hash"] == expected["final_state_hash"]
+    assert continued["samples"] == expected["samples"]
+    assert continued["steps_executed"] == 60
+
+
+def test_iter_steps_rejects_zero_sample_every():
+    with pytest.raises(ValueError, match="sample_every must be positive"):
+        _world().iter_steps(10, sample_every=0)
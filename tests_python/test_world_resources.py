@@ -0,0 +1,74 @@
+"""World binding: painting designed resource environments from Python."""
+
+from __future__ import annotations
+
+import json
+
+import pytest
+
+from digital_life import SimConfig, World
+
+
+def _world() -> World:
+    return World(
+        SimConfig(
+            num_organisms=8,
+            agents_per_organism=5,
+            world_size=20.0,
+            seed=3,
+            death_energy_threshold=0.05,
+            enable_reproduction=False,
+        )
+    )
+
+
+def _paint(world: World, value_at) -> None:
+    height, width, _ = world.resource_dimensions()
+    world.set_resource_grid([[value_at(r, c) for c in range(width)] for r in range(height)])
+
+
+def _final_alive(world: World, steps: int = 2000) -> int:
+    summary = world.run_with_callback(steps, steps, lambda view: None, callback_every=steps)
+    return json.loads(summary)["final_alive_count"]
+
+
+def test_resource_dimensions_match_world():
+    assert _world().resource_dimensions() == (20, 20, 1.0)
+
+
+def test_checkerboard_starves_organisms_in_empty_squares():
+    empty = _world()
+    _paint(empty, lambda r, c: 0.0)
+    assert _final_alive(empty) == 0
+
+    checkerboard = _world()
+    _paint(checkerboard, lambda r, c: 1.0 if (r // 5 + c // 5) % 2 == 0 else 0.0)
+    survivors = _final_alive(checkerboard)
+    assert 0 < survivors < 8
+
+
+def test_set_resource_grid_names_first_invalid_index():
+    world = _world()
+    grid = [[1.0] * 20 for _ in range(20)]
+    grid[3][7] = float("nan")
+    grid[4][0] = -1.0
+    with pytest.raises(ValueError, match=r"\(3, 7\)"):
+        world.set_resource_grid(grid)
+
+
+def test_set_resource_grid_rejects_wrong_shape():
+    world = _world()
+    with pytest.raises(ValueError, match="20 rows"):
+        world.set_resource_grid([[1.0] * 20 for _ in range(19)])
+    with pytest.raises(ValueError, match="row 0 must have 20 values"):
+        world.set_resource_grid([[1.0] * 21 for _ in range(20)])
+
+
+def test_cell_and_scale_setters_validate_values():
+    world = _world()
+    world.set_resource_cell(2.5, 3.5, 4.0)
+    world.scale_resources(0.5)
+    with pytest.raises(ValueError, match="non-negative"):
+        world.set_resource_cell(2.5, 3.5, -1.0)
+    with pytest.raises(ValueError, match="non-negative"):
+        world.scale_resources(float("inf"))